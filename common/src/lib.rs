@@ -160,6 +160,7 @@ pub trait SubnetInfo<AccountId> {
 pub trait BalanceOps<AccountId> {
     fn tao_balance(account_id: &AccountId) -> u64;
     fn alpha_balance(netuid: NetUid, coldkey: &AccountId, hotkey: &AccountId) -> u64;
+    fn coldkey_owns_hotkey(coldkey: &AccountId, hotkey: &AccountId) -> bool;
     fn increase_balance(coldkey: &AccountId, tao: u64);
     fn decrease_balance(coldkey: &AccountId, tao: u64) -> Result<u64, DispatchError>;
     fn increase_stake(
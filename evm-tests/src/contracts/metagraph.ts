@@ -57,6 +57,57 @@ export const IMetagraphABI = [
         stateMutability: "view",
         type: "function",
     },
+    {
+        inputs: [
+            {
+                internalType: "uint16",
+                name: "netuid",
+                type: "uint16",
+            },
+            {
+                internalType: "uint16",
+                name: "uid",
+                type: "uint16",
+            },
+        ],
+        name: "getAxonEndpoints",
+        outputs: [
+            {
+                components: [
+                    {
+                        internalType: "uint8",
+                        name: "ip_type",
+                        type: "uint8",
+                    },
+                    {
+                        internalType: "uint128",
+                        name: "ip",
+                        type: "uint128",
+                    },
+                    {
+                        internalType: "bytes",
+                        name: "hostname",
+                        type: "bytes",
+                    },
+                    {
+                        internalType: "uint16",
+                        name: "port",
+                        type: "uint16",
+                    },
+                    {
+                        internalType: "uint8",
+                        name: "transport",
+                        type: "uint8",
+                    },
+                ],
+                internalType: "struct AxonEndpoint[]",
+                name: "",
+                type: "tuple[]",
+            },
+        ],
+        stateMutability: "view",
+        type: "function",
+    },
     {
         inputs: [
             {
@@ -0,0 +1,83 @@
+import * as assert from "assert";
+
+import { getDevnetApi } from "../src/substrate"
+import { getPublicClient } from "../src/utils";
+import { ETH_LOCAL_URL } from "../src/config";
+import { devnet } from "@polkadot-api/descriptors"
+import { PublicClient, toHex } from "viem";
+import { TypedApi } from "polkadot-api";
+import { toViemAddress } from "../src/address-utils"
+import { IDrandABI, IDRAND_ADDRESS } from "../src/contracts/drand"
+
+describe("Test Drand Precompile", () => {
+    let publicClient: PublicClient;
+
+    let api: TypedApi<typeof devnet>;
+
+    before(async () => {
+        publicClient = await getPublicClient(ETH_LOCAL_URL)
+        api = await getDevnetApi()
+    })
+
+    it("getLastStoredRound matches the drand pallet", async () => {
+        const lastStoredRound = await publicClient.readContract({
+            abi: IDrandABI,
+            address: toViemAddress(IDRAND_ADDRESS),
+            functionName: "getLastStoredRound",
+            args: []
+        })
+        const oldestStoredRound = await publicClient.readContract({
+            abi: IDrandABI,
+            address: toViemAddress(IDRAND_ADDRESS),
+            functionName: "getOldestStoredRound",
+            args: []
+        })
+
+        assert.ok(typeof lastStoredRound === 'bigint', "Last stored round should be a bigint");
+        assert.ok(lastStoredRound >= oldestStoredRound, "Last stored round should not precede the oldest one");
+        assert.ok(lastStoredRound <= await api.query.Drand.LastStoredRound.getValue());
+    });
+
+    it("getRandomness returns the randomness of a stored round", async () => {
+        const round = await api.query.Drand.LastStoredRound.getValue()
+        assert.ok(round > BigInt(0), "The localnet should have stored drand pulses");
+
+        const pulse = await api.query.Drand.Pulses.getValue(round)
+        assert.ok(pulse !== undefined, "The pulse of the last stored round should be stored");
+
+        const available = await publicClient.readContract({
+            abi: IDrandABI,
+            address: toViemAddress(IDRAND_ADDRESS),
+            functionName: "isRoundAvailable",
+            args: [round]
+        })
+        assert.equal(available, true);
+
+        const randomness = await publicClient.readContract({
+            abi: IDrandABI,
+            address: toViemAddress(IDRAND_ADDRESS),
+            functionName: "getRandomness",
+            args: [round]
+        })
+        assert.equal(randomness, toHex(pulse.randomness.asBytes()));
+    });
+
+    it("getRandomness reverts for a round not reached yet", async () => {
+        const round = (await api.query.Drand.LastStoredRound.getValue()) + BigInt(1_000_000)
+
+        const available = await publicClient.readContract({
+            abi: IDrandABI,
+            address: toViemAddress(IDRAND_ADDRESS),
+            functionName: "isRoundAvailable",
+            args: [round]
+        })
+        assert.equal(available, false);
+
+        await assert.rejects(publicClient.readContract({
+            abi: IDrandABI,
+            address: toViemAddress(IDRAND_ADDRESS),
+            functionName: "getRandomness",
+            args: [round]
+        }))
+    });
+});
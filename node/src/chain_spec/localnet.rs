@@ -134,5 +134,9 @@ fn localnet_genesis(
         "evmChainId": {
             "chainId": 42,
         },
+        // Sign drand rounds locally so timelocks work without reaching the drand api
+        "drand": {
+            "useMockBeacon": true,
+        },
     })
 }
@@ -13,6 +13,11 @@ pub struct Cli {
     #[arg(long, value_enum, ignore_case = true)]
     pub sealing: Option<Sealing>,
 
+    /// Drand api endpoint for the offchain worker to query in place of the public ones, such as
+    /// a local relay. Can be repeated for several endpoints.
+    #[arg(long = "drand-endpoint", value_name = "URL")]
+    pub drand_endpoints: Vec<String>,
+
     #[command(flatten)]
     pub eth: EthConfiguration,
 }
@@ -236,7 +236,7 @@ pub fn run() -> sc_cli::Result<()> {
                 if cli.run.rpc_params.rpc_rate_limit.is_none() {
                     config.rpc.rate_limit = None;
                 }
-                service::build_full(config, cli.eth, cli.sealing)
+                service::build_full(config, cli.eth, cli.sealing, cli.drand_endpoints)
                     .map_err(Into::into)
                     .await
             })
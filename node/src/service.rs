@@ -14,9 +14,11 @@ use sc_service::{Configuration, PartialComponents, TaskManager, error::Error as
 use sc_telemetry::{Telemetry, TelemetryHandle, TelemetryWorker, log};
 use sc_transaction_pool::TransactionPoolHandle;
 use sc_transaction_pool_api::OffchainTransactionPoolFactory;
+use scale_codec::Encode;
 use sp_consensus::Error as ConsensusError;
 use sp_consensus_aura::sr25519::AuthorityPair as AuraPair;
 use sp_core::H256;
+use sp_core::offchain::OffchainStorage;
 use sp_runtime::traits::{Block as BlockT, Header, NumberFor};
 use std::collections::HashSet;
 use std::str::FromStr;
@@ -343,6 +345,7 @@ pub async fn new_full<NB>(
     mut config: Configuration,
     eth_config: EthConfiguration,
     sealing: Option<Sealing>,
+    drand_endpoints: Vec<String>,
 ) -> Result<TaskManager, ServiceError>
 where
     NumberFor<Block>: BlockNumberOps,
@@ -450,6 +453,18 @@ where
             log::debug!("Offchain worker key already exists");
         }
 
+        // Point the drand offchain worker at the endpoints given on the command line
+        if !drand_endpoints.is_empty() {
+            if let Some(mut offchain_storage) = backend.offchain_storage() {
+                offchain_storage.set(
+                    sp_core::offchain::STORAGE_PREFIX,
+                    pallet_drand::OFFCHAIN_ENDPOINTS_KEY,
+                    &drand_endpoints.encode(),
+                );
+                log::info!("Drand endpoints set to {:?}", drand_endpoints);
+            }
+        }
+
         task_manager.spawn_essential_handle().spawn(
             "offchain-workers-runner",
             None,
@@ -727,17 +742,36 @@ pub async fn build_full(
     config: Configuration,
     eth_config: EthConfiguration,
     sealing: Option<Sealing>,
+    drand_endpoints: Vec<String>,
 ) -> Result<TaskManager, ServiceError> {
     match config.network.network_backend {
         Some(sc_network::config::NetworkBackendType::Libp2p) => {
-            new_full::<sc_network::NetworkWorker<_, _>>(config, eth_config, sealing).await
+            new_full::<sc_network::NetworkWorker<_, _>>(
+                config,
+                eth_config,
+                sealing,
+                drand_endpoints,
+            )
+            .await
         }
         Some(sc_network::config::NetworkBackendType::Litep2p) => {
-            new_full::<sc_network::Litep2pNetworkBackend>(config, eth_config, sealing).await
+            new_full::<sc_network::Litep2pNetworkBackend>(
+                config,
+                eth_config,
+                sealing,
+                drand_endpoints,
+            )
+            .await
         }
         _ => {
             log::debug!("no network backend selected, falling back to libp2p");
-            new_full::<sc_network::NetworkWorker<_, _>>(config, eth_config, sealing).await
+            new_full::<sc_network::NetworkWorker<_, _>>(
+                config,
+                eth_config,
+                sealing,
+                drand_endpoints,
+            )
+            .await
         }
     }
 }
@@ -29,9 +29,13 @@ pub mod pallet {
     use frame_system::pallet_prelude::*;
     use pallet_evm_chain_id::{self, ChainId};
     use pallet_subtensor::utils::rate_limiting::TransactionType;
+    use pallet_subtensor::{ImmunityPolicy, PowAlgorithm, PruningPolicy};
     use sp_runtime::BoundedVec;
     use substrate_fixed::types::I96F32;
     use subtensor_runtime_common::NetUid;
+    use subtensor_swap_interface::{
+        MAX_PROTOCOL_LIQUIDITY_BANDS, ProtocolLiquidityPolicy, SwapHandler,
+    };
 
     /// The main data structure of the module.
     #[pallet::pallet]
@@ -107,6 +111,12 @@ pub mod pallet {
         BondsMovingAverageMaxReached,
         /// Only root can set negative sigmoid steepness values
         NegativeSigmoidSteepness,
+        /// The emission history pruning policy must cover between one and the maximum number of epochs
+        InvalidPruningPolicy,
+        /// The cuckoo cycle graph size is outside the range the solver and verifier support
+        InvalidPowAlgorithm,
+        /// Immunity cannot end after zero epochs with incentive
+        InvalidImmunityPolicy,
     }
     /// Enum for specifying the type of precompile operation.
     #[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, Debug, Copy)]
@@ -125,6 +135,8 @@ pub mod pallet {
         UidLookup,
         /// Enum for alpha precompile
         Alpha,
+        /// Enum for drand randomness precompile
+        Drand,
     }
 
     #[pallet::type_value]
@@ -1657,6 +1669,400 @@ pub mod pallet {
             Ok(())
         }
 
+        /// Sets the shape of the protocol owned liquidity of a subnet pool.
+        ///
+        /// # Parameters
+        /// - `origin`: The origin of the call, which must be the root account or subnet owner.
+        /// - `netuid`: The unique identifier for the subnet.
+        /// - `policy`: Full range, a concentrated band or several bands around the current price.
+        ///
+        /// # Errors
+        /// - `SubnetDoesNotExist`: If the specified subnet does not exist.
+        /// - `TxRateLimitExceeded`: If the subnet owner changes the policy more than once a day.
+        /// - `InvalidProtocolLiquidityPolicy`: If a band is narrower than the minimum width, wider
+        ///   than the price range or has zero weight.
+        ///
+        /// # Weight
+        /// Covers withdrawing and re-providing every protocol position of the subnet, one full
+        /// range position and at most `MAX_PROTOCOL_LIQUIDITY_BANDS` bands.
+        #[pallet::call_index(71)]
+        #[pallet::weight((
+			Weight::from_parts(60_000_000, 0)
+				.saturating_add(<T as frame_system::Config>::DbWeight::get().reads_writes(6, 2))
+				.saturating_add(
+					<T as frame_system::Config>::DbWeight::get()
+						.reads_writes(12, 14)
+						.saturating_mul(2_u64.saturating_mul(MAX_PROTOCOL_LIQUIDITY_BANDS.saturating_add(1) as u64)),
+				),
+			DispatchClass::Operational,
+			Pays::No
+		))]
+        pub fn sudo_set_protocol_liquidity_policy(
+            origin: OriginFor<T>,
+            netuid: NetUid,
+            policy: ProtocolLiquidityPolicy,
+        ) -> DispatchResult {
+            pallet_subtensor::Pallet::<T>::ensure_subnet_owner_or_root(origin.clone(), netuid)?;
+
+            ensure!(
+                pallet_subtensor::Pallet::<T>::if_subnet_exist(netuid),
+                Error::<T>::SubnetDoesNotExist
+            );
+
+            if let Ok(RawOrigin::Signed(who)) = origin.into() {
+                // SN Owner
+                // Ensure the origin passes the rate limit.
+                ensure!(
+                    pallet_subtensor::Pallet::<T>::passes_rate_limit_on_subnet(
+                        &TransactionType::SetProtocolLiquidityPolicy,
+                        &who,
+                        netuid,
+                    ),
+                    pallet_subtensor::Error::<T>::TxRateLimitExceeded
+                );
+
+                // Set last transaction block
+                let current_block = pallet_subtensor::Pallet::<T>::get_current_block_as_u64();
+                pallet_subtensor::Pallet::<T>::set_last_transaction_block_on_subnet(
+                    &who,
+                    netuid,
+                    &TransactionType::SetProtocolLiquidityPolicy,
+                    current_block,
+                );
+            }
+
+            <T as pallet_subtensor::Config>::SwapInterface::set_protocol_liquidity_policy(
+                netuid,
+                policy.clone(),
+            )?;
+
+            log::debug!(
+                "ProtocolLiquidityPolicySet( netuid: {:?} policy: {:?} ) ",
+                netuid,
+                policy
+            );
+            Ok(())
+        }
+
+        /// Sets the policy used to pick the neuron replaced when a full subnet receives a new
+        /// registration.
+        ///
+        /// # Parameters
+        /// - `origin`: The origin of the call, which must be the root account or subnet owner.
+        /// - `netuid`: The unique identifier for the subnet.
+        /// - `policy`: The pruning policy to rank the subnet's neurons by.
+        ///
+        /// # Errors
+        /// - `SubnetDoesNotExist`: If the specified subnet does not exist.
+        /// - `InvalidPruningPolicy`: If the emission history covers zero or more than
+        ///   `MAX_PRUNING_EMISSION_EPOCHS` epochs.
+        ///
+        /// # Weight
+        /// This function has a fixed weight of 0 and is classified as an operational transaction that does not incur any fees.
+        #[pallet::call_index(72)]
+        #[pallet::weight((0, DispatchClass::Operational, Pays::No))]
+        pub fn sudo_set_pruning_policy(
+            origin: OriginFor<T>,
+            netuid: NetUid,
+            policy: PruningPolicy,
+        ) -> DispatchResult {
+            pallet_subtensor::Pallet::<T>::ensure_subnet_owner_or_root(origin, netuid)?;
+
+            ensure!(
+                pallet_subtensor::Pallet::<T>::if_subnet_exist(netuid),
+                Error::<T>::SubnetDoesNotExist
+            );
+
+            if let PruningPolicy::EmissionHistory { epochs } = policy {
+                ensure!(
+                    (1..=pallet_subtensor::MAX_PRUNING_EMISSION_EPOCHS).contains(&epochs),
+                    Error::<T>::InvalidPruningPolicy
+                );
+            }
+
+            pallet_subtensor::Pallet::<T>::set_pruning_policy(netuid, policy);
+
+            log::debug!(
+                "PruningPolicySet( netuid: {:?} policy: {:?} ) ",
+                netuid,
+                policy
+            );
+            Ok(())
+        }
+
+        /// Enables or disables the registration auction of a subnet.
+        ///
+        /// While enabled, burned registrations are replaced by bids settled at the end of each
+        /// adjustment interval. Disabling the auction refunds all open bids.
+        ///
+        /// # Parameters
+        /// - `origin`: The origin of the call, which must be the root account or subnet owner.
+        /// - `netuid`: The unique identifier for the subnet.
+        /// - `enabled`: Whether the subnet registers through an auction.
+        ///
+        /// # Errors
+        /// - `SubnetDoesNotExist`: If the specified subnet does not exist.
+        ///
+        /// # Weight
+        /// Covers refunding the worst case of `MAX_REGISTRATION_BIDS` open bids when disabling.
+        #[pallet::call_index(73)]
+        #[pallet::weight((
+            Weight::from_parts(14_000_000, 0)
+                .saturating_add(<T as frame_system::Config>::DbWeight::get().reads(3))
+                .saturating_add(<T as frame_system::Config>::DbWeight::get().writes(1))
+                .saturating_add(pallet_subtensor::Pallet::<T>::refund_registration_bids_weight()),
+            DispatchClass::Operational,
+            Pays::No
+        ))]
+        pub fn sudo_set_registration_auction_enabled(
+            origin: OriginFor<T>,
+            netuid: NetUid,
+            enabled: bool,
+        ) -> DispatchResult {
+            pallet_subtensor::Pallet::<T>::ensure_subnet_owner_or_root(origin, netuid)?;
+
+            ensure!(
+                pallet_subtensor::Pallet::<T>::if_subnet_exist(netuid),
+                Error::<T>::SubnetDoesNotExist
+            );
+
+            pallet_subtensor::Pallet::<T>::set_registration_auction_enabled(netuid, enabled);
+
+            log::debug!(
+                "RegistrationAuctionSet( netuid: {:?} enabled: {:?} ) ",
+                netuid,
+                enabled
+            );
+            Ok(())
+        }
+
+        /// Sets the share of the registration burn refunded to a hotkey deregistering from a
+        /// subnet, and the number of blocks after registration over which it decays to zero.
+        ///
+        /// # Parameters
+        /// - `origin`: The origin of the call, which must be the root account or subnet owner.
+        /// - `netuid`: The unique identifier for the subnet.
+        /// - `refund`: The share refunded right after registration, `u16::MAX` refunding it all.
+        /// - `decay`: The number of blocks after which nothing is refunded.
+        ///
+        /// # Errors
+        /// - `SubnetDoesNotExist`: If the specified subnet does not exist.
+        ///
+        /// # Weight
+        /// This function has a fixed weight of 0 and is classified as an operational transaction that does not incur any fees.
+        #[pallet::call_index(74)]
+        #[pallet::weight((0, DispatchClass::Operational, Pays::No))]
+        pub fn sudo_set_deregistration_refund(
+            origin: OriginFor<T>,
+            netuid: NetUid,
+            refund: u16,
+            decay: u64,
+        ) -> DispatchResult {
+            pallet_subtensor::Pallet::<T>::ensure_subnet_owner_or_root(origin, netuid)?;
+
+            ensure!(
+                pallet_subtensor::Pallet::<T>::if_subnet_exist(netuid),
+                Error::<T>::SubnetDoesNotExist
+            );
+
+            pallet_subtensor::Pallet::<T>::set_deregistration_refund(netuid, refund, decay);
+
+            log::debug!(
+                "DeregistrationRefundSet( netuid: {:?} refund: {:?} decay: {:?} ) ",
+                netuid,
+                refund,
+                decay
+            );
+            Ok(())
+        }
+
+        /// Sets the proof of work a subnet accepts for registration.
+        ///
+        /// The difficulty counts solving attempts, and a cuckoo cycle attempt costs about
+        /// `2^edge_bits` seal hashes. Changing the algorithm rescales the difficulty and its
+        /// minimum and maximum by the ratio of the attempt costs, so a registration costs about
+        /// the same work as before. The difficulty then adjusts to the registrations as usual.
+        ///
+        /// # Parameters
+        /// - `origin`: The origin of the call, which must be the root account or subnet owner.
+        /// - `netuid`: The unique identifier for the subnet.
+        /// - `algorithm`: The proof of work registrations must solve.
+        ///
+        /// # Errors
+        /// - `SubnetDoesNotExist`: If the specified subnet does not exist.
+        /// - `InvalidPowAlgorithm`: If the cuckoo cycle graph has fewer than
+        ///   `MIN_CUCKOO_EDGE_BITS` or more than `MAX_CUCKOO_EDGE_BITS` edge bits.
+        ///
+        /// # Weight
+        /// Covers rescaling the difficulty, classified as an operational transaction that does
+        /// not incur any fees.
+        #[pallet::call_index(75)]
+        #[pallet::weight((
+            Weight::from_parts(14_000_000, 0)
+                .saturating_add(<T as frame_system::Config>::DbWeight::get().reads(5))
+                .saturating_add(<T as frame_system::Config>::DbWeight::get().writes(4)),
+            DispatchClass::Operational,
+            Pays::No
+        ))]
+        pub fn sudo_set_pow_algorithm(
+            origin: OriginFor<T>,
+            netuid: NetUid,
+            algorithm: PowAlgorithm,
+        ) -> DispatchResult {
+            pallet_subtensor::Pallet::<T>::ensure_subnet_owner_or_root(origin, netuid)?;
+
+            ensure!(
+                pallet_subtensor::Pallet::<T>::if_subnet_exist(netuid),
+                Error::<T>::SubnetDoesNotExist
+            );
+
+            if let PowAlgorithm::CuckooCycle { edge_bits } = algorithm {
+                ensure!(
+                    (pallet_subtensor::MIN_CUCKOO_EDGE_BITS
+                        ..=pallet_subtensor::MAX_CUCKOO_EDGE_BITS)
+                        .contains(&edge_bits),
+                    Error::<T>::InvalidPowAlgorithm
+                );
+            }
+
+            pallet_subtensor::Pallet::<T>::set_pow_algorithm(netuid, algorithm);
+
+            log::debug!(
+                "PowAlgorithmSet( netuid: {:?} algorithm: {:?} ) ",
+                netuid,
+                algorithm
+            );
+            Ok(())
+        }
+
+        /// Enables or disables the registration whitelist of a subnet.
+        ///
+        /// While enabled, only whitelisted hotkeys, or hotkeys burning their registration with a
+        /// voucher signed by the subnet owner, can register.
+        ///
+        /// # Parameters
+        /// - `origin`: The origin of the call, which must be the root account or subnet owner.
+        /// - `netuid`: The unique identifier for the subnet.
+        /// - `enabled`: Whether the subnet only registers approved hotkeys.
+        ///
+        /// # Errors
+        /// - `SubnetDoesNotExist`: If the specified subnet does not exist.
+        ///
+        /// # Weight
+        /// This function has a fixed weight of 0 and is classified as an operational transaction that does not incur any fees.
+        #[pallet::call_index(76)]
+        #[pallet::weight((0, DispatchClass::Operational, Pays::No))]
+        pub fn sudo_set_registration_whitelist_enabled(
+            origin: OriginFor<T>,
+            netuid: NetUid,
+            enabled: bool,
+        ) -> DispatchResult {
+            pallet_subtensor::Pallet::<T>::ensure_subnet_owner_or_root(origin, netuid)?;
+
+            ensure!(
+                pallet_subtensor::Pallet::<T>::if_subnet_exist(netuid),
+                Error::<T>::SubnetDoesNotExist
+            );
+
+            pallet_subtensor::Pallet::<T>::set_registration_whitelist_enabled(netuid, enabled);
+
+            log::debug!(
+                "RegistrationWhitelistSet( netuid: {:?} enabled: {:?} ) ",
+                netuid,
+                enabled
+            );
+            Ok(())
+        }
+
+        /// Adds a hotkey to or removes it from the registration whitelist of a subnet.
+        ///
+        /// # Parameters
+        /// - `origin`: The origin of the call, which must be the root account or subnet owner.
+        /// - `netuid`: The unique identifier for the subnet.
+        /// - `hotkey`: The hotkey to allow or disallow.
+        /// - `coldkey`: The only coldkey the hotkey can register with, None to disallow it.
+        ///
+        /// # Errors
+        /// - `SubnetDoesNotExist`: If the specified subnet does not exist.
+        ///
+        /// # Weight
+        /// Every entry is a storage write, so the call pays fees like any other to keep the
+        /// whitelist from growing for free.
+        #[pallet::call_index(77)]
+        #[pallet::weight((
+            Weight::from_parts(14_000_000, 0)
+                .saturating_add(<T as frame_system::Config>::DbWeight::get().reads(2))
+                .saturating_add(<T as frame_system::Config>::DbWeight::get().writes(1)),
+            DispatchClass::Normal,
+            Pays::Yes
+        ))]
+        pub fn sudo_set_registration_whitelisted(
+            origin: OriginFor<T>,
+            netuid: NetUid,
+            hotkey: <T as frame_system::Config>::AccountId,
+            coldkey: Option<<T as frame_system::Config>::AccountId>,
+        ) -> DispatchResult {
+            pallet_subtensor::Pallet::<T>::ensure_subnet_owner_or_root(origin, netuid)?;
+
+            ensure!(
+                pallet_subtensor::Pallet::<T>::if_subnet_exist(netuid),
+                Error::<T>::SubnetDoesNotExist
+            );
+
+            log::debug!(
+                "RegistrationWhitelistUpdated( netuid: {:?} hotkey: {:?} coldkey: {:?} ) ",
+                netuid,
+                hotkey,
+                coldkey
+            );
+
+            pallet_subtensor::Pallet::<T>::set_registration_whitelisted(netuid, &hotkey, coldkey);
+            Ok(())
+        }
+
+        /// Sets the policy deciding how long a newly registered neuron is immune to pruning.
+        ///
+        /// # Parameters
+        /// - `origin`: The origin of the call, which must be the root account or subnet owner.
+        /// - `netuid`: The unique identifier for the subnet.
+        /// - `policy`: Whether the immunity period ends early on earned incentive, or extends until
+        ///   the neuron receives its first weights.
+        ///
+        /// # Errors
+        /// - `SubnetDoesNotExist`: If the specified subnet does not exist.
+        /// - `InvalidImmunityPolicy`: If the immunity ends after zero epochs with incentive.
+        ///
+        /// # Weight
+        /// This function has a fixed weight of 0 and is classified as an operational transaction that does not incur any fees.
+        #[pallet::call_index(78)]
+        #[pallet::weight((0, DispatchClass::Operational, Pays::No))]
+        pub fn sudo_set_immunity_policy(
+            origin: OriginFor<T>,
+            netuid: NetUid,
+            policy: ImmunityPolicy,
+        ) -> DispatchResult {
+            pallet_subtensor::Pallet::<T>::ensure_subnet_owner_or_root(origin, netuid)?;
+
+            ensure!(
+                pallet_subtensor::Pallet::<T>::if_subnet_exist(netuid),
+                Error::<T>::SubnetDoesNotExist
+            );
+
+            ensure!(
+                policy != ImmunityPolicy::EarnedIncentive { epochs: 0 },
+                Error::<T>::InvalidImmunityPolicy
+            );
+
+            pallet_subtensor::Pallet::<T>::set_immunity_policy(netuid, policy);
+
+            log::debug!(
+                "ImmunityPolicySet( netuid: {:?} policy: {:?} ) ",
+                netuid,
+                policy
+            );
+            Ok(())
+        }
+
         /// Sets or updates the hotkey account associated with the owner of a specific subnet.
         ///
         /// This function allows either the root origin or the current subnet owner to set or update
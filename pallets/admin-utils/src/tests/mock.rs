@@ -4,7 +4,7 @@ use core::num::NonZeroU64;
 
 use frame_support::{
     PalletId, assert_ok, derive_impl, parameter_types,
-    traits::{Everything, Hooks, InherentBuilder, PrivilegeCmp},
+    traits::{Everything, Hooks, InherentBuilder, PrivilegeCmp, UnixTime},
 };
 use frame_system::{self as system, offchain::CreateTransactionBase};
 use frame_system::{EnsureNever, EnsureRoot, limits};
@@ -328,6 +328,10 @@ parameter_types! {
     pub const SwapMaxPositions: u32 = 100;
     pub const SwapMinimumLiquidity: u64 = 1_000;
     pub const SwapMinimumReserve: NonZeroU64 = NonZeroU64::new(1_000_000).unwrap();
+    pub const SwapPositionAutomationInterval: u32 = 100;
+    pub const SwapMaxAutomatedPositionsPerRun: u32 = 20;
+    pub const SwapMinProtocolBandWidth: u32 = 2_000; // about ±10% of the price
+    pub SwapProtocolFullRangeShare: Perbill = Perbill::from_percent(20);
 }
 
 impl pallet_subtensor_swap::Config for Test {
@@ -339,6 +343,10 @@ impl pallet_subtensor_swap::Config for Test {
     type MaxPositions = SwapMaxPositions;
     type MinimumLiquidity = SwapMinimumLiquidity;
     type MinimumReserve = SwapMinimumReserve;
+    type PositionAutomationInterval = SwapPositionAutomationInterval;
+    type MaxAutomatedPositionsPerRun = SwapMaxAutomatedPositionsPerRun;
+    type MinProtocolBandWidth = SwapMinProtocolBandWidth;
+    type ProtocolFullRangeShare = SwapProtocolFullRangeShare;
     type WeightInfo = ();
 }
 
@@ -397,6 +405,18 @@ impl pallet_drand::Config for Test {
     type Verifier = pallet_drand::verifier::QuicknetVerifier;
     type UnsignedPriority = ConstU64<{ 1 << 20 }>;
     type HttpFetchTimeout = ConstU64<1_000>;
+    type PulseRetention = ConstU64<1_000>;
+    type OnPulsesStored = SubtensorModule;
+    type UnixTime = MockUnixTime;
+}
+
+/// The time of every block, the drand genesis.
+pub struct MockUnixTime;
+
+impl UnixTime for MockUnixTime {
+    fn now() -> core::time::Duration {
+        core::time::Duration::ZERO
+    }
 }
 
 impl frame_system::offchain::SigningTypes for Test {
@@ -5,13 +5,17 @@ use frame_support::{
     traits::Hooks,
 };
 use frame_system::Config;
-use pallet_subtensor::{Error as SubtensorError, SubnetOwner, Tempo, WeightsVersionKeyRateLimit};
+use pallet_subtensor::{
+    Error as SubtensorError, ImmunityPolicy, PowAlgorithm, PruningPolicy, RegistrationWhitelist,
+    SubnetOwner, Tempo, WeightsVersionKeyRateLimit,
+};
 // use pallet_subtensor::{migrations, Event};
 use pallet_subtensor::Event;
 use sp_consensus_grandpa::AuthorityId as GrandpaId;
 use sp_core::{Get, Pair, U256, ed25519};
 use substrate_fixed::types::I96F32;
 use subtensor_runtime_common::NetUid;
+use subtensor_swap_interface::ProtocolLiquidityPolicy;
 
 use crate::Error;
 use crate::pallet::PrecompileEnable;
@@ -1924,3 +1928,383 @@ fn test_sudo_set_yuma3_enabled() {
         assert_eq!(SubtensorModule::get_yuma3_enabled(netuid), !to_be_set);
     });
 }
+
+#[test]
+fn test_sudo_set_protocol_liquidity_policy() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+        let sn_owner = U256::from(1);
+        let policy = ProtocolLiquidityPolicy::Concentrated { width: 10_000 };
+        add_network(netuid, 10);
+
+        assert_eq!(
+            AdminUtils::sudo_set_protocol_liquidity_policy(
+                <<Test as Config>::RuntimeOrigin>::signed(U256::from(1)),
+                netuid,
+                policy.clone()
+            ),
+            Err(DispatchError::BadOrigin)
+        );
+
+        assert_noop!(
+            AdminUtils::sudo_set_protocol_liquidity_policy(
+                <<Test as Config>::RuntimeOrigin>::root(),
+                netuid,
+                ProtocolLiquidityPolicy::Concentrated { width: 1 }
+            ),
+            pallet_subtensor_swap::Error::<Test>::InvalidProtocolLiquidityPolicy
+        );
+
+        assert_ok!(AdminUtils::sudo_set_protocol_liquidity_policy(
+            <<Test as Config>::RuntimeOrigin>::root(),
+            netuid,
+            policy.clone()
+        ));
+        assert_eq!(
+            pallet_subtensor_swap::ProtocolLiquidityPolicies::<Test>::get(netuid),
+            policy
+        );
+
+        SubnetOwner::<Test>::insert(netuid, sn_owner);
+
+        assert_ok!(AdminUtils::sudo_set_protocol_liquidity_policy(
+            <<Test as Config>::RuntimeOrigin>::signed(sn_owner),
+            netuid,
+            ProtocolLiquidityPolicy::FullRange
+        ));
+        assert_eq!(
+            pallet_subtensor_swap::ProtocolLiquidityPolicies::<Test>::get(netuid),
+            ProtocolLiquidityPolicy::FullRange
+        );
+
+        // The subnet owner is rate limited
+        assert_noop!(
+            AdminUtils::sudo_set_protocol_liquidity_policy(
+                <<Test as Config>::RuntimeOrigin>::signed(sn_owner),
+                netuid,
+                policy
+            ),
+            SubtensorError::<Test>::TxRateLimitExceeded
+        );
+    });
+}
+
+#[test]
+fn test_sudo_set_pruning_policy() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+        let sn_owner = U256::from(1);
+        let policy = PruningPolicy::EmissionHistory { epochs: 5 };
+        add_network(netuid, 10);
+        assert_eq!(
+            SubtensorModule::get_pruning_policy(netuid),
+            PruningPolicy::PruningScore
+        );
+
+        assert_eq!(
+            AdminUtils::sudo_set_pruning_policy(
+                <<Test as Config>::RuntimeOrigin>::signed(U256::from(1)),
+                netuid,
+                policy
+            ),
+            Err(DispatchError::BadOrigin)
+        );
+
+        for epochs in [0, pallet_subtensor::MAX_PRUNING_EMISSION_EPOCHS + 1] {
+            assert_noop!(
+                AdminUtils::sudo_set_pruning_policy(
+                    <<Test as Config>::RuntimeOrigin>::root(),
+                    netuid,
+                    PruningPolicy::EmissionHistory { epochs }
+                ),
+                Error::<Test>::InvalidPruningPolicy
+            );
+        }
+
+        assert_ok!(AdminUtils::sudo_set_pruning_policy(
+            <<Test as Config>::RuntimeOrigin>::root(),
+            netuid,
+            policy
+        ));
+        assert_eq!(SubtensorModule::get_pruning_policy(netuid), policy);
+
+        SubnetOwner::<Test>::insert(netuid, sn_owner);
+
+        assert_ok!(AdminUtils::sudo_set_pruning_policy(
+            <<Test as Config>::RuntimeOrigin>::signed(sn_owner),
+            netuid,
+            PruningPolicy::ProtectValidators
+        ));
+        assert_eq!(
+            SubtensorModule::get_pruning_policy(netuid),
+            PruningPolicy::ProtectValidators
+        );
+    });
+}
+
+#[test]
+fn test_sudo_set_registration_auction_enabled() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+        let sn_owner = U256::from(1);
+        add_network(netuid, 10);
+        assert!(!SubtensorModule::get_registration_auction_enabled(netuid));
+
+        assert_eq!(
+            AdminUtils::sudo_set_registration_auction_enabled(
+                <<Test as Config>::RuntimeOrigin>::signed(U256::from(1)),
+                netuid,
+                true
+            ),
+            Err(DispatchError::BadOrigin)
+        );
+
+        assert_noop!(
+            AdminUtils::sudo_set_registration_auction_enabled(
+                <<Test as Config>::RuntimeOrigin>::root(),
+                NetUid::from(2),
+                true
+            ),
+            Error::<Test>::SubnetDoesNotExist
+        );
+
+        assert_ok!(AdminUtils::sudo_set_registration_auction_enabled(
+            <<Test as Config>::RuntimeOrigin>::root(),
+            netuid,
+            true
+        ));
+        assert!(SubtensorModule::get_registration_auction_enabled(netuid));
+
+        SubnetOwner::<Test>::insert(netuid, sn_owner);
+
+        assert_ok!(AdminUtils::sudo_set_registration_auction_enabled(
+            <<Test as Config>::RuntimeOrigin>::signed(sn_owner),
+            netuid,
+            false
+        ));
+        assert!(!SubtensorModule::get_registration_auction_enabled(netuid));
+    });
+}
+
+#[test]
+fn test_sudo_set_deregistration_refund() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+        let sn_owner = U256::from(1);
+        add_network(netuid, 10);
+
+        assert_eq!(
+            AdminUtils::sudo_set_deregistration_refund(
+                <<Test as Config>::RuntimeOrigin>::signed(U256::from(1)),
+                netuid,
+                u16::MAX / 2,
+                1000
+            ),
+            Err(DispatchError::BadOrigin)
+        );
+
+        assert_ok!(AdminUtils::sudo_set_deregistration_refund(
+            <<Test as Config>::RuntimeOrigin>::root(),
+            netuid,
+            u16::MAX / 2,
+            1000
+        ));
+        assert_eq!(
+            SubtensorModule::get_deregistration_refund(netuid),
+            u16::MAX / 2
+        );
+        assert_eq!(
+            SubtensorModule::get_deregistration_refund_decay(netuid),
+            1000
+        );
+
+        SubnetOwner::<Test>::insert(netuid, sn_owner);
+
+        assert_ok!(AdminUtils::sudo_set_deregistration_refund(
+            <<Test as Config>::RuntimeOrigin>::signed(sn_owner),
+            netuid,
+            0,
+            1000
+        ));
+        assert_eq!(SubtensorModule::get_deregistration_refund(netuid), 0);
+    });
+}
+
+#[test]
+fn test_sudo_set_pow_algorithm() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+        let sn_owner = U256::from(1);
+        let algorithm = PowAlgorithm::CuckooCycle { edge_bits: 20 };
+        add_network(netuid, 10);
+        assert_eq!(
+            SubtensorModule::get_pow_algorithm(netuid),
+            PowAlgorithm::Seal
+        );
+
+        assert_eq!(
+            AdminUtils::sudo_set_pow_algorithm(
+                <<Test as Config>::RuntimeOrigin>::signed(U256::from(1)),
+                netuid,
+                algorithm
+            ),
+            Err(DispatchError::BadOrigin)
+        );
+
+        for edge_bits in [
+            pallet_subtensor::MIN_CUCKOO_EDGE_BITS - 1,
+            pallet_subtensor::MAX_CUCKOO_EDGE_BITS + 1,
+        ] {
+            assert_noop!(
+                AdminUtils::sudo_set_pow_algorithm(
+                    <<Test as Config>::RuntimeOrigin>::root(),
+                    netuid,
+                    PowAlgorithm::CuckooCycle { edge_bits }
+                ),
+                Error::<Test>::InvalidPowAlgorithm
+            );
+        }
+
+        SubtensorModule::set_difficulty(netuid, 10_000_000);
+        SubtensorModule::set_min_difficulty(netuid, 1_000);
+        SubtensorModule::set_max_difficulty(netuid, u64::MAX);
+        assert_ok!(AdminUtils::sudo_set_pow_algorithm(
+            <<Test as Config>::RuntimeOrigin>::root(),
+            netuid,
+            algorithm
+        ));
+        assert_eq!(SubtensorModule::get_pow_algorithm(netuid), algorithm);
+
+        // The difficulty is rescaled to the cost of a cycle attempt, at least one
+        assert_eq!(
+            SubtensorModule::get_difficulty_as_u64(netuid),
+            10_000_000 >> 20
+        );
+        assert_eq!(SubtensorModule::get_min_difficulty(netuid), 1);
+        assert_eq!(SubtensorModule::get_max_difficulty(netuid), u64::MAX >> 20);
+
+        SubnetOwner::<Test>::insert(netuid, sn_owner);
+
+        assert_ok!(AdminUtils::sudo_set_pow_algorithm(
+            <<Test as Config>::RuntimeOrigin>::signed(sn_owner),
+            netuid,
+            PowAlgorithm::Seal
+        ));
+        assert_eq!(
+            SubtensorModule::get_pow_algorithm(netuid),
+            PowAlgorithm::Seal
+        );
+        assert_eq!(
+            SubtensorModule::get_difficulty_as_u64(netuid),
+            (10_000_000 >> 20) << 20
+        );
+    });
+}
+
+#[test]
+fn test_sudo_set_registration_whitelist() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+        let sn_owner = U256::from(1);
+        let hotkey = U256::from(2);
+        let coldkey = U256::from(3);
+        add_network(netuid, 10);
+        assert!(!SubtensorModule::get_registration_whitelist_enabled(netuid));
+
+        assert_eq!(
+            AdminUtils::sudo_set_registration_whitelist_enabled(
+                <<Test as Config>::RuntimeOrigin>::signed(sn_owner),
+                netuid,
+                true
+            ),
+            Err(DispatchError::BadOrigin)
+        );
+        assert_noop!(
+            AdminUtils::sudo_set_registration_whitelisted(
+                <<Test as Config>::RuntimeOrigin>::root(),
+                NetUid::from(2),
+                hotkey,
+                Some(coldkey)
+            ),
+            Error::<Test>::SubnetDoesNotExist
+        );
+
+        assert_ok!(AdminUtils::sudo_set_registration_whitelist_enabled(
+            <<Test as Config>::RuntimeOrigin>::root(),
+            netuid,
+            true
+        ));
+        assert!(SubtensorModule::get_registration_whitelist_enabled(netuid));
+
+        SubnetOwner::<Test>::insert(netuid, sn_owner);
+
+        assert_ok!(AdminUtils::sudo_set_registration_whitelisted(
+            <<Test as Config>::RuntimeOrigin>::signed(sn_owner),
+            netuid,
+            hotkey,
+            Some(coldkey)
+        ));
+        assert_eq!(
+            RegistrationWhitelist::<Test>::get(netuid, hotkey),
+            Some(coldkey)
+        );
+
+        assert_ok!(AdminUtils::sudo_set_registration_whitelisted(
+            <<Test as Config>::RuntimeOrigin>::signed(sn_owner),
+            netuid,
+            hotkey,
+            None
+        ));
+        assert!(!RegistrationWhitelist::<Test>::contains_key(netuid, hotkey));
+    });
+}
+
+#[test]
+fn test_sudo_set_immunity_policy() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+        let sn_owner = U256::from(1);
+        let policy = ImmunityPolicy::AwaitFirstWeights { max_extension: 100 };
+        add_network(netuid, 10);
+        assert_eq!(
+            SubtensorModule::get_immunity_policy(netuid),
+            ImmunityPolicy::Blocks
+        );
+
+        assert_eq!(
+            AdminUtils::sudo_set_immunity_policy(
+                <<Test as Config>::RuntimeOrigin>::signed(sn_owner),
+                netuid,
+                policy
+            ),
+            Err(DispatchError::BadOrigin)
+        );
+        assert_noop!(
+            AdminUtils::sudo_set_immunity_policy(
+                <<Test as Config>::RuntimeOrigin>::root(),
+                netuid,
+                ImmunityPolicy::EarnedIncentive { epochs: 0 }
+            ),
+            Error::<Test>::InvalidImmunityPolicy
+        );
+
+        assert_ok!(AdminUtils::sudo_set_immunity_policy(
+            <<Test as Config>::RuntimeOrigin>::root(),
+            netuid,
+            policy
+        ));
+        assert_eq!(SubtensorModule::get_immunity_policy(netuid), policy);
+
+        SubnetOwner::<Test>::insert(netuid, sn_owner);
+
+        assert_ok!(AdminUtils::sudo_set_immunity_policy(
+            <<Test as Config>::RuntimeOrigin>::signed(sn_owner),
+            netuid,
+            ImmunityPolicy::EarnedIncentive { epochs: 3 }
+        ));
+        assert_eq!(
+            SubtensorModule::get_immunity_policy(netuid),
+            ImmunityPolicy::EarnedIncentive { epochs: 3 }
+        );
+    });
+}
@@ -14,19 +14,13 @@ pub use pallet::*;
 pub use types::*;
 pub use weights::WeightInfo;
 
-use ark_serialize::CanonicalDeserialize;
 use frame_support::{BoundedVec, traits::Currency};
+use pallet_drand::timelock::TimelockError;
 use scale_info::prelude::collections::BTreeSet;
 use sp_runtime::SaturatedConversion;
 use sp_runtime::{Saturating, traits::Zero};
 use sp_std::{boxed::Box, vec::Vec};
 use subtensor_runtime_common::NetUid;
-use tle::{
-    curves::drand::TinyBLS381,
-    stream_ciphers::AESGCMStreamCipherProvider,
-    tlock::{TLECiphertext, tld},
-};
-use w3f_bls::EngineBLS;
 
 type BalanceOf<T> =
     <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
@@ -37,7 +31,11 @@ pub mod pallet {
     use frame_support::{pallet_prelude::*, traits::ReservableCurrency};
     use frame_system::pallet_prelude::{BlockNumberFor, *};
 
+    /// The in-code storage version.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     #[pallet::without_storage_info]
     pub struct Pallet<T>(_);
 
@@ -119,6 +117,8 @@ pub mod pallet {
         SpaceLimitExceeded,
         /// Indicates that unreserve returned a leftover, which is unexpected.
         UnexpectedUnreserveLeftover,
+        /// No drand beacon is registered with the id a field is timelocked to
+        BeaconNotRegistered,
     }
 
     /// Tracks all CommitmentOf that have at least one timelocked field.
@@ -247,6 +247,16 @@ pub mod pallet {
                 usage.used_space = 0;
             }
 
+            // Ensure the beacons the fields are timelocked to are registered
+            for (_, _, beacon_id) in info.fields.iter().filter_map(Data::timelock) {
+                if let Some(beacon_id) = beacon_id {
+                    ensure!(
+                        pallet_drand::Beacons::<T>::contains_key(beacon_id),
+                        Error::<T>::BeaconNotRegistered
+                    );
+                }
+            }
+
             // check if ResetBondsFlag is set in the fields
             for field in info.fields.iter() {
                 if let Data::ResetBondsFlag = field {
@@ -269,6 +279,7 @@ pub mod pallet {
 
             let mut id = match <CommitmentOf<T>>::get(netuid, &who) {
                 Some(mut id) => {
+                    Self::release_timelocked_rounds(&id.info.fields);
                     id.info = *info.clone();
                     id.block = cur_block;
                     id
@@ -294,18 +305,16 @@ pub mod pallet {
                 }
             }
 
+            Self::hold_timelocked_rounds(&id.info.fields);
             <CommitmentOf<T>>::insert(netuid, &who, id);
             <LastCommitment<T>>::insert(netuid, &who, cur_block);
 
-            if let Some(Data::TimelockEncrypted { reveal_round, .. }) = info
-                .fields
-                .iter()
-                .find(|data| matches!(data, Data::TimelockEncrypted { .. }))
+            if let Some((_, reveal_round, _)) = info.fields.iter().find_map(|data| data.timelock())
             {
                 Self::deposit_event(Event::TimelockCommitment {
                     netuid,
                     who: who.clone(),
-                    reveal_round: *reveal_round,
+                    reveal_round,
                 });
 
                 TimelockedIndex::<T>::mutate(|index| {
@@ -368,6 +377,26 @@ pub mod pallet {
             }
             Weight::from_parts(0, 0)
         }
+
+        fn on_runtime_upgrade() -> Weight {
+            let mut weight = T::DbWeight::get().reads(1);
+            if Pallet::<T>::on_chain_storage_version() >= STORAGE_VERSION {
+                return weight;
+            }
+
+            // Hold the drand rounds the timelocked commitments made before they were tracked
+            // wait on
+            let index = TimelockedIndex::<T>::get();
+            for (netuid, who) in index.iter() {
+                if let Some(registration) = <CommitmentOf<T>>::get(netuid, who) {
+                    Self::hold_timelocked_rounds(&registration.info.fields);
+                }
+                weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+            }
+
+            STORAGE_VERSION.put::<Pallet<T>>();
+            weight.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+        }
     }
 }
 
@@ -418,79 +447,35 @@ impl<T: Config> Pallet<T> {
             let mut revealed_fields = Vec::new();
 
             for data in original_fields {
-                match data {
-                    Data::TimelockEncrypted {
-                        encrypted,
-                        reveal_round,
-                    } => {
-                        let pulse = match pallet_drand::Pulses::<T>::get(reveal_round) {
-                            Some(p) => p,
-                            None => {
-                                remain_fields.push(Data::TimelockEncrypted {
-                                    encrypted,
-                                    reveal_round,
-                                });
-                                continue;
-                            }
-                        };
-
-                        let signature_bytes = pulse
-                            .signature
-                            .strip_prefix(b"0x")
-                            .unwrap_or(&pulse.signature);
-                        let sig_reader = &mut &signature_bytes[..];
-                        let sig =
-                            <TinyBLS381 as EngineBLS>::SignatureGroup::deserialize_compressed(
-                                sig_reader,
-                            )
-                            .map_err(|e| {
-                                log::warn!(
-                                    "Failed to deserialize drand signature for {:?}: {:?}",
-                                    who,
-                                    e
-                                )
-                            })
-                            .ok();
-
-                        let Some(sig) = sig else {
-                            log::warn!("No sig after deserialization");
-                            continue;
-                        };
-
-                        let reader = &mut &encrypted[..];
-                        let commit = TLECiphertext::<TinyBLS381>::deserialize_compressed(reader)
-                            .map_err(|e| {
-                                log::warn!(
-                                    "Failed to deserialize TLECiphertext for {:?}: {:?}",
-                                    who,
-                                    e
-                                )
-                            })
-                            .ok();
-
-                        let Some(commit) = commit else {
-                            log::warn!("No commit after deserialization");
-                            continue;
-                        };
-
-                        let decrypted_bytes: Vec<u8> =
-                            tld::<TinyBLS381, AESGCMStreamCipherProvider>(commit, sig)
-                                .map_err(|e| {
-                                    log::warn!("Failed to decrypt timelock for {:?}: {:?}", who, e)
-                                })
-                                .ok()
-                                .unwrap_or_default();
-
-                        if decrypted_bytes.is_empty() {
-                            log::warn!("Bytes were decrypted for {:?} but they are empty", who);
-                            continue;
-                        }
-
-                        revealed_fields.push(decrypted_bytes);
+                let Some((encrypted, reveal_round, beacon_id)) = data.timelock() else {
+                    remain_fields.push(data);
+                    continue;
+                };
+
+                let decrypted =
+                    pallet_drand::Pallet::<T>::timelock_decrypt(beacon_id, reveal_round, encrypted);
+                // The field leaves the commitment unless its pulse is not stored yet
+                if !matches!(decrypted, Err(TimelockError::MissingPulse)) {
+                    pallet_drand::Pallet::<T>::release_reveal_round(beacon_id, reveal_round);
+                }
+                let decrypted_bytes = match decrypted {
+                    Ok(bytes) => bytes,
+                    Err(TimelockError::MissingPulse) => {
+                        remain_fields.push(data);
+                        continue;
                     }
+                    Err(e) => {
+                        log::warn!("Failed to decrypt timelock for {:?}: {:?}", who, e);
+                        continue;
+                    }
+                };
 
-                    other => remain_fields.push(other),
+                if decrypted_bytes.is_empty() {
+                    log::warn!("Bytes were decrypted for {:?} but they are empty", who);
+                    continue;
                 }
+
+                revealed_fields.push(decrypted_bytes);
             }
 
             if !revealed_fields.is_empty() {
@@ -535,7 +520,7 @@ impl<T: Config> Pallet<T> {
                         .info
                         .fields
                         .iter()
-                        .any(|f| matches!(f, Data::TimelockEncrypted { .. }));
+                        .any(|f| f.is_timelock_encrypted());
                     if !has_timelock {
                         TimelockedIndex::<T>::mutate(|idx| {
                             idx.remove(&(netuid, who.clone()));
@@ -547,4 +532,18 @@ impl<T: Config> Pallet<T> {
 
         Ok(())
     }
+
+    /// Keeps the pulses of the drand rounds the timelocked fields are revealed with.
+    fn hold_timelocked_rounds(fields: &[Data]) {
+        for (_, reveal_round, beacon_id) in fields.iter().filter_map(Data::timelock) {
+            pallet_drand::Pallet::<T>::hold_reveal_round(beacon_id, reveal_round);
+        }
+    }
+
+    /// Releases the drand rounds of the timelocked fields that leave the commitment.
+    fn release_timelocked_rounds(fields: &[Data]) {
+        for (_, reveal_round, beacon_id) in fields.iter().filter_map(Data::timelock) {
+            pallet_drand::Pallet::<T>::release_reveal_round(beacon_id, reveal_round);
+        }
+    }
 }
@@ -2,7 +2,7 @@ use crate as pallet_commitments;
 use frame_support::{
     derive_impl,
     pallet_prelude::{Get, TypeInfo},
-    traits::{ConstU32, ConstU64, InherentBuilder},
+    traits::{ConstU32, ConstU64, InherentBuilder, UnixTime},
 };
 use frame_system::offchain::CreateTransactionBase;
 use sp_core::H256;
@@ -123,6 +123,18 @@ impl pallet_drand::Config for Test {
     type Verifier = pallet_drand::verifier::QuicknetVerifier;
     type UnsignedPriority = ConstU64<{ 1 << 20 }>;
     type HttpFetchTimeout = ConstU64<1_000>;
+    type PulseRetention = ConstU64<1_000>;
+    type OnPulsesStored = ();
+    type UnixTime = MockUnixTime;
+}
+
+/// The time of every block, the drand genesis.
+pub struct MockUnixTime;
+
+impl UnixTime for MockUnixTime {
+    fn now() -> core::time::Duration {
+        core::time::Duration::ZERO
+    }
 }
 
 pub mod test_crypto {
@@ -255,11 +267,20 @@ pub fn produce_ciphertext(
     plaintext: &[u8],
     round: u64,
 ) -> BoundedVec<u8, ConstU32<MAX_TIMELOCK_COMMITMENT_SIZE_BYTES>> {
-    // 1) Deserialize the known Drand Quicknet public key:
     let pub_key_bytes = hex::decode(DRAND_QUICKNET_PUBKEY_HEX).expect("decode pubkey");
-    let pub_key =
-        <TinyBLS381 as EngineBLS>::PublicKeyGroup::deserialize_compressed(&pub_key_bytes[..])
-            .expect("bad pubkey bytes");
+    produce_ciphertext_with_key(&pub_key_bytes, plaintext, round)
+}
+
+/// Produces a ciphertext by TLE-encrypting `plaintext` for `round` of the beacon with the given
+/// compressed public key, such as the `pallet_drand::mock_beacon` one.
+pub fn produce_ciphertext_with_key(
+    pub_key_bytes: &[u8],
+    plaintext: &[u8],
+    round: u64,
+) -> BoundedVec<u8, ConstU32<MAX_TIMELOCK_COMMITMENT_SIZE_BYTES>> {
+    // 1) Deserialize the beacon public key:
+    let pub_key = <TinyBLS381 as EngineBLS>::PublicKeyGroup::deserialize_compressed(pub_key_bytes)
+        .expect("bad pubkey bytes");
 
     // 2) Prepare the identity for that round
     //    by hashing round.to_be_bytes() with SHA256:
@@ -9,6 +9,7 @@ use crate::{
     mock::{
         Balances, DRAND_QUICKNET_SIG_2000_HEX, DRAND_QUICKNET_SIG_HEX, RuntimeEvent, RuntimeOrigin,
         Test, TestMaxFields, insert_drand_pulse, new_test_ext, produce_ciphertext,
+        produce_ciphertext_with_key,
     },
 };
 use frame_support::pallet_prelude::Hooks;
@@ -37,6 +38,7 @@ fn manual_data_type_info() {
             Data::TimelockEncrypted { .. } => "TimelockEncrypted".to_string(),
             Data::ResetBondsFlag => "ResetBondsFlag".to_string(),
             Data::BigRaw(_) => "BigRaw".to_string(),
+            Data::BeaconTimelockEncrypted { .. } => "BeaconTimelockEncrypted".to_string(),
         };
         if let scale_info::TypeDef::Variant(variant) = &type_info.type_def {
             let variant = variant
@@ -67,6 +69,15 @@ fn manual_data_type_info() {
                         let reveal_round_len = reveal_round.encode().len() as u32; // Typically 8 bytes
                         encrypted_len + reveal_round_len
                     }
+                    Data::BeaconTimelockEncrypted {
+                        encrypted,
+                        reveal_round,
+                        beacon_id,
+                    } => {
+                        encrypted.encode().len() as u32
+                            + reveal_round.encode().len() as u32
+                            + beacon_id.encode().len() as u32
+                    }
                     Data::ResetBondsFlag => 0,
                 };
                 assert_eq!(
@@ -112,6 +123,13 @@ fn manual_data_type_info() {
         reveal_round: 12345,
     });
 
+    // Add a BeaconTimelockEncrypted instance
+    data.push(Data::BeaconTimelockEncrypted {
+        encrypted: vec![0u8; 64].try_into().expect("Expected not to panic"),
+        reveal_round: 12345,
+        beacon_id: b"mock".to_vec().try_into().expect("Expected not to panic"),
+    });
+
     for d in data.iter() {
         check_type_info(d);
     }
@@ -296,6 +314,131 @@ fn happy_path_timelock_commitments() {
     });
 }
 
+#[allow(clippy::indexing_slicing)]
+#[test]
+fn timelock_commitment_revealed_with_mock_beacon() {
+    new_test_ext().execute_with(|| {
+        let message_text = b"Hello mock beacon!";
+        let inner_info: CommitmentInfo<<Test as Config>::MaxFields> = CommitmentInfo {
+            fields: BoundedVec::try_from(vec![Data::Raw(
+                message_text.to_vec().try_into().expect("<= 128 bytes"),
+            )])
+            .expect("Too many fields"),
+        };
+
+        let reveal_round = 4321;
+        let encrypted = produce_ciphertext_with_key(
+            &pallet_drand::mock_beacon::public_key(),
+            &inner_info.encode(),
+            reveal_round,
+        );
+        let info_outer = CommitmentInfo {
+            fields: BoundedVec::try_from(vec![Data::TimelockEncrypted {
+                encrypted,
+                reveal_round,
+            }])
+            .expect("Too many fields"),
+        };
+
+        let who = 123;
+        let netuid = NetUid::from(42);
+        assert_ok!(Pallet::<Test>::set_commitment(
+            RuntimeOrigin::signed(who),
+            netuid,
+            Box::new(info_outer)
+        ));
+
+        let pulse = pallet_drand::mock_beacon::pulse(reveal_round).expect("Mock beacon pulse");
+        pallet_drand::Pulses::<Test>::insert(reveal_round, pulse);
+
+        System::<Test>::set_block_number(9999);
+        assert_ok!(Pallet::<Test>::reveal_timelocked_commitments());
+
+        let revealed =
+            RevealedCommitments::<Test>::get(netuid, who).expect("Should have revealed data");
+        let (revealed_bytes, _reveal_block) = revealed[0].clone();
+        assert!(
+            revealed_bytes
+                .windows(message_text.len())
+                .any(|window| window == message_text),
+            "Revealed data must contain the original message text."
+        );
+    });
+}
+
+#[allow(clippy::indexing_slicing)]
+#[test]
+fn timelock_commitment_revealed_with_registered_beacon() {
+    new_test_ext().execute_with(|| {
+        let message_text = b"Hello registered beacon!";
+        let inner_info: CommitmentInfo<<Test as Config>::MaxFields> = CommitmentInfo {
+            fields: BoundedVec::try_from(vec![Data::Raw(
+                message_text.to_vec().try_into().expect("<= 128 bytes"),
+            )])
+            .expect("Too many fields"),
+        };
+
+        let config = pallet_drand::Pallet::<Test>::mock_beacon_config();
+        let beacon_id = config.metadata.beacon_id.clone();
+        let reveal_round = 4321;
+        let encrypted = produce_ciphertext_with_key(
+            &pallet_drand::mock_beacon::public_key(),
+            &inner_info.encode(),
+            reveal_round,
+        );
+        let info_outer = Box::new(CommitmentInfo {
+            fields: BoundedVec::try_from(vec![Data::BeaconTimelockEncrypted {
+                encrypted,
+                reveal_round,
+                beacon_id: beacon_id.clone(),
+            }])
+            .expect("Too many fields"),
+        });
+
+        let who = 123;
+        let netuid = NetUid::from(42);
+        assert_noop!(
+            Pallet::<Test>::set_commitment(RuntimeOrigin::signed(who), netuid, info_outer.clone()),
+            Error::<Test>::BeaconNotRegistered
+        );
+
+        pallet_drand::Beacons::<Test>::insert(&beacon_id, config);
+        assert_ok!(Pallet::<Test>::set_commitment(
+            RuntimeOrigin::signed(who),
+            netuid,
+            info_outer
+        ));
+        assert_eq!(
+            pallet_drand::PendingReveals::<Test>::get(Some(beacon_id.clone()), reveal_round),
+            1
+        );
+
+        // The pulse of the main beacon does not reveal it
+        let pulse = pallet_drand::mock_beacon::pulse(reveal_round).expect("Mock beacon pulse");
+        pallet_drand::Pulses::<Test>::insert(reveal_round, pulse.clone());
+        System::<Test>::set_block_number(9999);
+        assert_ok!(Pallet::<Test>::reveal_timelocked_commitments());
+        assert!(RevealedCommitments::<Test>::get(netuid, who).is_none());
+
+        pallet_drand::BeaconPulses::<Test>::insert(&beacon_id, reveal_round, pulse);
+        assert_ok!(Pallet::<Test>::reveal_timelocked_commitments());
+
+        let revealed =
+            RevealedCommitments::<Test>::get(netuid, who).expect("Should have revealed data");
+        let (revealed_bytes, _reveal_block) = revealed[0].clone();
+        assert!(
+            revealed_bytes
+                .windows(message_text.len())
+                .any(|window| window == message_text),
+            "Revealed data must contain the original message text."
+        );
+        assert!(!pallet_drand::PendingReveals::<Test>::contains_key(
+            Some(beacon_id),
+            reveal_round
+        ));
+    });
+}
+
 #[test]
 fn reveal_timelocked_commitment_missing_round_does_nothing() {
     new_test_ext().execute_with(|| {
@@ -20,6 +20,7 @@ use frame_support::{
     BoundedVec, CloneNoBound, PartialEqNoBound, RuntimeDebugNoBound,
     traits::{ConstU32, Get},
 };
+use pallet_drand::types::BeaconId;
 use scale_info::{
     Path, Type, TypeInfo,
     build::{Fields, Variants},
@@ -35,7 +36,7 @@ use subtensor_macros::freeze_struct;
 /// - `Raw`: a direct blob up to 128 bytes
 /// - `BigRaw`: a larger blob up to 512 bytes
 /// - A cryptographic hash (BlakeTwo256, Sha256, Keccak256, ShaThree256)
-/// - A timelock-encrypted blob with a reveal round
+/// - A timelock-encrypted blob with a reveal round, of the main drand beacon or of a registered one
 /// - A reset flag (`ResetBondsFlag`)
 ///   Can also be `None`.
 #[derive(Clone, Eq, PartialEq, RuntimeDebug, MaxEncodedLen)]
@@ -65,6 +66,13 @@ pub enum Data {
     ResetBondsFlag,
     /// The data is stored directly (up to 512 bytes).
     BigRaw(BoundedVec<u8, ConstU32<MAX_BIGRAW_COMMITMENT_SIZE_BYTES>>),
+    /// A timelock-encrypted commitment with a reveal round of the registered drand beacon with
+    /// the id.
+    BeaconTimelockEncrypted {
+        encrypted: BoundedVec<u8, ConstU32<MAX_TIMELOCK_COMMITMENT_SIZE_BYTES>>,
+        reveal_round: u64,
+        beacon_id: BeaconId,
+    },
 }
 
 impl Data {
@@ -74,7 +82,24 @@ impl Data {
 
     /// Check if this is a timelock-encrypted commitment.
     pub fn is_timelock_encrypted(&self) -> bool {
-        matches!(self, Data::TimelockEncrypted { .. })
+        self.timelock().is_some()
+    }
+
+    /// The ciphertext, reveal round and beacon of a timelock-encrypted commitment, the beacon
+    /// being `None` for the main one.
+    pub fn timelock(&self) -> Option<(&[u8], u64, Option<&BeaconId>)> {
+        match self {
+            Data::TimelockEncrypted {
+                encrypted,
+                reveal_round,
+            } => Some((encrypted, *reveal_round, None)),
+            Data::BeaconTimelockEncrypted {
+                encrypted,
+                reveal_round,
+                beacon_id,
+            } => Some((encrypted, *reveal_round, Some(beacon_id))),
+            _ => None,
+        }
     }
 
     pub fn len_for_rate_limit(&self) -> u64 {
@@ -85,7 +110,8 @@ impl Data {
             | Data::Sha256(arr)
             | Data::Keccak256(arr)
             | Data::ShaThree256(arr) => arr.len() as u64,
-            Data::TimelockEncrypted { encrypted, .. } => encrypted.len() as u64,
+            Data::TimelockEncrypted { encrypted, .. }
+            | Data::BeaconTimelockEncrypted { encrypted, .. } => encrypted.len() as u64,
             Data::ResetBondsFlag => 0,
             Data::BigRaw(bytes) => bytes.len() as u64,
         }
@@ -123,6 +149,17 @@ impl Decode for Data {
                     BoundedVec::<u8, ConstU32<MAX_BIGRAW_COMMITMENT_SIZE_BYTES>>::decode(input)?;
                 Data::BigRaw(bigvec)
             }
+            137 => {
+                let encrypted =
+                    BoundedVec::<u8, ConstU32<MAX_TIMELOCK_COMMITMENT_SIZE_BYTES>>::decode(input)?;
+                let reveal_round = u64::decode(input)?;
+                let beacon_id = BeaconId::decode(input)?;
+                Data::BeaconTimelockEncrypted {
+                    encrypted,
+                    reveal_round,
+                    beacon_id,
+                }
+            }
             _ => return Err(codec::Error::from("invalid leading byte")),
         })
     }
@@ -157,6 +194,17 @@ impl Encode for Data {
                 r.extend_from_slice(&bigvec.encode());
                 r
             }
+            Data::BeaconTimelockEncrypted {
+                encrypted,
+                reveal_round,
+                beacon_id,
+            } => {
+                let mut r = vec![137];
+                r.extend_from_slice(&encrypted.encode());
+                r.extend_from_slice(&reveal_round.encode());
+                r.extend_from_slice(&beacon_id.encode());
+                r
+            }
         }
     }
 }
@@ -348,6 +396,18 @@ impl TypeInfo for Data {
                 v.index(136).fields(Fields::unnamed().field(|f| {
                     f.ty::<BoundedVec<u8, ConstU32<MAX_BIGRAW_COMMITMENT_SIZE_BYTES>>>()
                 }))
+            })
+            .variant("BeaconTimelockEncrypted", |v| {
+                v.index(137).fields(
+                    Fields::named()
+                        .field(|f| {
+                            f.name("encrypted")
+                                .ty::<BoundedVec<u8, ConstU32<MAX_TIMELOCK_COMMITMENT_SIZE_BYTES>>>(
+                                )
+                        })
+                        .field(|f| f.name("reveal_round").ty::<u64>())
+                        .field(|f| f.name("beacon_id").ty::<BeaconId>()),
+                )
             });
 
         Type::builder()
@@ -0,0 +1,16 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+sp_api::decl_runtime_apis! {
+    /// Verifiable randomness from the pulses of the drand beacon stored on chain.
+    ///
+    /// A round is available once its pulse is written, so rounds after `last_stored_round` are
+    /// not available yet, and those before `oldest_stored_round` are pruned.
+    pub trait DrandRuntimeApi {
+        /// The 32 bytes of randomness of the round, or `None` when its pulse is not stored.
+        fn randomness(round: u64) -> Option<[u8; 32]>;
+        /// The last round with a stored pulse, or 0 when none is stored.
+        fn last_stored_round() -> u64;
+        /// The oldest round with a stored pulse, or 0 when none is stored.
+        fn oldest_stored_round() -> u64;
+    }
+}
@@ -26,6 +26,15 @@
 //! signed transaction to encode them in the runtime. The runtime uses the optimized arkworks host
 //! functions to efficiently verify the pulse.
 //!
+//! The offchain worker queries the public drand api endpoints in `ENDPOINTS`, unless the node
+//! operator sets their own endpoints under `OFFCHAIN_ENDPOINTS_KEY` in offchain local storage.
+//! Chains configured with the `mock_beacon` instead produce its pulses locally, for local networks
+//! and tests without network access.
+//!
+//! Besides the main beacon in `BeaconConfig`, root can register further beacons of the same
+//! scheme in `Beacons`. Their pulses are written under the beacon id in `BeaconPulses`, so
+//! timelocks can target another drand network while those of the main beacon stay decryptable.
+//!
 //! Run `cargo doc --package pallet-drand --open` to view this pallet's documentation.
 
 // We make sure this pallet uses `no_std` for compiling to Wasm.
@@ -40,7 +49,10 @@ extern crate alloc;
 
 use alloc::{format, string::String, vec, vec::Vec};
 use codec::Encode;
-use frame_support::{pallet_prelude::*, traits::Randomness};
+use frame_support::{
+    pallet_prelude::*,
+    traits::{Randomness, UnixTime},
+};
 use frame_system::{
     offchain::{
         AppCrypto, CreateInherent, CreateSignedTransaction, SendUnsignedTransaction, SignedPayload,
@@ -53,11 +65,15 @@ use sha2::{Digest, Sha256};
 use sp_core::blake2_256;
 use sp_runtime::{
     KeyTypeId, Saturating,
+    offchain::storage::StorageValueRef,
     traits::{Hash, One},
     transaction_validity::{InvalidTransaction, TransactionValidity, ValidTransaction},
 };
 
 pub mod bls12_381;
+mod migrations;
+pub mod mock_beacon;
+pub mod timelock;
 pub mod types;
 pub mod utils;
 pub mod verifier;
@@ -75,7 +91,7 @@ mod tests;
 mod benchmarking;
 
 /// the main drand api endpoint
-const ENDPOINTS: [&str; 5] = [
+pub const ENDPOINTS: [&str; 5] = [
     "https://api.drand.sh",
     "https://api2.drand.sh",
     "https://api3.drand.sh",
@@ -83,14 +99,26 @@ const ENDPOINTS: [&str; 5] = [
     "https://api.drand.secureweb3.com:6875",
 ];
 
+/// Offchain local storage key of the drand api endpoints queried in place of `ENDPOINTS`, holding
+/// the SCALE encoded `Vec<String>` of the endpoint urls. It is set by the node's
+/// `--drand-endpoint` flag, or through the `offchain_localStorageSet` rpc under the `PERSISTENT`
+/// storage kind.
+pub const OFFCHAIN_ENDPOINTS_KEY: &[u8] = b"drand::endpoints";
+
 /// the drand quicknet chain hash
 /// quicknet uses 'Tiny' BLS381, with small 48-byte sigs in G1 and 96-byte pubkeys in G2
 pub const QUICKNET_CHAIN_HASH: &str =
     "52db9ba70e0cc0f6eaf7803dd07447a1f5477735fd3f661792ba94600c84e971";
 
-const CHAIN_HASH: &str = QUICKNET_CHAIN_HASH;
+/// the most pulses the offchain worker fetches at once and a `write_pulse` stores, bounding
+/// what `OnPulsesStored` runs in a single extrinsic
+pub const MAX_PULSES_TO_FETCH: u64 = 8;
 
-pub const MAX_PULSES_TO_FETCH: u64 = 50;
+/// the maximum number of beacons registered besides the main one
+pub const MAX_REGISTERED_BEACONS: u32 = 4;
+
+// Define a maximum length for the migration key
+type MigrationKeyMaxLen = ConstU32<128>;
 
 /// Defines application identifier for crypto keys of this module.
 ///
@@ -146,6 +174,12 @@ impl<T: SigningTypes> SignedPayload<T> for PulsesPayload<T::Public, BlockNumberF
     }
 }
 
+impl<T: SigningTypes> SignedPayload<T> for BeaconPulsesPayload<T::Public, BlockNumberFor<T>> {
+    fn public(&self) -> T::Public {
+        self.public.clone()
+    }
+}
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
@@ -174,6 +208,13 @@ pub mod pallet {
         /// complete.
         #[pallet::constant]
         type HttpFetchTimeout: Get<u64>;
+        /// The number of rounds before the last stored round whose pulses are kept.
+        #[pallet::constant]
+        type PulseRetention: Get<RoundNumber>;
+        /// something run with the rounds of the main beacon in the extrinsic storing their pulses
+        type OnPulsesStored: OnPulsesStored;
+        /// the time of the current block, to tell the round the main beacon is at
+        type UnixTime: UnixTime;
     }
 
     /// the drand beacon configuration
@@ -212,12 +253,40 @@ pub mod pallet {
         }
     }
 
+    #[pallet::genesis_config]
+    #[derive(frame_support::DefaultNoBound)]
+    pub struct GenesisConfig<T: Config> {
+        /// Whether the chain takes its pulses from the local mock beacon instead of quicknet.
+        pub use_mock_beacon: bool,
+        #[serde(skip)]
+        pub _phantom: PhantomData<T>,
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+        fn build(&self) {
+            if self.use_mock_beacon {
+                BeaconConfig::<T>::put(Pallet::<T>::mock_beacon_config());
+            }
+        }
+    }
+
     /// map round number to pulse
     #[pallet::storage]
     pub type Pulses<T: Config> = StorageMap<_, Blake2_128Concat, RoundNumber, Pulse, OptionQuery>;
 
     #[pallet::storage]
-    pub(super) type LastStoredRound<T: Config> = StorageValue<_, RoundNumber, ValueQuery>;
+    pub type LastStoredRound<T: Config> = StorageValue<_, RoundNumber, ValueQuery>;
+
+    /// the oldest round whose pulse is stored, the pulses of older rounds are pruned but for
+    /// those an outstanding reveal still holds
+    #[pallet::storage]
+    pub type OldestStoredRound<T: Config> = StorageValue<_, RoundNumber, ValueQuery>;
+
+    /// Storage for the migration run status.
+    #[pallet::storage]
+    pub type HasMigrationRun<T: Config> =
+        StorageMap<_, Identity, BoundedVec<u8, MigrationKeyMaxLen>, bool, ValueQuery>;
 
     /// Defines the block when next unsigned transaction will be accepted.
     ///
@@ -227,6 +296,52 @@ pub mod pallet {
     #[pallet::storage]
     pub(super) type NextUnsignedAt<T: Config> = StorageValue<_, BlockNumberFor<T>, ValueQuery>;
 
+    /// the beacons registered besides the main `BeaconConfig`, by beacon id
+    #[pallet::storage]
+    pub type Beacons<T: Config> =
+        StorageMap<_, Blake2_128Concat, BeaconId, BeaconConfiguration, OptionQuery>;
+
+    /// map beacon id and round number to pulse, for the registered beacons
+    #[pallet::storage]
+    pub type BeaconPulses<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        BeaconId,
+        Blake2_128Concat,
+        RoundNumber,
+        Pulse,
+        OptionQuery,
+    >;
+
+    /// the last stored round of each registered beacon
+    #[pallet::storage]
+    pub type BeaconLastStoredRound<T: Config> =
+        StorageMap<_, Blake2_128Concat, BeaconId, RoundNumber, ValueQuery>;
+
+    /// the oldest stored round of each beacon with stored pulses, including the beacons
+    /// deregistered while their pulses are being pruned
+    #[pallet::storage]
+    pub type BeaconOldestStoredRound<T: Config> =
+        StorageMap<_, Blake2_128Concat, BeaconId, RoundNumber, ValueQuery>;
+
+    /// the block when the next unsigned transaction of each registered beacon will be accepted
+    #[pallet::storage]
+    pub(super) type BeaconNextUnsignedAt<T: Config> =
+        StorageMap<_, Blake2_128Concat, BeaconId, BlockNumberFor<T>, ValueQuery>;
+
+    /// the number of outstanding reveals waiting on each round of the main beacon, keyed by
+    /// `None`, or of the registered beacon with the id, the pulses of those rounds are kept
+    #[pallet::storage]
+    pub type PendingReveals<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        Option<BeaconId>,
+        Twox64Concat,
+        RoundNumber,
+        u32,
+        ValueQuery,
+    >;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -235,6 +350,19 @@ pub mod pallet {
         NewPulse {
             rounds: Vec<RoundNumber>,
         },
+        /// A beacon was registered besides the main one.
+        BeaconRegistered {
+            beacon_id: BeaconId,
+        },
+        /// A registered beacon was removed, its pulses are pruned over the next blocks.
+        BeaconDeregistered {
+            beacon_id: BeaconId,
+        },
+        /// Successfully set new pulse(s) of a registered beacon.
+        NewBeaconPulse {
+            beacon_id: BeaconId,
+            rounds: Vec<RoundNumber>,
+        },
     }
 
     #[pallet::error]
@@ -251,6 +379,16 @@ pub mod pallet {
         InvalidRoundNumber,
         /// the pulse could not be verified
         PulseVerificationError,
+        /// a beacon with the same id is the main beacon or is registered already
+        BeaconAlreadyRegistered,
+        /// no beacon is registered with the id
+        BeaconNotRegistered,
+        /// the beacon's scheme differs from the main beacon's, which the verifier checks
+        UnsupportedBeaconScheme,
+        /// the maximum number of beacons are registered
+        TooManyBeacons,
+        /// more than `MAX_PULSES_TO_FETCH` pulses are written at once
+        TooManyPulses,
     }
 
     #[pallet::hooks]
@@ -260,6 +398,31 @@ pub mod pallet {
             if let Err(e) = Self::fetch_drand_pulse_and_send_unsigned(block_number) {
                 log::debug!("Drand: Failed to fetch pulse from drand. {:?}", e);
             }
+            for (beacon_id, config) in Beacons::<T>::iter() {
+                if let Err(e) =
+                    Self::fetch_beacon_pulses_and_send_unsigned(block_number, &beacon_id, &config)
+                {
+                    log::debug!(
+                        "Drand: Failed to fetch pulse from beacon {:?}. {:?}",
+                        beacon_id,
+                        e
+                    );
+                }
+            }
+        }
+
+        fn on_idle(_block_number: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            Self::prune_pulses(remaining_weight)
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            let mut weight = Weight::from_parts(0, 0);
+
+            weight = weight
+                // Record the oldest stored round for the pulses stored so far
+                .saturating_add(migrations::migrate_set_oldest_stored_round::<T>());
+
+            weight
         }
     }
 
@@ -284,11 +447,28 @@ pub mod pallet {
                         signature,
                         &payload.block_number,
                         &payload.public,
+                        None,
                     )
                 }
                 Call::write_pulse {
                     pulses_payload: payload,
                     signature,
+                } => {
+                    let signature = signature.as_ref().ok_or(InvalidTransaction::BadSigner)?;
+                    if payload.pulses.len() as u64 > MAX_PULSES_TO_FETCH {
+                        return InvalidTransaction::ExhaustsResources.into();
+                    }
+                    Self::validate_signature_and_parameters(
+                        payload,
+                        signature,
+                        &payload.block_number,
+                        &payload.public,
+                        None,
+                    )
+                }
+                Call::write_beacon_pulse {
+                    pulses_payload: payload,
+                    signature,
                 } => {
                     let signature = signature.as_ref().ok_or(InvalidTransaction::BadSigner)?;
                     Self::validate_signature_and_parameters(
@@ -296,6 +476,7 @@ pub mod pallet {
                         signature,
                         &payload.block_number,
                         &payload.public,
+                        Some(&payload.beacon_id),
                     )
                 }
                 _ => InvalidTransaction::Call.into(),
@@ -305,56 +486,60 @@ pub mod pallet {
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
-        /// Verify and write a pulse from the beacon into the runtime
+        /// Verify and write up to `MAX_PULSES_TO_FETCH` pulses from the beacon into the runtime
         #[pallet::call_index(0)]
-        #[pallet::weight(Weight::from_parts(5_708_000_000, 0)
-        .saturating_add(T::DbWeight::get().reads(2_u64))
-        .saturating_add(T::DbWeight::get().writes(3_u64)))]
+        #[pallet::weight(Pallet::<T>::write_pulse_weight().saturating_add(
+            T::OnPulsesStored::max_weight(
+                (pulses_payload.pulses.len() as u64).min(MAX_PULSES_TO_FETCH) as u32
+            )
+        ))]
         pub fn write_pulse(
             origin: OriginFor<T>,
             pulses_payload: PulsesPayload<T::Public, BlockNumberFor<T>>,
             _signature: Option<T::Signature>,
-        ) -> DispatchResult {
+        ) -> DispatchResultWithPostInfo {
             ensure_none(origin)?;
+            ensure!(
+                pulses_payload.pulses.len() as u64 <= MAX_PULSES_TO_FETCH,
+                Error::<T>::TooManyPulses
+            );
             let config = BeaconConfig::<T>::get();
 
-            let mut last_stored_round = LastStoredRound::<T>::get();
-            let mut new_rounds = Vec::new();
-
-            for pulse in &pulses_payload.pulses {
-                let is_verified = T::Verifier::verify(config.clone(), pulse.clone())
-                    .map_err(|_| Error::<T>::PulseVerificationError)?;
+            let pulses =
+                Self::verify_pulses(&config, pulses_payload.pulses, LastStoredRound::<T>::get())?;
+            let new_rounds: Vec<RoundNumber> = pulses.iter().map(|pulse| pulse.round).collect();
 
-                if is_verified {
-                    ensure!(
-                        pulse.round > last_stored_round,
-                        Error::<T>::InvalidRoundNumber
-                    );
-
-                    // Store the pulse
-                    Pulses::<T>::insert(pulse.round, pulse.clone());
+            for pulse in pulses {
+                // Store the pulse
+                Pulses::<T>::insert(pulse.round, pulse);
+            }
 
-                    // Update last stored round
-                    last_stored_round = pulse.round;
+            // Update LastStoredRound storage
+            if let Some(last_round) = new_rounds.last() {
+                LastStoredRound::<T>::put(last_round);
+            }
 
-                    // Collect the new round
-                    new_rounds.push(pulse.round);
+            // Mark the first round ever stored as the oldest one
+            if let Some(first_round) = new_rounds.first() {
+                if OldestStoredRound::<T>::get() == 0 {
+                    OldestStoredRound::<T>::put(first_round);
                 }
             }
 
-            // Update LastStoredRound storage
-            LastStoredRound::<T>::put(last_stored_round);
-
             // Update the next unsigned block number
             let current_block = frame_system::Pallet::<T>::block_number();
             <NextUnsignedAt<T>>::put(current_block.saturating_add(One::one()));
 
-            // Emit event with all new rounds
+            let mut weight = Self::write_pulse_weight();
             if !new_rounds.is_empty() {
+                // Run what waits on the new rounds before their pulses can be read on chain
+                weight = weight.saturating_add(T::OnPulsesStored::on_pulses_stored(&new_rounds));
+
+                // Emit event with all new rounds
                 Self::deposit_event(Event::NewPulse { rounds: new_rounds });
             }
 
-            Ok(())
+            Ok(Some(weight).into())
         }
         /// allows the root user to set the beacon configuration
         /// generally this would be called from an offchain worker context.
@@ -381,6 +566,116 @@ pub mod pallet {
             Self::deposit_event(Event::BeaconConfigChanged {});
             Ok(())
         }
+
+        /// Registers a beacon besides the main one, whose pulses the offchain worker then
+        /// writes under the beacon id, so timelocks can target it and the chain can move to
+        /// another drand network without invalidating the timelocks of the main beacon.
+        ///
+        /// * `origin`: the root user
+        /// * `config`: the beacon configuration, identified by its metadata beacon id
+        #[pallet::call_index(2)]
+        #[pallet::weight(Weight::from_parts(12_000_000, 0)
+        .saturating_add(T::DbWeight::get().reads(2_u64 + MAX_REGISTERED_BEACONS as u64))
+        .saturating_add(T::DbWeight::get().writes(1_u64)))]
+        pub fn register_beacon(
+            origin: OriginFor<T>,
+            config: BeaconConfiguration,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            let beacon_id = config.metadata.beacon_id.clone();
+            let main_config = BeaconConfig::<T>::get();
+
+            ensure!(
+                beacon_id != main_config.metadata.beacon_id
+                    && !Beacons::<T>::contains_key(&beacon_id),
+                Error::<T>::BeaconAlreadyRegistered
+            );
+            ensure!(
+                config.scheme_id == main_config.scheme_id,
+                Error::<T>::UnsupportedBeaconScheme
+            );
+            ensure!(
+                (Beacons::<T>::iter_keys().count() as u32) < MAX_REGISTERED_BEACONS,
+                Error::<T>::TooManyBeacons
+            );
+
+            Beacons::<T>::insert(&beacon_id, config);
+
+            Self::deposit_event(Event::BeaconRegistered { beacon_id });
+            Ok(())
+        }
+
+        /// Removes a registered beacon, whose pulses are then pruned over the next blocks,
+        /// keeping those outstanding reveals still wait on.
+        ///
+        /// * `origin`: the root user
+        /// * `beacon_id`: the id of the registered beacon
+        #[pallet::call_index(3)]
+        #[pallet::weight(Weight::from_parts(10_000_000, 0)
+        .saturating_add(T::DbWeight::get().reads(1_u64))
+        .saturating_add(T::DbWeight::get().writes(2_u64)))]
+        pub fn deregister_beacon(origin: OriginFor<T>, beacon_id: BeaconId) -> DispatchResult {
+            ensure_root(origin)?;
+            ensure!(
+                Beacons::<T>::contains_key(&beacon_id),
+                Error::<T>::BeaconNotRegistered
+            );
+
+            Beacons::<T>::remove(&beacon_id);
+            BeaconNextUnsignedAt::<T>::remove(&beacon_id);
+
+            Self::deposit_event(Event::BeaconDeregistered { beacon_id });
+            Ok(())
+        }
+
+        /// Verify and write pulses from a registered beacon into the runtime
+        #[pallet::call_index(4)]
+        #[pallet::weight(Weight::from_parts(5_708_000_000, 0)
+        .saturating_add(T::DbWeight::get().reads(3_u64))
+        .saturating_add(T::DbWeight::get().writes(4_u64)))]
+        pub fn write_beacon_pulse(
+            origin: OriginFor<T>,
+            pulses_payload: BeaconPulsesPayload<T::Public, BlockNumberFor<T>>,
+            _signature: Option<T::Signature>,
+        ) -> DispatchResult {
+            ensure_none(origin)?;
+            let beacon_id = pulses_payload.beacon_id;
+            let config = Beacons::<T>::get(&beacon_id).ok_or(Error::<T>::BeaconNotRegistered)?;
+
+            let pulses = Self::verify_pulses(
+                &config,
+                pulses_payload.pulses,
+                BeaconLastStoredRound::<T>::get(&beacon_id),
+            )?;
+            let new_rounds: Vec<RoundNumber> = pulses.iter().map(|pulse| pulse.round).collect();
+
+            for pulse in pulses {
+                BeaconPulses::<T>::insert(&beacon_id, pulse.round, pulse);
+            }
+
+            if let Some(last_round) = new_rounds.last() {
+                BeaconLastStoredRound::<T>::insert(&beacon_id, last_round);
+            }
+
+            // Mark the first round ever stored as the oldest one
+            if let Some(first_round) = new_rounds.first() {
+                if BeaconOldestStoredRound::<T>::get(&beacon_id) == 0 {
+                    BeaconOldestStoredRound::<T>::insert(&beacon_id, first_round);
+                }
+            }
+
+            let current_block = frame_system::Pallet::<T>::block_number();
+            BeaconNextUnsignedAt::<T>::insert(&beacon_id, current_block.saturating_add(One::one()));
+
+            if !new_rounds.is_empty() {
+                Self::deposit_event(Event::NewBeaconPulse {
+                    beacon_id,
+                    rounds: new_rounds,
+                });
+            }
+
+            Ok(())
+        }
     }
 }
 
@@ -396,88 +691,213 @@ impl<T: Config> Pallet<T> {
             return Err("Drand: Too early to send unsigned transaction");
         }
 
-        let mut last_stored_round = LastStoredRound::<T>::get();
+        let pulses =
+            Self::fetch_pulses_after(&BeaconConfig::<T>::get(), LastStoredRound::<T>::get())?;
+        if pulses.is_empty() {
+            return Ok(());
+        }
+
+        let signer = Signer::<T, T::AuthorityId>::all_accounts();
+        let results = signer.send_unsigned_transaction(
+            |account| PulsesPayload {
+                block_number,
+                pulses: pulses.clone(),
+                public: account.public.clone(),
+            },
+            |pulses_payload, signature| Call::write_pulse {
+                pulses_payload,
+                signature: Some(signature),
+            },
+        );
+
+        for (acc, res) in &results {
+            match res {
+                Ok(()) => log::debug!(
+                    "Drand: [{:?}] Submitted new pulses up to round: {:?}",
+                    acc.id,
+                    pulses.last().map(|pulse| pulse.round)
+                ),
+                Err(e) => log::error!(
+                    "Drand: [{:?}] Failed to submit transaction: {:?}",
+                    acc.id,
+                    e
+                ),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// fetch the latest public pulses from a registered drand beacon
+    /// then send a signed transaction to include them on-chain under the beacon id
+    fn fetch_beacon_pulses_and_send_unsigned(
+        block_number: BlockNumberFor<T>,
+        beacon_id: &BeaconId,
+        config: &BeaconConfiguration,
+    ) -> Result<(), &'static str> {
+        let next_unsigned_at = BeaconNextUnsignedAt::<T>::get(beacon_id);
+        if next_unsigned_at > block_number {
+            return Err("Drand: Too early to send unsigned transaction");
+        }
+
+        let pulses = Self::fetch_pulses_after(config, BeaconLastStoredRound::<T>::get(beacon_id))?;
+        if pulses.is_empty() {
+            return Ok(());
+        }
+
+        let signer = Signer::<T, T::AuthorityId>::all_accounts();
+        let results = signer.send_unsigned_transaction(
+            |account| BeaconPulsesPayload {
+                block_number,
+                beacon_id: beacon_id.clone(),
+                pulses: pulses.clone(),
+                public: account.public.clone(),
+            },
+            |pulses_payload, signature| Call::write_beacon_pulse {
+                pulses_payload,
+                signature: Some(signature),
+            },
+        );
+
+        for (acc, res) in &results {
+            match res {
+                Ok(()) => log::debug!(
+                    "Drand: [{:?}] Submitted new pulses of beacon {:?} up to round: {:?}",
+                    acc.id,
+                    beacon_id,
+                    pulses.last().map(|pulse| pulse.round)
+                ),
+                Err(e) => log::error!(
+                    "Drand: [{:?}] Failed to submit transaction: {:?}",
+                    acc.id,
+                    e
+                ),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// fetch the pulses of the beacon after the last stored round, up to `MAX_PULSES_TO_FETCH`
+    /// of them; when no round is stored yet, only the latest pulse is fetched
+    fn fetch_pulses_after(
+        config: &BeaconConfiguration,
+        last_stored_round: RoundNumber,
+    ) -> Result<Vec<Pulse>, &'static str> {
         let latest_unbounded_pulse =
-            Self::fetch_drand_latest().map_err(|_| "Failed to query drand")?;
+            Self::fetch_beacon_latest(config).map_err(|_| "Failed to query drand")?;
         let latest_pulse = latest_unbounded_pulse
             .try_into_pulse()
             .map_err(|_| "Drand: Received pulse contains invalid data")?;
         let current_round = latest_pulse.round;
 
-        // If last_stored_round is zero, set it to current_round - 1
-        if last_stored_round == 0 {
-            last_stored_round = current_round.saturating_sub(1);
-            LastStoredRound::<T>::put(last_stored_round);
-        }
+        // If last_stored_round is zero, start from current_round - 1
+        let last_stored_round = if last_stored_round == 0 {
+            current_round.saturating_sub(1)
+        } else {
+            last_stored_round
+        };
 
+        let mut pulses = Vec::new();
         if current_round > last_stored_round {
             let rounds_to_fetch = cmp::min(
                 current_round.saturating_sub(last_stored_round),
                 MAX_PULSES_TO_FETCH,
             );
-            let mut pulses = Vec::new();
 
             for round in (last_stored_round.saturating_add(1))
                 ..=(last_stored_round.saturating_add(rounds_to_fetch))
             {
-                let unbounded_pulse = Self::fetch_drand_by_round(round)
+                let unbounded_pulse = Self::fetch_beacon_by_round(config, round)
                     .map_err(|_| "Drand: Failed to query drand for round")?;
                 let pulse = unbounded_pulse
                     .try_into_pulse()
                     .map_err(|_| "Drand: Received pulse contains invalid data")?;
                 pulses.push(pulse);
             }
-
-            let signer = Signer::<T, T::AuthorityId>::all_accounts();
-
-            let results = signer.send_unsigned_transaction(
-                |account| PulsesPayload {
-                    block_number,
-                    pulses: pulses.clone(),
-                    public: account.public.clone(),
-                },
-                |pulses_payload, signature| Call::write_pulse {
-                    pulses_payload,
-                    signature: Some(signature),
-                },
-            );
-
-            for (acc, res) in &results {
-                match res {
-                    Ok(()) => log::debug!(
-                        "Drand: [{:?}] Submitted new pulses up to round: {:?}",
-                        acc.id,
-                        last_stored_round.saturating_add(rounds_to_fetch)
-                    ),
-                    Err(e) => log::error!(
-                        "Drand: [{:?}] Failed to submit transaction: {:?}",
-                        acc.id,
-                        e
-                    ),
-                }
-            }
         }
 
-        Ok(())
+        Ok(pulses)
     }
 
     fn fetch_drand_by_round(round: RoundNumber) -> Result<DrandResponseBody, &'static str> {
-        let relative_path = format!("/{}/public/{}", CHAIN_HASH, round);
-        Self::fetch_and_decode_from_any_endpoint(&relative_path)
+        Self::fetch_beacon_by_round(&BeaconConfig::<T>::get(), round)
     }
 
     fn fetch_drand_latest() -> Result<DrandResponseBody, &'static str> {
-        let relative_path = format!("/{}/public/latest", CHAIN_HASH);
+        Self::fetch_beacon_latest(&BeaconConfig::<T>::get())
+    }
+
+    fn fetch_beacon_by_round(
+        config: &BeaconConfiguration,
+        round: RoundNumber,
+    ) -> Result<DrandResponseBody, &'static str> {
+        if Self::is_mock_beacon(config) {
+            return mock_beacon::response_body(round)
+                .map_err(|_| "Drand: Failed to produce mock beacon pulse");
+        }
+
+        let relative_path = format!("/{}/public/{}", Self::chain_hash(config), round);
         Self::fetch_and_decode_from_any_endpoint(&relative_path)
     }
 
+    fn fetch_beacon_latest(
+        config: &BeaconConfiguration,
+    ) -> Result<DrandResponseBody, &'static str> {
+        if Self::is_mock_beacon(config) {
+            let now = sp_io::offchain::timestamp().unix_millis() / 1_000;
+            let round = mock_beacon::round_at(config, now);
+            return mock_beacon::response_body(round)
+                .map_err(|_| "Drand: Failed to produce mock beacon pulse");
+        }
+
+        let relative_path = format!("/{}/public/latest", Self::chain_hash(config));
+        Self::fetch_and_decode_from_any_endpoint(&relative_path)
+    }
+
+    /// The hex encoded chain hash the drand api serves the beacon under.
+    fn chain_hash(config: &BeaconConfiguration) -> String {
+        config
+            .hash
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    /// The beacon configuration of the `mock_beacon`, in the timing of quicknet.
+    pub fn mock_beacon_config() -> BeaconConfiguration {
+        BeaconConfiguration {
+            public_key: mock_beacon::public_key(),
+            metadata: Metadata {
+                beacon_id: BoundedHash::truncate_from(mock_beacon::MOCK_BEACON_ID.to_vec()),
+            },
+            ..DefaultBeaconConfig::<T>()
+        }
+    }
+
+    /// Whether the beacon configuration is the one of the `mock_beacon`.
+    fn is_mock_beacon(config: &BeaconConfiguration) -> bool {
+        config.public_key == mock_beacon::public_key()
+    }
+
+    /// The drand api endpoints set in offchain local storage, or `ENDPOINTS` when none are set.
+    fn endpoints() -> Vec<String> {
+        StorageValueRef::persistent(OFFCHAIN_ENDPOINTS_KEY)
+            .get::<Vec<String>>()
+            .map_err(|_| log::warn!("Drand: Failed to decode the endpoints in local storage"))
+            .ok()
+            .flatten()
+            .filter(|endpoints| !endpoints.is_empty())
+            .unwrap_or_else(|| ENDPOINTS.iter().map(|e| String::from(*e)).collect())
+    }
+
     /// Try to fetch from multiple endpoints simultaneously and return the first successfully decoded JSON response.
     fn fetch_and_decode_from_any_endpoint(
         relative_path: &str,
     ) -> Result<DrandResponseBody, &'static str> {
-        let uris: Vec<String> = ENDPOINTS
+        let uris: Vec<String> = Self::endpoints()
             .iter()
-            .map(|e| format!("{}{}", e, relative_path))
+            .map(|e| format!("{}{}", e.trim_end_matches('/'), relative_path))
             .collect();
         let deadline = sp_io::offchain::timestamp().add(
             sp_runtime::offchain::Duration::from_millis(T::HttpFetchTimeout::get()),
@@ -567,11 +987,197 @@ impl<T: Config> Pallet<T> {
     /// get the randomness at a specific block height
     /// returns [0u8;32] if it does not exist
     pub fn random_at(round: RoundNumber) -> [u8; 32] {
-        let pulse = Pulses::<T>::get(round).unwrap_or_default();
-        let rand = pulse.randomness.clone();
-        let bounded_rand: [u8; 32] = rand.into_inner().try_into().unwrap_or([0u8; 32]);
+        Self::randomness(round).unwrap_or([0u8; 32])
+    }
 
-        bounded_rand
+    /// get the randomness of the round from the main beacon
+    /// returns None if its pulse is not stored, as the round is not reached yet or was pruned
+    pub fn randomness(round: RoundNumber) -> Option<[u8; 32]> {
+        Pulses::<T>::get(round).and_then(|pulse| pulse.randomness.into_inner().try_into().ok())
+    }
+
+    /// the round the main beacon publishes at the time of the current block, whether or not its
+    /// pulse is stored yet; 0 before the beacon's genesis
+    pub fn current_round() -> RoundNumber {
+        let config = BeaconConfig::<T>::get();
+        let now = T::UnixTime::now().as_secs();
+        let genesis_time = u64::from(config.genesis_time);
+        if now < genesis_time {
+            return 0;
+        }
+        now.saturating_sub(genesis_time)
+            .checked_div(u64::from(config.period))
+            .unwrap_or_default()
+            .saturating_add(1)
+    }
+
+    /// get the pulse of the round from the main beacon, or from the registered beacon with the id
+    pub fn get_pulse(beacon_id: Option<&BeaconId>, round: RoundNumber) -> Option<Pulse> {
+        match beacon_id {
+            None => Pulses::<T>::get(round),
+            Some(beacon_id) => BeaconPulses::<T>::get(beacon_id, round),
+        }
+    }
+
+    /// Verifies the pulses against the beacon configuration, returning the verified ones and
+    /// failing when one of them is not after the last stored round.
+    fn verify_pulses(
+        config: &BeaconConfiguration,
+        pulses: Vec<Pulse>,
+        mut last_stored_round: RoundNumber,
+    ) -> Result<Vec<Pulse>, DispatchError> {
+        let mut verified = Vec::new();
+
+        for pulse in pulses {
+            let is_verified = T::Verifier::verify(config.clone(), pulse.clone())
+                .map_err(|_| Error::<T>::PulseVerificationError)?;
+
+            if is_verified {
+                ensure!(
+                    pulse.round > last_stored_round,
+                    Error::<T>::InvalidRoundNumber
+                );
+
+                // Update last stored round
+                last_stored_round = pulse.round;
+
+                // Collect the new pulse
+                verified.push(pulse);
+            }
+        }
+
+        Ok(verified)
+    }
+
+    /// Removes the pulses of the rounds older than the retention window, but for the rounds an
+    /// outstanding reveal waits on, until the remaining weight runs out.
+    /// The main beacon is pruned first, then the registered beacons and those deregistered
+    /// beacons whose pulses are not fully removed yet.
+    pub fn prune_pulses(remaining_weight: Weight) -> Weight {
+        let mut weight = Self::prune_beacon_pulses(None, remaining_weight);
+
+        let db_weight = T::DbWeight::get();
+        for beacon_id in BeaconOldestStoredRound::<T>::iter_keys() {
+            weight = weight.saturating_add(db_weight.reads(1));
+            if remaining_weight.any_lt(weight) {
+                break;
+            }
+            weight = weight.saturating_add(Self::prune_beacon_pulses(
+                Some(&beacon_id),
+                remaining_weight.saturating_sub(weight),
+            ));
+        }
+
+        weight
+    }
+
+    /// Prunes the pulses of the main beacon, or of the beacon with the id.
+    fn prune_beacon_pulses(beacon_id: Option<&BeaconId>, remaining_weight: Weight) -> Weight {
+        let db_weight = T::DbWeight::get();
+        let mut weight = db_weight.reads(3);
+        if remaining_weight.any_lt(weight) {
+            return Weight::zero();
+        }
+
+        let (oldest_stored_round, last_stored_round, registered) = match beacon_id {
+            None => (
+                OldestStoredRound::<T>::get(),
+                LastStoredRound::<T>::get(),
+                true,
+            ),
+            Some(beacon_id) => (
+                BeaconOldestStoredRound::<T>::get(beacon_id),
+                BeaconLastStoredRound::<T>::get(beacon_id),
+                Beacons::<T>::contains_key(beacon_id),
+            ),
+        };
+        // The pulses of a deregistered beacon are all pruned
+        let prune_below = if registered {
+            last_stored_round.saturating_sub(T::PulseRetention::get())
+        } else {
+            last_stored_round.saturating_add(1)
+        };
+        if oldest_stored_round == 0 || oldest_stored_round >= prune_below {
+            return weight;
+        }
+
+        // Reserve the writes of the new oldest stored round and, for a deregistered beacon,
+        // of its last stored round
+        weight = weight.saturating_add(db_weight.writes(2));
+        let pending_key = beacon_id.cloned();
+        let mut round = oldest_stored_round;
+        while round < prune_below
+            && remaining_weight.all_gte(weight.saturating_add(db_weight.reads_writes(1, 1)))
+        {
+            // Keep the pulses of the rounds an outstanding reveal still waits on, they are
+            // removed when the last reveal releases them
+            weight = weight.saturating_add(db_weight.reads(1));
+            if !registered || !PendingReveals::<T>::contains_key(&pending_key, round) {
+                match beacon_id {
+                    None => Pulses::<T>::remove(round),
+                    Some(beacon_id) => BeaconPulses::<T>::remove(beacon_id, round),
+                }
+                weight = weight.saturating_add(db_weight.writes(1));
+            }
+            round = round.saturating_add(1);
+        }
+
+        if round != oldest_stored_round {
+            match beacon_id {
+                None => OldestStoredRound::<T>::put(round),
+                // Forget a deregistered beacon once all its pulses are removed
+                Some(beacon_id) if !registered && round > last_stored_round => {
+                    BeaconOldestStoredRound::<T>::remove(beacon_id);
+                    BeaconLastStoredRound::<T>::remove(beacon_id);
+                }
+                Some(beacon_id) => BeaconOldestStoredRound::<T>::insert(beacon_id, round),
+            }
+            log::debug!(
+                "Drand: Pruned pulses of beacon {:?} from round {:?} up to round {:?}",
+                beacon_id,
+                oldest_stored_round,
+                round
+            );
+        }
+
+        weight
+    }
+
+    /// The weight of `write_pulse` besides what `OnPulsesStored` runs.
+    fn write_pulse_weight() -> Weight {
+        Weight::from_parts(5_708_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(4_u64))
+    }
+
+    /// Keeps the pulse of the round of the main beacon, or of the beacon with the id, until
+    /// the reveal waiting on it releases the round.
+    pub fn hold_reveal_round(beacon_id: Option<&BeaconId>, round: RoundNumber) {
+        PendingReveals::<T>::mutate(beacon_id.cloned(), round, |count| {
+            *count = count.saturating_add(1)
+        });
+    }
+
+    /// Releases the round a reveal waited on, its pulse is pruned once no reveal holds it. The
+    /// pulse of a round pruning already went past is removed with the last release, so a
+    /// reveal must be done reading the pulse before it releases the round.
+    pub fn release_reveal_round(beacon_id: Option<&BeaconId>, round: RoundNumber) {
+        let released = PendingReveals::<T>::mutate_exists(beacon_id.cloned(), round, |count| {
+            let remaining = count.unwrap_or_default().saturating_sub(1);
+            *count = (remaining > 0).then_some(remaining);
+            count.is_none()
+        });
+        if !released {
+            return;
+        }
+
+        match beacon_id {
+            None if round < OldestStoredRound::<T>::get() => Pulses::<T>::remove(round),
+            Some(beacon_id) if round < BeaconOldestStoredRound::<T>::get(beacon_id) => {
+                BeaconPulses::<T>::remove(beacon_id, round)
+            }
+            _ => {}
+        }
     }
 
     fn validate_signature_and_parameters(
@@ -579,21 +1185,26 @@ impl<T: Config> Pallet<T> {
         signature: &T::Signature,
         block_number: &BlockNumberFor<T>,
         public: &T::Public,
+        beacon_id: Option<&BeaconId>,
     ) -> TransactionValidity {
         let signature_valid =
             SignedPayload::<T>::verify::<T::AuthorityId>(payload, signature.clone());
         if !signature_valid {
             return InvalidTransaction::BadProof.into();
         }
-        Self::validate_transaction_parameters(block_number, public)
+        Self::validate_transaction_parameters(block_number, public, beacon_id)
     }
 
     fn validate_transaction_parameters(
         block_number: &BlockNumberFor<T>,
         public: &T::Public,
+        beacon_id: Option<&BeaconId>,
     ) -> TransactionValidity {
         // Now let's check if the transaction has any chance to succeed.
-        let next_unsigned_at = NextUnsignedAt::<T>::get();
+        let next_unsigned_at = match beacon_id {
+            None => NextUnsignedAt::<T>::get(),
+            Some(beacon_id) => BeaconNextUnsignedAt::<T>::get(beacon_id),
+        };
         if &next_unsigned_at > block_number {
             return InvalidTransaction::Stale.into();
         }
@@ -603,7 +1214,7 @@ impl<T: Config> Pallet<T> {
             return InvalidTransaction::Future.into();
         }
 
-        let provides_tag = (next_unsigned_at, public.encode()).using_encoded(blake2_256);
+        let provides_tag = (next_unsigned_at, public.encode(), beacon_id).using_encoded(blake2_256);
 
         ValidTransaction::with_tag_prefix("DrandOffchainWorker")
             // We set the priority to the value stored at `UnsignedPriority`.
@@ -638,6 +1249,26 @@ pub fn message(current_round: RoundNumber, prev_sig: &[u8]) -> Vec<u8> {
     hasher.finalize().to_vec()
 }
 
+/// Something run with the rounds of the main beacon in the extrinsic storing their pulses, so no
+/// other extrinsic can read the pulses first.
+pub trait OnPulsesStored {
+    /// Runs with the newly stored rounds, in order, and returns the weight used.
+    fn on_pulses_stored(rounds: &[RoundNumber]) -> Weight;
+
+    /// The most weight `on_pulses_stored` uses for the number of rounds.
+    fn max_weight(rounds: u32) -> Weight;
+}
+
+impl OnPulsesStored for () {
+    fn on_pulses_stored(_rounds: &[RoundNumber]) -> Weight {
+        Weight::zero()
+    }
+
+    fn max_weight(_rounds: u32) -> Weight {
+        Weight::zero()
+    }
+}
+
 impl<T: Config> Randomness<T::Hash, BlockNumberFor<T>> for Pallet<T> {
     // this function hashes together the subject with the latest known randomness from quicknet
     fn random(subject: &[u8]) -> (T::Hash, BlockNumberFor<T>) {
@@ -0,0 +1,81 @@
+use alloc::string::String;
+use frame_support::{BoundedVec, traits::Get, weights::Weight};
+
+use crate::*;
+
+/// Records the oldest stored round for the pulses written before pruning existed.
+///
+/// Pulses are written for consecutive rounds from the first one stored up to `LastStoredRound`,
+/// so the oldest stored round is found by binary search instead of iterating every pulse.
+pub fn migrate_set_oldest_stored_round<T: Config>() -> Weight {
+    let migration_name = BoundedVec::truncate_from(b"migrate_set_oldest_stored_round".to_vec());
+    let mut weight = T::DbWeight::get().reads(1);
+
+    if HasMigrationRun::<T>::get(&migration_name) {
+        log::info!(
+            "Migration '{:?}' has already run. Skipping.",
+            String::from_utf8_lossy(&migration_name)
+        );
+        return weight;
+    }
+
+    log::info!(
+        "Running migration '{}'",
+        String::from_utf8_lossy(&migration_name)
+    );
+
+    let last_stored_round = LastStoredRound::<T>::get();
+    weight = weight.saturating_add(T::DbWeight::get().reads(2));
+
+    if OldestStoredRound::<T>::get() == 0 && Pulses::<T>::contains_key(last_stored_round) {
+        let mut low: RoundNumber = 1;
+        let mut high = last_stored_round;
+        while low < high {
+            let mid = low.saturating_add(high.saturating_sub(low) / 2);
+            if Pulses::<T>::contains_key(mid) {
+                high = mid;
+            } else {
+                low = mid.saturating_add(1);
+            }
+            weight = weight.saturating_add(T::DbWeight::get().reads(1));
+        }
+
+        OldestStoredRound::<T>::put(low);
+        weight = weight.saturating_add(T::DbWeight::get().writes(1));
+
+        log::info!("Set the oldest stored round to {}", low);
+    }
+
+    HasMigrationRun::<T>::insert(&migration_name, true);
+    weight = weight.saturating_add(T::DbWeight::get().writes(1));
+
+    log::info!(
+        "Migration '{:?}' completed successfully.",
+        String::from_utf8_lossy(&migration_name)
+    );
+
+    weight
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{Test, new_test_ext};
+
+    #[test]
+    fn test_migrate_set_oldest_stored_round_works() {
+        new_test_ext().execute_with(|| {
+            for round in 1_000..=1_100 {
+                Pulses::<Test>::insert(round, Pulse::default());
+            }
+            LastStoredRound::<Test>::put(1_100);
+
+            migrate_set_oldest_stored_round::<Test>();
+
+            assert_eq!(OldestStoredRound::<Test>::get(), 1_000);
+            assert!(HasMigrationRun::<Test>::get(BoundedVec::truncate_from(
+                b"migrate_set_oldest_stored_round".to_vec()
+            )));
+        });
+    }
+}
@@ -0,0 +1,2 @@
+mod migrate_set_oldest_stored_round;
+pub use migrate_set_oldest_stored_round::*;
@@ -0,0 +1,74 @@
+//! A deterministic stand-in for the drand quicknet beacon
+//!
+//! The mock beacon signs rounds with a publicly known secret key, producing pulses in the
+//! quicknet format (unchained, signatures in G1 and public key in G2) that pass the
+//! `QuicknetVerifier` and decrypt ciphertexts timelocked to its public key. As anyone can sign
+//! its rounds, it is only meant for local networks and tests, where it lets commit-reveal and
+//! timelocked commitments run without reaching the drand api.
+
+use crate::{
+    message,
+    types::{BeaconConfiguration, DrandResponseBody, OpaquePublicKey, Pulse, RoundNumber},
+};
+use alloc::{format, string::String, vec::Vec};
+use ark_ec::{CurveGroup, Group, hashing::HashToCurve};
+use ark_ff::PrimeField;
+use ark_serialize::CanonicalSerialize;
+use sha2::{Digest, Sha256};
+use tle::curves::drand::TinyBLS381;
+use w3f_bls::engine::EngineBLS;
+
+/// the seed the mock beacon secret key is derived from
+pub const MOCK_BEACON_SEED: &[u8] = b"subtensor mock drand beacon";
+
+/// the beacon id of the mock beacon configuration
+pub const MOCK_BEACON_ID: &[u8] = b"mock";
+
+type Scalar = <TinyBLS381 as EngineBLS>::Scalar;
+
+fn secret_key() -> Scalar {
+    Scalar::from_be_bytes_mod_order(&Sha256::digest(MOCK_BEACON_SEED))
+}
+
+/// the public key of the mock beacon, compressed in G2
+pub fn public_key() -> OpaquePublicKey {
+    let public_key =
+        (<TinyBLS381 as EngineBLS>::PublicKeyGroup::generator() * secret_key()).into_affine();
+    let mut bytes = Vec::new();
+    let _ = public_key.serialize_compressed(&mut bytes);
+    OpaquePublicKey::truncate_from(bytes)
+}
+
+/// the response body the drand api would serve for the round of the mock beacon
+pub fn response_body(round: RoundNumber) -> Result<DrandResponseBody, String> {
+    // sig = sk * H(m), with m = sha256({} || {round})
+    let message_hash = <TinyBLS381 as EngineBLS>::hash_to_curve_map()
+        .hash(&message(round, &[]))
+        .map_err(|e| format!("Failed to hash message: {}", e))?;
+    let signature = (message_hash * secret_key()).into_affine();
+
+    let mut signature_bytes = Vec::new();
+    signature
+        .serialize_compressed(&mut signature_bytes)
+        .map_err(|e| format!("Failed to serialize signature: {}", e))?;
+
+    Ok(DrandResponseBody {
+        round,
+        randomness: Sha256::digest(&signature_bytes).to_vec(),
+        signature: signature_bytes,
+    })
+}
+
+/// the pulse of the mock beacon for the round
+pub fn pulse(round: RoundNumber) -> Result<Pulse, String> {
+    response_body(round)?.try_into_pulse()
+}
+
+/// the round the beacon of the configuration is at, at the unix time in seconds
+pub fn round_at(config: &BeaconConfiguration, unix_seconds: u64) -> RoundNumber {
+    unix_seconds
+        .saturating_sub(config.genesis_time.into())
+        .checked_div(config.period.into())
+        .unwrap_or_default()
+        .saturating_add(1)
+}
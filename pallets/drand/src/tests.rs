@@ -15,18 +15,27 @@
  */
 
 use crate::{
-    BeaconConfig, BeaconConfigurationPayload, BeaconInfoResponse, Call, DrandResponseBody,
-    ENDPOINTS, Error, Pulse, Pulses, PulsesPayload, QUICKNET_CHAIN_HASH, mock::*,
+    BeaconConfig, BeaconConfigurationPayload, BeaconInfoResponse, BeaconLastStoredRound,
+    BeaconOldestStoredRound, BeaconPulses, BeaconPulsesPayload, Beacons, Call, DrandResponseBody,
+    ENDPOINTS, Error, LastStoredRound, MAX_PULSES_TO_FETCH, OFFCHAIN_ENDPOINTS_KEY,
+    OldestStoredRound, PendingReveals, Pulse, Pulses, PulsesPayload, QUICKNET_CHAIN_HASH,
+    mock::*,
+    mock_beacon,
+    timelock::TimelockError,
+    types::BeaconId,
+    verifier::{QuicknetVerifier, Verifier},
 };
 use codec::Encode;
 use frame_support::{
     assert_noop, assert_ok,
-    pallet_prelude::{InvalidTransaction, TransactionSource},
+    pallet_prelude::{InvalidTransaction, TransactionSource, Weight},
 };
 use frame_system::RawOrigin;
 use sp_runtime::{
+    BuildStorage,
     offchain::{
-        OffchainWorkerExt,
+        OffchainDbExt, OffchainWorkerExt, Timestamp,
+        storage::StorageValueRef,
         testing::{PendingRequest, TestOffchainExt},
     },
     traits::ValidateUnsigned,
@@ -84,6 +93,7 @@ fn it_can_submit_valid_pulse_when_beacon_config_exists() {
         let pulse = Pulses::<Test>::get(ROUND_NUMBER);
         assert!(pulse.is_some());
         assert_eq!(pulse, Some(p));
+        assert_eq!(OldestStoredRound::<Test>::get(), ROUND_NUMBER);
     });
 }
 
@@ -350,6 +360,7 @@ fn can_execute_and_handle_valid_http_responses() {
 
     let (offchain, state) = TestOffchainExt::new();
     let mut t = sp_io::TestExternalities::default();
+    t.register_extension(OffchainDbExt::new(offchain.clone()));
     t.register_extension(OffchainWorkerExt::new(offchain));
 
     {
@@ -415,6 +426,7 @@ fn validate_unsigned_rejects_future_block_number() {
 fn test_all_endpoints_fail() {
     let (offchain, state) = TestOffchainExt::new();
     let mut t = sp_io::TestExternalities::default();
+    t.register_extension(OffchainDbExt::new(offchain.clone()));
     t.register_extension(OffchainWorkerExt::new(offchain));
 
     {
@@ -447,6 +459,7 @@ fn test_eventual_success() {
 
     let (offchain, state) = TestOffchainExt::new();
     let mut t = sp_io::TestExternalities::default();
+    t.register_extension(OffchainDbExt::new(offchain.clone()));
     t.register_extension(OffchainWorkerExt::new(offchain));
 
     {
@@ -518,6 +531,7 @@ fn test_invalid_json_then_success() {
 
     let (offchain, state) = TestOffchainExt::new();
     let mut t = sp_io::TestExternalities::default();
+    t.register_extension(OffchainDbExt::new(offchain.clone()));
     t.register_extension(OffchainWorkerExt::new(offchain));
 
     {
@@ -549,3 +563,419 @@ fn test_invalid_json_then_success() {
         assert_eq!(actual, expected_pulse);
     });
 }
+
+#[test]
+fn test_endpoints_from_local_storage() {
+    let expected_pulse: DrandResponseBody = serde_json::from_str(DRAND_PULSE).unwrap();
+
+    let (offchain, state) = TestOffchainExt::new();
+    let mut t = sp_io::TestExternalities::default();
+    t.register_extension(OffchainDbExt::new(offchain.clone()));
+    t.register_extension(OffchainWorkerExt::new(offchain));
+
+    // Only the endpoint set in local storage is queried
+    state.write().expect_request(PendingRequest {
+        method: "GET".into(),
+        uri: format!("http://127.0.0.1:8080/{}/public/1000", QUICKNET_CHAIN_HASH),
+        response: Some(DRAND_PULSE.as_bytes().to_vec()),
+        sent: true,
+        ..Default::default()
+    });
+
+    t.execute_with(|| {
+        StorageValueRef::persistent(OFFCHAIN_ENDPOINTS_KEY)
+            .set(&vec![String::from("http://127.0.0.1:8080/")]);
+
+        let actual = Drand::fetch_drand_by_round(1000u64).unwrap();
+        assert_eq!(actual, expected_pulse);
+    });
+}
+
+#[test]
+fn test_prune_pulses_keeps_retention_window() {
+    new_test_ext().execute_with(|| {
+        for round in 1..=1_500 {
+            Pulses::<Test>::insert(round, Pulse::default());
+        }
+        OldestStoredRound::<Test>::put(1);
+        LastStoredRound::<Test>::put(1_500);
+
+        // The mock keeps the last 1_000 rounds
+        Drand::prune_pulses(Weight::MAX);
+
+        assert_eq!(OldestStoredRound::<Test>::get(), 500);
+        assert!(!Pulses::<Test>::contains_key(1));
+        assert!(!Pulses::<Test>::contains_key(499));
+        assert!(Pulses::<Test>::contains_key(500));
+        assert!(Pulses::<Test>::contains_key(1_500));
+
+        // Nothing left to prune
+        Drand::prune_pulses(Weight::MAX);
+        assert_eq!(OldestStoredRound::<Test>::get(), 500);
+    });
+}
+
+#[test]
+fn test_prune_pulses_keeps_held_rounds() {
+    new_test_ext().execute_with(|| {
+        for round in 1..=1_500 {
+            Pulses::<Test>::insert(round, Pulse::default());
+        }
+        OldestStoredRound::<Test>::put(1);
+        LastStoredRound::<Test>::put(1_500);
+
+        // Two reveals wait on round 300, the rounds around it are pruned
+        Drand::hold_reveal_round(None, 300);
+        Drand::hold_reveal_round(None, 300);
+        Drand::prune_pulses(Weight::MAX);
+        assert_eq!(OldestStoredRound::<Test>::get(), 500);
+        assert!(!Pulses::<Test>::contains_key(299));
+        assert!(Pulses::<Test>::contains_key(300));
+        assert!(!Pulses::<Test>::contains_key(301));
+        assert!(!Pulses::<Test>::contains_key(499));
+
+        // The round is kept until every reveal released it
+        Drand::release_reveal_round(None, 300);
+        Drand::prune_pulses(Weight::MAX);
+        assert!(Pulses::<Test>::contains_key(300));
+
+        Drand::release_reveal_round(None, 300);
+        assert!(!PendingReveals::<Test>::contains_key(None::<BeaconId>, 300));
+        assert!(!Pulses::<Test>::contains_key(300));
+
+        // A released round pruning has not reached yet is pruned as usual
+        Drand::hold_reveal_round(None, 1_200);
+        Drand::release_reveal_round(None, 1_200);
+        assert!(Pulses::<Test>::contains_key(1_200));
+    });
+}
+
+#[test]
+fn test_current_round() {
+    new_test_ext().execute_with(|| {
+        let config = BeaconConfig::<Test>::get();
+        let genesis_time = u64::from(config.genesis_time);
+
+        // Before the beacon's genesis
+        assert_eq!(Drand::current_round(), 0);
+
+        MockNow::set(genesis_time);
+        assert_eq!(Drand::current_round(), 1);
+        MockNow::set(genesis_time + 10 * u64::from(config.period) - 1);
+        assert_eq!(Drand::current_round(), 10);
+        MockNow::set(0);
+    });
+}
+
+#[test]
+fn test_mock_beacon_pulse_verifies() {
+    new_test_ext().execute_with(|| {
+        let pulse = mock_beacon::pulse(ROUND_NUMBER).unwrap();
+
+        assert_eq!(
+            QuicknetVerifier::verify(Drand::mock_beacon_config(), pulse.clone()),
+            Ok(true)
+        );
+        // Not a quicknet pulse
+        assert_eq!(
+            QuicknetVerifier::verify(BeaconConfig::<Test>::get(), pulse),
+            Ok(false)
+        );
+    });
+}
+
+#[test]
+fn test_write_mock_beacon_pulses() {
+    let mut ext: sp_io::TestExternalities = RuntimeGenesisConfig {
+        drand: crate::GenesisConfig {
+            use_mock_beacon: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+    .build_storage()
+    .unwrap()
+    .into();
+
+    ext.execute_with(|| {
+        assert_eq!(BeaconConfig::<Test>::get(), Drand::mock_beacon_config());
+
+        let alice = sp_keyring::Sr25519Keyring::Alice;
+        let pulses: Vec<Pulse> = (ROUND_NUMBER..ROUND_NUMBER + 3)
+            .map(|round| mock_beacon::pulse(round).unwrap())
+            .collect();
+        let pulses_payload = PulsesPayload {
+            pulses: pulses.clone(),
+            block_number: 1,
+            public: alice.public(),
+        };
+
+        assert_ok!(Drand::write_pulse(
+            RuntimeOrigin::none(),
+            pulses_payload,
+            None
+        ));
+
+        for pulse in pulses {
+            assert_eq!(Pulses::<Test>::get(pulse.round), Some(pulse));
+        }
+
+        // At most `MAX_PULSES_TO_FETCH` pulses are written at once
+        let pulses: Vec<Pulse> = (ROUND_NUMBER + 3..=ROUND_NUMBER + 3 + MAX_PULSES_TO_FETCH)
+            .map(|round| mock_beacon::pulse(round).unwrap())
+            .collect();
+        let pulses_payload = PulsesPayload {
+            pulses,
+            block_number: 1,
+            public: alice.public(),
+        };
+        let signature = alice.sign(&pulses_payload.encode());
+        assert_noop!(
+            Drand::validate_unsigned(
+                TransactionSource::External,
+                &Call::write_pulse {
+                    pulses_payload: pulses_payload.clone(),
+                    signature: Some(signature),
+                }
+            ),
+            InvalidTransaction::ExhaustsResources
+        );
+        assert_noop!(
+            Drand::write_pulse(RuntimeOrigin::none(), pulses_payload, None),
+            Error::<Test>::TooManyPulses
+        );
+    });
+}
+
+#[test]
+fn test_fetch_latest_from_mock_beacon() {
+    let (offchain, state) = TestOffchainExt::new();
+    let mut t = new_test_ext();
+    t.register_extension(OffchainDbExt::new(offchain.clone()));
+    t.register_extension(OffchainWorkerExt::new(offchain));
+
+    t.execute_with(|| {
+        let config = Drand::mock_beacon_config();
+        BeaconConfig::<Test>::put(config.clone());
+
+        // Ten periods after genesis, with no http request made
+        let now = u64::from(config.genesis_time) + 10 * u64::from(config.period);
+        state.write().timestamp = Timestamp::from_unix_millis(now * 1_000);
+
+        let latest = Drand::fetch_drand_latest().unwrap();
+        assert_eq!(latest.round, 11);
+        assert_eq!(latest, mock_beacon::response_body(11).unwrap());
+    });
+}
+
+#[test]
+fn test_register_beacon() {
+    new_test_ext().execute_with(|| {
+        let config = Drand::mock_beacon_config();
+        let beacon_id = config.metadata.beacon_id.clone();
+
+        let alice = sp_keyring::Sr25519Keyring::Alice;
+        assert_noop!(
+            Drand::register_beacon(RuntimeOrigin::signed(alice.public()), config.clone()),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        // The main beacon is not registered twice
+        assert_noop!(
+            Drand::register_beacon(RuntimeOrigin::root(), BeaconConfig::<Test>::get()),
+            Error::<Test>::BeaconAlreadyRegistered
+        );
+        let mut other_scheme = config.clone();
+        other_scheme.scheme_id = b"pedersen-bls-chained".to_vec().try_into().unwrap();
+        assert_noop!(
+            Drand::register_beacon(RuntimeOrigin::root(), other_scheme),
+            Error::<Test>::UnsupportedBeaconScheme
+        );
+
+        assert_ok!(Drand::register_beacon(
+            RuntimeOrigin::root(),
+            config.clone()
+        ));
+        assert_eq!(Beacons::<Test>::get(&beacon_id), Some(config.clone()));
+        assert_noop!(
+            Drand::register_beacon(RuntimeOrigin::root(), config),
+            Error::<Test>::BeaconAlreadyRegistered
+        );
+
+        assert_ok!(Drand::deregister_beacon(
+            RuntimeOrigin::root(),
+            beacon_id.clone()
+        ));
+        assert!(!Beacons::<Test>::contains_key(&beacon_id));
+        assert_noop!(
+            Drand::deregister_beacon(RuntimeOrigin::root(), beacon_id),
+            Error::<Test>::BeaconNotRegistered
+        );
+    });
+}
+
+#[test]
+fn test_write_beacon_pulse() {
+    new_test_ext().execute_with(|| {
+        let config = Drand::mock_beacon_config();
+        let beacon_id = config.metadata.beacon_id.clone();
+        let alice = sp_keyring::Sr25519Keyring::Alice;
+        let pulses: Vec<Pulse> = (ROUND_NUMBER..ROUND_NUMBER + 3)
+            .map(|round| mock_beacon::pulse(round).unwrap())
+            .collect();
+        let payload = BeaconPulsesPayload {
+            block_number: 1,
+            beacon_id: beacon_id.clone(),
+            pulses: pulses.clone(),
+            public: alice.public(),
+        };
+
+        assert_noop!(
+            Drand::write_beacon_pulse(RuntimeOrigin::none(), payload.clone(), None),
+            Error::<Test>::BeaconNotRegistered
+        );
+
+        assert_ok!(Drand::register_beacon(RuntimeOrigin::root(), config));
+        assert_ok!(Drand::write_beacon_pulse(
+            RuntimeOrigin::none(),
+            payload,
+            None
+        ));
+
+        // The pulses are stored under the beacon, not the main one
+        for pulse in pulses {
+            assert_eq!(
+                BeaconPulses::<Test>::get(&beacon_id, pulse.round),
+                Some(pulse.clone())
+            );
+            assert_eq!(Drand::get_pulse(Some(&beacon_id), pulse.round), Some(pulse));
+            assert_eq!(Drand::get_pulse(None, pulse.round), None);
+        }
+        assert_eq!(
+            BeaconLastStoredRound::<Test>::get(&beacon_id),
+            ROUND_NUMBER + 2
+        );
+        assert_eq!(
+            BeaconOldestStoredRound::<Test>::get(&beacon_id),
+            ROUND_NUMBER
+        );
+        assert_eq!(LastStoredRound::<Test>::get(), 0);
+
+        // Older rounds are rejected
+        let stale_payload = BeaconPulsesPayload {
+            block_number: 1,
+            beacon_id,
+            pulses: vec![mock_beacon::pulse(ROUND_NUMBER).unwrap()],
+            public: alice.public(),
+        };
+        assert_noop!(
+            Drand::write_beacon_pulse(RuntimeOrigin::none(), stale_payload, None),
+            Error::<Test>::InvalidRoundNumber
+        );
+    });
+}
+
+#[test]
+fn test_prune_deregistered_beacon_pulses() {
+    new_test_ext().execute_with(|| {
+        let config = Drand::mock_beacon_config();
+        let beacon_id = config.metadata.beacon_id.clone();
+        assert_ok!(Drand::register_beacon(RuntimeOrigin::root(), config));
+
+        for round in 1..=1_500 {
+            BeaconPulses::<Test>::insert(&beacon_id, round, Pulse::default());
+        }
+        BeaconOldestStoredRound::<Test>::insert(&beacon_id, 1);
+        BeaconLastStoredRound::<Test>::insert(&beacon_id, 1_500);
+
+        // The registered beacon keeps the last 1_000 rounds
+        Drand::prune_pulses(Weight::MAX);
+        assert_eq!(BeaconOldestStoredRound::<Test>::get(&beacon_id), 500);
+        assert!(!BeaconPulses::<Test>::contains_key(&beacon_id, 499));
+        assert!(BeaconPulses::<Test>::contains_key(&beacon_id, 500));
+
+        // Once deregistered, all its pulses are pruned and the beacon forgotten
+        assert_ok!(Drand::deregister_beacon(
+            RuntimeOrigin::root(),
+            beacon_id.clone()
+        ));
+        Drand::prune_pulses(Weight::MAX);
+        assert!(!BeaconPulses::<Test>::contains_key(&beacon_id, 1_500));
+        assert!(!BeaconOldestStoredRound::<Test>::contains_key(&beacon_id));
+        assert!(!BeaconLastStoredRound::<Test>::contains_key(&beacon_id));
+    });
+}
+
+#[test]
+fn test_randomness_of_stored_rounds() {
+    new_test_ext().execute_with(|| {
+        let pulse = mock_beacon::pulse(ROUND_NUMBER).unwrap();
+        Pulses::<Test>::insert(ROUND_NUMBER, pulse.clone());
+
+        let expected: [u8; 32] = pulse.randomness.into_inner().try_into().unwrap();
+        assert_eq!(Drand::randomness(ROUND_NUMBER), Some(expected));
+        assert_eq!(Drand::random_at(ROUND_NUMBER), expected);
+
+        // A round whose pulse is not stored has no randomness
+        assert_eq!(Drand::randomness(ROUND_NUMBER + 1), None);
+        assert_eq!(Drand::random_at(ROUND_NUMBER + 1), [0u8; 32]);
+    });
+}
+
+#[test]
+fn test_timelock_decrypt() {
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+    use sha2::{Digest, Sha256};
+    use tle::{
+        curves::drand::TinyBLS381, ibe::fullident::Identity,
+        stream_ciphers::AESGCMStreamCipherProvider, tlock::tle,
+    };
+    use w3f_bls::engine::EngineBLS;
+
+    new_test_ext().execute_with(|| {
+        let message = b"sealed".to_vec();
+        let public_key = <TinyBLS381 as EngineBLS>::PublicKeyGroup::deserialize_compressed(
+            &mock_beacon::public_key()[..],
+        )
+        .unwrap();
+        let identity = Identity::new(
+            b"",
+            vec![Sha256::digest(ROUND_NUMBER.to_be_bytes()).to_vec()],
+        );
+        let ciphertext = tle::<TinyBLS381, AESGCMStreamCipherProvider, _>(
+            public_key,
+            [1; 32],
+            &message,
+            identity,
+            ark_std::test_rng(),
+        )
+        .unwrap();
+        let mut ciphertext_bytes = Vec::new();
+        ciphertext
+            .serialize_compressed(&mut ciphertext_bytes)
+            .unwrap();
+
+        assert_eq!(
+            Drand::timelock_decrypt(None, ROUND_NUMBER, &ciphertext_bytes),
+            Err(TimelockError::MissingPulse)
+        );
+        assert_eq!(
+            Drand::timelock_decrypt(None, ROUND_NUMBER, b"not a ciphertext"),
+            Err(TimelockError::InvalidCiphertext)
+        );
+
+        Pulses::<Test>::insert(ROUND_NUMBER, mock_beacon::pulse(ROUND_NUMBER).unwrap());
+        Pulses::<Test>::insert(
+            ROUND_NUMBER + 1,
+            mock_beacon::pulse(ROUND_NUMBER + 1).unwrap(),
+        );
+        assert_eq!(
+            Drand::timelock_decrypt(None, ROUND_NUMBER, &ciphertext_bytes),
+            Ok(message)
+        );
+        // The ciphertext is timelocked to its round only
+        assert_eq!(
+            Drand::timelock_decrypt(None, ROUND_NUMBER + 1, &ciphertext_bytes),
+            Err(TimelockError::DecryptionFailed)
+        );
+    });
+}
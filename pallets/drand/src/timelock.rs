@@ -0,0 +1,57 @@
+//! Decryption of the ciphertexts timelocked to the rounds of the stored beacons
+//!
+//! Ciphertexts are encrypted with `tle` for the identity `sha256(round)` of a beacon, and
+//! serialized compressed. Once the pulse of the round is verified and stored its signature is the
+//! key to decrypt them, which is what commit-reveal weights, timelocked commitments and sealed
+//! calls reveal with.
+
+use crate::{
+    Config, Pallet,
+    types::{BeaconId, RoundNumber},
+};
+use alloc::vec::Vec;
+use ark_serialize::CanonicalDeserialize;
+use tle::{
+    curves::drand::TinyBLS381,
+    stream_ciphers::AESGCMStreamCipherProvider,
+    tlock::{TLECiphertext, tld},
+};
+use w3f_bls::engine::EngineBLS;
+
+/// the reasons a timelocked ciphertext cannot be decrypted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimelockError {
+    /// the ciphertext is not a compressed `TLECiphertext`
+    InvalidCiphertext,
+    /// the pulse of the round is not stored, as the round is not reached yet or was pruned
+    MissingPulse,
+    /// the signature of the stored pulse is not a compressed point
+    InvalidPulse,
+    /// the ciphertext is not timelocked to the round
+    DecryptionFailed,
+}
+
+impl<T: Config> Pallet<T> {
+    /// decrypts the ciphertext timelocked to the round of the main beacon, or of the registered
+    /// beacon with the id
+    pub fn timelock_decrypt(
+        beacon_id: Option<&BeaconId>,
+        round: RoundNumber,
+        ciphertext: &[u8],
+    ) -> Result<Vec<u8>, TimelockError> {
+        let ciphertext = TLECiphertext::<TinyBLS381>::deserialize_compressed(ciphertext)
+            .map_err(|_| TimelockError::InvalidCiphertext)?;
+
+        let pulse = Self::get_pulse(beacon_id, round).ok_or(TimelockError::MissingPulse)?;
+        let signature_bytes = pulse
+            .signature
+            .strip_prefix(b"0x")
+            .unwrap_or(&pulse.signature);
+        let signature =
+            <TinyBLS381 as EngineBLS>::SignatureGroup::deserialize_compressed(signature_bytes)
+                .map_err(|_| TimelockError::InvalidPulse)?;
+
+        tld::<TinyBLS381, AESGCMStreamCipherProvider>(ciphertext, signature)
+            .map_err(|_| TimelockError::DecryptionFailed)
+    }
+}
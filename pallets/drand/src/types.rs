@@ -27,6 +27,8 @@ pub type OpaquePublicKey = BoundedVec<u8, ConstU32<96>>;
 pub type BoundedHash = BoundedVec<u8, ConstU32<32>>;
 /// the round number to track rounds of the beacon
 pub type RoundNumber = u64;
+/// the id of a beacon, as in the metadata of its configuration
+pub type BeaconId = BoundedHash;
 
 /// the expected response body from the drand api endpoint `api.drand.sh/{chainId}/info`
 #[freeze_struct("f9e09b3273fe00cd")]
@@ -203,3 +205,14 @@ pub struct PulsesPayload<Public, BlockNumber> {
     pub pulses: Vec<Pulse>,
     pub public: Public,
 }
+
+/// Payload used to hold the pulses of a registered beacon
+/// required to submit a transaction.
+#[freeze_struct("ee6a075f43647381")]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct BeaconPulsesPayload<Public, BlockNumber> {
+    pub block_number: BlockNumber,
+    pub beacon_id: BeaconId,
+    pub pulses: Vec<Pulse>,
+    pub public: Public,
+}
@@ -15,7 +15,7 @@ use sp_api::ProvideRuntimeApi;
 
 pub use subtensor_custom_rpc_runtime_api::{
     DelegateInfoRuntimeApi, NeuronInfoRuntimeApi, SubnetInfoRuntimeApi,
-    SubnetRegistrationRuntimeApi,
+    SubnetRegistrationRuntimeApi, WeightsInfoRuntimeApi,
 };
 
 #[rpc(client, server)]
@@ -48,6 +48,8 @@ pub trait SubtensorCustomApi<BlockHash> {
     fn get_neurons(&self, netuid: NetUid, at: Option<BlockHash>) -> RpcResult<Vec<u8>>;
     #[method(name = "neuronInfo_getNeuron")]
     fn get_neuron(&self, netuid: NetUid, uid: u16, at: Option<BlockHash>) -> RpcResult<Vec<u8>>;
+    #[method(name = "neuronInfo_getNeuronCertificates")]
+    fn get_neuron_certificates(&self, netuid: NetUid, at: Option<BlockHash>) -> RpcResult<Vec<u8>>;
     #[method(name = "subnetInfo_getSubnetInfo")]
     fn get_subnet_info(&self, netuid: NetUid, at: Option<BlockHash>) -> RpcResult<Vec<u8>>;
     #[method(name = "subnetInfo_getSubnetsInfo")]
@@ -64,6 +66,12 @@ pub trait SubtensorCustomApi<BlockHash> {
         netuid: NetUid,
         at: Option<BlockHash>,
     ) -> RpcResult<Vec<u8>>;
+    #[method(name = "subnetInfo_getSubnetHyperparamsV3")]
+    fn get_subnet_hyperparams_v3(
+        &self,
+        netuid: NetUid,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<u8>>;
     #[method(name = "subnetInfo_getAllDynamicInfo")]
     fn get_all_dynamic_info(&self, at: Option<BlockHash>) -> RpcResult<Vec<u8>>;
     #[method(name = "subnetInfo_getDynamicInfo")]
@@ -76,6 +84,13 @@ pub trait SubtensorCustomApi<BlockHash> {
     fn get_subnet_state(&self, netuid: NetUid, at: Option<BlockHash>) -> RpcResult<Vec<u8>>;
     #[method(name = "subnetInfo_getLockCost")]
     fn get_network_lock_cost(&self, at: Option<BlockHash>) -> RpcResult<u64>;
+    #[method(name = "subnetInfo_getPruningPreview")]
+    fn get_pruning_preview(
+        &self,
+        netuid: NetUid,
+        count: u16,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<u8>>;
     #[method(name = "subnetInfo_getSelectiveMetagraph")]
     fn get_selective_metagraph(
         &self,
@@ -83,6 +98,24 @@ pub trait SubtensorCustomApi<BlockHash> {
         metagraph_index: Vec<u16>,
         at: Option<BlockHash>,
     ) -> RpcResult<Vec<u8>>;
+    #[method(name = "weightsInfo_getCrv3RevealStatuses")]
+    fn get_crv3_reveal_statuses(
+        &self,
+        netuid: NetUid,
+        commit_epoch: u64,
+        hotkey_vec: Option<Vec<u8>>,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<u8>>;
+    #[method(name = "weightsInfo_validateWeights")]
+    fn validate_weights(
+        &self,
+        netuid: NetUid,
+        hotkey_vec: Vec<u8>,
+        uids: Vec<u16>,
+        values: Vec<u16>,
+        version_key: u64,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<u8>>;
 }
 
 pub struct SubtensorCustom<C, P> {
@@ -131,6 +164,7 @@ where
     C::Api: NeuronInfoRuntimeApi<Block>,
     C::Api: SubnetInfoRuntimeApi<Block>,
     C::Api: SubnetRegistrationRuntimeApi<Block>,
+    C::Api: WeightsInfoRuntimeApi<Block>,
 {
     fn get_delegates(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Vec<u8>> {
         let api = self.client.runtime_api();
@@ -258,6 +292,24 @@ where
         }
     }
 
+    fn get_neuron_certificates(
+        &self,
+        netuid: NetUid,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<u8>> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        match api.get_neuron_certificates(at, netuid) {
+            Ok(result) => Ok(result.encode()),
+            Err(e) => Err(Error::RuntimeError(format!(
+                "Unable to get neuron certificates: {:?}",
+                e
+            ))
+            .into()),
+        }
+    }
+
     fn get_subnet_info(
         &self,
         netuid: NetUid,
@@ -306,6 +358,22 @@ where
         }
     }
 
+    fn get_subnet_hyperparams_v3(
+        &self,
+        netuid: NetUid,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<u8>> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        match api.get_subnet_hyperparams_v3(at, netuid) {
+            Ok(result) => Ok(result.encode()),
+            Err(e) => {
+                Err(Error::RuntimeError(format!("Unable to get subnet info: {:?}", e)).into())
+            }
+        }
+    }
+
     fn get_all_dynamic_info(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Vec<u8>> {
         let api = self.client.runtime_api();
         let at = at.unwrap_or_else(|| self.client.info().best_hash);
@@ -430,6 +498,23 @@ where
         })
     }
 
+    fn get_pruning_preview(
+        &self,
+        netuid: NetUid,
+        count: u16,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<u8>> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        match api.get_pruning_preview(at, netuid, count) {
+            Ok(result) => Ok(result.encode()),
+            Err(e) => {
+                Err(Error::RuntimeError(format!("Unable to get pruning preview: {:?}", e)).into())
+            }
+        }
+    }
+
     fn get_selective_metagraph(
         &self,
         netuid: NetUid,
@@ -448,4 +533,65 @@ where
             .into()),
         }
     }
+
+    fn get_crv3_reveal_statuses(
+        &self,
+        netuid: NetUid,
+        commit_epoch: u64,
+        hotkey_vec: Option<Vec<u8>>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<u8>> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        let hotkey = match hotkey_vec
+            .map(|hotkey_vec| AccountId32::decode(&mut &hotkey_vec[..]))
+            .transpose()
+        {
+            Ok(hotkey) => hotkey,
+            Err(e) => {
+                return Err(Error::RuntimeError(format!(
+                    "Unable to get crv3 reveal statuses: {:?}",
+                    e
+                ))
+                .into());
+            }
+        };
+        match api.get_crv3_reveal_statuses(at, netuid, commit_epoch, hotkey) {
+            Ok(result) => Ok(result.encode()),
+            Err(e) => Err(Error::RuntimeError(format!(
+                "Unable to get crv3 reveal statuses: {:?}",
+                e
+            ))
+            .into()),
+        }
+    }
+
+    fn validate_weights(
+        &self,
+        netuid: NetUid,
+        hotkey_vec: Vec<u8>,
+        uids: Vec<u16>,
+        values: Vec<u16>,
+        version_key: u64,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<u8>> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        let hotkey = match AccountId32::decode(&mut &hotkey_vec[..]) {
+            Ok(hotkey) => hotkey,
+            Err(e) => {
+                return Err(
+                    Error::RuntimeError(format!("Unable to validate weights: {:?}", e)).into(),
+                );
+            }
+        };
+        match api.validate_weights(at, netuid, hotkey, uids, values, version_key) {
+            Ok(result) => Ok(result.encode()),
+            Err(e) => {
+                Err(Error::RuntimeError(format!("Unable to validate weights: {:?}", e)).into())
+            }
+        }
+    }
 }
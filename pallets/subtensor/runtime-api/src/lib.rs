@@ -3,13 +3,19 @@ extern crate alloc;
 use alloc::vec::Vec;
 use codec::Compact;
 use pallet_subtensor::rpc_info::{
+    crv3_reveal_status::CRV3RevealStatusInfo,
     delegate_info::DelegateInfo,
     dynamic_info::DynamicInfo,
     metagraph::{Metagraph, SelectiveMetagraph},
+    neuron_certificates::NeuronCertificatesInfo,
     neuron_info::{NeuronInfo, NeuronInfoLite},
+    pruning_preview::PruningPreview,
     show_subnet::SubnetState,
     stake_info::StakeInfo,
-    subnet_info::{SubnetHyperparams, SubnetHyperparamsV2, SubnetInfo, SubnetInfov2},
+    subnet_info::{
+        SubnetHyperparams, SubnetHyperparamsV2, SubnetHyperparamsV3, SubnetInfo, SubnetInfov2,
+    },
+    weights_validation::WeightsValidationInfo,
 };
 use sp_runtime::AccountId32;
 use subtensor_runtime_common::NetUid;
@@ -28,6 +34,7 @@ sp_api::decl_runtime_apis! {
         fn get_neuron(netuid: NetUid, uid: u16) -> Option<NeuronInfo<AccountId32>>;
         fn get_neurons_lite(netuid: NetUid) -> Vec<NeuronInfoLite<AccountId32>>;
         fn get_neuron_lite(netuid: NetUid, uid: u16) -> Option<NeuronInfoLite<AccountId32>>;
+        fn get_neuron_certificates(netuid: NetUid) -> Vec<NeuronCertificatesInfo<AccountId32>>;
     }
 
     pub trait SubnetInfoRuntimeApi {
@@ -37,6 +44,7 @@ sp_api::decl_runtime_apis! {
         fn get_subnets_info_v2() -> Vec<Option<SubnetInfov2<AccountId32>>>;
         fn get_subnet_hyperparams(netuid: NetUid) -> Option<SubnetHyperparams>;
         fn get_subnet_hyperparams_v2(netuid: NetUid) -> Option<SubnetHyperparamsV2>;
+        fn get_subnet_hyperparams_v3(netuid: NetUid) -> Option<SubnetHyperparamsV3>;
         fn get_all_dynamic_info() -> Vec<Option<DynamicInfo<AccountId32>>>;
         fn get_all_metagraphs() -> Vec<Option<Metagraph<AccountId32>>>;
         fn get_metagraph(netuid: NetUid) -> Option<Metagraph<AccountId32>>;
@@ -54,5 +62,11 @@ sp_api::decl_runtime_apis! {
 
     pub trait SubnetRegistrationRuntimeApi {
         fn get_network_registration_cost() -> u64;
+        fn get_pruning_preview(netuid: NetUid, count: u16) -> Option<PruningPreview<AccountId32>>;
+    }
+
+    pub trait WeightsInfoRuntimeApi {
+        fn get_crv3_reveal_statuses(netuid: NetUid, commit_epoch: u64, hotkey: Option<AccountId32>) -> Vec<CRV3RevealStatusInfo<AccountId32>>;
+        fn validate_weights(netuid: NetUid, hotkey: AccountId32, uids: Vec<u16>, values: Vec<u16>, version_key: u64) -> WeightsValidationInfo;
     }
 }
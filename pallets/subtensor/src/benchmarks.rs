@@ -47,6 +47,42 @@ mod pallet_benchmarks {
         );
     }
 
+    #[benchmark]
+    fn register_cuckoo_cycle() {
+        let netuid = NetUid::from(1);
+        let tempo: u16 = 1;
+        let hotkey: T::AccountId = account("Alice", 0, 1);
+        let coldkey: T::AccountId = account("Test", 0, 2);
+        let edge_bits = crate::MIN_CUCKOO_EDGE_BITS;
+
+        Subtensor::<T>::init_new_network(netuid, tempo);
+        Subtensor::<T>::set_network_registration_allowed(netuid, true);
+        Subtensor::<T>::set_network_pow_registration_allowed(netuid, true);
+        Subtensor::<T>::set_pow_algorithm(netuid, PowAlgorithm::CuckooCycle { edge_bits });
+        // Verification does not depend on the difficulty, keep solving cheap.
+        Subtensor::<T>::set_difficulty(netuid, 1);
+
+        let block_number: u64 = Subtensor::<T>::get_current_block_as_u64();
+        let (nonce, work): (u64, Vec<u8>) = Subtensor::<T>::create_cuckoo_work_for_block_number(
+            netuid,
+            block_number,
+            3,
+            &hotkey,
+            edge_bits,
+        );
+
+        #[extrinsic_call]
+        register(
+            RawOrigin::Signed(hotkey.clone()),
+            netuid,
+            block_number,
+            nonce,
+            work,
+            hotkey.clone(),
+            coldkey.clone(),
+        );
+    }
+
     #[benchmark]
     fn set_weights() {
         let netuid = NetUid::from(1);
@@ -77,7 +113,8 @@ mod pallet_benchmarks {
             assert_ok!(Subtensor::<T>::do_burned_registration(
                 RawOrigin::Signed(coldkey.clone()).into(),
                 netuid,
-                hotkey.clone()
+                hotkey.clone(),
+                None
             ));
             let uid = Subtensor::<T>::get_uid_for_net_and_hotkey(netuid, &hotkey).unwrap();
             Subtensor::<T>::set_validator_permit_for_uid(netuid, uid, true);
@@ -116,7 +153,8 @@ mod pallet_benchmarks {
         assert_ok!(Subtensor::<T>::do_burned_registration(
             RawOrigin::Signed(coldkey.clone()).into(),
             netuid,
-            hotkey.clone()
+            hotkey.clone(),
+            None
         ));
 
         #[extrinsic_call]
@@ -144,7 +182,8 @@ mod pallet_benchmarks {
         assert_ok!(Subtensor::<T>::do_burned_registration(
             RawOrigin::Signed(coldkey.clone()).into(),
             netuid,
-            hotkey.clone()
+            hotkey.clone(),
+            None
         ));
 
         #[extrinsic_call]
@@ -179,7 +218,8 @@ mod pallet_benchmarks {
         assert_ok!(Subtensor::<T>::do_burned_registration(
             RawOrigin::Signed(caller.clone()).into(),
             netuid,
-            caller.clone()
+            caller.clone(),
+            None
         ));
         Subtensor::<T>::set_serving_rate_limit(netuid, 0);
 
@@ -217,7 +257,8 @@ mod pallet_benchmarks {
         assert_ok!(Subtensor::<T>::do_burned_registration(
             RawOrigin::Signed(caller.clone()).into(),
             netuid,
-            caller.clone()
+            caller.clone(),
+            None
         ));
         Subtensor::<T>::set_serving_rate_limit(netuid, 0);
 
@@ -270,7 +311,8 @@ mod pallet_benchmarks {
         assert_ok!(Subtensor::<T>::do_burned_registration(
             RawOrigin::Signed(coldkey.clone()).into(),
             netuid,
-            hotkey.clone()
+            hotkey.clone(),
+            None
         ));
 
         #[extrinsic_call]
@@ -430,7 +472,8 @@ mod pallet_benchmarks {
         assert_ok!(Subtensor::<T>::do_burned_registration(
             RawOrigin::Signed(coldkey.clone()).into(),
             netuid,
-            hotkey.clone()
+            hotkey.clone(),
+            None
         ));
 
         #[extrinsic_call]
@@ -579,7 +622,8 @@ mod pallet_benchmarks {
         assert_ok!(Subtensor::<T>::do_burned_registration(
             RawOrigin::Signed(coldkey.clone()).into(),
             netuid,
-            hotkey.clone()
+            hotkey.clone(),
+            None
         ));
 
         let alpha_amount: u64 = 1_000_000;
@@ -619,7 +663,8 @@ mod pallet_benchmarks {
         assert_ok!(Subtensor::<T>::do_burned_registration(
             RawOrigin::Signed(coldkey.clone()).into(),
             netuid,
-            hotkey.clone()
+            hotkey.clone(),
+            None
         ));
 
         let alpha_amount: u64 = 1_000_000;
@@ -659,7 +704,8 @@ mod pallet_benchmarks {
         assert_ok!(Subtensor::<T>::do_burned_registration(
             RawOrigin::Signed(coldkey.clone()).into(),
             netuid,
-            hotkey.clone()
+            hotkey.clone(),
+            None
         ));
         assert_eq!(SubnetOwner::<T>::get(netuid), coldkey.clone());
         assert_eq!(FirstEmissionBlockNumber::<T>::get(netuid), None);
@@ -716,7 +762,8 @@ mod pallet_benchmarks {
         assert_ok!(Subtensor::<T>::do_burned_registration(
             RawOrigin::Signed(coldkey.clone()).into(),
             netuid,
-            hotkey.clone()
+            hotkey.clone(),
+            None
         ));
 
         #[extrinsic_call]
@@ -815,7 +862,8 @@ mod pallet_benchmarks {
         assert_ok!(Subtensor::<T>::do_burned_registration(
             RawOrigin::Signed(coldkey.clone()).into(),
             netuid,
-            hotkey.clone()
+            hotkey.clone(),
+            None
         ));
 
         let u64_staked_amt = 100_000_000_000;
@@ -1116,6 +1164,56 @@ mod pallet_benchmarks {
         );
     }
 
+    #[benchmark]
+    fn submit_sealed_stake_call() {
+        let coldkey: T::AccountId = whitelisted_caller();
+        let vec_sealed_call: Vec<u8> = vec![0; MAX_SEALED_CALL_SIZE_BYTES as usize];
+        let sealed_call: BoundedVec<_, _> = vec_sealed_call.try_into().unwrap();
+        let reveal_round: u64 = pallet_drand::LastStoredRound::<T>::get().saturating_add(1);
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(coldkey.clone()),
+            sealed_call.clone(),
+            reveal_round,
+        );
+    }
+
+    #[benchmark]
+    fn batch_commit_crv3_weights() {
+        let hotkey: T::AccountId = whitelisted_caller();
+        let netuid = NetUid::from(1);
+        let count: usize = 3;
+        let commit: Vec<u8> = vec![0; MAX_CRV3_COMMIT_SIZE_BYTES as usize];
+        let netuids: Vec<Compact<NetUid>> = vec![Compact(netuid); count];
+        let commits: Vec<Vec<u8>> = vec![commit; count];
+        let reveal_rounds: Vec<u64> = vec![0; count];
+
+        Subtensor::<T>::init_new_network(netuid, 1);
+        Subtensor::<T>::set_network_pow_registration_allowed(netuid, true);
+        SubtokenEnabled::<T>::insert(netuid, true);
+
+        let reg_fee = Subtensor::<T>::get_burn_as_u64(netuid);
+        Subtensor::<T>::add_balance_to_coldkey_account(&hotkey, reg_fee.saturating_mul(2));
+
+        assert_ok!(Subtensor::<T>::burned_register(
+            RawOrigin::Signed(hotkey.clone()).into(),
+            netuid,
+            hotkey.clone()
+        ));
+
+        Subtensor::<T>::set_commit_reveal_weights_enabled(netuid, true);
+        Subtensor::<T>::set_weights_set_rate_limit(netuid, 0);
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(hotkey.clone()),
+            netuids.clone(),
+            commits.clone(),
+            reveal_rounds.clone(),
+        );
+    }
+
     #[benchmark]
     fn decrease_take() {
         let coldkey: T::AccountId = whitelisted_caller();
@@ -1354,7 +1452,8 @@ mod pallet_benchmarks {
         assert_ok!(Subtensor::<T>::do_burned_registration(
             RawOrigin::Signed(coldkey.clone()).into(),
             netuid,
-            hotkey.clone()
+            hotkey.clone(),
+            None
         ));
 
         let staked_amt = 100_000_000_000;
@@ -1406,7 +1505,8 @@ mod pallet_benchmarks {
         assert_ok!(Subtensor::<T>::do_burned_registration(
             RawOrigin::Signed(coldkey.clone()).into(),
             netuid,
-            hotkey.clone()
+            hotkey.clone(),
+            None
         ));
 
         let u64_staked_amt = 100_000_000_000;
@@ -5,11 +5,14 @@ use subtensor_runtime_common::NetUid;
 
 impl<T: Config + pallet_drand::Config> Pallet<T> {
     /// Executes the necessary operations for each block.
-    pub fn block_step() -> Result<(), &'static str> {
+    ///
+    /// Returns the weight of the steps whose cost depends on the state, on top of the fixed
+    /// block step weight.
+    pub fn block_step() -> Result<Weight, &'static str> {
         let block_number: u64 = Self::get_current_block_as_u64();
         log::debug!("block_step for block: {:?} ", block_number);
         // --- 1. Adjust difficulties.
-        Self::adjust_registration_terms_for_networks();
+        let mut weight = Self::adjust_registration_terms_for_networks();
         // --- 2. Get the current coinbase emission.
         let block_emission: U96F32 =
             U96F32::saturating_from_num(Self::get_block_emission().unwrap_or(0));
@@ -18,8 +21,14 @@ impl<T: Config + pallet_drand::Config> Pallet<T> {
         Self::run_coinbase(block_emission);
         // --- 4. Set pending children on the epoch; but only after the coinbase has been run.
         Self::try_set_pending_children(block_number);
+        // --- 5. Remove the neuron certificates expiring at this block.
+        weight.saturating_accrue(Self::remove_expired_neuron_certificates(block_number));
+        // --- 6. Prune and renumber the uids of the subnets being resized.
+        weight.saturating_accrue(Self::step_subnet_resizes());
+        // --- 7. Forget the registration vouchers spent before they expired.
+        weight.saturating_accrue(Self::remove_expired_registration_vouchers(block_number));
         // Return ok.
-        Ok(())
+        Ok(weight)
     }
 
     fn try_set_pending_children(block_number: u64) {
@@ -33,12 +42,17 @@ impl<T: Config + pallet_drand::Config> Pallet<T> {
 
     /// Adjusts the network difficulties/burns of every active network. Resetting state parameters.
     ///
-    pub fn adjust_registration_terms_for_networks() {
+    /// Returns the weight of settling the registration auctions.
+    pub fn adjust_registration_terms_for_networks() -> Weight {
         log::debug!("adjust_registration_terms_for_networks");
+        let mut weight = Weight::zero();
 
         // --- 1. Iterate through each network.
         for (netuid, _) in NetworksAdded::<T>::iter() {
-            // --- 2. Pull counters for network difficulty.
+            // --- 2. Drain block registrations for each network. Needed for registration rate limits.
+            Self::set_registrations_this_block(netuid, 0);
+
+            // --- 3. Pull counters for network difficulty.
             let last_adjustment_block: u64 = Self::get_last_adjustment_block(netuid);
             let adjustment_interval: u16 = Self::get_adjustment_interval(netuid);
             let current_block: u64 = Self::get_current_block_as_u64();
@@ -50,12 +64,17 @@ impl<T: Config + pallet_drand::Config> Pallet<T> {
                 current_block
             );
 
-            // --- 3. Check if we are at the adjustment interval for this network.
+            // --- 4. Check if we are at the adjustment interval for this network.
             // If so, we need to adjust the registration difficulty based on target and actual registrations.
             if current_block.saturating_sub(last_adjustment_block) >= adjustment_interval as u64 {
                 log::debug!("interval reached.");
 
-                // --- 4. Get the current counters for this network w.r.t burn and difficulty values.
+                // The auction winners count towards the registrations of this interval and block.
+                if Self::get_registration_auction_enabled(netuid) {
+                    weight.saturating_accrue(Self::settle_registration_auction(netuid));
+                }
+
+                // --- 5. Get the current counters for this network w.r.t burn and difficulty values.
                 let current_burn: u64 = Self::get_burn_as_u64(netuid);
                 let current_difficulty: u64 = Self::get_difficulty_as_u64(netuid);
                 let registrations_this_interval: u16 =
@@ -66,7 +85,7 @@ impl<T: Config + pallet_drand::Config> Pallet<T> {
                     Self::get_burn_registrations_this_interval(netuid);
                 let target_registrations_this_interval: u16 =
                     Self::get_target_registrations_per_interval(netuid);
-                // --- 5. Adjust burn + pow
+                // --- 6. Adjust burn + pow
                 // There are six cases to consider. A, B, C, D, E, F
                 if registrations_this_interval > target_registrations_this_interval {
                     #[allow(clippy::comparison_chain)]
@@ -175,7 +194,7 @@ impl<T: Config + pallet_drand::Config> Pallet<T> {
                     }
                 }
 
-                // --- 6. Drain all counters for this network for this interval.
+                // --- 7. Drain all counters for this network for this interval.
                 Self::set_last_adjustment_block(netuid, current_block);
                 Self::set_registrations_this_interval(netuid, 0);
                 Self::set_pow_registrations_this_interval(netuid, 0);
@@ -183,10 +202,9 @@ impl<T: Config + pallet_drand::Config> Pallet<T> {
             } else {
                 log::debug!("interval not reached.");
             }
-
-            // --- 7. Drain block registrations for each network. Needed for registration rate limits.
-            Self::set_registrations_this_block(netuid, 0);
         }
+
+        weight
     }
 
     /// Calculates the upgraded difficulty by multiplying the current difficulty by the ratio ( reg_actual + reg_target / reg_target + reg_target )
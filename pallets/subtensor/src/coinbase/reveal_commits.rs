@@ -1,27 +1,13 @@
 use super::*;
-use ark_serialize::CanonicalDeserialize;
 use codec::Decode;
+pub use crv3_commit::WeightsTlockPayload;
+use frame_support::BoundedVec;
 use frame_support::dispatch;
-use frame_support::traits::OriginTrait;
+use frame_support::traits::{ConstU32, OriginTrait};
+use pallet_drand::timelock::TimelockError;
+use pallet_drand::types::{BeaconId, RoundNumber};
+use sp_std::collections::vec_deque::VecDeque;
 use subtensor_runtime_common::NetUid;
-use tle::curves::drand::TinyBLS381;
-use tle::stream_ciphers::AESGCMStreamCipherProvider;
-use tle::tlock::TLECiphertext;
-use tle::tlock::tld;
-use w3f_bls::EngineBLS;
-
-/// Contains all necessary information to set weights.
-///
-/// In the context of commit-reveal v3, this is the payload which should be
-/// encrypted, compressed, serialized, and submitted to the `commit_crv3_weights`
-/// extrinsic.
-#[derive(Encode, Decode)]
-#[freeze_struct("46e75a8326ba3665")]
-pub struct WeightsTlockPayload {
-    pub uids: Vec<u16>,
-    pub values: Vec<u16>,
-    pub version_key: u64,
-}
 
 impl<T: Config> Pallet<T> {
     /// The `reveal_crv3_commits` function is run at the very beginning of epoch `n`,
@@ -34,12 +20,22 @@ impl<T: Config> Pallet<T> {
             cur_epoch.saturating_sub(Self::get_reveal_period(netuid).saturating_sub(1));
 
         // Clean expired commits
-        for (epoch, _) in CRV3WeightCommits::<T>::iter_prefix(netuid) {
+        for (epoch, commits) in CRV3WeightCommits::<T>::iter_prefix(netuid) {
             if epoch < reveal_epoch {
                 CRV3WeightCommits::<T>::remove(netuid, epoch);
+                Self::drop_crv3_commits(netuid, epoch, &commits, CRV3RevealStatus::Expired);
             }
         }
 
+        // Clean the reveal statuses of the commits revealed `CRV3_REVEAL_STATUS_EPOCHS` ago
+        let expired_statuses: Vec<(u64, T::AccountId)> =
+            CRV3RevealStatuses::<T>::iter_key_prefix((netuid,))
+                .filter(|(epoch, _)| epoch.saturating_add(CRV3_REVEAL_STATUS_EPOCHS) < reveal_epoch)
+                .collect();
+        for (epoch, who) in expired_statuses {
+            CRV3RevealStatuses::<T>::remove((netuid, epoch, who));
+        }
+
         // No commits to reveal until at least epoch 2.
         if cur_epoch < 2 {
             log::warn!("Failed to reveal commit for subnet {} Too early", netuid);
@@ -49,67 +45,39 @@ impl<T: Config> Pallet<T> {
         let mut entries = CRV3WeightCommits::<T>::take(netuid, reveal_epoch);
 
         // Keep popping item off the end of the queue until we sucessfully reveal a commit.
-        while let Some((who, serialized_compresssed_commit, round_number)) = entries.pop_front() {
-            let reader = &mut &serialized_compresssed_commit[..];
-            let commit = match TLECiphertext::<TinyBLS381>::deserialize_compressed(reader) {
-                Ok(c) => c,
+        while let Some((who, serialized_compresssed_commit, round_number, beacon_id)) =
+            entries.pop_front()
+        {
+            let decrypted = pallet_drand::Pallet::<T>::timelock_decrypt(
+                beacon_id.as_ref(),
+                round_number,
+                &serialized_compresssed_commit,
+            );
+            // Released once read, the pulse may be pruned with it
+            pallet_drand::Pallet::<T>::release_reveal_round(beacon_id.as_ref(), round_number);
+            let decrypted_bytes: Vec<u8> = match decrypted {
+                Ok(d) => d,
                 Err(e) => {
                     log::warn!(
-                        "Failed to reveal commit for subnet {} submitted by {:?} due to error deserializing the commit: {:?}",
+                        "Failed to reveal commit for subnet {} submitted by {:?} with round {} of beacon {:?}: {:?}",
                         netuid,
                         who,
+                        round_number,
+                        beacon_id,
                         e
                     );
-                    continue;
-                }
-            };
-
-            // Try to get the round number from pallet_drand.
-            let pulse = match pallet_drand::Pulses::<T>::get(round_number) {
-                Some(p) => p,
-                None => {
-                    // Round number used was not found on the chain. Skip this commit.
-                    log::warn!(
-                        "Failed to reveal commit for subnet {} submitted by {:?} due to missing round number {} at time of reveal.",
-                        netuid,
-                        who,
-                        round_number
-                    );
-                    continue;
-                }
-            };
-
-            let signature_bytes = pulse
-                .signature
-                .strip_prefix(b"0x")
-                .unwrap_or(&pulse.signature);
-
-            let sig_reader = &mut &signature_bytes[..];
-            let sig = match <TinyBLS381 as EngineBLS>::SignatureGroup::deserialize_compressed(
-                sig_reader,
-            ) {
-                Ok(s) => s,
-                Err(e) => {
-                    log::error!(
-                        "Failed to reveal commit for subnet {} submitted by {:?} due to error deserializing signature from drand pallet: {:?}",
+                    let status = match e {
+                        TimelockError::InvalidCiphertext => CRV3RevealStatus::InvalidCommit,
+                        TimelockError::MissingPulse => CRV3RevealStatus::MissingPulse,
+                        TimelockError::InvalidPulse => CRV3RevealStatus::InvalidPulse,
+                        TimelockError::DecryptionFailed => CRV3RevealStatus::DecryptionFailed,
+                    };
+                    Self::record_crv3_reveal_status(
                         netuid,
-                        who,
-                        e
-                    );
-                    continue;
-                }
-            };
-
-            let decrypted_bytes: Vec<u8> = match tld::<TinyBLS381, AESGCMStreamCipherProvider>(
-                commit, sig,
-            ) {
-                Ok(d) => d,
-                Err(e) => {
-                    log::warn!(
-                        "Failed to reveal commit for subnet {} submitted by {:?} due to error decrypting the commit: {:?}",
-                        netuid,
-                        who,
-                        e
+                        reveal_epoch,
+                        &who,
+                        round_number,
+                        status,
                     );
                     continue;
                 }
@@ -126,6 +94,13 @@ impl<T: Config> Pallet<T> {
                         who,
                         e
                     );
+                    Self::record_crv3_reveal_status(
+                        netuid,
+                        reveal_epoch,
+                        &who,
+                        round_number,
+                        CRV3RevealStatus::InvalidPayload,
+                    );
                     continue;
                 }
             };
@@ -143,12 +118,93 @@ impl<T: Config> Pallet<T> {
                     who,
                     e
                 );
+                Self::record_crv3_reveal_status(
+                    netuid,
+                    reveal_epoch,
+                    &who,
+                    round_number,
+                    Self::crv3_reveal_status_of(e),
+                );
                 continue;
             } else {
-                Self::deposit_event(Event::CRV3WeightsRevealed(netuid, who));
+                Self::record_crv3_reveal_status(
+                    netuid,
+                    reveal_epoch,
+                    &who,
+                    round_number,
+                    CRV3RevealStatus::Revealed,
+                );
             };
         }
 
         Ok(())
     }
+
+    /// Records the outcome of revealing a v3 commit of the hotkey and emits its event.
+    fn record_crv3_reveal_status(
+        netuid: NetUid,
+        commit_epoch: u64,
+        who: &T::AccountId,
+        reveal_round: RoundNumber,
+        status: CRV3RevealStatus,
+    ) {
+        CRV3RevealStatuses::<T>::append((netuid, commit_epoch, who), (reveal_round, status));
+
+        match status {
+            CRV3RevealStatus::Revealed => {
+                Self::deposit_event(Event::CRV3WeightsRevealed(netuid, who.clone()))
+            }
+            _ => Self::deposit_event(Event::CRV3WeightsRevealFailed(
+                netuid,
+                who.clone(),
+                reveal_round,
+                status,
+            )),
+        }
+    }
+
+    /// Releases the drand rounds the v3 commits waited on, once they leave the queue.
+    pub fn release_crv3_reveal_rounds(
+        commits: &VecDeque<(
+            T::AccountId,
+            BoundedVec<u8, ConstU32<MAX_CRV3_COMMIT_SIZE_BYTES>>,
+            RoundNumber,
+            Option<BeaconId>,
+        )>,
+    ) {
+        for (_, _, round, beacon_id) in commits {
+            pallet_drand::Pallet::<T>::release_reveal_round(beacon_id.as_ref(), *round);
+        }
+    }
+
+    /// Releases the drand rounds of the v3 commits removed unrevealed and records their status,
+    /// returning the number of commits.
+    pub fn drop_crv3_commits(
+        netuid: NetUid,
+        commit_epoch: u64,
+        commits: &VecDeque<(
+            T::AccountId,
+            BoundedVec<u8, ConstU32<MAX_CRV3_COMMIT_SIZE_BYTES>>,
+            RoundNumber,
+            Option<BeaconId>,
+        )>,
+        status: CRV3RevealStatus,
+    ) -> u64 {
+        Self::release_crv3_reveal_rounds(commits);
+        for (who, _, round, _) in commits {
+            Self::record_crv3_reveal_status(netuid, commit_epoch, who, *round, status);
+        }
+        commits.len() as u64
+    }
+
+    /// The reveal status of weights `do_set_weights` rejected with the error.
+    fn crv3_reveal_status_of(error: DispatchError) -> CRV3RevealStatus {
+        if error == DispatchError::from(Error::<T>::IncorrectWeightVersionKey) {
+            CRV3RevealStatus::VersionKeyMismatch
+        } else if error == DispatchError::from(Error::<T>::SettingWeightsTooFast) {
+            CRV3RevealStatus::RateLimited
+        } else {
+            CRV3RevealStatus::WeightsRejected(error)
+        }
+    }
 }
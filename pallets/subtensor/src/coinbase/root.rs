@@ -449,9 +449,15 @@ impl<T: Config> Pallet<T> {
         let keys = Keys::<T>::iter_prefix(netuid).collect::<Vec<_>>();
         let _ = Keys::<T>::clear_prefix(netuid, u32::MAX, None);
         let _ = Bonds::<T>::clear_prefix(netuid, u32::MAX, None);
+        let _ = RegistrationBurnedAlpha::<T>::clear_prefix(netuid, u32::MAX, None);
+        VacantUids::<T>::remove(netuid);
 
         // --- 8. Removes the weights for this subnet (do not remove).
         let _ = Weights::<T>::clear_prefix(netuid, u32::MAX, None);
+        for (_, commits) in CRV3WeightCommits::<T>::drain_prefix(netuid) {
+            Self::release_crv3_reveal_rounds(&commits);
+        }
+        let _ = CRV3RevealStatuses::<T>::clear_prefix((netuid,), u32::MAX, None);
 
         // --- 9. Iterate over stored weights and fill the matrix.
         for (uid_i, weights_i) in
@@ -480,6 +486,19 @@ impl<T: Config> Pallet<T> {
         Consensus::<T>::remove(netuid);
         Dividends::<T>::remove(netuid);
         PruningScores::<T>::remove(netuid);
+        UidPruningPolicy::<T>::remove(netuid);
+        SubnetImmunityPolicy::<T>::remove(netuid);
+        SubnetResizes::<T>::remove(netuid);
+        IncentiveEpochs::<T>::remove(netuid);
+        Self::refund_registration_bids(netuid);
+        RegistrationAuctionEnabled::<T>::remove(netuid);
+        DeregistrationRefund::<T>::remove(netuid);
+        DeregistrationRefundDecay::<T>::remove(netuid);
+        SubnetPowAlgorithm::<T>::remove(netuid);
+        RegistrationWhitelistEnabled::<T>::remove(netuid);
+        let _ = RegistrationWhitelist::<T>::clear_prefix(netuid, u32::MAX, None);
+        Self::clear_pruning_emission_history(netuid);
+        IncentiveEma::<T>::remove(netuid);
         LastUpdate::<T>::remove(netuid);
         ValidatorPermit::<T>::remove(netuid);
         ValidatorTrust::<T>::remove(netuid);
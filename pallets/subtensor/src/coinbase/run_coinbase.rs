@@ -215,8 +215,8 @@ impl<T: Config> Pallet<T> {
         // --- 7. Drain pending emission through the subnet based on tempo.
         // Run the epoch for *all* subnets, even if we don't emit anything.
         for &netuid in subnets.iter() {
-            // Pass on subnets that have not reached their tempo.
-            if Self::should_run_epoch(netuid, current_block) {
+            // Pass on subnets that have not reached their tempo or are being resized.
+            if Self::should_run_epoch(netuid, current_block) && !Self::is_subnet_resizing(netuid) {
                 if let Err(e) = Self::reveal_crv3_commits(netuid) {
                     log::warn!(
                         "Failed to reveal commits for subnet {} due to error: {:?}",
@@ -393,6 +393,8 @@ impl<T: Config> Pallet<T> {
         PruningScores::<T>::insert(netuid, cloned_pruning_scores);
         ValidatorTrust::<T>::insert(netuid, cloned_validator_trust);
         ValidatorPermit::<T>::insert(netuid, new_validator_permits.clone());
+        Self::update_pruning_history(netuid);
+        Self::update_immunity_history(netuid);
 
         new_validator_permits
             .iter()
@@ -846,6 +848,8 @@ impl<T: Config> Pallet<T> {
         PruningScores::<T>::insert(netuid, cloned_pruning_scores);
         ValidatorTrust::<T>::insert(netuid, cloned_validator_trust);
         ValidatorPermit::<T>::insert(netuid, new_validator_permits.clone());
+        Self::update_pruning_history(netuid);
+        Self::update_immunity_history(netuid);
 
         new_validator_permits
             .iter()
@@ -907,8 +911,10 @@ impl<T: Config> Pallet<T> {
     }
 
     /// Output unnormalized sparse weights, input weights are assumed to be row max-upscaled in u16.
+    /// Weights pointing at vacant uids, or at uids past the subnet size, are left out.
     pub fn get_weights_sparse(netuid: NetUid) -> Vec<Vec<(u16, I32F32)>> {
         let n = Self::get_subnetwork_n(netuid) as usize;
+        let vacant = Self::get_vacant_uids_mask(netuid);
         let mut weights: Vec<Vec<(u16, I32F32)>> = vec![vec![]; n];
         for (uid_i, weights_i) in
             <Weights<T> as IterableStorageDoubleMap<NetUid, u16, Vec<(u16, u16)>>>::iter_prefix(
@@ -916,7 +922,10 @@ impl<T: Config> Pallet<T> {
             )
             .filter(|(uid_i, _)| *uid_i < n as u16)
         {
-            for (uid_j, weight_ij) in weights_i.iter().filter(|(uid_j, _)| *uid_j < n as u16) {
+            for (uid_j, weight_ij) in weights_i
+                .iter()
+                .filter(|(uid_j, _)| !vacant.get(*uid_j as usize).copied().unwrap_or(true))
+            {
                 weights
                     .get_mut(uid_i as usize)
                     .expect("uid_i is filtered to be less than n; qed")
@@ -927,8 +936,10 @@ impl<T: Config> Pallet<T> {
     }
 
     /// Output unnormalized weights in [n, n] matrix, input weights are assumed to be row max-upscaled in u16.
+    /// Weights pointing at vacant uids, or at uids past the subnet size, are left out.
     pub fn get_weights(netuid: NetUid) -> Vec<Vec<I32F32>> {
         let n = Self::get_subnetwork_n(netuid) as usize;
+        let vacant = Self::get_vacant_uids_mask(netuid);
         let mut weights: Vec<Vec<I32F32>> = vec![vec![I32F32::saturating_from_num(0.0); n]; n];
         for (uid_i, weights_vec) in
             <Weights<T> as IterableStorageDoubleMap<NetUid, u16, Vec<(u16, u16)>>>::iter_prefix(
@@ -938,7 +949,7 @@ impl<T: Config> Pallet<T> {
         {
             for (uid_j, weight_ij) in weights_vec
                 .into_iter()
-                .filter(|(uid_j, _)| *uid_j < n as u16)
+                .filter(|(uid_j, _)| !vacant.get(uid_j as usize).copied().unwrap_or(true))
             {
                 *weights
                     .get_mut(uid_i as usize)
@@ -952,8 +963,10 @@ impl<T: Config> Pallet<T> {
     }
 
     /// Output unnormalized sparse bonds, input bonds are assumed to be column max-upscaled in u16.
+    /// Bonds pointing at vacant uids, or at uids past the subnet size, are left out.
     pub fn get_bonds_sparse(netuid: NetUid) -> Vec<Vec<(u16, I32F32)>> {
         let n = Self::get_subnetwork_n(netuid) as usize;
+        let vacant = Self::get_vacant_uids_mask(netuid);
         let mut bonds: Vec<Vec<(u16, I32F32)>> = vec![vec![]; n];
         for (uid_i, bonds_vec) in
             <Bonds<T> as IterableStorageDoubleMap<NetUid, u16, Vec<(u16, u16)>>>::iter_prefix(
@@ -961,7 +974,10 @@ impl<T: Config> Pallet<T> {
             )
             .filter(|(uid_i, _)| *uid_i < n as u16)
         {
-            for (uid_j, bonds_ij) in bonds_vec {
+            for (uid_j, bonds_ij) in bonds_vec
+                .into_iter()
+                .filter(|(uid_j, _)| !vacant.get(*uid_j as usize).copied().unwrap_or(true))
+            {
                 bonds
                     .get_mut(uid_i as usize)
                     .expect("uid_i is filtered to be less than n; qed")
@@ -972,8 +988,10 @@ impl<T: Config> Pallet<T> {
     }
 
     /// Output unnormalized bonds in [n, n] matrix, input bonds are assumed to be column max-upscaled in u16.
+    /// Bonds pointing at vacant uids, or at uids past the subnet size, are left out.
     pub fn get_bonds(netuid: NetUid) -> Vec<Vec<I32F32>> {
         let n: usize = Self::get_subnetwork_n(netuid) as usize;
+        let vacant = Self::get_vacant_uids_mask(netuid);
         let mut bonds: Vec<Vec<I32F32>> = vec![vec![I32F32::saturating_from_num(0.0); n]; n];
         for (uid_i, bonds_vec) in
             <Bonds<T> as IterableStorageDoubleMap<NetUid, u16, Vec<(u16, u16)>>>::iter_prefix(
@@ -981,7 +999,10 @@ impl<T: Config> Pallet<T> {
             )
             .filter(|(uid_i, _)| *uid_i < n as u16)
         {
-            for (uid_j, bonds_ij) in bonds_vec.into_iter().filter(|(uid_j, _)| *uid_j < n as u16) {
+            for (uid_j, bonds_ij) in bonds_vec
+                .into_iter()
+                .filter(|(uid_j, _)| !vacant.get(*uid_j as usize).copied().unwrap_or(true))
+            {
                 *bonds
                     .get_mut(uid_i as usize)
                     .expect("uid_i has been filtered to be less than n; qed")
@@ -60,7 +60,59 @@ mod tests;
 // apparently this is stabilized since rust 1.36
 extern crate alloc;
 
-pub const MAX_CRV3_COMMIT_SIZE_BYTES: u32 = 5000;
+pub const MAX_CRV3_COMMIT_SIZE_BYTES: u32 = crv3_commit::MAX_COMMIT_SIZE_BYTES;
+
+/// Most commits a `batch_commit_crv3_weights` call can hold.
+pub const MAX_CRV3_BATCH_COMMITS: usize = 32;
+
+/// Longest emission history a subnet can prune by, in epochs.
+pub const MAX_PRUNING_EMISSION_EPOCHS: u8 = 32;
+
+/// Longest a registration voucher can be valid for, in blocks.
+pub const MAX_REGISTRATION_VOUCHER_VALIDITY: u64 = 7200;
+
+/// Most bids a subnet's registration auction holds at once.
+pub const MAX_REGISTRATION_BIDS: u32 = 256;
+
+/// Most endpoints a single axon can advertise.
+pub const MAX_AXON_ENDPOINTS: u32 = 8;
+
+/// Longest DNS hostname an axon endpoint can advertise, in bytes.
+pub const MAX_AXON_HOSTNAME_LEN: u32 = 253;
+
+/// Most certificates a neuron publishes at once, the current one and its rotation.
+pub const MAX_NEURON_CERTIFICATES: u32 = 2;
+
+/// Most blocks ahead a certificate expiry can be set, about a year.
+pub const MAX_CERTIFICATE_VALIDITY: u64 = 2_628_000;
+
+/// Most scheduled certificate expiries removed per block, the rest wait for the next blocks.
+pub const MAX_CERTIFICATE_EXPIRIES_PER_BLOCK: u32 = 64;
+
+/// Smallest cuckoo cycle graph a subnet can register with, in edge bits.
+pub const MIN_CUCKOO_EDGE_BITS: u8 = cuckoo_pow::MIN_EDGE_BITS;
+
+/// Largest cuckoo cycle graph a subnet can register with, in edge bits.
+pub const MAX_CUCKOO_EDGE_BITS: u8 = cuckoo_pow::MAX_EDGE_BITS;
+
+/// Most uids a pending subnet resize prunes or renumbers per block.
+pub const SUBNET_RESIZE_UIDS_PER_BLOCK: u16 = 64;
+
+/// Epochs the outcomes of revealing v3 weight commits are kept for after their reveal.
+pub const CRV3_REVEAL_STATUS_EPOCHS: u64 = 4;
+
+/// Largest timelocked stake call a coldkey can seal, in bytes.
+pub const MAX_SEALED_CALL_SIZE_BYTES: u32 = 1024;
+
+/// Most sealed stake calls timelocked to a single drand round.
+pub const MAX_SEALED_CALLS_PER_ROUND: u32 = 16;
+
+/// Furthest a sealed stake call can be timelocked past the last stored drand round, in rounds.
+pub const MAX_SEALED_CALL_ROUND_DELAY: u64 = 1200;
+
+/// Fewest rounds a sealed stake call must be timelocked past the round drand publishes at the
+/// time of the block, so its pulse is not public while the call waits to be included.
+pub const MIN_SEALED_CALL_ROUND_MARGIN: u64 = 10;
 
 #[deny(missing_docs)]
 #[import_section(errors::errors)]
@@ -84,7 +136,7 @@ pub mod pallet {
         },
     };
     use frame_system::pallet_prelude::*;
-    use pallet_drand::types::RoundNumber;
+    use pallet_drand::types::{BeaconId, RoundNumber};
     use sp_core::{ConstU32, ConstU64, H160, H256};
     use sp_runtime::traits::{Dispatchable, TrailingZeroInput};
     use sp_std::collections::vec_deque::VecDeque;
@@ -149,6 +201,64 @@ pub mod pallet {
         pub placeholder2: u8,
     }
 
+    /// Hostname of an axon endpoint.
+    pub type AxonHostnameOf = BoundedVec<u8, ConstU32<{ crate::MAX_AXON_HOSTNAME_LEN }>>;
+
+    /// Endpoints advertised by an axon.
+    pub type AxonEndpointsOf = BoundedVec<AxonEndpoint, ConstU32<{ crate::MAX_AXON_ENDPOINTS }>>;
+
+    /// Address an axon endpoint is reachable at.
+    #[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, Debug)]
+    pub enum AxonHost {
+        /// Numerically encoded ip address.
+        Ip {
+            /// Ip type, 4 for ipv4 and 6 for ipv6.
+            ip_type: u8,
+            /// u128 encoded ip address of type v6 or v4.
+            ip: u128,
+        },
+        /// ASCII DNS hostname, resolved by the client.
+        Dns(AxonHostnameOf),
+    }
+
+    /// Transport an axon endpoint is served over.
+    #[derive(Encode, Decode, Default, TypeInfo, Clone, Copy, PartialEq, Eq, Debug)]
+    pub enum AxonTransport {
+        /// Plain TCP.
+        #[default]
+        Tcp,
+        /// Plain UDP.
+        Udp,
+        /// QUIC over UDP.
+        Quic,
+        /// HTTP/2 over TCP.
+        Http2,
+    }
+
+    /// Data structure for a single axon endpoint.
+    #[crate::freeze_struct("c000fb38eaa2e5c0")]
+    #[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, Debug)]
+    pub struct AxonEndpoint {
+        ///  Endpoint ip address or hostname.
+        pub host: AxonHost,
+        ///  Endpoint u16 encoded port.
+        pub port: u16,
+        ///  Endpoint transport.
+        pub transport: AxonTransport,
+    }
+
+    /// Data structure for Axon information advertising several endpoints.
+    #[crate::freeze_struct("a5da57468418a612")]
+    #[derive(Encode, Decode, Default, TypeInfo, Clone, PartialEq, Eq, Debug)]
+    pub struct AxonInfoV2 {
+        ///  Axon serving block.
+        pub block: u64,
+        ///  Axon version
+        pub version: u32,
+        ///  Axon endpoints, in order of preference.
+        pub endpoints: AxonEndpointsOf,
+    }
+
     /// Struct for NeuronCertificate.
     pub type NeuronCertificateOf = NeuronCertificate;
     /// Data structure for NeuronCertificate information.
@@ -179,6 +289,24 @@ pub mod pallet {
         }
     }
 
+    /// Published certificates of a neuron, oldest first.
+    pub type NeuronCertificatesOf =
+        BoundedVec<NeuronCertificateMetadata, ConstU32<{ crate::MAX_NEURON_CERTIFICATES }>>;
+
+    /// Data structure for a published NeuronCertificate and its lifecycle.
+    #[freeze_struct("5811c509ab97e0da")]
+    #[derive(Decode, Encode, TypeInfo, PartialEq, Eq, Clone, Debug)]
+    pub struct NeuronCertificateMetadata {
+        ///  The certificate, holding the public key and its algorithm
+        pub certificate: NeuronCertificateOf,
+        ///  Blake2-256 hash of the algorithm byte followed by the public key
+        pub fingerprint: H256,
+        ///  Block the certificate was published at
+        pub published_at: u64,
+        ///  Block the certificate expires at, None if it does not expire
+        pub not_after: Option<u64>,
+    }
+
     ///  Struct for Prometheus.
     pub type PrometheusInfoOf = PrometheusInfo;
 
@@ -302,6 +430,134 @@ pub mod pallet {
         pub additional: Vec<u8>,
     }
 
+    /// Ranking used to pick the neuron replaced by a new registration on a full subnet.
+    #[derive(Encode, Decode, Default, TypeInfo, Clone, Copy, PartialEq, Eq, Debug)]
+    pub enum PruningPolicy {
+        /// Lowest pruning score of the last epoch.
+        #[default]
+        PruningScore,
+        /// Lowest total emission over the most recent epochs.
+        EmissionHistory {
+            /// Number of epochs the emission is summed over.
+            epochs: u8,
+        },
+        /// Lowest exponential moving average of incentive.
+        IncentiveEma,
+        /// Lowest pruning score, sparing neurons that hold a validator permit.
+        ProtectValidators,
+    }
+
+    /// How long a newly registered neuron is immune to pruning.
+    #[derive(Encode, Decode, Default, TypeInfo, Clone, Copy, PartialEq, Eq, Debug)]
+    pub enum ImmunityPolicy {
+        /// `ImmunityPeriod` blocks after registration.
+        #[default]
+        Blocks,
+        /// `ImmunityPeriod` blocks after registration, ending early once the neuron has earned
+        /// incentive in enough epochs.
+        EarnedIncentive {
+            /// Number of epochs with incentive after which the neuron is no longer immune.
+            epochs: u16,
+        },
+        /// `ImmunityPeriod` blocks after registration, extended while the neuron has not earned
+        /// incentive from its first weights yet.
+        AwaitFirstWeights {
+            /// Longest extension of the immunity period, in blocks.
+            max_extension: u16,
+        },
+    }
+
+    /// Outcome of revealing a commit-reveal v3 weight commit.
+    #[derive(Encode, Decode, TypeInfo, Clone, Copy, PartialEq, Eq, Debug)]
+    pub enum CRV3RevealStatus {
+        /// The weights were set.
+        Revealed,
+        /// The commit is not a serialized timelock ciphertext.
+        InvalidCommit,
+        /// The drand pulse of the reveal round was not stored at the time of reveal.
+        MissingPulse,
+        /// The signature of the drand pulse could not be deserialized.
+        InvalidPulse,
+        /// The commit could not be decrypted with the pulse of the reveal round.
+        DecryptionFailed,
+        /// The decrypted commit is not an encoded `WeightsTlockPayload`.
+        InvalidPayload,
+        /// The payload's version key is not the subnet's weights version key.
+        VersionKeyMismatch,
+        /// The hotkey set weights within the subnet's weights rate limit.
+        RateLimited,
+        /// The weights were rejected by another check of `do_set_weights`.
+        WeightsRejected(DispatchError),
+        /// The commit was not revealed by the end of its reveal epoch and was removed.
+        Expired,
+        /// The commit was removed when the subnet was resized, its uids changed.
+        Dropped,
+    }
+
+    /// Proof of work a subnet accepts for registration.
+    #[derive(Encode, Decode, Default, TypeInfo, Clone, Copy, PartialEq, Eq, Debug)]
+    pub enum PowAlgorithm {
+        /// Keccak and sha256 seal of the block hash, hotkey and nonce.
+        #[default]
+        Seal,
+        /// Cycle of `cuckoo_pow::PROOF_SIZE` edges in a graph seeded by the block hash, hotkey
+        /// and nonce, whose hash must meet the difficulty.
+        CuckooCycle {
+            /// Size of the graph as a power of two of its edges.
+            edge_bits: u8,
+        },
+    }
+
+    /// Stage of a subnet resize running over several blocks.
+    #[derive(Encode, Decode, TypeInfo, Clone, Copy, PartialEq, Eq, Debug)]
+    pub enum SubnetResizeStage {
+        /// Pruning the lowest-ranked uids.
+        Prune,
+        /// Collecting the uids left free, from the uid on.
+        CollectVacant {
+            /// Next uid to check.
+            next_uid: u16,
+        },
+        /// Moving the neurons to their compacted position, from the uid on.
+        Compact {
+            /// Next uid to move.
+            next_uid: u16,
+        },
+        /// Dropping the free uids from the per-uid vectors, from the vector on.
+        ShrinkVectors {
+            /// Next vector to shrink.
+            next_vector: u8,
+        },
+        /// Dropping the pending weight commits, which point at the uids before the resize.
+        ClearCommits,
+    }
+
+    /// Progress of a subnet resize running over several blocks.
+    #[freeze_struct("450d063e1ab37d68")]
+    #[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, Debug)]
+    pub struct SubnetResize {
+        /// Max allowed uids of the subnet once resized.
+        pub max_allowed_uids: u16,
+        /// Lowest-ranked uids left to prune.
+        pub prune: Vec<u16>,
+        /// Sorted uids the compaction closes, complete once they are collected.
+        pub vacant: Vec<u16>,
+        /// Stage the resize is at.
+        pub stage: SubnetResizeStage,
+    }
+
+    /// Subnet owner's approval for a hotkey to register on a whitelisted subnet.
+    #[freeze_struct("f33ce4bd0175ec10")]
+    #[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, Debug)]
+    pub struct RegistrationVoucher {
+        /// Last block the voucher can register at.
+        pub expires_at: u64,
+        /// Number the owner picks to tell its vouchers apart, each one registers once.
+        pub nonce: u64,
+        /// Signature of the owner coldkey over the netuid, the hotkey, `expires_at` and `nonce`.
+        pub signature: sp_core::sr25519::Signature,
+    }
+
     /// ============================
     /// ==== Staking + Accounts ====
     /// ============================
@@ -430,6 +686,11 @@ pub mod pallet {
         0
     }
     #[pallet::type_value]
+    /// Default number of blocks the deregistration refund decays over, a week.
+    pub fn DefaultDeregistrationRefundDecay<T: Config>() -> u64 {
+        50400
+    }
+    #[pallet::type_value]
     /// Default EMA price halving blocks
     pub fn DefaultEMAPriceMovingBlocks<T: Config>() -> u64 {
         T::InitialEmaPriceHalvingPeriod::get()
@@ -1249,6 +1510,9 @@ pub mod pallet {
     pub type NetworkPowRegistrationAllowed<T: Config> =
         StorageMap<_, Identity, NetUid, bool, ValueQuery, DefaultRegistrationAllowed<T>>;
     #[pallet::storage]
+    /// --- MAP ( netuid ) --> proof of work accepted for registration
+    pub type SubnetPowAlgorithm<T> = StorageMap<_, Identity, NetUid, PowAlgorithm, ValueQuery>;
+    #[pallet::storage]
     /// --- MAP ( netuid ) --> block_created
     pub type NetworkRegisteredAt<T: Config> =
         StorageMap<_, Identity, NetUid, u64, ValueQuery, DefaultNetworkRegisteredAt<T>>;
@@ -1407,6 +1671,58 @@ pub mod pallet {
     pub type RegistrationsThisBlock<T> =
         StorageMap<_, Identity, NetUid, u16, ValueQuery, DefaultRegistrationsThisBlock<T>>;
     #[pallet::storage]
+    /// --- MAP ( netuid ) --> registration_auction_enabled
+    pub type RegistrationAuctionEnabled<T> = StorageMap<_, Identity, NetUid, bool, ValueQuery>;
+    #[pallet::storage]
+    /// --- DMAP ( netuid, hotkey ) --> ( coldkey, bid, block ) | Open bids of the registration auction.
+    pub type RegistrationBids<T: Config> = StorageDoubleMap<
+        _,
+        Identity,
+        NetUid,
+        Blake2_128Concat,
+        T::AccountId,
+        (T::AccountId, u64, u64),
+        OptionQuery,
+    >;
+    #[pallet::storage]
+    /// --- MAP ( netuid ) --> Number of open bids of the registration auction.
+    pub type RegistrationBidCount<T> = StorageMap<_, Identity, NetUid, u32, ValueQuery>;
+    #[pallet::storage]
+    /// --- MAP ( netuid ) --> registration_whitelist_enabled
+    pub type RegistrationWhitelistEnabled<T> = StorageMap<_, Identity, NetUid, bool, ValueQuery>;
+    #[pallet::storage]
+    /// --- DMAP ( netuid, hotkey ) --> coldkey | Hotkeys the owner allows to register, with their coldkey.
+    pub type RegistrationWhitelist<T: Config> = StorageDoubleMap<
+        _,
+        Identity,
+        NetUid,
+        Blake2_128Concat,
+        T::AccountId,
+        T::AccountId,
+        OptionQuery,
+    >;
+    #[pallet::storage]
+    /// --- DMAP ( expires_at, ( netuid, nonce ) ) --> () | Vouchers used or revoked before they expire.
+    pub type SpentRegistrationVouchers<T> =
+        StorageDoubleMap<_, Identity, u64, Identity, (NetUid, u64), (), OptionQuery>;
+    #[pallet::storage]
+    /// --- MAP ( netuid ) --> share of the registration burn refunded on deregistration
+    pub type DeregistrationRefund<T> = StorageMap<_, Identity, NetUid, u16, ValueQuery>;
+    #[pallet::storage]
+    /// --- MAP ( netuid ) --> blocks after registration until the deregistration refund is zero
+    pub type DeregistrationRefundDecay<T> =
+        StorageMap<_, Identity, NetUid, u64, ValueQuery, DefaultDeregistrationRefundDecay<T>>;
+    #[pallet::storage]
+    /// --- DMAP ( netuid, uid ) --> alpha burned by the registration of the neuron
+    pub type RegistrationBurnedAlpha<T> =
+        StorageDoubleMap<_, Identity, NetUid, Identity, u16, u64, ValueQuery>;
+    #[pallet::storage]
+    /// --- MAP ( netuid ) --> uids freed by deregistration, filled by the next registrations
+    pub type VacantUids<T> = StorageMap<_, Identity, NetUid, Vec<u16>, ValueQuery>;
+    #[pallet::storage]
+    /// --- MAP ( netuid ) --> resize of the subnet in progress
+    pub type SubnetResizes<T> = StorageMap<_, Identity, NetUid, SubnetResize, OptionQuery>;
+    #[pallet::storage]
     /// --- MAP ( netuid ) --> Halving time of average moving price.
     pub type EMAPriceHalvingBlocks<T> =
         StorageMap<_, Identity, NetUid, u64, ValueQuery, DefaultEMAPriceMovingBlocks<T>>;
@@ -1509,6 +1825,35 @@ pub mod pallet {
     pub type PruningScores<T: Config> =
         StorageMap<_, Identity, NetUid, Vec<u16>, ValueQuery, EmptyU16Vec<T>>;
     #[pallet::storage]
+    /// --- MAP ( netuid ) --> pruning_policy
+    pub type UidPruningPolicy<T> = StorageMap<_, Identity, NetUid, PruningPolicy, ValueQuery>;
+    #[pallet::storage]
+    /// --- MAP ( netuid ) --> immunity_policy
+    pub type SubnetImmunityPolicy<T> = StorageMap<_, Identity, NetUid, ImmunityPolicy, ValueQuery>;
+    #[pallet::storage]
+    /// --- MAP ( netuid ) --> epochs with incentive since registration, per uid
+    pub type IncentiveEpochs<T: Config> =
+        StorageMap<_, Identity, NetUid, Vec<u16>, ValueQuery, EmptyU16Vec<T>>;
+    #[pallet::storage]
+    /// --- DMAP ( netuid, slot ) --> emission of one of the most recent epochs, slots are reused in turn
+    pub type PruningEmissionHistory<T: Config> =
+        StorageDoubleMap<_, Identity, NetUid, Identity, u8, Vec<u64>, ValueQuery>;
+    #[pallet::storage]
+    /// --- MAP ( netuid ) --> emission summed over the most recent epochs, per uid
+    pub type PruningEmissionTotal<T: Config> =
+        StorageMap<_, Identity, NetUid, Vec<u64>, ValueQuery, EmptyU64Vec<T>>;
+    #[pallet::storage]
+    /// --- MAP ( netuid ) --> number of epochs recorded in the emission history
+    pub type PruningEmissionEpochs<T: Config> = StorageMap<_, Identity, NetUid, u64, ValueQuery>;
+    #[pallet::storage]
+    /// --- MAP ( netuid ) --> epochs recorded before the neuron registered, per uid
+    pub type PruningEmissionStart<T: Config> =
+        StorageMap<_, Identity, NetUid, Vec<u64>, ValueQuery, EmptyU64Vec<T>>;
+    #[pallet::storage]
+    /// --- MAP ( netuid ) --> incentive_ema
+    pub type IncentiveEma<T: Config> =
+        StorageMap<_, Identity, NetUid, Vec<u16>, ValueQuery, EmptyU16Vec<T>>;
+    #[pallet::storage]
     /// --- MAP ( netuid ) --> validator_permit
     pub type ValidatorPermit<T: Config> =
         StorageMap<_, Identity, NetUid, Vec<bool>, ValueQuery, EmptyBoolVec<T>>;
@@ -1559,6 +1904,17 @@ pub mod pallet {
         AxonInfoOf,
         OptionQuery,
     >;
+    #[pallet::storage]
+    /// --- MAP ( netuid, hotkey ) --> axon_info_v2
+    pub type AxonsV2<T: Config> = StorageDoubleMap<
+        _,
+        Identity,
+        NetUid,
+        Blake2_128Concat,
+        T::AccountId,
+        AxonInfoV2,
+        OptionQuery,
+    >;
     /// --- MAP ( netuid, hotkey ) --> certificate
     #[pallet::storage]
     pub type NeuronCertificates<T: Config> = StorageDoubleMap<
@@ -1571,6 +1927,31 @@ pub mod pallet {
         OptionQuery,
     >;
     #[pallet::storage]
+    /// --- MAP ( netuid, hotkey ) --> published certificates, oldest first
+    pub type PublishedNeuronCertificates<T: Config> = StorageDoubleMap<
+        _,
+        Identity,
+        NetUid,
+        Blake2_128Concat,
+        T::AccountId,
+        NeuronCertificatesOf,
+        ValueQuery,
+    >;
+    #[pallet::storage]
+    /// --- DMAP ( block, ( netuid, hotkey ) ) --> () | neurons with a certificate expiring at this block
+    pub type NeuronCertificateExpiries<T: Config> = StorageDoubleMap<
+        _,
+        Identity,
+        u64,
+        Blake2_128Concat,
+        (NetUid, T::AccountId),
+        (),
+        OptionQuery,
+    >;
+    #[pallet::storage]
+    /// --- ITEM ( block ) | First block whose certificate expiries are not all removed yet.
+    pub type NeuronCertificateExpiryCursor<T> = StorageValue<_, u64, OptionQuery>;
+    #[pallet::storage]
     /// --- MAP ( netuid, hotkey ) --> prometheus_info
     pub type Prometheus<T: Config> = StorageDoubleMap<
         _,
@@ -1642,7 +2023,7 @@ pub mod pallet {
         OptionQuery,
     >;
     #[pallet::storage]
-    /// --- MAP (netuid, commit_epoch) --> VecDeque<(who, serialized_compressed_commit, reveal_round)> | Stores a queue of v3 commits for an account on a given netuid.
+    /// --- MAP (netuid, commit_epoch) --> VecDeque<(who, serialized_compressed_commit, reveal_round, beacon_id)> | Stores a queue of v3 commits for an account on a given netuid, revealed with the pulse of the main drand beacon, or of the registered beacon with the id.
     pub type CRV3WeightCommits<T: Config> = StorageDoubleMap<
         _,
         Twox64Concat,
@@ -1653,10 +2034,38 @@ pub mod pallet {
             T::AccountId,
             BoundedVec<u8, ConstU32<MAX_CRV3_COMMIT_SIZE_BYTES>>,
             RoundNumber,
+            Option<BeaconId>,
         )>,
         ValueQuery,
     >;
     #[pallet::storage]
+    /// --- NMAP (netuid, commit_epoch, hotkey) --> Vec<(reveal_round, status)> | Outcomes of revealing the v3 commits of a hotkey committed during an epoch, in reveal order, kept for `CRV3_REVEAL_STATUS_EPOCHS` epochs.
+    pub type CRV3RevealStatuses<T: Config> = StorageNMap<
+        _,
+        (
+            NMapKey<Identity, NetUid>,               // subnet
+            NMapKey<Identity, u64>,                  // commit epoch
+            NMapKey<Blake2_128Concat, T::AccountId>, // hot
+        ),
+        Vec<(RoundNumber, CRV3RevealStatus)>,
+        ValueQuery,
+    >;
+    #[pallet::storage]
+    /// --- MAP (reveal_round) --> Vec<(coldkey, sealed_call)> | Timelocked stake calls dispatched once the pulse of their drand round is stored, in submission order.
+    pub type SealedStakeCalls<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        RoundNumber,
+        BoundedVec<
+            (
+                T::AccountId,
+                BoundedVec<u8, ConstU32<MAX_SEALED_CALL_SIZE_BYTES>>,
+            ),
+            ConstU32<MAX_SEALED_CALLS_PER_ROUND>,
+        >,
+        ValueQuery,
+    >;
+    #[pallet::storage]
     /// --- Map (netuid) --> Number of epochs allowed for commit reveal periods
     pub type RevealPeriodEpochs<T: Config> =
         StorageMap<_, Twox64Concat, NetUid, u64, ValueQuery, DefaultRevealPeriodEpochs<T>>;
@@ -2077,7 +2486,8 @@ where
                     Err(CustomTransactionError::StakeAmountTooLow.into())
                 }
             }
-            Some(Call::commit_crv3_weights { netuid, .. }) => {
+            Some(Call::commit_crv3_weights { netuid, .. })
+            | Some(Call::commit_crv3_weights_for_beacon { netuid, .. }) => {
                 if Self::check_weights_min_stake(who, *netuid) {
                     let priority: u64 = Pallet::<T>::get_priority_set_weights(who, *netuid);
                     let validity = ValidTransaction {
@@ -2090,6 +2500,40 @@ where
                     Err(CustomTransactionError::StakeAmountTooLow.into())
                 }
             }
+            Some(Call::batch_commit_crv3_weights { netuids, .. }) => {
+                // The batch is free, so the hotkey needs the minimum stake on every subnet it
+                // commits to, as for a single commit.
+                if netuids.is_empty() || netuids.len() > MAX_CRV3_BATCH_COMMITS {
+                    return Err(CustomTransactionError::BadRequest.into());
+                }
+                if !netuids
+                    .iter()
+                    .all(|netuid| Self::check_weights_min_stake(who, netuid.0))
+                {
+                    return Err(CustomTransactionError::StakeAmountTooLow.into());
+                }
+                let priority: u64 = netuids
+                    .iter()
+                    .map(|netuid| Pallet::<T>::get_priority_set_weights(who, netuid.0))
+                    .min()
+                    .unwrap_or_default();
+                let validity = ValidTransaction {
+                    priority,
+                    longevity: 1,
+                    ..Default::default()
+                };
+                Ok((validity, Some(who.clone()), origin))
+            }
+            Some(Call::submit_sealed_stake_call { .. }) => {
+                if ColdkeySwapScheduled::<T>::contains_key(who) {
+                    return Err(CustomTransactionError::ColdkeyInSwapSchedule.into());
+                }
+                let validity = ValidTransaction {
+                    priority: Self::get_priority_vanilla(),
+                    ..Default::default()
+                };
+                Ok((validity, Some(who.clone()), origin))
+            }
             Some(Call::add_stake {
                 hotkey,
                 netuid,
@@ -2330,7 +2774,11 @@ where
                 )
                 .map(|validity| (validity, Some(who.clone()), origin.clone()))
             }
-            Some(Call::register { netuid, .. } | Call::burned_register { netuid, .. }) => {
+            Some(
+                Call::register { netuid, .. }
+                | Call::burned_register { netuid, .. }
+                | Call::burned_register_with_voucher { netuid, .. },
+            ) => {
                 if ColdkeySwapScheduled::<T>::contains_key(who) {
                     return Err(CustomTransactionError::ColdkeyInSwapSchedule.into());
                 }
@@ -2396,6 +2844,16 @@ where
                 )
                 .map(|validity| (validity, Some(who.clone()), origin.clone()))
             }
+            Some(Call::serve_axon_v2 {
+                netuid, endpoints, ..
+            }) => {
+                // Fully validate the user input
+                Self::result_to_validity(
+                    Pallet::<T>::validate_serve_axon_v2(who, *netuid, endpoints),
+                    Self::get_priority_vanilla(),
+                )
+                .map(|validity| (validity, Some(who.clone()), origin.clone()))
+            }
             _ => {
                 if let Some(
                     BalancesCall::transfer_keep_alive { .. }
@@ -2439,6 +2897,7 @@ where
             Some(Call::register { .. }) => Ok(Some(CallType::Register)),
             Some(Call::serve_axon { .. }) => Ok(Some(CallType::Serve)),
             Some(Call::serve_axon_tls { .. }) => Ok(Some(CallType::Serve)),
+            Some(Call::serve_axon_v2 { .. }) => Ok(Some(CallType::Serve)),
             Some(Call::register_network { .. }) => Ok(Some(CallType::RegisterNetwork)),
             _ => Ok(Some(CallType::Other)),
         }
@@ -2597,6 +3056,10 @@ impl<T: Config + pallet_balances::Config<Balance = u64>>
         Self::get_stake_for_hotkey_and_coldkey_on_subnet(hotkey, coldkey, netuid)
     }
 
+    fn coldkey_owns_hotkey(coldkey: &T::AccountId, hotkey: &T::AccountId) -> bool {
+        Self::coldkey_owns_hotkey(coldkey, hotkey)
+    }
+
     fn increase_balance(coldkey: &T::AccountId, tao: u64) {
         Self::add_balance_to_coldkey_account(coldkey, tao)
     }
@@ -12,7 +12,9 @@ mod dispatches {
     use sp_core::ecdsa::Signature;
     use sp_runtime::{Percent, traits::Saturating};
 
+    use crate::MAX_CRV3_BATCH_COMMITS;
     use crate::MAX_CRV3_COMMIT_SIZE_BYTES;
+    use crate::{MAX_SEALED_CALL_SIZE_BYTES, MAX_SEALED_CALLS_PER_ROUND};
     /// Dispatchable functions allow users to interact with the pallet and invoke state changes.
     /// These functions materialize as "extrinsics", which are often compared to transactions.
     /// Dispatchable functions must be annotated with a weight and must return a DispatchResult.
@@ -267,9 +269,11 @@ mod dispatches {
         ///        to produce a [`TLECiphertext<TinyBLS381>`] type.
         ///     4. Serialize and compress using the `ark-serialize` `CanonicalSerialize` trait.
         ///
+        ///     The `crv3-commit` crate does all of this in `encrypt_commit`.
+        ///
         /// * reveal_round (`u64`):
         ///    - The drand reveal round which will be avaliable during epoch `n+1` from the current
-        ///      epoch.
+        ///      epoch, as computed by `crv3_commit::reveal_round`.
         ///
         /// # Raises:
         /// * `CommitRevealV3Disabled`:
@@ -278,9 +282,12 @@ mod dispatches {
         /// * `TooManyUnrevealedCommits`:
         ///   - Attempting to commit when the user has more than the allowed limit of unrevealed commits.
         ///
+        /// * `CommitRevealRoundNotInFuture`:
+        ///   - Attempting to commit to a drand round whose pulse is already stored.
+        ///
         #[pallet::call_index(99)]
         #[pallet::weight((Weight::from_parts(73_750_000, 0)
-		.saturating_add(T::DbWeight::get().reads(6_u64))
+		.saturating_add(T::DbWeight::get().reads(7_u64))
 		.saturating_add(T::DbWeight::get().writes(2)), DispatchClass::Normal, Pays::No))]
         pub fn commit_crv3_weights(
             origin: T::RuntimeOrigin,
@@ -890,7 +897,7 @@ mod dispatches {
         ///
         #[pallet::call_index(6)]
         #[pallet::weight((Weight::from_parts(216_200_000, 0)
-		.saturating_add(T::DbWeight::get().reads(26))
+		.saturating_add(T::DbWeight::get().reads(31))
 		.saturating_add(T::DbWeight::get().writes(23)), DispatchClass::Normal, Pays::No))]
         pub fn register(
             origin: OriginFor<T>,
@@ -925,14 +932,14 @@ mod dispatches {
         /// User register a new subnetwork via burning token
         #[pallet::call_index(7)]
         #[pallet::weight((Weight::from_parts(354_400_000, 0)
-		.saturating_add(T::DbWeight::get().reads(49))
+		.saturating_add(T::DbWeight::get().reads(53))
 		.saturating_add(T::DbWeight::get().writes(43)), DispatchClass::Normal, Pays::No))]
         pub fn burned_register(
             origin: OriginFor<T>,
             netuid: NetUid,
             hotkey: T::AccountId,
         ) -> DispatchResult {
-            Self::do_burned_registration(origin, netuid, hotkey)
+            Self::do_burned_registration(origin, netuid, hotkey, None)
         }
 
         /// The extrinsic for user to change its hotkey in subnet or all subnets.
@@ -2175,5 +2182,383 @@ mod dispatches {
             Self::deposit_event(Event::SymbolUpdated { netuid, symbol });
             Ok(())
         }
+
+        /// Places or raises a bid for a registration slot on a subnet running a registration
+        /// auction.
+        ///
+        /// # Arguments
+        /// * `origin` - The coldkey paying the bid, which is held until the auction settles.
+        /// * `netuid` - The unique identifier of the subnet to register on.
+        /// * `hotkey` - The hotkey to register.
+        /// * `bid` - The total bid of the hotkey, at least the current burn.
+        ///
+        /// # Errors
+        /// Returns an error if:
+        /// * The subnet does not run a registration auction.
+        /// * The hotkey is already registered on the subnet.
+        /// * The bid is below the burn or does not raise the previous bid of the hotkey.
+        /// * The auction holds the maximum number of bids.
+        ///
+        /// # Events
+        /// Emits a `RegistrationBidPlaced` event on success.
+        #[pallet::call_index(113)]
+        #[pallet::weight((Weight::from_parts(45_000_000, 0)
+		.saturating_add(T::DbWeight::get().reads(12))
+		.saturating_add(T::DbWeight::get().writes(5)), DispatchClass::Normal, Pays::Yes))]
+        pub fn bid_registration(
+            origin: OriginFor<T>,
+            netuid: NetUid,
+            hotkey: T::AccountId,
+            bid: u64,
+        ) -> DispatchResult {
+            Self::do_bid_registration(origin, netuid, hotkey, bid)
+        }
+
+        /// Deregisters the calling hotkey from a subnet, freeing its uid for the next registration.
+        ///
+        /// A share of the registration burn, set by the subnet and decaying with the time since
+        /// registration, is refunded to the coldkey owning the hotkey.
+        ///
+        /// # Arguments
+        /// * `origin` - The hotkey leaving the subnet.
+        /// * `netuid` - The unique identifier of the subnet to leave.
+        ///
+        /// # Errors
+        /// Returns an error if:
+        /// * The hotkey is not registered on the subnet.
+        /// * The subnet is the root network or the hotkey is the subnet owner hotkey.
+        ///
+        /// # Events
+        /// Emits a `NeuronDeregistered` event on success.
+        #[pallet::call_index(114)]
+        #[pallet::weight((Weight::from_parts(120_000_000, 0)
+		.saturating_add(T::DbWeight::get().reads(30))
+		.saturating_add(T::DbWeight::get().writes(28)), DispatchClass::Normal, Pays::Yes))]
+        pub fn deregister(origin: OriginFor<T>, netuid: NetUid) -> DispatchResult {
+            Self::do_deregister(origin, netuid)
+        }
+
+        /// Serves or updates the axon of the calling hotkey with several endpoints.
+        ///
+        /// Each endpoint is an ip address or a DNS hostname with a port and a transport, letting
+        /// neurons behind load balancers or dynamic addresses advertise how to reach them. The
+        /// first ip endpoint is mirrored to the legacy axon info for older clients.
+        ///
+        /// # Arguments
+        /// * `origin` - The hotkey serving the axon.
+        /// * `netuid` - The unique identifier of the subnet.
+        /// * `version` - The bittensor version identifier.
+        /// * `endpoints` - The endpoints, in order of preference.
+        /// * `certificate` - Optional TLS certificate for inter neuron communication.
+        ///
+        /// # Errors
+        /// Returns an error if:
+        /// * The hotkey is not registered on any network.
+        /// * No endpoint is given, or an endpoint has an invalid address, hostname or port.
+        /// * The serving rate limit is exceeded.
+        ///
+        /// # Events
+        /// Emits an `AxonServed` event on success.
+        #[pallet::call_index(115)]
+        #[pallet::weight((Weight::from_parts(36_000_000, 0)
+		.saturating_add(T::DbWeight::get().reads(5))
+		.saturating_add(T::DbWeight::get().writes(3)), DispatchClass::Normal, Pays::No))]
+        pub fn serve_axon_v2(
+            origin: OriginFor<T>,
+            netuid: NetUid,
+            version: u32,
+            endpoints: AxonEndpointsOf,
+            certificate: Option<Vec<u8>>,
+        ) -> DispatchResult {
+            Self::do_serve_axon_v2(origin, netuid, version, endpoints, certificate)
+        }
+
+        /// Publishes a TLS certificate for the calling hotkey, keeping the previous one valid
+        /// so peers can rotate to the new key without downtime.
+        ///
+        /// # Arguments
+        /// * `origin` - The hotkey of the neuron.
+        /// * `netuid` - The unique identifier of the subnet the neuron is registered on.
+        /// * `certificate` - The algorithm byte followed by the public key.
+        /// * `not_after` - The block the certificate expires at, None if it does not expire.
+        ///
+        /// # Errors
+        /// Returns an error if:
+        /// * The hotkey is not registered on the subnet.
+        /// * The certificate is empty or its public key is longer than 64 bytes.
+        /// * The expiry block is not in the future.
+        ///
+        /// # Events
+        /// Emits a `NeuronCertificatePublished` event on success.
+        #[pallet::call_index(116)]
+        #[pallet::weight((Weight::from_parts(25_000_000, 0)
+		.saturating_add(T::DbWeight::get().reads(3))
+		.saturating_add(T::DbWeight::get().writes(3)), DispatchClass::Normal, Pays::Yes))]
+        pub fn publish_certificate(
+            origin: OriginFor<T>,
+            netuid: NetUid,
+            certificate: Vec<u8>,
+            not_after: Option<u64>,
+        ) -> DispatchResult {
+            Self::do_publish_certificate(origin, netuid, certificate, not_after)
+        }
+
+        /// Revokes a certificate of the calling hotkey before its expiry.
+        ///
+        /// # Arguments
+        /// * `origin` - The hotkey of the neuron.
+        /// * `netuid` - The unique identifier of the subnet.
+        /// * `fingerprint` - The Blake2-256 fingerprint of the certificate to revoke.
+        ///
+        /// # Errors
+        /// Returns an error if the neuron has no published certificate with this fingerprint.
+        ///
+        /// # Events
+        /// Emits a `NeuronCertificateRevoked` event on success.
+        #[pallet::call_index(117)]
+        #[pallet::weight((Weight::from_parts(20_000_000, 0)
+		.saturating_add(T::DbWeight::get().reads(1))
+		.saturating_add(T::DbWeight::get().writes(2)), DispatchClass::Normal, Pays::Yes))]
+        pub fn revoke_certificate(
+            origin: OriginFor<T>,
+            netuid: NetUid,
+            fingerprint: H256,
+        ) -> DispatchResult {
+            Self::do_revoke_certificate(origin, netuid, fingerprint)
+        }
+
+        /// Registers a hotkey by burning TAO on a whitelisted subnet it is missing from, with the
+        /// approval of the subnet owner.
+        ///
+        /// # Arguments
+        /// * `origin` - The coldkey paying the burn.
+        /// * `netuid` - The unique identifier of the subnet.
+        /// * `hotkey` - The hotkey to register.
+        /// * `voucher` - The sr25519 signature of the subnet owner coldkey over
+        ///   `registration_voucher_message(netuid, hotkey, coldkey, expires_at, nonce)`, with the
+        ///   origin as `coldkey`, `expires_at` and `nonce`.
+        ///
+        /// # Errors
+        /// Returns the errors of `burned_register`, and an error if the voucher has expired, is
+        /// valid for longer than `MAX_REGISTRATION_VOUCHER_VALIDITY`, has been used or revoked, or
+        /// is not signed by the subnet owner for this chain, netuid, hotkey and coldkey.
+        ///
+        /// # Events
+        /// Emits `RegistrationVoucherUsed` and `NeuronRegistered` events on success.
+        #[pallet::call_index(118)]
+        #[pallet::weight((Weight::from_parts(404_400_000, 0)
+		.saturating_add(T::DbWeight::get().reads(55))
+		.saturating_add(T::DbWeight::get().writes(44)), DispatchClass::Normal, Pays::No))]
+        pub fn burned_register_with_voucher(
+            origin: OriginFor<T>,
+            netuid: NetUid,
+            hotkey: T::AccountId,
+            voucher: RegistrationVoucher,
+        ) -> DispatchResult {
+            Self::do_burned_registration(origin, netuid, hotkey, Some(voucher))
+        }
+
+        /// Resizes a subnet to a new max allowed uids, pruning its lowest-ranked neurons when
+        /// shrinking and renumbering the uids to close the ones left free.
+        ///
+        /// The pruning and renumbering run over the next blocks. Until they are done the subnet
+        /// takes no registrations, deregistrations or weights, and skips its epochs. Pending
+        /// weight commits of the subnet point at the old uids and are dropped.
+        ///
+        /// # Arguments
+        /// * `origin` - The origin of the call, must be root.
+        /// * `netuid` - The unique identifier of the subnet.
+        /// * `max_allowed_uids` - The new max allowed uids of the subnet.
+        ///
+        /// # Errors
+        /// * `BadOrigin` - If the origin is not root.
+        /// * `InvalidSubnetResize` - If the subnet is the root network or the size is zero.
+        /// * `SubNetworkDoesNotExist` - If the subnet does not exist.
+        /// * `SubnetResizeInProgress` - If the subnet is being resized already.
+        ///
+        /// # Events
+        /// Emits `SubnetResizeScheduled`, and `SubnetResized` once the uids are in place.
+        #[pallet::call_index(119)]
+        #[pallet::weight((Weight::from_parts(1_000_000_000, 0)
+		.saturating_add(T::DbWeight::get().reads(8200))
+		.saturating_add(T::DbWeight::get().writes(3)), DispatchClass::Operational, Pays::No))]
+        pub fn resize_subnet(
+            origin: OriginFor<T>,
+            netuid: NetUid,
+            max_allowed_uids: u16,
+        ) -> DispatchResult {
+            Self::do_resize_subnet(origin, netuid, max_allowed_uids)
+        }
+
+        /// ---- Used to commit encrypted commit-reveal v3 weight values timelocked to a drand
+        /// beacon registered besides the main one, to later be revealed with its pulse.
+        ///
+        /// # Args:
+        /// * `origin`: (`<T as frame_system::Config>::RuntimeOrigin`):
+        ///   - The committing hotkey.
+        ///
+        /// * `netuid` (`u16`):
+        ///   - The u16 network identifier.
+        ///
+        /// * `commit` (`Vec<u8>`):
+        ///   - The encrypted compressed commit, as for `commit_crv3_weights`, encrypted to the
+        ///     public key of the beacon.
+        ///
+        /// * `reveal_round` (`u64`):
+        ///   - The round of the beacon which will be avaliable during epoch `n+1` from the
+        ///     current epoch.
+        ///
+        /// * `beacon_id` (`BeaconId`):
+        ///   - The id of the beacon registered in the drand pallet.
+        ///
+        /// # Raises:
+        /// * `BeaconNotRegistered`:
+        ///   - Attempting to commit to a beacon which is not registered.
+        ///
+        /// * `CommitRevealV3Disabled`:
+        ///   - Attempting to commit when the commit-reveal mechanism is disabled.
+        ///
+        /// * `TooManyUnrevealedCommits`:
+        ///   - Attempting to commit when the user has more than the allowed limit of unrevealed commits.
+        ///
+        /// * `CommitRevealRoundNotInFuture`:
+        ///   - Attempting to commit to a drand round whose pulse is already stored.
+        ///
+        #[pallet::call_index(120)]
+        #[pallet::weight((Weight::from_parts(73_750_000, 0)
+		.saturating_add(T::DbWeight::get().reads(8_u64))
+		.saturating_add(T::DbWeight::get().writes(2)), DispatchClass::Normal, Pays::No))]
+        pub fn commit_crv3_weights_for_beacon(
+            origin: T::RuntimeOrigin,
+            netuid: NetUid,
+            commit: BoundedVec<u8, ConstU32<MAX_CRV3_COMMIT_SIZE_BYTES>>,
+            reveal_round: u64,
+            beacon_id: BeaconId,
+        ) -> DispatchResult {
+            Self::do_commit_crv3_weights_for_beacon(
+                origin,
+                netuid,
+                commit,
+                reveal_round,
+                Some(beacon_id),
+            )
+        }
+
+        /// --- Allows a hotkey to commit encrypted commit-reveal v3 weights for multiple netuids
+        /// as a batch.
+        ///
+        /// # Args:
+        /// * `origin`: (<T as frame_system::Config>Origin):
+        ///     - The caller, a hotkey who wishes to commit their weights.
+        ///
+        /// * `netuids` (Vec<Compact<u16>>):
+        /// 	- The network uids we are committing these weights on, at most
+        /// 	  `MAX_CRV3_BATCH_COMMITS`.
+        ///
+        /// * `commits` (Vec<Vec<u8>>):
+        /// 	- The encrypted compressed commits, as for `commit_crv3_weights`.
+        ///
+        /// * `reveal_rounds` (Vec<u64>):
+        /// 	- The drand reveal rounds of the commits.
+        ///
+        /// # Event:
+        /// * CRV3WeightsCommitted;
+        /// 	- On successfully storing each commit.
+        /// * BatchWeightsCompleted;
+        /// 	- On success of the batch, with the netuids committed to.
+        /// * BatchCompletedWithErrors;
+        /// 	- On failure of any of the commits in the batch.
+        /// * BatchCRV3CommitItemFailed;
+        /// 	- On failure for each failed commit in the batch.
+        ///
+        #[pallet::call_index(121)]
+        #[pallet::weight((Weight::from_parts(73_750_000, 0)
+		.saturating_add(T::DbWeight::get().reads(7_u64))
+		.saturating_add(T::DbWeight::get().writes(2))
+		.saturating_mul(netuids.len().min(MAX_CRV3_BATCH_COMMITS) as u64), DispatchClass::Normal, Pays::No))]
+        pub fn batch_commit_crv3_weights(
+            origin: OriginFor<T>,
+            netuids: Vec<Compact<NetUid>>,
+            commits: Vec<Vec<u8>>,
+            reveal_rounds: Vec<u64>,
+        ) -> DispatchResult {
+            Self::do_batch_commit_crv3_weights(origin, netuids, commits, reveal_rounds)
+        }
+
+        /// --- Seals a stake call until a drand round, to be dispatched in the extrinsic storing
+        /// its pulse, so that it cannot be front-run on the subnet pools. The submission pays
+        /// for opening and dispatching the call, whatever it holds.
+        ///
+        /// # Args:
+        /// * `origin`: (`<T as frame_system::Config>::RuntimeOrigin`):
+        ///   - The coldkey the call is dispatched for.
+        ///
+        /// * `sealed_call` (`Vec<u8>`):
+        ///   - The SCALE encoded `add_stake`, `remove_stake`, `swap_stake` call, or one of their
+        ///     `_limit` variants, timelock encrypted to the round of the main drand beacon and
+        ///     serialized compressed, as for `commit_crv3_weights`.
+        ///
+        /// * `reveal_round` (`u64`):
+        ///   - The drand round the call is timelocked to.
+        ///
+        /// # Event:
+        /// * SealedStakeCallSubmitted;
+        ///   - On storing the sealed call.
+        /// * SealedStakeCallDispatched;
+        ///   - Once the call is opened and dispatched with the pulse, with its result.
+        ///
+        /// # Raises:
+        /// * `SealedCallRoundNotInFuture`:
+        ///   - The pulse of the round is already stored.
+        ///
+        /// * `SealedCallRoundTooSoon`:
+        ///   - The round is less than `MIN_SEALED_CALL_ROUND_MARGIN` rounds past the round of
+        ///     the block's time.
+        ///
+        /// * `SealedCallRoundTooFar`:
+        ///   - The round is more than `MAX_SEALED_CALL_ROUND_DELAY` rounds away.
+        ///
+        /// * `TooManySealedCalls`:
+        ///   - The round already holds `MAX_SEALED_CALLS_PER_ROUND` sealed calls.
+        ///
+        #[pallet::call_index(122)]
+        #[pallet::weight((Weight::from_parts(30_000_000, 0)
+		.saturating_add(T::DbWeight::get().reads(5))
+		.saturating_add(T::DbWeight::get().writes(2))
+		.saturating_add(Pallet::<T>::sealed_stake_call_weight()), DispatchClass::Normal, Pays::Yes))]
+        pub fn submit_sealed_stake_call(
+            origin: OriginFor<T>,
+            sealed_call: BoundedVec<u8, ConstU32<MAX_SEALED_CALL_SIZE_BYTES>>,
+            reveal_round: u64,
+        ) -> DispatchResult {
+            Self::do_submit_sealed_stake_call(origin, sealed_call, reveal_round)
+        }
+
+        /// Revokes a registration voucher of the subnet owner before it is used.
+        ///
+        /// # Arguments
+        /// * `origin` - The subnet owner coldkey.
+        /// * `netuid` - The unique identifier of the subnet.
+        /// * `nonce` - The nonce of the voucher.
+        /// * `expires_at` - The last block the voucher can register at.
+        ///
+        /// # Errors
+        /// Returns an error if the origin is not the subnet owner, or the voucher has expired, is
+        /// valid for longer than `MAX_REGISTRATION_VOUCHER_VALIDITY` or is already spent.
+        ///
+        /// # Events
+        /// Emits a `RegistrationVoucherRevoked` event on success.
+        #[pallet::call_index(123)]
+        #[pallet::weight((Weight::from_parts(15_000_000, 0)
+		.saturating_add(T::DbWeight::get().reads(2))
+		.saturating_add(T::DbWeight::get().writes(1)), DispatchClass::Normal, Pays::Yes))]
+        pub fn revoke_registration_voucher(
+            origin: OriginFor<T>,
+            netuid: NetUid,
+            nonce: u64,
+            expires_at: u64,
+        ) -> DispatchResult {
+            Self::do_revoke_registration_voucher(origin, netuid, nonce, expires_at)
+        }
     }
 }
@@ -242,5 +242,60 @@ mod errors {
         SymbolDoesNotExist,
         /// Symbol already in use.
         SymbolAlreadyInUse,
+        /// The subnet does not run a registration auction.
+        RegistrationAuctionDisabled,
+        /// The subnet registers through its auction, place a bid instead.
+        RegistrationAuctionActive,
+        /// The bid is below the burn or does not raise the previous bid.
+        RegistrationBidTooLow,
+        /// The registration auction holds the maximum number of bids.
+        TooManyRegistrationBids,
+        /// The hotkey cannot leave this subnet, it is the root network or the subnet owner hotkey.
+        DeregistrationNotPermitted,
+        /// The axon has no endpoints.
+        NoAxonEndpoints,
+        /// An axon endpoint hostname is not a valid DNS name.
+        InvalidAxonHostname,
+        /// The certificate is empty or its public key is longer than 64 bytes.
+        InvalidCertificate,
+        /// The certificate expiry is not in the future or further than `MAX_CERTIFICATE_VALIDITY`.
+        InvalidCertificateExpiry,
+        /// The neuron has no published certificate with this fingerprint.
+        CertificateNotFound,
+        /// The subnet only registers whitelisted or vouched hotkeys.
+        HotKeyNotWhitelisted,
+        /// The registration voucher is not signed by the subnet owner for this hotkey.
+        InvalidRegistrationVoucher,
+        /// The registration voucher has expired.
+        RegistrationVoucherExpired,
+        /// The registration voucher is valid for longer than `MAX_REGISTRATION_VOUCHER_VALIDITY`.
+        RegistrationVoucherValidityTooLong,
+        /// The registration voucher has already been used or revoked.
+        RegistrationVoucherSpent,
+        /// The subnet is being resized, its uids are not settled.
+        SubnetResizeInProgress,
+        /// Subnets other than root can be resized to at least one uid.
+        InvalidSubnetResize,
+        /// No drand beacon is registered with the id.
+        BeaconNotRegistered,
+        /// The commit is larger than `MAX_CRV3_COMMIT_SIZE_BYTES`.
+        CommitTooLarge,
+        /// The pulse of the drand round is already stored, the call would not be sealed.
+        SealedCallRoundNotInFuture,
+        /// The drand round is more than `MAX_SEALED_CALL_ROUND_DELAY` rounds away.
+        SealedCallRoundTooFar,
+        /// The drand round already holds `MAX_SEALED_CALLS_PER_ROUND` sealed calls.
+        TooManySealedCalls,
+        /// The sealed call could not be decrypted with the pulse of its round.
+        SealedCallDecryptionFailed,
+        /// The sealed call is not an encoded stake call.
+        InvalidSealedCall,
+        /// The pulse of the drand round the v3 commit is timelocked to is already stored.
+        CommitRevealRoundNotInFuture,
+        /// The batch holds more than `MAX_CRV3_BATCH_COMMITS` commits.
+        TooManyBatchCommits,
+        /// The drand round is less than `MIN_SEALED_CALL_ROUND_MARGIN` rounds past the round
+        /// drand publishes at the time of the block, its pulse may already be public.
+        SealedCallRoundTooSoon,
     }
 }
@@ -70,6 +70,18 @@ mod events {
         BondsPenaltySet(NetUid, u16),
         /// bonds reset is set for a subnet.
         BondsResetOnSet(NetUid, bool),
+        /// the uid pruning policy is set for a subnet.
+        PruningPolicySet(NetUid, PruningPolicy),
+        /// the immunity policy of newly registered neurons is set for a subnet.
+        ImmunityPolicySet(NetUid, ImmunityPolicy),
+        /// the registration auction is enabled or disabled for a subnet.
+        RegistrationAuctionSet(NetUid, bool),
+        /// the deregistration refund share and decay are set for a subnet.
+        DeregistrationRefundSet(NetUid, u16, u64),
+        /// the proof of work accepted for registration is set for a subnet.
+        PowAlgorithmSet(NetUid, PowAlgorithm),
+        /// the registration whitelist is enabled or disabled for a subnet.
+        RegistrationWhitelistSet(NetUid, bool),
         /// setting the max number of allowed validators on a subnet.
         MaxAllowedValidatorsSet(NetUid, u16),
         /// the axon server information is added to the network.
@@ -264,6 +276,12 @@ mod events {
         /// - **error**: The dispatch error emitted by the failed item.
         BatchWeightItemFailed(sp_runtime::DispatchError),
 
+        /// A commit-reveal v3 commit among a batch of commits failed.
+        ///
+        /// - **netuid**: The network identifier of the failed commit.
+        /// - **error**: The dispatch error emitted by the failed commit.
+        BatchCRV3CommitItemFailed(NetUid, sp_runtime::DispatchError),
+
         /// Stake has been transferred from one coldkey to another on the same subnet.
         /// Parameters:
         /// (origin_coldkey, destination_coldkey, hotkey, origin_netuid, destination_netuid, amount)
@@ -330,6 +348,27 @@ mod events {
         /// - **who**: The account ID of the user revealing the weights.
         CRV3WeightsRevealed(NetUid, T::AccountId),
 
+        /// A v3 weight commit could not be revealed.
+        ///
+        /// - **netuid**: The network identifier.
+        /// - **who**: The account ID of the user who committed the weights.
+        /// - **reveal_round**: The drand round the commit was timelocked to.
+        /// - **status**: The reason the reveal failed.
+        CRV3WeightsRevealFailed(NetUid, T::AccountId, RoundNumber, CRV3RevealStatus),
+
+        /// A stake call has been sealed until a drand round.
+        ///
+        /// - **coldkey**: The coldkey the call is dispatched for.
+        /// - **reveal_round**: The drand round the call is timelocked to.
+        SealedStakeCallSubmitted(T::AccountId, RoundNumber),
+
+        /// A sealed stake call has been opened and dispatched.
+        ///
+        /// - **coldkey**: The coldkey the call was dispatched for.
+        /// - **reveal_round**: The drand round the call was timelocked to.
+        /// - **result**: The result of the call, or the reason it could not be opened.
+        SealedStakeCallDispatched(T::AccountId, RoundNumber, DispatchResult),
+
         /// Commit-Reveal periods has been successfully set.
         ///
         /// - **netuid**: The network identifier.
@@ -381,5 +420,135 @@ mod events {
             /// The symbol that has been updated.
             symbol: Vec<u8>,
         },
+
+        /// A bid has been placed or raised in a registration auction.
+        RegistrationBidPlaced {
+            /// The subnet ID
+            netuid: NetUid,
+            /// The hotkey bidding for a slot.
+            hotkey: T::AccountId,
+            /// The coldkey paying the bid.
+            coldkey: T::AccountId,
+            /// The total bid of the hotkey.
+            bid: u64,
+        },
+
+        /// A bid, or the part of a winning bid above the clearing price, has been refunded.
+        RegistrationBidRefunded {
+            /// The subnet ID
+            netuid: NetUid,
+            /// The hotkey of the bid.
+            hotkey: T::AccountId,
+            /// The coldkey receiving the refund.
+            coldkey: T::AccountId,
+            /// The refunded amount.
+            amount: u64,
+        },
+
+        /// A registration auction has been settled at the end of the adjustment interval.
+        RegistrationAuctionSettled {
+            /// The subnet ID
+            netuid: NetUid,
+            /// The number of hotkeys registered.
+            registered: u16,
+            /// The burn paid by every registered hotkey.
+            clearing_price: u64,
+        },
+
+        /// A hotkey has left a subnet, freeing its uid.
+        NeuronDeregistered {
+            /// The subnet ID
+            netuid: NetUid,
+            /// The freed uid.
+            uid: u16,
+            /// The hotkey leaving the subnet.
+            hotkey: T::AccountId,
+            /// The TAO refunded to the coldkey owning the hotkey.
+            refund: u64,
+        },
+
+        /// A neuron has published a TLS certificate.
+        NeuronCertificatePublished {
+            /// The subnet ID
+            netuid: NetUid,
+            /// The hotkey of the neuron.
+            hotkey: T::AccountId,
+            /// The fingerprint of the certificate.
+            fingerprint: H256,
+            /// The block the certificate expires at, if any.
+            not_after: Option<u64>,
+        },
+
+        /// A neuron certificate has been removed before its expiry.
+        NeuronCertificateRevoked {
+            /// The subnet ID
+            netuid: NetUid,
+            /// The hotkey of the neuron.
+            hotkey: T::AccountId,
+            /// The fingerprint of the certificate.
+            fingerprint: H256,
+        },
+
+        /// A neuron certificate has expired and been removed.
+        NeuronCertificateExpired {
+            /// The subnet ID
+            netuid: NetUid,
+            /// The hotkey of the neuron.
+            hotkey: T::AccountId,
+            /// The fingerprint of the certificate.
+            fingerprint: H256,
+        },
+
+        /// A hotkey has been added to or removed from the registration whitelist of a subnet.
+        RegistrationWhitelistUpdated {
+            /// The subnet ID
+            netuid: NetUid,
+            /// The hotkey allowed or no longer allowed to register.
+            hotkey: T::AccountId,
+            /// The coldkey the hotkey may register with, None if it is no longer whitelisted.
+            coldkey: Option<T::AccountId>,
+        },
+
+        /// A hotkey has registered with a voucher of the subnet owner.
+        RegistrationVoucherUsed {
+            /// The subnet ID
+            netuid: NetUid,
+            /// The hotkey registered.
+            hotkey: T::AccountId,
+            /// The last block the voucher could register at.
+            expires_at: u64,
+            /// The nonce of the voucher.
+            nonce: u64,
+        },
+
+        /// The subnet owner revoked a registration voucher before it expired.
+        RegistrationVoucherRevoked {
+            /// The subnet ID
+            netuid: NetUid,
+            /// The nonce of the voucher.
+            nonce: u64,
+            /// The last block the voucher could register at.
+            expires_at: u64,
+        },
+
+        /// A subnet started pruning and renumbering its uids to a new size.
+        SubnetResizeScheduled {
+            /// The subnet ID
+            netuid: NetUid,
+            /// The max allowed uids once resized.
+            max_allowed_uids: u16,
+            /// Number of neurons pruned.
+            pruned: u16,
+        },
+
+        /// A subnet is resized and its uids are renumbered.
+        SubnetResized {
+            /// The subnet ID
+            netuid: NetUid,
+            /// The max allowed uids of the subnet.
+            max_allowed_uids: u16,
+            /// Number of uids of the subnet.
+            subnetwork_n: u16,
+        },
     }
 }
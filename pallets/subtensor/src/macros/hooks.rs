@@ -19,12 +19,13 @@ mod hooks {
 
             let block_step_result = Self::block_step();
             match block_step_result {
-                Ok(_) => {
+                Ok(block_step_weight) => {
                     // --- If the block step was successful, return the weight.
                     log::debug!("Successfully ran block step.");
                     Weight::from_parts(110_634_229_000_u64, 0)
                         .saturating_add(T::DbWeight::get().reads(8304_u64))
                         .saturating_add(T::DbWeight::get().writes(110_u64))
+                        .saturating_add(block_step_weight)
                         .saturating_add(hotkey_swap_clean_up_weight)
                 }
                 Err(e) => {
@@ -125,7 +126,15 @@ mod hooks {
                 // Fix the owner disable the registration
                 .saturating_add(migrations::migrate_set_registration_enable::migrate_set_registration_enable::<T>())
                 // Migrate Subnet Identities to V3
-                .saturating_add(migrations::migrate_subnet_identities_to_v3::migrate_subnet_identities_to_v3::<T>());
+                .saturating_add(migrations::migrate_subnet_identities_to_v3::migrate_subnet_identities_to_v3::<T>())
+                // Copy axons to the multi endpoint AxonsV2
+                .saturating_add(migrations::migrate_axons_to_v2::migrate_axons_to_v2::<T>())
+                // Publish the existing neuron certificates with their metadata
+                .saturating_add(migrations::migrate_published_neuron_certificates::migrate_published_neuron_certificates::<T>())
+                // Record the drand beacon of the pending v3 weight commits
+                .saturating_add(migrations::migrate_crv3_commits_beacon_id::migrate_crv3_commits_beacon_id::<T>())
+                // Hold the drand rounds the pending reveals wait on
+                .saturating_add(migrations::migrate_hold_pending_reveal_rounds::migrate_hold_pending_reveal_rounds::<T>());
             weight
         }
 
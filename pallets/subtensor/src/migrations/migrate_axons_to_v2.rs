@@ -0,0 +1,61 @@
+use super::*;
+use frame_support::weights::Weight;
+use log;
+use scale_info::prelude::string::String;
+
+pub fn migrate_axons_to_v2<T: Config>() -> Weight {
+    use frame_support::traits::Get;
+    let migration_name = b"migrate_axons_to_v2".to_vec();
+
+    // Start counting weight
+    let mut weight = T::DbWeight::get().reads(1);
+
+    // Check if we already ran this migration
+    if HasMigrationRun::<T>::get(&migration_name) {
+        log::info!(
+            target: "runtime",
+            "Migration '{:?}' has already run. Skipping.",
+            String::from_utf8_lossy(&migration_name)
+        );
+        return weight;
+    }
+
+    log::info!(
+        target: "runtime",
+        "Running migration '{}'",
+        String::from_utf8_lossy(&migration_name)
+    );
+
+    // -----------------------------
+    // 1) Copy every axon as a single endpoint AxonInfoV2
+    // -----------------------------
+    // The legacy map is kept for older clients, serving keeps both in sync from now on.
+    // This runs in a single upgrade block: axons only exist for registered uids, so the copy is
+    // bounded by the total uid count of all subnets (a few tens of thousands of entries), two
+    // reads and one write each, which fits the block weight of the upgrade.
+    let mut migrated: u64 = 0;
+    for (netuid, hotkey, axon_info) in Axons::<T>::iter() {
+        weight = weight.saturating_add(T::DbWeight::get().reads(2));
+        if AxonsV2::<T>::contains_key(netuid, &hotkey) {
+            continue;
+        }
+        AxonsV2::<T>::insert(netuid, &hotkey, Pallet::<T>::axon_info_to_v2(&axon_info));
+        weight = weight.saturating_add(T::DbWeight::get().writes(1));
+        migrated = migrated.saturating_add(1);
+    }
+
+    // -----------------------------
+    // Mark the migration as done
+    // -----------------------------
+    HasMigrationRun::<T>::insert(&migration_name, true);
+    weight = weight.saturating_add(T::DbWeight::get().writes(1));
+
+    log::info!(
+        target: "runtime",
+        "Migration '{}' completed successfully. {} axons migrated.",
+        String::from_utf8_lossy(&migration_name),
+        migrated
+    );
+
+    weight
+}
@@ -0,0 +1,65 @@
+use super::*;
+use frame_support::{traits::Get, weights::Weight};
+use pallet_drand::types::{BeaconId, RoundNumber};
+use scale_info::prelude::string::String;
+use sp_std::collections::vec_deque::VecDeque;
+
+/// The v3 weight commits before they recorded the drand beacon they are revealed with.
+type OldCRV3Commits<AccountId> = VecDeque<(
+    AccountId,
+    BoundedVec<u8, ConstU32<MAX_CRV3_COMMIT_SIZE_BYTES>>,
+    RoundNumber,
+)>;
+
+/// Migrate the CRV3WeightCommits to record the beacon of each commit, the existing commits being
+/// revealed with the main beacon.
+pub fn migrate_crv3_commits_beacon_id<T: Config>() -> Weight {
+    let migration_name = b"migrate_crv3_commits_beacon_id".to_vec();
+    let mut weight = T::DbWeight::get().reads(1);
+
+    if HasMigrationRun::<T>::get(&migration_name) {
+        log::info!(
+            "Migration '{:?}' has already run. Skipping.",
+            String::from_utf8_lossy(&migration_name)
+        );
+        return weight;
+    }
+
+    log::info!(
+        "Running migration '{}'",
+        String::from_utf8_lossy(&migration_name)
+    );
+
+    // ------------------------------
+    // Step 1: Translate the commits of every subnet and epoch
+    // ------------------------------
+    let mut translated: u64 = 0;
+    CRV3WeightCommits::<T>::translate::<OldCRV3Commits<T::AccountId>, _>(
+        |_netuid, _epoch, commits| {
+            translated = translated.saturating_add(1);
+            Some(
+                commits
+                    .into_iter()
+                    .map(|(who, commit, reveal_round)| {
+                        (who, commit, reveal_round, None::<BeaconId>)
+                    })
+                    .collect(),
+            )
+        },
+    );
+    weight = weight.saturating_add(T::DbWeight::get().reads_writes(translated, translated));
+
+    // ------------------------------
+    // Step 2: Mark Migration as Completed
+    // ------------------------------
+    HasMigrationRun::<T>::insert(&migration_name, true);
+    weight = weight.saturating_add(T::DbWeight::get().writes(1));
+
+    log::info!(
+        "Migration '{}' completed successfully. {} commit queues migrated.",
+        String::from_utf8_lossy(&migration_name),
+        translated
+    );
+
+    weight
+}
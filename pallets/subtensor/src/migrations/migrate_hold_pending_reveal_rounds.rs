@@ -0,0 +1,61 @@
+use super::*;
+use frame_support::{traits::Get, weights::Weight};
+use scale_info::prelude::string::String;
+
+/// Hold the drand rounds the v3 weight commits and the sealed stake calls made before the rounds
+/// were tracked wait on, so their pulses are not pruned before the reveal.
+pub fn migrate_hold_pending_reveal_rounds<T: Config>() -> Weight {
+    let migration_name = b"migrate_hold_pending_reveal_rounds".to_vec();
+    let mut weight = T::DbWeight::get().reads(1);
+
+    if HasMigrationRun::<T>::get(&migration_name) {
+        log::info!(
+            "Migration '{:?}' has already run. Skipping.",
+            String::from_utf8_lossy(&migration_name)
+        );
+        return weight;
+    }
+
+    log::info!(
+        "Running migration '{}'",
+        String::from_utf8_lossy(&migration_name)
+    );
+
+    // ------------------------------
+    // Step 1: Hold the rounds of the pending v3 weight commits
+    // ------------------------------
+    let mut held: u64 = 0;
+    for commits in CRV3WeightCommits::<T>::iter_values() {
+        weight = weight.saturating_add(T::DbWeight::get().reads(1));
+        for (_, _, round, beacon_id) in commits {
+            pallet_drand::Pallet::<T>::hold_reveal_round(beacon_id.as_ref(), round);
+            held = held.saturating_add(1);
+        }
+    }
+
+    // ------------------------------
+    // Step 2: Hold the rounds of the pending sealed stake calls
+    // ------------------------------
+    for (round, calls) in SealedStakeCalls::<T>::iter() {
+        weight = weight.saturating_add(T::DbWeight::get().reads(1));
+        for _ in calls {
+            pallet_drand::Pallet::<T>::hold_reveal_round(None, round);
+            held = held.saturating_add(1);
+        }
+    }
+    weight = weight.saturating_add(T::DbWeight::get().reads_writes(held, held));
+
+    // ------------------------------
+    // Step 3: Mark Migration as Completed
+    // ------------------------------
+    HasMigrationRun::<T>::insert(&migration_name, true);
+    weight = weight.saturating_add(T::DbWeight::get().writes(1));
+
+    log::info!(
+        "Migration '{}' completed successfully. {} pending reveals held.",
+        String::from_utf8_lossy(&migration_name),
+        held
+    );
+
+    weight
+}
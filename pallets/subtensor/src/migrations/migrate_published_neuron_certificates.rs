@@ -0,0 +1,70 @@
+use super::*;
+use frame_support::weights::Weight;
+use log;
+use scale_info::prelude::string::String;
+use sp_std::vec;
+
+pub fn migrate_published_neuron_certificates<T: Config>() -> Weight {
+    use frame_support::traits::Get;
+    let migration_name = b"migrate_published_neuron_certificates".to_vec();
+
+    // Start counting weight
+    let mut weight = T::DbWeight::get().reads(1);
+
+    // Check if we already ran this migration
+    if HasMigrationRun::<T>::get(&migration_name) {
+        log::info!(
+            target: "runtime",
+            "Migration '{:?}' has already run. Skipping.",
+            String::from_utf8_lossy(&migration_name)
+        );
+        return weight;
+    }
+
+    log::info!(
+        target: "runtime",
+        "Running migration '{}'",
+        String::from_utf8_lossy(&migration_name)
+    );
+
+    // -----------------------------
+    // 1) Publish every certificate without expiry
+    // -----------------------------
+    // NeuronCertificates keeps mirroring the newest published certificate.
+    let current_block = Pallet::<T>::get_current_block_as_u64();
+    let mut migrated: u64 = 0;
+    for (netuid, hotkey, certificate) in NeuronCertificates::<T>::iter() {
+        weight = weight.saturating_add(T::DbWeight::get().reads(2));
+        if PublishedNeuronCertificates::<T>::contains_key(netuid, &hotkey) {
+            continue;
+        }
+        let published = NeuronCertificateMetadata {
+            fingerprint: Pallet::<T>::get_certificate_fingerprint(&certificate),
+            certificate,
+            published_at: current_block,
+            not_after: None,
+        };
+        PublishedNeuronCertificates::<T>::insert(
+            netuid,
+            &hotkey,
+            NeuronCertificatesOf::truncate_from(vec![published]),
+        );
+        weight = weight.saturating_add(T::DbWeight::get().writes(1));
+        migrated = migrated.saturating_add(1);
+    }
+
+    // -----------------------------
+    // Mark the migration as done
+    // -----------------------------
+    HasMigrationRun::<T>::insert(&migration_name, true);
+    weight = weight.saturating_add(T::DbWeight::get().writes(1));
+
+    log::info!(
+        target: "runtime",
+        "Migration '{}' completed successfully. {} certificates migrated.",
+        String::from_utf8_lossy(&migration_name),
+        migrated
+    );
+
+    weight
+}
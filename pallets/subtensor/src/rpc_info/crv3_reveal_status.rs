@@ -0,0 +1,45 @@
+use super::*;
+use frame_support::pallet_prelude::{Decode, Encode};
+extern crate alloc;
+use codec::Compact;
+use subtensor_runtime_common::NetUid;
+
+#[freeze_struct("618a7cc66f177966")]
+#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug, TypeInfo)]
+pub struct CRV3RevealStatusInfo<AccountId: TypeInfo + Encode + Decode> {
+    pub hotkey: AccountId,
+    pub reveal_round: Compact<u64>, // drand round the commit was timelocked to
+    pub status: CRV3RevealStatus,
+}
+
+impl<T: Config> Pallet<T> {
+    /// Returns the outcomes of revealing the v3 commits of the subnet committed during the epoch,
+    /// or of the hotkey's commits only. The outcomes are kept for `CRV3_REVEAL_STATUS_EPOCHS`
+    /// epochs after their reveal.
+    pub fn get_crv3_reveal_statuses(
+        netuid: NetUid,
+        commit_epoch: u64,
+        hotkey: Option<T::AccountId>,
+    ) -> Vec<CRV3RevealStatusInfo<T::AccountId>> {
+        let statuses: Vec<(T::AccountId, Vec<(u64, CRV3RevealStatus)>)> = match hotkey {
+            Some(hotkey) => {
+                let statuses = CRV3RevealStatuses::<T>::get((netuid, commit_epoch, &hotkey));
+                vec![(hotkey, statuses)]
+            }
+            None => CRV3RevealStatuses::<T>::iter_prefix((netuid, commit_epoch)).collect(),
+        };
+
+        statuses
+            .into_iter()
+            .flat_map(|(hotkey, statuses)| {
+                statuses
+                    .into_iter()
+                    .map(move |(reveal_round, status)| CRV3RevealStatusInfo {
+                        hotkey: hotkey.clone(),
+                        reveal_round: reveal_round.into(),
+                        status,
+                    })
+            })
+            .collect()
+    }
+}
@@ -9,7 +9,7 @@ use substrate_fixed::types::I96F32;
 use subtensor_macros::freeze_struct;
 use subtensor_runtime_common::NetUid;
 
-#[freeze_struct("140374b562d8498b")]
+#[freeze_struct("13824a83f79fbef2")]
 #[derive(Decode, Encode, PartialEq, Eq, Clone, Debug, TypeInfo)]
 pub struct Metagraph<AccountId: TypeInfo + Encode + Decode> {
     // Subnet index
@@ -107,9 +107,12 @@ pub struct Metagraph<AccountId: TypeInfo + Encode + Decode> {
     // Dividend break down.
     tao_dividends_per_hotkey: Vec<(AccountId, Compact<u64>)>, // List of dividend payouts in tao via root.
     alpha_dividends_per_hotkey: Vec<(AccountId, Compact<u64>)>, // List of dividend payout in alpha via subnet.
+
+    // Multi endpoint axons.
+    axons_v2: Vec<AxonInfoV2>, // UID axons with all their endpoints.
 }
 
-#[freeze_struct("55ca82be1558e748")]
+#[freeze_struct("91152b4ae4829956")]
 #[derive(Decode, Encode, PartialEq, Eq, Clone, Debug, TypeInfo)]
 pub struct SelectiveMetagraph<AccountId: TypeInfo + Encode + Decode + Clone> {
     // Subnet index
@@ -210,6 +213,9 @@ pub struct SelectiveMetagraph<AccountId: TypeInfo + Encode + Decode + Clone> {
 
     // validators
     validators: Option<Vec<Compact<u16>>>, // List of validators
+
+    // Multi endpoint axons.
+    axons_v2: Option<Vec<AxonInfoV2>>, // UID axons with all their endpoints.
 }
 
 impl<AccountId> SelectiveMetagraph<AccountId>
@@ -367,6 +373,7 @@ where
                 self.alpha_dividends_per_hotkey = other.alpha_dividends_per_hotkey.clone()
             }
             Some(SelectiveMetagraphIndex::Validators) => self.validators = other.validators.clone(),
+            Some(SelectiveMetagraphIndex::AxonsV2) => self.axons_v2 = other.axons_v2.clone(),
             None => {}
         };
     }
@@ -451,6 +458,7 @@ where
             tao_dividends_per_hotkey: None,
             alpha_dividends_per_hotkey: None,
             validators: None,
+            axons_v2: None,
         }
     }
 }
@@ -529,6 +537,7 @@ pub enum SelectiveMetagraphIndex {
     TaoDividendsPerHotkey,
     AlphaDividendsPerHotkey,
     Validators,
+    AxonsV2,
 }
 
 impl SelectiveMetagraphIndex {
@@ -607,6 +616,7 @@ impl SelectiveMetagraphIndex {
             70 => Some(SelectiveMetagraphIndex::TaoDividendsPerHotkey),
             71 => Some(SelectiveMetagraphIndex::AlphaDividendsPerHotkey),
             72 => Some(SelectiveMetagraphIndex::Validators),
+            73 => Some(SelectiveMetagraphIndex::AxonsV2),
             _ => None,
         }
     }
@@ -623,6 +633,7 @@ impl<T: Config> Pallet<T> {
         let mut block_at_registration: Vec<Compact<u64>> = vec![];
         let mut identities: Vec<Option<ChainIdentityOfV2>> = vec![];
         let mut axons: Vec<AxonInfo> = vec![];
+        let mut axons_v2: Vec<AxonInfoV2> = vec![];
         for uid in 0..n {
             let hotkey = Keys::<T>::get(netuid, uid);
             let coldkey = Owner::<T>::get(hotkey.clone());
@@ -631,6 +642,7 @@ impl<T: Config> Pallet<T> {
             block_at_registration.push(BlockAtRegistration::<T>::get(netuid, uid).into());
             identities.push(IdentitiesV2::<T>::get(coldkey.clone()));
             axons.push(Self::get_axon_info(netuid, &hotkey));
+            axons_v2.push(Self::get_axon_info_v2(netuid, &hotkey));
         }
         let mut tao_dividends_per_hotkey: Vec<(T::AccountId, Compact<u64>)> = vec![];
         let mut alpha_dividends_per_hotkey: Vec<(T::AccountId, Compact<u64>)> = vec![];
@@ -785,6 +797,9 @@ impl<T: Config> Pallet<T> {
             // Dividend break down.
             tao_dividends_per_hotkey,
             alpha_dividends_per_hotkey,
+
+            // Multi endpoint axons.
+            axons_v2,
         })
     }
     pub fn get_all_metagraphs() -> Vec<Option<Metagraph<T::AccountId>>> {
@@ -1365,6 +1380,19 @@ impl<T: Config> Pallet<T> {
                 }
             }
             Some(SelectiveMetagraphIndex::Validators) => Self::get_validators(netuid),
+            Some(SelectiveMetagraphIndex::AxonsV2) => {
+                let n: u16 = Self::get_subnetwork_n(netuid);
+                let mut axons_v2: Vec<AxonInfoV2> = vec![];
+                for uid in 0..n {
+                    let hotkey = Keys::<T>::get(netuid, uid);
+                    axons_v2.push(Self::get_axon_info_v2(netuid, &hotkey));
+                }
+                SelectiveMetagraph {
+                    netuid: netuid.into(),
+                    axons_v2: Some(axons_v2),
+                    ..Default::default()
+                }
+            }
             None => SelectiveMetagraph {
                 // Subnet index
                 netuid: netuid.into(),
@@ -1490,6 +1518,7 @@ fn test_selective_metagraph() {
         tao_dividends_per_hotkey: None,
         alpha_dividends_per_hotkey: None,
         validators: None,
+        axons_v2: None,
     };
 
     // test init value
@@ -1,8 +1,12 @@
 use super::*;
+pub mod crv3_reveal_status;
 pub mod delegate_info;
 pub mod dynamic_info;
 pub mod metagraph;
+pub mod neuron_certificates;
 pub mod neuron_info;
+pub mod pruning_preview;
 pub mod show_subnet;
 pub mod stake_info;
 pub mod subnet_info;
+pub mod weights_validation;
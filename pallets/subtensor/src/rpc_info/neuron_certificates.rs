@@ -0,0 +1,37 @@
+use super::*;
+use frame_support::pallet_prelude::{Decode, Encode};
+extern crate alloc;
+use codec::Compact;
+use subtensor_runtime_common::NetUid;
+
+#[freeze_struct("a6d3ef58fa2fada2")]
+#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug, TypeInfo)]
+pub struct NeuronCertificatesInfo<AccountId: TypeInfo + Encode + Decode> {
+    pub uid: Compact<u16>,
+    pub hotkey: AccountId,
+    pub certificates: Vec<NeuronCertificateMetadata>, // valid certificates, oldest first
+}
+
+impl<T: Config> Pallet<T> {
+    /// Returns the valid certificates of every neuron of the subnet publishing at least one.
+    pub fn get_neuron_certificates(netuid: NetUid) -> Vec<NeuronCertificatesInfo<T::AccountId>> {
+        if !Self::if_subnet_exist(netuid) {
+            return Vec::new();
+        }
+
+        (0..Self::get_subnetwork_n(netuid))
+            .filter_map(|uid| {
+                let hotkey = Self::get_hotkey_for_net_and_uid(netuid, uid).ok()?;
+                let certificates = Self::get_valid_neuron_certificates(netuid, &hotkey);
+                if certificates.is_empty() {
+                    return None;
+                }
+                Some(NeuronCertificatesInfo {
+                    uid: uid.into(),
+                    hotkey,
+                    certificates,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,77 @@
+use super::*;
+use frame_support::pallet_prelude::{Decode, Encode};
+extern crate alloc;
+use codec::Compact;
+use subtensor_runtime_common::NetUid;
+
+#[freeze_struct("5050256de77e3d1")]
+#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug, TypeInfo)]
+pub struct PruningCandidate<AccountId: TypeInfo + Encode + Decode> {
+    pub uid: Compact<u16>,
+    pub hotkey: AccountId,
+    pub pruning_score: Compact<u16>,
+    pub policy_score: Compact<u64>, // score the subnet's pruning policy ranks neurons out of immunity by
+    pub immune: bool,
+    pub immunity_blocks_remaining: Compact<u64>,
+}
+
+#[freeze_struct("c9d9541d0bf427db")]
+#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug, TypeInfo)]
+pub struct PruningPreview<AccountId: TypeInfo + Encode + Decode> {
+    pub netuid: Compact<NetUid>,
+    pub pruning_policy: PruningPolicy,
+    pub subnet_full: bool, // a registration only prunes a neuron once the subnet is full
+    pub uid_to_prune: Option<Compact<u16>>,
+    pub candidates: Vec<PruningCandidate<AccountId>>, // next candidates, in pruning order
+}
+
+impl<T: Config> Pallet<T> {
+    /// Previews the neurons a registration on the subnet would prune, without changing any
+    /// pruning score.
+    pub fn get_pruning_preview(netuid: NetUid, count: u16) -> Option<PruningPreview<T::AccountId>> {
+        if !Self::if_subnet_exist(netuid) {
+            return None;
+        }
+
+        let policy = Self::get_pruning_policy(netuid);
+        let policy_scores = Self::get_pruning_policy_scores(netuid, policy);
+        let validator_permits = ValidatorPermit::<T>::get(netuid);
+
+        let pruning_order = Self::get_pruning_order(netuid);
+        let candidates = pruning_order
+            .iter()
+            .take(usize::from(count))
+            .filter_map(|&uid| {
+                let hotkey = Self::get_hotkey_for_net_and_uid(netuid, uid).ok()?;
+                Some(PruningCandidate {
+                    uid: uid.into(),
+                    hotkey,
+                    pruning_score: Self::get_pruning_score_for_uid(netuid, uid).into(),
+                    policy_score: policy_scores
+                        .get(usize::from(uid))
+                        .copied()
+                        .unwrap_or(u64::MAX)
+                        .into(),
+                    immune: Self::get_neuron_is_prune_immune(
+                        netuid,
+                        uid,
+                        policy,
+                        &validator_permits,
+                    ),
+                    immunity_blocks_remaining: Self::get_neuron_immunity_blocks_remaining(
+                        netuid, uid,
+                    )
+                    .into(),
+                })
+            })
+            .collect();
+
+        Some(PruningPreview {
+            netuid: netuid.into(),
+            pruning_policy: policy,
+            subnet_full: Self::get_subnetwork_n(netuid) >= Self::get_max_allowed_uids(netuid),
+            uid_to_prune: pruning_order.first().map(|&uid| uid.into()),
+            candidates,
+        })
+    }
+}
@@ -123,6 +123,51 @@ pub struct SubnetHyperparamsV2 {
     user_liquidity_enabled: bool,
 }
 
+#[freeze_struct("5e4c8fe70be0ff51")]
+#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug, TypeInfo)]
+pub struct SubnetHyperparamsV3 {
+    rho: Compact<u16>,
+    kappa: Compact<u16>,
+    immunity_period: Compact<u16>,
+    min_allowed_weights: Compact<u16>,
+    max_weights_limit: Compact<u16>,
+    tempo: Compact<u16>,
+    min_difficulty: Compact<u64>,
+    max_difficulty: Compact<u64>,
+    weights_version: Compact<u64>,
+    weights_rate_limit: Compact<u64>,
+    adjustment_interval: Compact<u16>,
+    activity_cutoff: Compact<u16>,
+    pub registration_allowed: bool,
+    target_regs_per_interval: Compact<u16>,
+    min_burn: Compact<u64>,
+    max_burn: Compact<u64>,
+    bonds_moving_avg: Compact<u64>,
+    max_regs_per_block: Compact<u16>,
+    serving_rate_limit: Compact<u64>,
+    max_validators: Compact<u16>,
+    adjustment_alpha: Compact<u64>,
+    difficulty: Compact<u64>,
+    commit_reveal_period: Compact<u64>,
+    commit_reveal_weights_enabled: bool,
+    alpha_high: Compact<u16>,
+    alpha_low: Compact<u16>,
+    liquid_alpha_enabled: bool,
+    alpha_sigmoid_steepness: I32F32,
+    yuma_version: Compact<u16>,
+    subnet_is_active: bool,
+    transfers_enabled: bool,
+    bonds_reset_enabled: bool,
+    user_liquidity_enabled: bool,
+    pruning_policy: PruningPolicy,
+    registration_auction_enabled: bool,
+    deregistration_refund: Compact<u16>,
+    deregistration_refund_decay: Compact<u64>,
+    pow_algorithm: PowAlgorithm,
+    registration_whitelist_enabled: bool,
+    immunity_policy: ImmunityPolicy,
+}
+
 impl<T: Config> Pallet<T> {
     pub fn get_subnet_info(netuid: NetUid) -> Option<SubnetInfo<T::AccountId>> {
         if !Self::if_subnet_exist(netuid) {
@@ -405,4 +450,96 @@ impl<T: Config> Pallet<T> {
             user_liquidity_enabled,
         })
     }
+
+    pub fn get_subnet_hyperparams_v3(netuid: NetUid) -> Option<SubnetHyperparamsV3> {
+        if !Self::if_subnet_exist(netuid) {
+            return None;
+        }
+
+        let rho = Self::get_rho(netuid);
+        let kappa = Self::get_kappa(netuid);
+        let immunity_period = Self::get_immunity_period(netuid);
+        let min_allowed_weights = Self::get_min_allowed_weights(netuid);
+        let max_weights_limit = Self::get_max_weight_limit(netuid);
+        let tempo = Self::get_tempo(netuid);
+        let min_difficulty = Self::get_min_difficulty(netuid);
+        let max_difficulty = Self::get_max_difficulty(netuid);
+        let weights_version = Self::get_weights_version_key(netuid);
+        let weights_rate_limit = Self::get_weights_set_rate_limit(netuid);
+        let adjustment_interval = Self::get_adjustment_interval(netuid);
+        let activity_cutoff = Self::get_activity_cutoff(netuid);
+        let registration_allowed = Self::get_network_registration_allowed(netuid);
+        let target_regs_per_interval = Self::get_target_registrations_per_interval(netuid);
+        let min_burn = Self::get_min_burn_as_u64(netuid);
+        let max_burn = Self::get_max_burn_as_u64(netuid);
+        let bonds_moving_avg = Self::get_bonds_moving_average(netuid);
+        let max_regs_per_block = Self::get_max_registrations_per_block(netuid);
+        let serving_rate_limit = Self::get_serving_rate_limit(netuid);
+        let max_validators = Self::get_max_allowed_validators(netuid);
+        let adjustment_alpha = Self::get_adjustment_alpha(netuid);
+        let difficulty = Self::get_difficulty_as_u64(netuid);
+        let commit_reveal_period = Self::get_reveal_period(netuid);
+        let commit_reveal_weights_enabled = Self::get_commit_reveal_weights_enabled(netuid);
+        let liquid_alpha_enabled = Self::get_liquid_alpha_enabled(netuid);
+        let (alpha_low, alpha_high): (u16, u16) = Self::get_alpha_values(netuid);
+        let alpha_sigmoid_steepness = Self::get_alpha_sigmoid_steepness(netuid);
+        let yuma_version: u16 = match Self::get_yuma3_enabled(netuid) {
+            true => 3u16,
+            false => 2u16,
+        };
+        let subnet_token_enabled = Self::get_subtoken_enabled(netuid);
+        let transfers_enabled = Self::get_transfer_toggle(netuid);
+        let bonds_reset = Self::get_bonds_reset(netuid);
+        let user_liquidity_enabled: bool = Self::is_user_liquidity_enabled(netuid);
+        let pruning_policy = Self::get_pruning_policy(netuid);
+        let registration_auction_enabled = Self::get_registration_auction_enabled(netuid);
+        let deregistration_refund = Self::get_deregistration_refund(netuid);
+        let deregistration_refund_decay = Self::get_deregistration_refund_decay(netuid);
+        let pow_algorithm = Self::get_pow_algorithm(netuid);
+        let registration_whitelist_enabled = Self::get_registration_whitelist_enabled(netuid);
+        let immunity_policy = Self::get_immunity_policy(netuid);
+
+        Some(SubnetHyperparamsV3 {
+            rho: rho.into(),
+            kappa: kappa.into(),
+            immunity_period: immunity_period.into(),
+            min_allowed_weights: min_allowed_weights.into(),
+            max_weights_limit: max_weights_limit.into(),
+            tempo: tempo.into(),
+            min_difficulty: min_difficulty.into(),
+            max_difficulty: max_difficulty.into(),
+            weights_version: weights_version.into(),
+            weights_rate_limit: weights_rate_limit.into(),
+            adjustment_interval: adjustment_interval.into(),
+            activity_cutoff: activity_cutoff.into(),
+            registration_allowed,
+            target_regs_per_interval: target_regs_per_interval.into(),
+            min_burn: min_burn.into(),
+            max_burn: max_burn.into(),
+            bonds_moving_avg: bonds_moving_avg.into(),
+            max_regs_per_block: max_regs_per_block.into(),
+            serving_rate_limit: serving_rate_limit.into(),
+            max_validators: max_validators.into(),
+            adjustment_alpha: adjustment_alpha.into(),
+            difficulty: difficulty.into(),
+            commit_reveal_period: commit_reveal_period.into(),
+            commit_reveal_weights_enabled,
+            alpha_high: alpha_high.into(),
+            alpha_low: alpha_low.into(),
+            liquid_alpha_enabled,
+            alpha_sigmoid_steepness,
+            yuma_version: yuma_version.into(),
+            subnet_is_active: subnet_token_enabled,
+            transfers_enabled,
+            bonds_reset_enabled: bonds_reset,
+            user_liquidity_enabled,
+            pruning_policy,
+            registration_auction_enabled,
+            deregistration_refund: deregistration_refund.into(),
+            deregistration_refund_decay: deregistration_refund_decay.into(),
+            pow_algorithm,
+            registration_whitelist_enabled,
+            immunity_policy,
+        })
+    }
 }
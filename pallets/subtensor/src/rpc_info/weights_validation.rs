@@ -0,0 +1,46 @@
+use super::*;
+use crate::epoch::math::*;
+use frame_support::pallet_prelude::{Decode, Encode};
+extern crate alloc;
+use codec::Compact;
+use subtensor_runtime_common::NetUid;
+
+#[freeze_struct("132380dba7ba40bd")]
+#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug, TypeInfo)]
+pub struct WeightsValidationInfo {
+    pub violations: Vec<DispatchError>, // every rule of set_weights broken, first one is raised
+    pub weights: Vec<(Compact<u16>, Compact<u16>)>, // (uid, weight) as set_weights would store them
+}
+
+impl<T: Config> Pallet<T> {
+    /// Dry-runs `set_weights` for the hotkey, returning every rule the weights break and the
+    /// max-upscaled weights that would be stored. The weights are empty when the uids and values
+    /// differ in length.
+    pub fn validate_weights(
+        netuid: NetUid,
+        hotkey: T::AccountId,
+        uids: Vec<u16>,
+        values: Vec<u16>,
+        version_key: u64,
+    ) -> WeightsValidationInfo {
+        let violations =
+            Self::get_weights_violations(netuid, &hotkey, &uids, &values, version_key, false)
+                .into_iter()
+                .map(Into::into)
+                .collect();
+
+        let weights = if Self::uids_match_values(&uids, &values) {
+            uids.into_iter()
+                .zip(vec_u16_max_upscale_to_u16(&values))
+                .map(|(uid, weight)| (uid.into(), weight.into()))
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        WeightsValidationInfo {
+            violations,
+            weights,
+        }
+    }
+}
@@ -7,5 +7,6 @@ pub mod increase_take;
 pub mod move_stake;
 pub mod recycle_alpha;
 pub mod remove_stake;
+pub mod sealed_stake;
 pub mod set_children;
 pub mod stake_utils;
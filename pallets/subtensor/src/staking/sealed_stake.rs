@@ -0,0 +1,219 @@
+use super::*;
+use codec::DecodeLimit;
+use frame_support::{dispatch::GetDispatchInfo, traits::UnfilteredDispatchable};
+use pallet_drand::types::RoundNumber;
+
+/// Deepest nesting a sealed call is decoded with, stake calls hold no nested calls.
+const SEALED_CALL_DECODE_DEPTH: u32 = 8;
+
+/// Weight of decrypting a sealed call with the pulse of its round, a pairing and a decryption.
+const SEALED_CALL_DECRYPT_WEIGHT: Weight = Weight::from_parts(5_000_000_000, 0);
+
+impl<T: Config> Pallet<T> {
+    /// ---- The implementation for the extrinsic submit_sealed_stake_call.
+    ///
+    /// Stores a stake call timelocked to a future drand round, to be decrypted and dispatched
+    /// once the pulse of the round is stored. Until drand publishes the pulse nobody can read
+    /// the call and trade ahead of it on the subnet pools. The round must be at least
+    /// `MIN_SEALED_CALL_ROUND_MARGIN` rounds past the round of the block's time, as the pulse
+    /// of a round is public off chain before it is stored.
+    ///
+    /// Once drand publishes the pulse, the block author can still open the call before
+    /// including the pulse, and order its own transactions ahead of it in that block.
+    ///
+    /// # Args:
+    /// * `origin`: (`<T as frame_system::Config>::RuntimeOrigin`):
+    ///   - The signature of the coldkey the call is dispatched for.
+    ///
+    /// * `sealed_call` (`BoundedVec<u8>`):
+    ///   - A SCALE encoded `add_stake`, `remove_stake`, `swap_stake` call or one of their `_limit`
+    ///     variants, encrypted with `tle` to the round of the main drand beacon and serialized
+    ///     compressed, as for `commit_crv3_weights`.
+    ///
+    /// * `reveal_round` (`u64`):
+    ///   - The drand round the call is timelocked to.
+    ///
+    /// # Raises:
+    /// * `SealedCallRoundNotInFuture`:
+    ///   - The pulse of the round is already stored, anyone could open the call.
+    ///
+    /// * `SealedCallRoundTooSoon`:
+    ///   - The round is less than `MIN_SEALED_CALL_ROUND_MARGIN` rounds past the round of the
+    ///     block's time.
+    ///
+    /// * `SealedCallRoundTooFar`:
+    ///   - The round is more than `MAX_SEALED_CALL_ROUND_DELAY` rounds past the last stored one.
+    ///
+    /// * `TooManySealedCalls`:
+    ///   - The round already holds `MAX_SEALED_CALLS_PER_ROUND` sealed calls.
+    ///
+    pub fn do_submit_sealed_stake_call(
+        origin: T::RuntimeOrigin,
+        sealed_call: BoundedVec<u8, ConstU32<MAX_SEALED_CALL_SIZE_BYTES>>,
+        reveal_round: RoundNumber,
+    ) -> DispatchResult {
+        let coldkey = ensure_signed(origin)?;
+
+        let last_stored_round = pallet_drand::LastStoredRound::<T>::get();
+        ensure!(
+            reveal_round > last_stored_round,
+            Error::<T>::SealedCallRoundNotInFuture
+        );
+        ensure!(
+            reveal_round
+                >= pallet_drand::Pallet::<T>::current_round()
+                    .saturating_add(MIN_SEALED_CALL_ROUND_MARGIN),
+            Error::<T>::SealedCallRoundTooSoon
+        );
+        ensure!(
+            reveal_round <= last_stored_round.saturating_add(MAX_SEALED_CALL_ROUND_DELAY),
+            Error::<T>::SealedCallRoundTooFar
+        );
+
+        SealedStakeCalls::<T>::try_mutate(reveal_round, |calls| {
+            calls
+                .try_push((coldkey.clone(), sealed_call))
+                .map_err(|_| Error::<T>::TooManySealedCalls)
+        })?;
+        pallet_drand::Pallet::<T>::hold_reveal_round(None, reveal_round);
+
+        log::debug!(
+            "SealedStakeCallSubmitted( coldkey:{:?}, reveal_round:{:?} )",
+            coldkey,
+            reveal_round
+        );
+        Self::deposit_event(Event::SealedStakeCallSubmitted(coldkey, reveal_round));
+
+        Ok(())
+    }
+
+    /// Dispatches the sealed stake calls of the rounds whose pulses were just stored, in round
+    /// then submission order, in the extrinsic storing the pulses so no call can be placed
+    /// between the pulses and the sealed calls. The extrinsic stores at most
+    /// `MAX_PULSES_TO_FETCH` pulses and is weighed for all their calls, so every call is
+    /// opened. Returns the weight used.
+    pub fn dispatch_sealed_stake_calls(rounds: &[RoundNumber]) -> Weight {
+        let mut weight = Weight::zero();
+
+        for round in rounds {
+            weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+
+            for (coldkey, sealed_call) in SealedStakeCalls::<T>::take(round) {
+                weight = weight.saturating_add(T::DbWeight::get().reads_writes(2, 2));
+                weight = weight.saturating_add(Self::dispatch_sealed_stake_call(
+                    coldkey,
+                    *round,
+                    &sealed_call,
+                ));
+                // Released once the call is opened, the pulse may be pruned with it
+                pallet_drand::Pallet::<T>::release_reveal_round(None, *round);
+            }
+        }
+
+        weight
+    }
+
+    /// The most weight opening and dispatching a sealed call uses, charged when it is submitted.
+    pub fn sealed_stake_call_weight() -> Weight {
+        SEALED_CALL_DECRYPT_WEIGHT
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(Self::max_sealed_stake_call_weight())
+    }
+
+    /// The weight of the heaviest stake call a sealed call may hold, `swap_stake_limit`.
+    fn max_sealed_stake_call_weight() -> Weight {
+        Weight::from_parts(426_500_000, 0)
+            .saturating_add(T::DbWeight::get().reads(32))
+            .saturating_add(T::DbWeight::get().writes(17))
+    }
+
+    /// Opens the sealed call and dispatches it for the coldkey, returning the weight used.
+    fn dispatch_sealed_stake_call(
+        coldkey: T::AccountId,
+        round: RoundNumber,
+        sealed_call: &[u8],
+    ) -> Weight {
+        let mut weight = SEALED_CALL_DECRYPT_WEIGHT.saturating_add(T::DbWeight::get().reads(2));
+
+        let result = Self::open_sealed_stake_call(round, sealed_call).and_then(|call| {
+            weight = weight.saturating_add(call.get_dispatch_info().call_weight);
+
+            // As for stake calls submitted in the clear.
+            ensure!(
+                !ColdkeySwapScheduled::<T>::contains_key(&coldkey),
+                Error::<T>::ColdkeyIsInArbitration
+            );
+
+            call.dispatch_bypass_filter(frame_system::RawOrigin::Signed(coldkey.clone()).into())
+                .map(|_| ())
+                .map_err(|e| e.error)
+        });
+
+        if let Err(e) = result {
+            log::debug!(
+                "Sealed stake call of {:?} for round {} failed: {:?}",
+                coldkey,
+                round,
+                e
+            );
+        }
+        Self::deposit_event(Event::SealedStakeCallDispatched(coldkey, round, result));
+
+        weight
+    }
+
+    /// Decrypts the sealed call with the pulse of its round and decodes it, allowing only stake
+    /// calls.
+    fn open_sealed_stake_call(
+        round: RoundNumber,
+        sealed_call: &[u8],
+    ) -> Result<Call<T>, DispatchError> {
+        let encoded_call = pallet_drand::Pallet::<T>::timelock_decrypt(None, round, sealed_call)
+            .map_err(|_| Error::<T>::SealedCallDecryptionFailed)?;
+        let call = Call::<T>::decode_all_with_depth_limit(
+            SEALED_CALL_DECODE_DEPTH,
+            &mut &encoded_call[..],
+        )
+        .map_err(|_| Error::<T>::InvalidSealedCall)?;
+
+        ensure!(
+            matches!(
+                call,
+                Call::add_stake { .. }
+                    | Call::remove_stake { .. }
+                    | Call::swap_stake { .. }
+                    | Call::add_stake_limit { .. }
+                    | Call::remove_stake_limit { .. }
+                    | Call::swap_stake_limit { .. }
+            ),
+            Error::<T>::InvalidSealedCall
+        );
+        ensure!(
+            call.get_dispatch_info()
+                .call_weight
+                .all_lte(Self::max_sealed_stake_call_weight()),
+            Error::<T>::InvalidSealedCall
+        );
+
+        Ok(call)
+    }
+}
+
+impl<T: Config> pallet_drand::OnPulsesStored for Pallet<T> {
+    fn on_pulses_stored(rounds: &[RoundNumber]) -> Weight {
+        Self::dispatch_sealed_stake_calls(rounds)
+    }
+
+    /// The sealed calls of a round are taken, and each is released and opened.
+    fn max_weight(rounds: u32) -> Weight {
+        T::DbWeight::get()
+            .reads_writes(1, 1)
+            .saturating_add(
+                T::DbWeight::get()
+                    .reads_writes(2, 2)
+                    .saturating_add(Self::sealed_stake_call_weight())
+                    .saturating_mul(u64::from(MAX_SEALED_CALLS_PER_ROUND)),
+            )
+            .saturating_mul(u64::from(rounds))
+    }
+}
@@ -0,0 +1,297 @@
+use super::*;
+use frame_system::pallet_prelude::*;
+use sp_core::{H256, blake2_256};
+use sp_std::collections::btree_set::BTreeSet;
+use subtensor_runtime_common::NetUid;
+
+impl<T: Config> Pallet<T> {
+    /// Publishes a TLS certificate for the calling hotkey on a subnet.
+    ///
+    /// A neuron keeps up to `MAX_NEURON_CERTIFICATES` certificates, so a new key can be published
+    /// while peers still trust the previous one. Publishing beyond that drops the oldest
+    /// certificate, republishing a certificate only refreshes its expiry.
+    ///
+    /// # Args:
+    /// * 'origin': (<T as frame_system::Config>RuntimeOrigin):
+    ///     - The hotkey of the neuron.
+    ///
+    /// * 'netuid' (NetUid):
+    ///     - The subnet the neuron is registered on.
+    ///
+    /// * 'certificate' (Vec<u8>):
+    ///     - The algorithm byte followed by the public key.
+    ///
+    /// * 'not_after' (Option<u64>):
+    ///     - The block the certificate expires at, None if it does not expire. At most
+    ///       `MAX_CERTIFICATE_VALIDITY` blocks ahead.
+    ///
+    /// # Raises:
+    /// * 'HotKeyNotRegisteredInSubNet':
+    ///     - The hotkey is not registered on the subnet.
+    ///
+    /// * 'InvalidCertificate':
+    ///     - The certificate is empty or its public key is too long.
+    ///
+    /// * 'InvalidCertificateExpiry':
+    ///     - The expiry block is not in the future or too far ahead.
+    pub fn do_publish_certificate(
+        origin: OriginFor<T>,
+        netuid: NetUid,
+        certificate: Vec<u8>,
+        not_after: Option<u64>,
+    ) -> DispatchResult {
+        let hotkey = ensure_signed(origin)?;
+
+        ensure!(
+            Self::is_hotkey_registered_on_network(netuid, &hotkey),
+            Error::<T>::HotKeyNotRegisteredInSubNet
+        );
+        let certificate = NeuronCertificateOf::try_from(certificate)
+            .map_err(|_| Error::<T>::InvalidCertificate)?;
+        if let Some(not_after) = not_after {
+            let current_block = Self::get_current_block_as_u64();
+            ensure!(
+                not_after > current_block
+                    && not_after <= current_block.saturating_add(MAX_CERTIFICATE_VALIDITY),
+                Error::<T>::InvalidCertificateExpiry
+            );
+        }
+
+        Self::publish_neuron_certificate(netuid, &hotkey, certificate, not_after);
+
+        Ok(())
+    }
+
+    /// Revokes a certificate of the calling hotkey before its expiry.
+    ///
+    /// # Raises:
+    /// * 'CertificateNotFound':
+    ///     - The neuron has no published certificate with this fingerprint.
+    pub fn do_revoke_certificate(
+        origin: OriginFor<T>,
+        netuid: NetUid,
+        fingerprint: H256,
+    ) -> DispatchResult {
+        let hotkey = ensure_signed(origin)?;
+
+        let previous = PublishedNeuronCertificates::<T>::get(netuid, &hotkey).into_inner();
+        let mut certificates = previous.clone();
+        certificates.retain(|published| published.fingerprint != fingerprint);
+        ensure!(
+            certificates.len() < previous.len(),
+            Error::<T>::CertificateNotFound
+        );
+
+        Self::set_neuron_certificates(netuid, &hotkey, &previous, certificates);
+
+        log::debug!(
+            "NeuronCertificateRevoked( netuid:{:?} hotkey:{:?} fingerprint:{:?} )",
+            netuid,
+            hotkey,
+            fingerprint
+        );
+        Self::deposit_event(Event::NeuronCertificateRevoked {
+            netuid,
+            hotkey,
+            fingerprint,
+        });
+
+        Ok(())
+    }
+
+    /// Adds a certificate to the published certificates of a neuron, dropping expired ones and the
+    /// oldest one when the neuron already publishes `MAX_NEURON_CERTIFICATES`.
+    pub fn publish_neuron_certificate(
+        netuid: NetUid,
+        hotkey: &T::AccountId,
+        certificate: NeuronCertificateOf,
+        not_after: Option<u64>,
+    ) {
+        let current_block = Self::get_current_block_as_u64();
+        let fingerprint = Self::get_certificate_fingerprint(&certificate);
+
+        let previous = PublishedNeuronCertificates::<T>::get(netuid, hotkey).into_inner();
+        let mut certificates = previous.clone();
+        certificates.retain(|published| {
+            published.fingerprint != fingerprint
+                && Self::is_certificate_valid(published, current_block)
+        });
+        certificates.push(NeuronCertificateMetadata {
+            certificate,
+            fingerprint,
+            published_at: current_block,
+            not_after,
+        });
+        let dropped = certificates
+            .len()
+            .saturating_sub(MAX_NEURON_CERTIFICATES as usize);
+        certificates.drain(..dropped);
+
+        // A republished certificate moves its expiry, dropped certificates lose theirs.
+        Self::set_neuron_certificates(netuid, hotkey, &previous, certificates);
+
+        Self::deposit_event(Event::NeuronCertificatePublished {
+            netuid,
+            hotkey: hotkey.clone(),
+            fingerprint,
+            not_after,
+        });
+    }
+
+    /// Removes the certificates expired by this block.
+    ///
+    /// At most `MAX_CERTIFICATE_EXPIRIES_PER_BLOCK` scheduled expiries are removed per block,
+    /// each block scanned counting as one. Expiries left over are removed in the next blocks,
+    /// starting from the block stored in `NeuronCertificateExpiryCursor`. Expired certificates
+    /// are no longer served in the meantime.
+    ///
+    /// Returns the weight of the removal.
+    pub fn remove_expired_neuron_certificates(block: u64) -> Weight {
+        let mut weight = T::DbWeight::get().reads_writes(1, 1);
+        let mut cursor = NeuronCertificateExpiryCursor::<T>::get().unwrap_or(block);
+        let mut budget = MAX_CERTIFICATE_EXPIRIES_PER_BLOCK as usize;
+
+        while cursor <= block && budget > 0 {
+            let due: Vec<(NetUid, T::AccountId)> =
+                NeuronCertificateExpiries::<T>::drain_prefix(cursor)
+                    .take(budget)
+                    .map(|(neuron, ())| neuron)
+                    .collect();
+            weight.saturating_accrue(T::DbWeight::get().reads(1));
+            if due.len() < budget {
+                cursor = cursor.saturating_add(1);
+            }
+            budget = budget.saturating_sub(due.len().max(1));
+
+            for (netuid, hotkey) in due {
+                weight.saturating_accrue(Self::remove_expired_certificates_of(
+                    netuid, &hotkey, block,
+                ));
+            }
+        }
+        NeuronCertificateExpiryCursor::<T>::put(cursor);
+
+        weight
+    }
+
+    /// Removes the expired certificates of a neuron whose expiry was due.
+    fn remove_expired_certificates_of(netuid: NetUid, hotkey: &T::AccountId, block: u64) -> Weight {
+        let weight = T::DbWeight::get().reads_writes(2, 4);
+        let previous = PublishedNeuronCertificates::<T>::get(netuid, hotkey).into_inner();
+        let (valid, expired): (Vec<_>, Vec<_>) = previous
+            .iter()
+            .cloned()
+            .partition(|published| Self::is_certificate_valid(published, block));
+        if expired.is_empty() {
+            return weight;
+        }
+
+        Self::set_neuron_certificates(netuid, hotkey, &previous, valid);
+        for published in expired {
+            Self::deposit_event(Event::NeuronCertificateExpired {
+                netuid,
+                hotkey: hotkey.clone(),
+                fingerprint: published.fingerprint,
+            });
+        }
+
+        weight
+    }
+
+    /// Removes every certificate of a neuron leaving the subnet, with their expiries.
+    pub fn clear_neuron_certificates(netuid: NetUid, hotkey: &T::AccountId) {
+        let previous = PublishedNeuronCertificates::<T>::take(netuid, hotkey);
+        Self::reschedule_certificate_expiries(netuid, hotkey, &previous, &[]);
+        NeuronCertificates::<T>::remove(netuid, hotkey);
+    }
+
+    /// Moves the certificates of a neuron to a new hotkey.
+    pub fn swap_neuron_certificates(
+        netuid: NetUid,
+        old_hotkey: &T::AccountId,
+        new_hotkey: &T::AccountId,
+    ) {
+        let certificates = PublishedNeuronCertificates::<T>::take(netuid, old_hotkey);
+        if certificates.is_empty() {
+            return;
+        }
+        // The expiries move with the certificates
+        Self::reschedule_certificate_expiries(netuid, old_hotkey, &certificates, &[]);
+        let previous = PublishedNeuronCertificates::<T>::get(netuid, new_hotkey);
+        Self::set_neuron_certificates(netuid, new_hotkey, &previous, certificates.into_inner());
+    }
+
+    /// Returns the certificates of a neuron valid at the current block, oldest first.
+    pub fn get_valid_neuron_certificates(
+        netuid: NetUid,
+        hotkey: &T::AccountId,
+    ) -> Vec<NeuronCertificateMetadata> {
+        let current_block = Self::get_current_block_as_u64();
+        PublishedNeuronCertificates::<T>::get(netuid, hotkey)
+            .into_iter()
+            .filter(|published| Self::is_certificate_valid(published, current_block))
+            .collect()
+    }
+
+    /// Hashes the algorithm byte followed by the public key, the bytes a certificate is
+    /// published as.
+    pub fn get_certificate_fingerprint(certificate: &NeuronCertificateOf) -> H256 {
+        let mut bytes = Vec::with_capacity(certificate.public_key.len().saturating_add(1));
+        bytes.push(certificate.algorithm);
+        bytes.extend_from_slice(&certificate.public_key);
+        H256(blake2_256(&bytes))
+    }
+
+    pub fn is_certificate_valid(published: &NeuronCertificateMetadata, block: u64) -> bool {
+        published
+            .not_after
+            .is_none_or(|not_after| block < not_after)
+    }
+
+    /// Stores the published certificates of a neuron in place of its `previous` ones and mirrors
+    /// the newest one to `NeuronCertificates` for clients reading a single certificate.
+    fn set_neuron_certificates(
+        netuid: NetUid,
+        hotkey: &T::AccountId,
+        previous: &[NeuronCertificateMetadata],
+        certificates: Vec<NeuronCertificateMetadata>,
+    ) {
+        Self::reschedule_certificate_expiries(netuid, hotkey, previous, &certificates);
+        match certificates.last() {
+            Some(newest) => NeuronCertificates::<T>::insert(netuid, hotkey, &newest.certificate),
+            None => {
+                PublishedNeuronCertificates::<T>::remove(netuid, hotkey);
+                NeuronCertificates::<T>::remove(netuid, hotkey);
+                return;
+            }
+        }
+        PublishedNeuronCertificates::<T>::insert(
+            netuid,
+            hotkey,
+            NeuronCertificatesOf::truncate_from(certificates),
+        );
+    }
+
+    /// Unschedules the expiries of the `previous` certificates of a neuron no longer needed and
+    /// schedules the new ones of `certificates`.
+    fn reschedule_certificate_expiries(
+        netuid: NetUid,
+        hotkey: &T::AccountId,
+        previous: &[NeuronCertificateMetadata],
+        certificates: &[NeuronCertificateMetadata],
+    ) {
+        let expiries = |certificates: &[NeuronCertificateMetadata]| {
+            certificates
+                .iter()
+                .filter_map(|published| published.not_after)
+                .collect::<BTreeSet<u64>>()
+        };
+        let (previous, current) = (expiries(previous), expiries(certificates));
+        for not_after in previous.difference(&current) {
+            NeuronCertificateExpiries::<T>::remove(not_after, (netuid, hotkey.clone()));
+        }
+        for not_after in current.difference(&previous) {
+            NeuronCertificateExpiries::<T>::insert(not_after, (netuid, hotkey.clone()), ());
+        }
+    }
+}
@@ -1,6 +1,10 @@
 use super::*;
+pub mod certificates;
 pub mod leasing;
 pub mod registration;
+pub mod registration_auction;
+pub mod registration_whitelist;
+pub mod resize;
 pub mod serving;
 pub mod subnet;
 pub mod symbols;
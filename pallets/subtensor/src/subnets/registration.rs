@@ -1,6 +1,7 @@
 use super::*;
+use safe_math::*;
 use sp_core::{H256, U256};
-use sp_io::hashing::{keccak_256, sha2_256};
+use sp_io::hashing::{blake2_256, keccak_256, sha2_256};
 use sp_runtime::Saturating;
 use subtensor_runtime_common::NetUid;
 use subtensor_swap_interface::SwapHandler;
@@ -15,7 +16,13 @@ impl<T: Config> Pallet<T> {
         let block_number: u64 = Self::get_current_block_as_u64();
         let current_subnetwork_n: u16 = Self::get_subnetwork_n(netuid);
 
-        if current_subnetwork_n < Self::get_max_allowed_uids(netuid) {
+        if let Some(vacant_uid) = VacantUids::<T>::get(netuid).first().copied() {
+            // A uid freed by deregistration is filled first.
+            neuron_uid = vacant_uid;
+
+            Self::replace_neuron(netuid, neuron_uid, hotkey, block_number);
+            log::debug!("fill vacant neuron");
+        } else if current_subnetwork_n < Self::get_max_allowed_uids(netuid) {
             // No replacement required, the uid appends the subnetwork.
             // We increment the subnetwork count here but not below.
             neuron_uid = current_subnetwork_n;
@@ -50,6 +57,9 @@ impl<T: Config> Pallet<T> {
     /// * 'hotkey' ( T::AccountId ):
     ///     - Hotkey to be registered to the network.
     ///
+    /// * 'voucher' ( Option<RegistrationVoucher> ):
+    ///     - Approval of the subnet owner, for hotkeys missing from the registration whitelist.
+    ///
     /// # Event:
     /// * NeuronRegistered;
     ///     - On successfully registereing a uid to a neuron slot on a subnetwork.
@@ -64,10 +74,17 @@ impl<T: Config> Pallet<T> {
     /// * 'HotKeyAlreadyRegisteredInSubNet':
     ///     - The hotkey is already registered on this network.
     ///
+    /// * 'SubnetResizeInProgress':
+    ///     - The subnet is being resized.
+    ///
+    /// * 'HotKeyNotWhitelisted':
+    ///     - The subnet runs a registration whitelist without the hotkey and no voucher is given.
+    ///
     pub fn do_burned_registration(
         origin: T::RuntimeOrigin,
         netuid: NetUid,
         hotkey: T::AccountId,
+        voucher: Option<RegistrationVoucher>,
     ) -> DispatchResult {
         // --- 1. Check that the caller has signed the transaction. (the coldkey of the pairing)
         let coldkey = ensure_signed(origin)?;
@@ -94,6 +111,21 @@ impl<T: Config> Pallet<T> {
             Error::<T>::SubNetRegistrationDisabled
         );
 
+        // --- 3. Subnets running a registration auction only register its winners.
+        ensure!(
+            !Self::get_registration_auction_enabled(netuid),
+            Error::<T>::RegistrationAuctionActive
+        );
+
+        // --- 3. Subnets being resized take no registrations until their uids are settled.
+        ensure!(
+            !Self::is_subnet_resizing(netuid),
+            Error::<T>::SubnetResizeInProgress
+        );
+
+        // --- 3. Whitelisted subnets only register approved hotkeys.
+        Self::ensure_registration_whitelisted(netuid, &hotkey, &coldkey, voucher.as_ref())?;
+
         // --- 4. Ensure we are not exceeding the max allowed registrations per block.
         ensure!(
             Self::get_registrations_this_block(netuid)
@@ -148,6 +180,7 @@ impl<T: Config> Pallet<T> {
 
         // Actually perform the registration.
         let neuron_uid: u16 = Self::register_neuron(netuid, &hotkey);
+        RegistrationBurnedAlpha::<T>::insert(netuid, neuron_uid, burned_alpha);
 
         // --- 14. Record the registration and increment block and interval counters.
         BurnRegistrationsThisInterval::<T>::mutate(netuid, |val| val.saturating_inc());
@@ -168,6 +201,111 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
+    /// ---- The implementation for the extrinsic deregister: a hotkey leaving a subnet.
+    ///
+    /// The refund is paid by the subnet pool: the refunded share of the alpha burned at
+    /// registration is reissued into `SubnetAlphaOut` and sold back to the pool, so the TAO comes
+    /// out of the pool's TAO reserve. This is the TAO the burned registration bought the alpha
+    /// with, less the price moves since, and nothing is taken from the recycled TAO.
+    ///
+    /// # Args:
+    /// * 'origin': (<T as frame_system::Config>RuntimeOrigin):
+    ///     - The signature of the leaving hotkey.
+    ///
+    /// * 'netuid' (NetUid):
+    ///     - The network to leave.
+    ///
+    /// # Event:
+    /// * NeuronDeregistered;
+    ///     - On freeing the uid of the hotkey.
+    ///
+    /// # Raises:
+    /// * 'DeregistrationNotPermitted':
+    ///     - Attempting to leave the root network or to deregister the subnet owner hotkey.
+    ///
+    /// * 'HotKeyNotRegisteredInSubNet':
+    ///     - The hotkey is not registered on this network.
+    ///
+    /// * 'SubnetResizeInProgress':
+    ///     - The subnet is being resized.
+    ///
+    pub fn do_deregister(origin: T::RuntimeOrigin, netuid: NetUid) -> DispatchResult {
+        // --- 1. Check that the caller has signed the transaction. (the hotkey leaving)
+        let hotkey = ensure_signed(origin)?;
+
+        // --- 2. Ensure the hotkey holds a slot it may give up.
+        ensure!(!netuid.is_root(), Error::<T>::DeregistrationNotPermitted);
+        ensure!(
+            Self::if_subnet_exist(netuid),
+            Error::<T>::SubNetworkDoesNotExist
+        );
+        let neuron_uid = Self::get_uid_for_net_and_hotkey(netuid, &hotkey)?;
+        ensure!(
+            SubnetOwnerHotkey::<T>::try_get(netuid).ok().as_ref() != Some(&hotkey),
+            Error::<T>::DeregistrationNotPermitted
+        );
+        ensure!(
+            !Self::is_subnet_resizing(netuid),
+            Error::<T>::SubnetResizeInProgress
+        );
+
+        // --- 3. Free the uid.
+        let refund_alpha = Self::get_deregistration_refund_alpha(netuid, neuron_uid);
+        Self::vacate_neuron(netuid, neuron_uid);
+
+        // --- 4. Reissue the refunded share of the burned alpha and sell it back to the pool, which
+        // pays the refund out of its TAO reserve.
+        let mut refund: u64 = 0;
+        if refund_alpha > 0 {
+            SubnetAlphaOut::<T>::mutate(netuid, |total| {
+                *total = total.saturating_add(refund_alpha)
+            });
+            refund = Self::swap_alpha_for_tao(
+                netuid,
+                refund_alpha,
+                T::SwapInterface::min_price(),
+                false,
+            )?
+            .amount_paid_out;
+            let coldkey = Self::get_owning_coldkey_for_hotkey(&hotkey);
+            Self::add_balance_to_coldkey_account(&coldkey, refund);
+        }
+
+        // --- 5. Deposit successful event.
+        log::debug!(
+            "NeuronDeregistered( netuid:{:?} uid:{:?} hotkey:{:?} refund:{:?} ) ",
+            netuid,
+            neuron_uid,
+            hotkey,
+            refund
+        );
+        Self::deposit_event(Event::NeuronDeregistered {
+            netuid,
+            uid: neuron_uid,
+            hotkey,
+            refund,
+        });
+
+        Ok(())
+    }
+
+    /// Alpha refunded to the neuron if it deregistered now: `DeregistrationRefund` of the alpha
+    /// burned by its registration, decaying linearly to zero over `DeregistrationRefundDecay`
+    /// blocks.
+    pub fn get_deregistration_refund_alpha(netuid: NetUid, uid: u16) -> u64 {
+        let burned_alpha = RegistrationBurnedAlpha::<T>::get(netuid, uid);
+        let decay = DeregistrationRefundDecay::<T>::get(netuid);
+        let age = Self::get_current_block_as_u64()
+            .saturating_sub(Self::get_neuron_block_at_registration(netuid, uid));
+        let remaining = decay.saturating_sub(age);
+
+        u128::from(burned_alpha)
+            .saturating_mul(u128::from(DeregistrationRefund::<T>::get(netuid)))
+            .saturating_mul(u128::from(remaining))
+            .safe_div(u128::from(u16::MAX).saturating_mul(u128::from(decay)))
+            .min(u128::from(burned_alpha)) as u64
+    }
+
     /// ---- The implementation for the extrinsic do_registration.
     ///
     /// # Args:
@@ -215,6 +353,9 @@ impl<T: Config> Pallet<T> {
     /// *'InvalidSeal':
     ///     - The seal is incorrect.
     ///
+    /// *'SubnetResizeInProgress':
+    ///     - The subnet is being resized.
+    ///
     pub fn do_registration(
         origin: T::RuntimeOrigin,
         netuid: NetUid,
@@ -255,6 +396,15 @@ impl<T: Config> Pallet<T> {
             Error::<T>::SubNetRegistrationDisabled
         );
 
+        // --- 3. Subnets being resized take no registrations until their uids are settled.
+        ensure!(
+            !Self::is_subnet_resizing(netuid),
+            Error::<T>::SubnetResizeInProgress
+        );
+
+        // --- 3. Whitelisted subnets only register approved hotkeys.
+        Self::ensure_registration_whitelisted(netuid, &hotkey, &coldkey, None)?;
+
         // --- 4. Ensure we are not exceeding the max allowed registrations per block.
         ensure!(
             Self::get_registrations_this_block(netuid)
@@ -289,15 +439,30 @@ impl<T: Config> Pallet<T> {
 
         // --- 8. Ensure the supplied work passes the difficulty.
         let difficulty: U256 = Self::get_difficulty(netuid);
-        let work_hash: H256 = Self::vec_to_hash(work.clone());
-        ensure!(
-            Self::hash_meets_difficulty(&work_hash, difficulty),
-            Error::<T>::InvalidDifficulty
-        ); // Check that the work meets difficulty.
-
-        // --- 7. Check Work is the product of the nonce, the block number, and hotkey. Add this as used work.
-        let seal: H256 = Self::create_seal_hash(block_number, nonce, &hotkey);
-        ensure!(seal == work_hash, Error::<T>::InvalidSeal);
+        match Self::get_pow_algorithm(netuid) {
+            PowAlgorithm::Seal => {
+                let work_hash: H256 = Self::vec_to_hash(work.clone());
+                ensure!(
+                    Self::hash_meets_difficulty(&work_hash, difficulty),
+                    Error::<T>::InvalidDifficulty
+                ); // Check that the work meets difficulty.
+
+                // --- 7. Check Work is the product of the nonce, the block number, and hotkey.
+                let seal: H256 = Self::create_seal_hash(block_number, nonce, &hotkey);
+                ensure!(seal == work_hash, Error::<T>::InvalidSeal);
+            }
+            PowAlgorithm::CuckooCycle { edge_bits } => {
+                Self::verify_cuckoo_work(
+                    block_number,
+                    nonce,
+                    &hotkey,
+                    edge_bits,
+                    &work,
+                    difficulty,
+                )?;
+            }
+        }
+        // Add this as used work.
         UsedWork::<T>::insert(work.clone(), current_block_number);
 
         // DEPRECATED --- 8. Ensure that the key passes the registration requirement
@@ -411,74 +576,180 @@ impl<T: Config> Pallet<T> {
     /// the lowest pruning score, the immune neuron registered earliest is pruned.
     /// Ties for earliest registration are broken by the neuron with the lowest uid.
     pub fn get_neuron_to_prune(netuid: NetUid) -> u16 {
-        let mut min_score: u16 = u16::MAX;
-        let mut min_score_in_immunity: u16 = u16::MAX;
-        let mut earliest_registration: u64 = u64::MAX;
-        let mut earliest_registration_in_immunity: u64 = u64::MAX;
-        let mut uid_to_prune: u16 = 0;
-        let mut uid_to_prune_in_immunity: u16 = 0;
-
-        // This boolean is used instead of checking if min_score == u16::MAX, to avoid the case
-        // where all non-immune neurons have pruning score u16::MAX
-        // This may be unlikely in practice.
-        let mut found_non_immune = false;
-
-        let neurons_n = Self::get_subnetwork_n(netuid);
-        if neurons_n == 0 {
+        if Self::get_subnetwork_n(netuid) == 0 {
             return 0; // If there are no neurons in this network.
         }
 
-        for neuron_uid in 0..neurons_n {
-            // Do not deregister the owner's hotkey from the `SubnetOwnerHotkey` map
-            if let Ok(hotkey) = Self::get_hotkey_for_net_and_uid(netuid, neuron_uid) {
-                if let Ok(top_sn_owner_hotkey) = SubnetOwnerHotkey::<T>::try_get(netuid) {
-                    if top_sn_owner_hotkey == hotkey {
-                        continue;
-                    }
-                }
+        let uid_to_prune = Self::get_pruning_order(netuid)
+            .first()
+            .copied()
+            .unwrap_or_default();
+        Self::set_pruning_score_for_uid(netuid, uid_to_prune, u16::MAX);
+        uid_to_prune
+    }
+
+    /// Uids of the subnet in the order `get_neuron_to_prune` would pick them, without the subnet
+    /// owner's hotkey.
+    ///
+    /// Neurons out of immunity come first, ranked by the subnet's pruning policy, followed by the
+    /// immune neurons ranked by pruning score. Ties go to the earliest registration, then to the
+    /// lowest uid.
+    pub fn get_pruning_order(netuid: NetUid) -> Vec<u16> {
+        let policy = Self::get_pruning_policy(netuid);
+        let policy_scores = Self::get_pruning_policy_scores(netuid, policy);
+        let validator_permits = ValidatorPermit::<T>::get(netuid);
+        let owner_hotkey = SubnetOwnerHotkey::<T>::try_get(netuid).ok();
+
+        let mut non_immune: Vec<(u64, u64, u16)> = Vec::new();
+        let mut immune: Vec<(u64, u64, u16)> = Vec::new();
+        for neuron_uid in 0..Self::get_subnetwork_n(netuid) {
+            // Do not deregister the owner's hotkey from the `SubnetOwnerHotkey` map, vacated uids
+            // have no neuron to deregister
+            match Self::get_hotkey_for_net_and_uid(netuid, neuron_uid) {
+                Ok(hotkey) if owner_hotkey.as_ref() != Some(&hotkey) => {}
+                _ => continue,
             }
 
-            let pruning_score: u16 = Self::get_pruning_score_for_uid(netuid, neuron_uid);
             let block_at_registration: u64 =
                 Self::get_neuron_block_at_registration(netuid, neuron_uid);
-            let is_immune = Self::get_neuron_is_immune(netuid, neuron_uid);
-
-            if is_immune {
-                // if the immune neuron has a lower pruning score than the minimum for immune neurons,
-                // or, if the pruning scores are equal and the immune neuron was registered earlier than the current minimum for immune neurons,
-                // then update the minimum pruning score and the uid to prune for immune neurons
-                if pruning_score < min_score_in_immunity
-                    || (pruning_score == min_score_in_immunity
-                        && block_at_registration < earliest_registration_in_immunity)
-                {
-                    min_score_in_immunity = pruning_score;
-                    earliest_registration_in_immunity = block_at_registration;
-                    uid_to_prune_in_immunity = neuron_uid;
-                }
+            if Self::get_neuron_is_prune_immune(netuid, neuron_uid, policy, &validator_permits) {
+                // Immune neurons are always ranked by their pruning score
+                let pruning_score: u16 = Self::get_pruning_score_for_uid(netuid, neuron_uid);
+                immune.push((u64::from(pruning_score), block_at_registration, neuron_uid));
             } else {
-                found_non_immune = true;
-                // if the non-immune neuron has a lower pruning score than the minimum for non-immune neurons,
-                // or, if the pruning scores are equal and the non-immune neuron was registered earlier than the current minimum for non-immune neurons,
-                // then update the minimum pruning score and the uid to prune for non-immune neurons
-                if pruning_score < min_score
-                    || (pruning_score == min_score && block_at_registration < earliest_registration)
-                {
-                    min_score = pruning_score;
-                    earliest_registration = block_at_registration;
-                    uid_to_prune = neuron_uid;
-                }
+                let pruning_score: u64 = policy_scores
+                    .get(neuron_uid as usize)
+                    .copied()
+                    .unwrap_or(u64::MAX);
+                non_immune.push((pruning_score, block_at_registration, neuron_uid));
             }
         }
 
-        if found_non_immune {
-            Self::set_pruning_score_for_uid(netuid, uid_to_prune, u16::MAX);
-            uid_to_prune
-        } else {
-            Self::set_pruning_score_for_uid(netuid, uid_to_prune_in_immunity, u16::MAX);
-            uid_to_prune_in_immunity
+        non_immune.sort_unstable();
+        immune.sort_unstable();
+        non_immune
+            .into_iter()
+            .chain(immune)
+            .map(|(_, _, neuron_uid)| neuron_uid)
+            .collect()
+    }
+
+    /// Whether the neuron is only pruned once every neuron out of immunity is gone, which covers
+    /// the immunity period and validator permits under `PruningPolicy::ProtectValidators`.
+    pub fn get_neuron_is_prune_immune(
+        netuid: NetUid,
+        uid: u16,
+        policy: PruningPolicy,
+        validator_permits: &[bool],
+    ) -> bool {
+        let is_protected = policy == PruningPolicy::ProtectValidators
+            && validator_permits
+                .get(uid as usize)
+                .copied()
+                .unwrap_or(false);
+        Self::get_neuron_is_immune(netuid, uid) || is_protected
+    }
+
+    /// Scores of the subnet's neurons under the given pruning policy, the neuron with the lowest
+    /// score is pruned first.
+    pub fn get_pruning_policy_scores(netuid: NetUid, policy: PruningPolicy) -> Vec<u64> {
+        let neurons_n = usize::from(Self::get_subnetwork_n(netuid));
+        match policy {
+            PruningPolicy::PruningScore | PruningPolicy::ProtectValidators => {
+                let pruning_scores = PruningScores::<T>::get(netuid);
+                (0..neurons_n)
+                    .map(|uid| u64::from(pruning_scores.get(uid).copied().unwrap_or(u16::MAX)))
+                    .collect()
+            }
+            PruningPolicy::EmissionHistory { .. } => {
+                let total_emission = PruningEmissionTotal::<T>::get(netuid);
+                (0..neurons_n)
+                    .map(|uid| total_emission.get(uid).copied().unwrap_or_default())
+                    .collect()
+            }
+            PruningPolicy::IncentiveEma => {
+                let incentive_ema = IncentiveEma::<T>::get(netuid);
+                (0..neurons_n)
+                    .map(|uid| u64::from(incentive_ema.get(uid).copied().unwrap_or_default()))
+                    .collect()
+            }
         }
     }
 
+    /// Records the epoch results the subnet's pruning policy ranks neurons by.
+    pub fn update_pruning_history(netuid: NetUid) {
+        match Self::get_pruning_policy(netuid) {
+            PruningPolicy::EmissionHistory { epochs } => {
+                let emission = Emission::<T>::get(netuid);
+                let recorded = PruningEmissionEpochs::<T>::get(netuid);
+                let slot = recorded.checked_rem(u64::from(epochs)).unwrap_or_default() as u8;
+
+                // The epoch recorded in the slot leaves the window, except for neurons registered
+                // after it whose total never included it
+                let expired = PruningEmissionHistory::<T>::get(netuid, slot);
+                let expired_epoch = recorded.saturating_sub(u64::from(epochs));
+                let start = PruningEmissionStart::<T>::get(netuid);
+                PruningEmissionTotal::<T>::mutate(netuid, |total_emission| {
+                    total_emission.resize(total_emission.len().max(emission.len()), 0);
+                    for (uid, total) in total_emission.iter_mut().enumerate() {
+                        if start.get(uid).copied().unwrap_or_default() <= expired_epoch {
+                            *total =
+                                total.saturating_sub(expired.get(uid).copied().unwrap_or_default());
+                        }
+                        *total =
+                            total.saturating_add(emission.get(uid).copied().unwrap_or_default());
+                    }
+                });
+
+                PruningEmissionHistory::<T>::insert(netuid, slot, emission);
+                PruningEmissionEpochs::<T>::insert(netuid, recorded.saturating_add(1));
+            }
+            PruningPolicy::IncentiveEma => {
+                let incentive = Incentive::<T>::get(netuid);
+                IncentiveEma::<T>::mutate(netuid, |incentive_ema| {
+                    incentive_ema.resize(incentive.len(), 0);
+                    // Every epoch contributes a tenth of the average
+                    for (ema, value) in incentive_ema.iter_mut().zip(incentive) {
+                        *ema = u32::from(*ema)
+                            .saturating_mul(9)
+                            .saturating_add(u32::from(value))
+                            .safe_div(10) as u16;
+                    }
+                });
+            }
+            PruningPolicy::PruningScore | PruningPolicy::ProtectValidators => {}
+        }
+    }
+
+    /// Drops the emission history of the subnet, so it starts over.
+    pub fn clear_pruning_emission_history(netuid: NetUid) {
+        let _ = PruningEmissionHistory::<T>::clear_prefix(
+            netuid,
+            u32::from(MAX_PRUNING_EMISSION_EPOCHS),
+            None,
+        );
+        PruningEmissionTotal::<T>::remove(netuid);
+        PruningEmissionEpochs::<T>::remove(netuid);
+        PruningEmissionStart::<T>::remove(netuid);
+    }
+
+    /// Counts the epochs each neuron earned incentive in, which end or extend its immunity under
+    /// the subnet's immunity policy.
+    pub fn update_immunity_history(netuid: NetUid) {
+        if Self::get_immunity_policy(netuid) == ImmunityPolicy::Blocks {
+            return;
+        }
+        let incentive = Incentive::<T>::get(netuid);
+        IncentiveEpochs::<T>::mutate(netuid, |incentive_epochs| {
+            incentive_epochs.resize(incentive.len(), 0);
+            for (epochs, value) in incentive_epochs.iter_mut().zip(incentive) {
+                if value > 0 {
+                    *epochs = epochs.saturating_add(1);
+                }
+            }
+        });
+    }
+
     /// Determine whether the given hash satisfies the given difficulty.
     /// The test is done by multiplying the two together. If the product
     /// overflows the bounds of U256, then the product (and thus the hash)
@@ -586,6 +857,79 @@ impl<T: Config> Pallet<T> {
         seal_hash
     }
 
+    /// Seeds the cuckoo graph of a registration with the block hash, the hotkey and the nonce.
+    pub fn create_cuckoo_seed(block_number: u64, nonce: u64, hotkey: &T::AccountId) -> [u8; 32] {
+        let block_hash: H256 = Self::get_block_hash_from_u64(block_number);
+        let binding = hotkey.encode();
+        let mut full_bytes: Vec<u8> = block_hash.as_bytes().to_vec();
+        full_bytes.extend_from_slice(binding.get(..32).unwrap_or_default());
+        full_bytes.extend_from_slice(&nonce.to_le_bytes());
+        blake2_256(&full_bytes)
+    }
+
+    /// Checks the work is a cuckoo cycle of the graph seeded by the block number, the nonce and
+    /// the hotkey, encoded as little endian `u32` edge indices, whose blake2 hash meets the
+    /// difficulty.
+    ///
+    /// The hash check comes first, it is a single hash while the cycle takes one per node.
+    pub fn verify_cuckoo_work(
+        block_number: u64,
+        nonce: u64,
+        hotkey: &T::AccountId,
+        edge_bits: u8,
+        work: &[u8],
+        difficulty: U256,
+    ) -> DispatchResult {
+        ensure!(
+            work.len() == cuckoo_pow::PROOF_SIZE.saturating_mul(4),
+            Error::<T>::InvalidSeal
+        );
+        let work_hash: H256 = H256::from(blake2_256(work));
+        ensure!(
+            Self::hash_meets_difficulty(&work_hash, difficulty),
+            Error::<T>::InvalidDifficulty
+        );
+
+        let proof: Vec<u32> = work
+            .chunks_exact(4)
+            .map(|chunk| u32::from_le_bytes(chunk.try_into().unwrap_or_default()))
+            .collect();
+        let seed = Self::create_cuckoo_seed(block_number, nonce, hotkey);
+        let keys = cuckoo_pow::keys_from_seed(&seed);
+        cuckoo_pow::verify(&keys, edge_bits, &proof).map_err(|error| {
+            log::trace!(
+                target: LOG_TARGET,
+                "Cuckoo cycle rejected: hotkey: {:?}, nonce: {:?}, error: {:?}",
+                hotkey,
+                nonce,
+                error
+            );
+            Error::<T>::InvalidSeal.into()
+        })
+    }
+
+    /// Helper function for creating nonce and cuckoo cycle work.
+    pub fn create_cuckoo_work_for_block_number(
+        netuid: NetUid,
+        block_number: u64,
+        start_nonce: u64,
+        hotkey: &T::AccountId,
+        edge_bits: u8,
+    ) -> (u64, Vec<u8>) {
+        let difficulty: U256 = Self::get_difficulty(netuid);
+        let mut nonce: u64 = start_nonce;
+        loop {
+            let seed = Self::create_cuckoo_seed(block_number, nonce, hotkey);
+            if let Some(proof) = cuckoo_pow::solve(&cuckoo_pow::keys_from_seed(&seed), edge_bits) {
+                let work: Vec<u8> = proof.iter().flat_map(|edge| edge.to_le_bytes()).collect();
+                if Self::hash_meets_difficulty(&H256::from(blake2_256(&work)), difficulty) {
+                    return (nonce, work);
+                }
+            }
+            nonce.saturating_inc();
+        }
+    }
+
     /// Helper function for creating nonce and work.
     pub fn create_work_for_block_number(
         netuid: NetUid,
@@ -0,0 +1,300 @@
+use super::*;
+use frame_system::pallet_prelude::*;
+use subtensor_runtime_common::NetUid;
+use subtensor_swap_interface::SwapHandler;
+
+impl<T: Config> Pallet<T> {
+    /// Places or raises a bid for a registration slot of a subnet running a registration
+    /// auction.
+    ///
+    /// The bid is withdrawn from the coldkey right away and held until the auction settles at the
+    /// end of the adjustment interval. At most `TargetRegistrationsPerInterval` of the highest
+    /// bids register, and no more than `MaxRegistrationsPerBlock`, all of them paying the clearing
+    /// price: the highest losing bid or the burn, whichever is larger. The rest of every bid is
+    /// refunded.
+    ///
+    /// # Args:
+    /// * 'origin': (<T as frame_system::Config>RuntimeOrigin):
+    ///     - The coldkey paying the bid.
+    ///
+    /// * 'netuid' (NetUid):
+    ///     - The subnet to register on.
+    ///
+    /// * 'hotkey' (T::AccountId):
+    ///     - The hotkey to register.
+    ///
+    /// * 'bid' (u64):
+    ///     - The total bid of the hotkey, at least the current burn.
+    ///
+    /// # Raises:
+    /// * 'RegistrationAuctionDisabled':
+    ///     - The subnet does not run a registration auction.
+    ///
+    /// * 'RegistrationBidTooLow':
+    ///     - The bid is below the burn or does not raise the previous bid of the hotkey.
+    ///
+    /// * 'TooManyRegistrationBids':
+    ///     - The auction holds `MAX_REGISTRATION_BIDS` bids already.
+    ///
+    /// * 'HotKeyNotWhitelisted':
+    ///     - The subnet runs a registration whitelist without the hotkey.
+    pub fn do_bid_registration(
+        origin: OriginFor<T>,
+        netuid: NetUid,
+        hotkey: T::AccountId,
+        bid: u64,
+    ) -> DispatchResult {
+        let coldkey = ensure_signed(origin)?;
+
+        ensure!(
+            !netuid.is_root(),
+            Error::<T>::RegistrationNotPermittedOnRootSubnet
+        );
+        ensure!(
+            Self::if_subnet_exist(netuid),
+            Error::<T>::SubNetworkDoesNotExist
+        );
+        ensure!(
+            Self::get_network_registration_allowed(netuid),
+            Error::<T>::SubNetRegistrationDisabled
+        );
+        ensure!(
+            Self::get_registration_auction_enabled(netuid),
+            Error::<T>::RegistrationAuctionDisabled
+        );
+        Self::ensure_registration_whitelisted(netuid, &hotkey, &coldkey, None)?;
+        ensure!(
+            !Uids::<T>::contains_key(netuid, &hotkey),
+            Error::<T>::HotKeyAlreadyRegisteredInSubNet
+        );
+        ensure!(
+            Self::get_max_allowed_uids(netuid) != 0,
+            Error::<T>::NoNeuronIdAvailable
+        );
+        ensure!(
+            bid >= Self::get_burn_as_u64(netuid),
+            Error::<T>::RegistrationBidTooLow
+        );
+
+        let previous_bid = match RegistrationBids::<T>::get(netuid, &hotkey) {
+            Some((_, previous_bid, _)) => {
+                ensure!(bid > previous_bid, Error::<T>::RegistrationBidTooLow);
+                Some(previous_bid)
+            }
+            None => {
+                ensure!(
+                    RegistrationBidCount::<T>::get(netuid) < MAX_REGISTRATION_BIDS,
+                    Error::<T>::TooManyRegistrationBids
+                );
+                None
+            }
+        };
+        let is_new_bid = previous_bid.is_none();
+        let previous_bid = previous_bid.unwrap_or_default();
+
+        let increase = bid.saturating_sub(previous_bid);
+        ensure!(
+            Self::can_remove_balance_from_coldkey_account(&coldkey, increase),
+            Error::<T>::NotEnoughBalanceToStake
+        );
+
+        Self::create_account_if_non_existent(&coldkey, &hotkey);
+        ensure!(
+            Self::coldkey_owns_hotkey(&coldkey, &hotkey),
+            Error::<T>::NonAssociatedColdKey
+        );
+
+        let withdrawn = Self::remove_balance_from_coldkey_account(&coldkey, increase)?;
+        let bid = previous_bid.saturating_add(withdrawn);
+        if is_new_bid {
+            RegistrationBidCount::<T>::mutate(netuid, |count| count.saturating_inc());
+        }
+        RegistrationBids::<T>::insert(
+            netuid,
+            &hotkey,
+            (coldkey.clone(), bid, Self::get_current_block_as_u64()),
+        );
+
+        Self::deposit_event(Event::RegistrationBidPlaced {
+            netuid,
+            hotkey,
+            coldkey,
+            bid,
+        });
+
+        Ok(())
+    }
+
+    /// Registers the winning bids of the subnet's registration auction and refunds the rest.
+    ///
+    /// Bids are ranked by amount, earlier bids first on ties. Bids below the burn and bids of
+    /// hotkeys registered in the meantime are refunded in full. The winners count against the
+    /// registrations of the block, so at most `MaxRegistrationsPerBlock` of them register.
+    ///
+    /// Returns the weight of the settlement.
+    pub fn settle_registration_auction(netuid: NetUid) -> Weight {
+        let mut weight = T::DbWeight::get().reads(1);
+        let mut bids: Vec<(T::AccountId, T::AccountId, u64, u64)> =
+            RegistrationBids::<T>::drain_prefix(netuid)
+                .map(|(hotkey, (coldkey, bid, block))| (hotkey, coldkey, bid, block))
+                .collect();
+        if bids.is_empty() {
+            return weight;
+        }
+        RegistrationBidCount::<T>::remove(netuid);
+        weight.saturating_accrue(
+            T::DbWeight::get()
+                .reads_writes(bids.len() as u64, (bids.len() as u64).saturating_add(1)),
+        );
+        bids.sort_by(|(_, _, bid_a, block_a), (_, _, bid_b, block_b)| {
+            bid_b.cmp(bid_a).then(block_a.cmp(block_b))
+        });
+
+        let burn = Self::get_burn_as_u64(netuid);
+        let (eligible, ineligible): (Vec<_>, Vec<_>) =
+            bids.into_iter().partition(|(hotkey, _, bid, _)| {
+                *bid >= burn && !Uids::<T>::contains_key(netuid, hotkey)
+            });
+        weight.saturating_accrue(
+            T::DbWeight::get()
+                .reads((eligible.len().saturating_add(ineligible.len()) as u64).saturating_add(6)),
+        );
+
+        let slots = if Self::get_network_registration_allowed(netuid)
+            && !Self::is_subnet_resizing(netuid)
+        {
+            usize::from(
+                Self::get_target_registrations_per_interval(netuid).min(
+                    Self::get_max_registrations_per_block(netuid)
+                        .saturating_sub(Self::get_registrations_this_block(netuid)),
+                ),
+            )
+        } else {
+            0
+        };
+        let clearing_price = eligible
+            .get(slots)
+            .map_or(burn, |(_, _, bid, _)| (*bid).max(burn));
+
+        let mut registered: u16 = 0;
+        for (rank, (hotkey, coldkey, bid, _)) in eligible.into_iter().enumerate() {
+            if rank < slots {
+                weight.saturating_accrue(Self::register_auction_winner_weight());
+            }
+            weight.saturating_accrue(Self::refund_registration_bid_weight());
+            if rank < slots
+                && Self::register_auction_winner(netuid, &hotkey, clearing_price).is_ok()
+            {
+                registered = registered.saturating_add(1);
+                Self::refund_registration_bid(
+                    netuid,
+                    &hotkey,
+                    &coldkey,
+                    bid.saturating_sub(clearing_price),
+                );
+            } else {
+                Self::refund_registration_bid(netuid, &hotkey, &coldkey, bid);
+            }
+        }
+        for (hotkey, coldkey, bid, _) in ineligible {
+            weight.saturating_accrue(Self::refund_registration_bid_weight());
+            Self::refund_registration_bid(netuid, &hotkey, &coldkey, bid);
+        }
+
+        log::debug!(
+            "RegistrationAuctionSettled( netuid:{:?} registered:{:?} clearing_price:{:?} )",
+            netuid,
+            registered,
+            clearing_price
+        );
+        Self::deposit_event(Event::RegistrationAuctionSettled {
+            netuid,
+            registered,
+            clearing_price,
+        });
+
+        weight
+    }
+
+    /// Refunds every open bid of the subnet's registration auction.
+    ///
+    /// At most `MAX_REGISTRATION_BIDS` bids are open, see `refund_registration_bids_weight`.
+    pub fn refund_registration_bids(netuid: NetUid) {
+        RegistrationBidCount::<T>::remove(netuid);
+        for (hotkey, (coldkey, bid, _)) in RegistrationBids::<T>::drain_prefix(netuid) {
+            Self::refund_registration_bid(netuid, &hotkey, &coldkey, bid);
+        }
+    }
+
+    /// Burns the clearing price the same way a burned registration does and registers the
+    /// hotkey.
+    fn register_auction_winner(
+        netuid: NetUid,
+        hotkey: &T::AccountId,
+        clearing_price: u64,
+    ) -> DispatchResult {
+        let burned_alpha: u64 =
+            Self::swap_tao_for_alpha(netuid, clearing_price, T::SwapInterface::max_price())?
+                .amount_paid_out;
+        SubnetAlphaOut::<T>::mutate(netuid, |total| *total = total.saturating_sub(burned_alpha));
+
+        let neuron_uid: u16 = Self::register_neuron(netuid, hotkey);
+        RegistrationBurnedAlpha::<T>::insert(netuid, neuron_uid, burned_alpha);
+
+        BurnRegistrationsThisInterval::<T>::mutate(netuid, |val| val.saturating_inc());
+        RegistrationsThisInterval::<T>::mutate(netuid, |val| val.saturating_inc());
+        RegistrationsThisBlock::<T>::mutate(netuid, |val| val.saturating_inc());
+        Self::increase_rao_recycled(netuid, clearing_price);
+
+        log::debug!(
+            "NeuronRegistered( netuid:{:?} uid:{:?} hotkey:{:?}  ) ",
+            netuid,
+            neuron_uid,
+            hotkey
+        );
+        Self::deposit_event(Event::NeuronRegistered(netuid, neuron_uid, hotkey.clone()));
+
+        Ok(())
+    }
+
+    /// Weight of registering one winner, the swap and registration of a burned registration.
+    fn register_auction_winner_weight() -> Weight {
+        Weight::from_parts(354_400_000, 0)
+            .saturating_add(T::DbWeight::get().reads(53))
+            .saturating_add(T::DbWeight::get().writes(43))
+    }
+
+    /// Weight of refunding all open bids of a subnet, for the worst case of
+    /// `MAX_REGISTRATION_BIDS` bids.
+    pub fn refund_registration_bids_weight() -> Weight {
+        T::DbWeight::get().reads_writes(1, 1).saturating_add(
+            Self::refund_registration_bid_weight()
+                .saturating_add(T::DbWeight::get().reads_writes(1, 1))
+                .saturating_mul(u64::from(MAX_REGISTRATION_BIDS)),
+        )
+    }
+
+    /// Weight of refunding one bid.
+    fn refund_registration_bid_weight() -> Weight {
+        Weight::from_parts(20_000_000, 0).saturating_add(T::DbWeight::get().reads_writes(1, 1))
+    }
+
+    fn refund_registration_bid(
+        netuid: NetUid,
+        hotkey: &T::AccountId,
+        coldkey: &T::AccountId,
+        amount: u64,
+    ) {
+        if amount == 0 {
+            return;
+        }
+
+        Self::add_balance_to_coldkey_account(coldkey, amount);
+        Self::deposit_event(Event::RegistrationBidRefunded {
+            netuid,
+            hotkey: hotkey.clone(),
+            coldkey: coldkey.clone(),
+            amount,
+        });
+    }
+}
@@ -0,0 +1,182 @@
+use super::*;
+use frame_system::pallet_prelude::BlockNumberFor;
+use sp_core::sr25519;
+use sp_runtime::traits::Zero;
+use subtensor_runtime_common::NetUid;
+
+/// Domain of the message a subnet owner signs to vouch for a hotkey.
+const REGISTRATION_VOUCHER_CONTEXT: &[u8] = b"subtensor-registration-voucher";
+
+impl<T: Config> Pallet<T> {
+    /// Ensures a hotkey may register on a subnet running a registration whitelist.
+    ///
+    /// Subnets without a whitelist register any hotkey. Otherwise the hotkey has to be on the
+    /// subnet's whitelist, or come with an unexpired voucher signed by the subnet owner coldkey.
+    /// Both name the coldkey the hotkey registers with, so nobody else can take the approval.
+    /// A voucher registers once, it is spent by the registration.
+    ///
+    /// # Raises:
+    /// * 'HotKeyNotWhitelisted':
+    ///     - The hotkey is neither whitelisted nor vouched for with this coldkey.
+    ///
+    /// * 'RegistrationVoucherExpired':
+    ///     - The voucher expired before the current block.
+    ///
+    /// * 'RegistrationVoucherValidityTooLong':
+    ///     - The voucher expires more than `MAX_REGISTRATION_VOUCHER_VALIDITY` blocks away.
+    ///
+    /// * 'RegistrationVoucherSpent':
+    ///     - The voucher has already been used or revoked.
+    ///
+    /// * 'InvalidRegistrationVoucher':
+    ///     - The voucher is not signed by the subnet owner of this chain for this netuid, hotkey
+    ///       and coldkey.
+    pub fn ensure_registration_whitelisted(
+        netuid: NetUid,
+        hotkey: &T::AccountId,
+        coldkey: &T::AccountId,
+        voucher: Option<&RegistrationVoucher>,
+    ) -> DispatchResult {
+        if !Self::get_registration_whitelist_enabled(netuid)
+            || RegistrationWhitelist::<T>::get(netuid, hotkey).as_ref() == Some(coldkey)
+        {
+            return Ok(());
+        }
+
+        let voucher = voucher.ok_or(Error::<T>::HotKeyNotWhitelisted)?;
+        Self::ensure_registration_voucher_unspent(netuid, voucher.nonce, voucher.expires_at)?;
+
+        let owner = SubnetOwner::<T>::get(netuid).encode();
+        let public = <[u8; 32]>::try_from(owner.as_slice())
+            .map(sr25519::Public::from_raw)
+            .map_err(|_| Error::<T>::InvalidRegistrationVoucher)?;
+        let message = Self::registration_voucher_message(
+            netuid,
+            hotkey,
+            coldkey,
+            voucher.expires_at,
+            voucher.nonce,
+        );
+        ensure!(
+            sp_io::crypto::sr25519_verify(&voucher.signature, &message, &public),
+            Error::<T>::InvalidRegistrationVoucher
+        );
+
+        SpentRegistrationVouchers::<T>::insert(voucher.expires_at, (netuid, voucher.nonce), ());
+        Self::deposit_event(Event::RegistrationVoucherUsed {
+            netuid,
+            hotkey: hotkey.clone(),
+            expires_at: voucher.expires_at,
+            nonce: voucher.nonce,
+        });
+        Ok(())
+    }
+
+    /// Revokes a voucher of the subnet owner before it is used.
+    ///
+    /// # Raises:
+    /// * 'RegistrationVoucherExpired':
+    ///     - The voucher expired before the current block, there is nothing to revoke.
+    ///
+    /// * 'RegistrationVoucherValidityTooLong':
+    ///     - The voucher expires more than `MAX_REGISTRATION_VOUCHER_VALIDITY` blocks away.
+    ///
+    /// * 'RegistrationVoucherSpent':
+    ///     - The voucher has already been used or revoked.
+    pub fn do_revoke_registration_voucher(
+        origin: T::RuntimeOrigin,
+        netuid: NetUid,
+        nonce: u64,
+        expires_at: u64,
+    ) -> DispatchResult {
+        Self::ensure_subnet_owner(origin, netuid)?;
+        Self::ensure_registration_voucher_unspent(netuid, nonce, expires_at)?;
+
+        SpentRegistrationVouchers::<T>::insert(expires_at, (netuid, nonce), ());
+        Self::deposit_event(Event::RegistrationVoucherRevoked {
+            netuid,
+            nonce,
+            expires_at,
+        });
+        Ok(())
+    }
+
+    /// Ensures a voucher is within its validity and neither used nor revoked.
+    fn ensure_registration_voucher_unspent(
+        netuid: NetUid,
+        nonce: u64,
+        expires_at: u64,
+    ) -> DispatchResult {
+        let current_block = Self::get_current_block_as_u64();
+        ensure!(
+            current_block <= expires_at,
+            Error::<T>::RegistrationVoucherExpired
+        );
+        ensure!(
+            expires_at <= current_block.saturating_add(MAX_REGISTRATION_VOUCHER_VALIDITY),
+            Error::<T>::RegistrationVoucherValidityTooLong
+        );
+        ensure!(
+            !SpentRegistrationVouchers::<T>::contains_key(expires_at, (netuid, nonce)),
+            Error::<T>::RegistrationVoucherSpent
+        );
+        Ok(())
+    }
+
+    /// Forgets the vouchers spent before they expired at the previous block, they can no longer
+    /// be used anyway.
+    ///
+    /// Returns the weight of the removal.
+    pub fn remove_expired_registration_vouchers(block: u64) -> Weight {
+        let Some(expired_at) = block.checked_sub(1) else {
+            return Weight::zero();
+        };
+        let removed = SpentRegistrationVouchers::<T>::clear_prefix(expired_at, u32::MAX, None);
+        T::DbWeight::get().reads_writes(
+            u64::from(removed.loops).saturating_add(1),
+            u64::from(removed.unique),
+        )
+    }
+
+    /// Message the subnet owner coldkey signs to vouch for a hotkey registering with `coldkey`
+    /// until `expires_at`.
+    ///
+    /// The genesis hash binds the voucher to this chain, so it cannot be replayed on another
+    /// network where the owner uses the same key.
+    pub fn registration_voucher_message(
+        netuid: NetUid,
+        hotkey: &T::AccountId,
+        coldkey: &T::AccountId,
+        expires_at: u64,
+        nonce: u64,
+    ) -> Vec<u8> {
+        (
+            REGISTRATION_VOUCHER_CONTEXT,
+            frame_system::Pallet::<T>::block_hash(BlockNumberFor::<T>::zero()),
+            netuid,
+            hotkey,
+            coldkey,
+            expires_at,
+            nonce,
+        )
+            .encode()
+    }
+
+    /// Whitelists a hotkey to register with `coldkey`, or removes it from the whitelist with
+    /// None.
+    pub fn set_registration_whitelisted(
+        netuid: NetUid,
+        hotkey: &T::AccountId,
+        coldkey: Option<T::AccountId>,
+    ) {
+        match &coldkey {
+            Some(coldkey) => RegistrationWhitelist::<T>::insert(netuid, hotkey, coldkey),
+            None => RegistrationWhitelist::<T>::remove(netuid, hotkey),
+        }
+        Self::deposit_event(Event::RegistrationWhitelistUpdated {
+            netuid,
+            hotkey: hotkey.clone(),
+            coldkey,
+        });
+    }
+}
@@ -0,0 +1,352 @@
+use super::*;
+use subtensor_runtime_common::NetUid;
+
+impl<T: Config> Pallet<T> {
+    /// ---- The implementation for the extrinsic resize_subnet.
+    ///
+    /// # Args:
+    /// * 'origin': (<T as frame_system::Config>RuntimeOrigin):
+    ///     - Must be root.
+    ///
+    /// * 'netuid' (NetUid):
+    ///     - The subnet to resize.
+    ///
+    /// * 'max_allowed_uids' (u16):
+    ///     - The new max allowed uids of the subnet.
+    ///
+    /// A subnet holding more neurons than the new limit prunes its lowest-ranked neurons, and the
+    /// uids left free are closed by renumbering the neurons above them. The pruning and renumbering
+    /// run over the next blocks, `SUBNET_RESIZE_UIDS_PER_BLOCK` uids at a time, followed by one
+    /// per-uid vector per block and the pending weight commits. Meanwhile the subnet takes no
+    /// registrations or weights and skips its epochs.
+    ///
+    /// # Event:
+    /// * SubnetResizeScheduled;
+    ///     - On starting to prune and renumber the subnet's uids.
+    ///
+    /// * SubnetResized;
+    ///     - On resizing a subnet with no uids to prune or renumber.
+    ///
+    /// # Raises:
+    /// * 'InvalidSubnetResize':
+    ///     - Attempting to resize the root network or to zero uids.
+    ///
+    /// * 'SubNetworkDoesNotExist':
+    ///     - The subnet does not exist.
+    ///
+    /// * 'SubnetResizeInProgress':
+    ///     - The subnet is being resized already.
+    ///
+    pub fn do_resize_subnet(
+        origin: T::RuntimeOrigin,
+        netuid: NetUid,
+        max_allowed_uids: u16,
+    ) -> DispatchResult {
+        ensure_root(origin)?;
+        ensure!(
+            !netuid.is_root() && max_allowed_uids > 0,
+            Error::<T>::InvalidSubnetResize
+        );
+        ensure!(
+            Self::if_subnet_exist(netuid),
+            Error::<T>::SubNetworkDoesNotExist
+        );
+        ensure!(
+            !Self::is_subnet_resizing(netuid),
+            Error::<T>::SubnetResizeInProgress
+        );
+
+        let subnetwork_n = Self::get_subnetwork_n(netuid);
+        let neurons_n = u16::try_from(Keys::<T>::iter_prefix(netuid).count()).unwrap_or(u16::MAX);
+        if neurons_n == subnetwork_n && subnetwork_n <= max_allowed_uids {
+            // No uid to prune or close
+            Self::set_max_allowed_uids(netuid, max_allowed_uids);
+            Self::deposit_event(Event::SubnetResized {
+                netuid,
+                max_allowed_uids,
+                subnetwork_n,
+            });
+            return Ok(());
+        }
+
+        let prune: Vec<u16> = Self::get_pruning_order(netuid)
+            .into_iter()
+            .take(usize::from(neurons_n.saturating_sub(max_allowed_uids)))
+            .collect();
+        let pruned = u16::try_from(prune.len()).unwrap_or(u16::MAX);
+        SubnetResizes::<T>::insert(
+            netuid,
+            SubnetResize {
+                max_allowed_uids,
+                prune,
+                vacant: Vec::new(),
+                stage: SubnetResizeStage::Prune,
+            },
+        );
+
+        log::debug!(
+            "SubnetResizeScheduled( netuid:{:?} max_allowed_uids:{:?} pruned:{:?} )",
+            netuid,
+            max_allowed_uids,
+            pruned
+        );
+        Self::deposit_event(Event::SubnetResizeScheduled {
+            netuid,
+            max_allowed_uids,
+            pruned,
+        });
+        Ok(())
+    }
+
+    /// Whether the subnet's uids are being pruned or renumbered.
+    pub fn is_subnet_resizing(netuid: NetUid) -> bool {
+        SubnetResizes::<T>::contains_key(netuid)
+    }
+
+    /// Advances every pending subnet resize by up to `SUBNET_RESIZE_UIDS_PER_BLOCK` uids.
+    ///
+    /// Returns the weight of the steps.
+    pub fn step_subnet_resizes() -> Weight {
+        let mut weight = Weight::zero();
+        for (netuid, resize) in SubnetResizes::<T>::iter().collect::<Vec<_>>() {
+            weight.saturating_accrue(Self::step_subnet_resize(netuid, resize));
+        }
+        weight
+    }
+
+    /// Runs the stages of the resize in turn until `SUBNET_RESIZE_UIDS_PER_BLOCK` uids are
+    /// handled: prunes the next uids, collects the uids left free, moves the next uids to their
+    /// compacted position, shrinks one per-uid vector per block and clears the pending weight
+    /// commits, then finishes the resize.
+    ///
+    /// Returns the weight of the step.
+    pub fn step_subnet_resize(netuid: NetUid, mut resize: SubnetResize) -> Weight {
+        let mut budget = SUBNET_RESIZE_UIDS_PER_BLOCK;
+        let mut weight = T::DbWeight::get().reads_writes(1, 1);
+
+        loop {
+            match resize.stage {
+                // --- 1. Prune the lowest-ranked neurons.
+                SubnetResizeStage::Prune => {
+                    while budget > 0 {
+                        let Some(uid) = resize.prune.pop() else {
+                            break;
+                        };
+                        Self::release_uid(netuid, uid);
+                        Weights::<T>::remove(netuid, uid);
+                        Bonds::<T>::remove(netuid, uid);
+                        weight.saturating_accrue(T::DbWeight::get().reads_writes(2, 10));
+                        budget = budget.saturating_sub(1);
+                    }
+                    if !resize.prune.is_empty() {
+                        break;
+                    }
+                    resize.stage = SubnetResizeStage::CollectVacant { next_uid: 0 };
+                }
+
+                // --- 2. Collect the uids left free once the pruning is done.
+                SubnetResizeStage::CollectVacant { mut next_uid } => {
+                    let subnetwork_n = Self::get_subnetwork_n(netuid);
+                    weight.saturating_accrue(T::DbWeight::get().reads(1));
+                    while budget > 0 && next_uid < subnetwork_n {
+                        if !Keys::<T>::contains_key(netuid, next_uid) {
+                            resize.vacant.push(next_uid);
+                        }
+                        weight.saturating_accrue(T::DbWeight::get().reads(1));
+                        next_uid = next_uid.saturating_add(1);
+                        budget = budget.saturating_sub(1);
+                    }
+                    if next_uid < subnetwork_n {
+                        resize.stage = SubnetResizeStage::CollectVacant { next_uid };
+                        break;
+                    }
+                    resize.stage = SubnetResizeStage::Compact { next_uid: 0 };
+                }
+
+                // --- 3. Move the neurons down to close the free uids, in increasing uid order so
+                // every target uid is already empty.
+                SubnetResizeStage::Compact { mut next_uid } => {
+                    let subnetwork_n = Self::get_subnetwork_n(netuid);
+                    while budget > 0 && next_uid < subnetwork_n {
+                        Self::compact_uid(netuid, next_uid, &resize.vacant);
+                        weight.saturating_accrue(T::DbWeight::get().reads_writes(7, 11));
+                        next_uid = next_uid.saturating_add(1);
+                        budget = budget.saturating_sub(1);
+                    }
+                    if next_uid < subnetwork_n {
+                        resize.stage = SubnetResizeStage::Compact { next_uid };
+                        break;
+                    }
+                    resize.stage = SubnetResizeStage::ShrinkVectors { next_vector: 0 };
+                }
+
+                // --- 4. Shrink the per-uid vectors to the compacted uids, one per block.
+                SubnetResizeStage::ShrinkVectors { next_vector } => {
+                    if budget < SUBNET_RESIZE_UIDS_PER_BLOCK {
+                        break;
+                    }
+                    budget = 0;
+                    if Self::shrink_uid_vector(netuid, next_vector, &resize.vacant) {
+                        weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+                        resize.stage = SubnetResizeStage::ShrinkVectors {
+                            next_vector: next_vector.saturating_add(1),
+                        };
+                    } else {
+                        Self::apply_subnet_resize(netuid, resize.max_allowed_uids, &resize.vacant);
+                        weight.saturating_accrue(T::DbWeight::get().reads_writes(
+                            3,
+                            u64::from(MAX_PRUNING_EMISSION_EPOCHS).saturating_add(8),
+                        ));
+                        resize.stage = SubnetResizeStage::ClearCommits;
+                    }
+                    break;
+                }
+
+                // --- 5. Drop the pending weight commits, they point at the uids before the resize.
+                SubnetResizeStage::ClearCommits => {
+                    if budget == 0 {
+                        break;
+                    }
+                    let removed = WeightCommits::<T>::clear_prefix(netuid, u32::from(budget), None);
+                    weight.saturating_accrue(
+                        T::DbWeight::get()
+                            .reads_writes(u64::from(removed.loops), u64::from(removed.unique)),
+                    );
+                    budget = budget.saturating_sub(removed.unique as u16);
+                    if removed.maybe_cursor.is_some() || budget == 0 {
+                        break;
+                    }
+                    let mut removed: u16 = 0;
+                    let mut dropped: u64 = 0;
+                    for (epoch, commits) in
+                        CRV3WeightCommits::<T>::drain_prefix(netuid).take(usize::from(budget))
+                    {
+                        dropped = dropped.saturating_add(Self::drop_crv3_commits(
+                            netuid,
+                            epoch,
+                            &commits,
+                            CRV3RevealStatus::Dropped,
+                        ));
+                        removed = removed.saturating_add(1);
+                    }
+                    weight.saturating_accrue(T::DbWeight::get().reads_writes(
+                        u64::from(removed),
+                        u64::from(removed).saturating_add(dropped),
+                    ));
+                    if removed >= budget {
+                        break;
+                    }
+
+                    Self::finish_subnet_resize(netuid, resize.max_allowed_uids);
+                    return weight;
+                }
+            }
+        }
+
+        SubnetResizes::<T>::insert(netuid, resize);
+        weight
+    }
+
+    /// Position of a uid once the vacant uids are closed, `None` for a vacant uid.
+    pub fn get_compacted_uid(uid: u16, vacant: &[u16]) -> Option<u16> {
+        match vacant.binary_search(&uid) {
+            Ok(_) => None,
+            Err(vacant_below) => Some(uid.saturating_sub(vacant_below as u16)),
+        }
+    }
+
+    /// Moves the neuron under the uid to its compacted position, with the uids its weights and
+    /// bonds point at.
+    fn compact_uid(netuid: NetUid, uid: u16, vacant: &[u16]) {
+        let Some(new_uid) = Self::get_compacted_uid(uid, vacant) else {
+            return;
+        };
+        let compact_row = |row: Vec<(u16, u16)>| -> Vec<(u16, u16)> {
+            row.into_iter()
+                .filter_map(|(uid_j, value)| {
+                    Self::get_compacted_uid(uid_j, vacant).map(|new_uid_j| (new_uid_j, value))
+                })
+                .collect()
+        };
+        let weights = compact_row(Weights::<T>::take(netuid, uid));
+        let bonds = compact_row(Bonds::<T>::take(netuid, uid));
+
+        if new_uid != uid {
+            if let Ok(hotkey) = Keys::<T>::try_get(netuid, uid) {
+                Uids::<T>::insert(netuid, &hotkey, new_uid);
+            }
+            Keys::<T>::swap(netuid, uid, netuid, new_uid);
+            BlockAtRegistration::<T>::swap(netuid, uid, netuid, new_uid);
+            RegistrationBurnedAlpha::<T>::swap(netuid, uid, netuid, new_uid);
+            AssociatedEvmAddress::<T>::swap(netuid, uid, netuid, new_uid);
+        }
+        if !weights.is_empty() {
+            Weights::<T>::insert(netuid, new_uid, weights);
+        }
+        if !bonds.is_empty() {
+            Bonds::<T>::insert(netuid, new_uid, bonds);
+        }
+    }
+
+    /// Drops the vacant uids from the per-uid vector at the index, false once every vector is
+    /// shrunk.
+    fn shrink_uid_vector(netuid: NetUid, index: u8, vacant: &[u16]) -> bool {
+        fn compact<V>(values: &mut Vec<V>, vacant: &[u16]) {
+            let mut uid: u16 = 0;
+            values.retain(|_| {
+                let keep = vacant.binary_search(&uid).is_err();
+                uid = uid.saturating_add(1);
+                keep
+            });
+        }
+
+        match index {
+            0 => Rank::<T>::mutate(netuid, |v| compact(v, vacant)),
+            1 => Trust::<T>::mutate(netuid, |v| compact(v, vacant)),
+            2 => Active::<T>::mutate(netuid, |v| compact(v, vacant)),
+            3 => Emission::<T>::mutate(netuid, |v| compact(v, vacant)),
+            4 => Consensus::<T>::mutate(netuid, |v| compact(v, vacant)),
+            5 => Incentive::<T>::mutate(netuid, |v| compact(v, vacant)),
+            6 => Dividends::<T>::mutate(netuid, |v| compact(v, vacant)),
+            7 => LastUpdate::<T>::mutate(netuid, |v| compact(v, vacant)),
+            8 => PruningScores::<T>::mutate(netuid, |v| compact(v, vacant)),
+            9 => ValidatorTrust::<T>::mutate(netuid, |v| compact(v, vacant)),
+            10 => ValidatorPermit::<T>::mutate(netuid, |v| compact(v, vacant)),
+            11 => StakeWeight::<T>::mutate(netuid, |v| compact(v, vacant)),
+            12 => IncentiveEma::<T>::mutate(netuid, |v| compact(v, vacant)),
+            13 => IncentiveEpochs::<T>::mutate(netuid, |v| compact(v, vacant)),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Applies the compacted size and the new max allowed uids once the per-uid vectors are
+    /// shrunk.
+    fn apply_subnet_resize(netuid: NetUid, max_allowed_uids: u16, vacant: &[u16]) {
+        // The uids move, so the emission history starts over
+        Self::clear_pruning_emission_history(netuid);
+
+        let subnetwork_n = Self::get_subnetwork_n(netuid).saturating_sub(vacant.len() as u16);
+        SubnetworkN::<T>::insert(netuid, subnetwork_n);
+        VacantUids::<T>::remove(netuid);
+        Self::set_max_allowed_uids(netuid, max_allowed_uids);
+    }
+
+    /// Ends the resize once its pending weight commits are dropped.
+    fn finish_subnet_resize(netuid: NetUid, max_allowed_uids: u16) {
+        SubnetResizes::<T>::remove(netuid);
+
+        let subnetwork_n = Self::get_subnetwork_n(netuid);
+        log::debug!(
+            "SubnetResized( netuid:{:?} max_allowed_uids:{:?} subnetwork_n:{:?} )",
+            netuid,
+            max_allowed_uids,
+            subnetwork_n
+        );
+        Self::deposit_event(Event::SubnetResized {
+            netuid,
+            max_allowed_uids,
+            subnetwork_n,
+        });
+    }
+}
@@ -1,4 +1,5 @@
 use super::*;
+use sp_std::vec;
 use subtensor_runtime_common::NetUid;
 
 impl<T: Config> Pallet<T> {
@@ -86,7 +87,7 @@ impl<T: Config> Pallet<T> {
         // Check+insert certificate
         if let Some(certificate) = certificate {
             if let Ok(certificate) = NeuronCertificateOf::try_from(certificate) {
-                NeuronCertificates::<T>::insert(netuid, hotkey_id.clone(), certificate)
+                Self::publish_neuron_certificate(netuid, &hotkey_id, certificate, None)
             }
         }
 
@@ -108,6 +109,23 @@ impl<T: Config> Pallet<T> {
             axon_validated.err().unwrap_or(Error::<T>::InvalidPort)
         );
 
+        // Replace the preferred ip endpoint of the axon, keeping its other endpoints.
+        let mut axon_v2 = Self::get_axon_info_v2(netuid, &hotkey_id);
+        let endpoint = Self::axon_info_to_endpoint(&prev_axon);
+        axon_v2.block = prev_axon.block;
+        axon_v2.version = version;
+        match axon_v2
+            .endpoints
+            .iter_mut()
+            .find(|endpoint| matches!(endpoint.host, AxonHost::Ip { .. }))
+        {
+            Some(preferred) => *preferred = endpoint,
+            None => {
+                // Index 0 is always in bounds, a full list drops its least preferred endpoint.
+                let _ = axon_v2.endpoints.force_insert_keep_left(0, endpoint);
+            }
+        }
+        AxonsV2::<T>::insert(netuid, hotkey_id.clone(), axon_v2);
         Axons::<T>::insert(netuid, hotkey_id.clone(), prev_axon);
 
         // We deposit axon served event.
@@ -118,6 +136,102 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
+    /// ---- The implementation for the extrinsic serve_axon_v2 which sets several endpoints for the axon of a uid on a network.
+    ///
+    /// # Args:
+    /// * 'origin': (<T as frame_system::Config>RuntimeOrigin):
+    ///     - The signature of the caller.
+    ///
+    /// * 'netuid' (u16):
+    ///     - The u16 network identifier.
+    ///
+    /// * 'version' (u64):
+    ///     - The bittensor version identifier.
+    ///
+    /// * 'endpoints' (AxonEndpointsOf):
+    ///     - The ip or DNS endpoints with their port and transport, in order of preference.
+    ///
+    /// * 'certificate' (Option<Vec<u8>>):
+    ///     - Certificate for mutual Tls connection between neurons
+    ///
+    /// # Event:
+    /// * AxonServed;
+    ///     - On successfully serving the axon info.
+    ///
+    /// # Raises:
+    /// * 'NotRegistered':
+    ///     - Attempting to serve from a non registered account.
+    ///
+    /// * 'NoAxonEndpoints':
+    ///     - No endpoint was given.
+    ///
+    /// * 'InvalidIpType', 'InvalidIpAddress', 'InvalidAxonHostname', 'InvalidPort':
+    ///     - An endpoint does not resolve to a proper address.
+    ///
+    /// * 'ServingRateLimitExceeded':
+    ///     - Attempting to set axon information withing the rate limit min.
+    ///
+    pub fn do_serve_axon_v2(
+        origin: T::RuntimeOrigin,
+        netuid: NetUid,
+        version: u32,
+        endpoints: AxonEndpointsOf,
+        certificate: Option<Vec<u8>>,
+    ) -> dispatch::DispatchResult {
+        // We check the callers (hotkey) signature.
+        let hotkey_id = ensure_signed(origin)?;
+
+        // Validate user input
+        Self::validate_serve_axon_v2(&hotkey_id, netuid, &endpoints)?;
+
+        // Check+insert certificate
+        if let Some(certificate) = certificate {
+            if let Ok(certificate) = NeuronCertificateOf::try_from(certificate) {
+                Self::publish_neuron_certificate(netuid, &hotkey_id, certificate, None)
+            }
+        }
+
+        // Mirror the preferred ip endpoint to the legacy axon info. An axon served on DNS
+        // endpoints only keeps its legacy info as is, legacy clients cannot reach it anyway.
+        let current_block: u64 = Self::get_current_block_as_u64();
+        let preferred_ip = endpoints.iter().find_map(|endpoint| match endpoint.host {
+            AxonHost::Ip { ip_type, ip } => Some((
+                ip,
+                ip_type,
+                endpoint.port,
+                Self::axon_transport_to_protocol(endpoint.transport),
+            )),
+            AxonHost::Dns(_) => None,
+        });
+        if let Some((ip, ip_type, port, protocol)) = preferred_ip {
+            let mut prev_axon = Self::get_axon_info(netuid, &hotkey_id);
+            prev_axon.block = current_block;
+            prev_axon.version = version;
+            prev_axon.ip = ip;
+            prev_axon.port = port;
+            prev_axon.ip_type = ip_type;
+            prev_axon.protocol = protocol;
+            Axons::<T>::insert(netuid, hotkey_id.clone(), prev_axon);
+        }
+
+        AxonsV2::<T>::insert(
+            netuid,
+            hotkey_id.clone(),
+            AxonInfoV2 {
+                block: current_block,
+                version,
+                endpoints,
+            },
+        );
+
+        // We deposit axon served event.
+        log::debug!("AxonServed( hotkey:{:?} ) ", hotkey_id.clone());
+        Self::deposit_event(Event::AxonServed(netuid, hotkey_id));
+
+        // Return is successful dispatch.
+        Ok(())
+    }
+
     /// ---- The implementation for the extrinsic serve_prometheus.
     ///
     /// # Args:
@@ -257,6 +371,43 @@ impl<T: Config> Pallet<T> {
         }
     }
 
+    pub fn get_axon_info_v2(netuid: NetUid, hotkey: &T::AccountId) -> AxonInfoV2 {
+        AxonsV2::<T>::get(netuid, hotkey).unwrap_or_default()
+    }
+
+    /// Converts a legacy axon info to a single endpoint axon info.
+    pub fn axon_info_to_v2(axon_info: &AxonInfoOf) -> AxonInfoV2 {
+        AxonInfoV2 {
+            block: axon_info.block,
+            version: axon_info.version,
+            endpoints: BoundedVec::truncate_from(vec![Self::axon_info_to_endpoint(axon_info)]),
+        }
+    }
+
+    /// The ip endpoint of a legacy axon info.
+    pub fn axon_info_to_endpoint(axon_info: &AxonInfoOf) -> AxonEndpoint {
+        AxonEndpoint {
+            host: AxonHost::Ip {
+                ip_type: axon_info.ip_type,
+                ip: axon_info.ip,
+            },
+            port: axon_info.port,
+            transport: if axon_info.protocol == 1 {
+                AxonTransport::Udp
+            } else {
+                AxonTransport::Tcp
+            },
+        }
+    }
+
+    /// Legacy protocol number of a transport, UDP:1 or TCP:0.
+    pub fn axon_transport_to_protocol(transport: AxonTransport) -> u8 {
+        match transport {
+            AxonTransport::Udp | AxonTransport::Quic => 1,
+            AxonTransport::Tcp | AxonTransport::Http2 => 0,
+        }
+    }
+
     pub fn get_prometheus_info(netuid: NetUid, hotkey: &T::AccountId) -> PrometheusInfoOf {
         if let Some(prometheus) = Prometheus::<T>::get(netuid, hotkey) {
             prometheus
@@ -308,6 +459,42 @@ impl<T: Config> Pallet<T> {
         Ok(true)
     }
 
+    pub fn validate_axon_endpoint(endpoint: &AxonEndpoint) -> Result<(), Error<T>> {
+        match &endpoint.host {
+            AxonHost::Ip { ip_type, ip } => {
+                ensure!(Self::is_valid_ip_type(*ip_type), Error::<T>::InvalidIpType);
+                ensure!(
+                    Self::is_valid_ip_address(*ip_type, *ip, false),
+                    Error::<T>::InvalidIpAddress
+                );
+            }
+            AxonHost::Dns(hostname) => {
+                ensure!(
+                    Self::is_valid_hostname(hostname),
+                    Error::<T>::InvalidAxonHostname
+                );
+            }
+        }
+        ensure!(endpoint.port != 0, Error::<T>::InvalidPort);
+
+        Ok(())
+    }
+
+    /// Checks a hostname is made of dot separated labels of 1 to 63 ASCII letters, digits or
+    /// hyphens, none starting or ending with a hyphen.
+    pub fn is_valid_hostname(hostname: &[u8]) -> bool {
+        !hostname.is_empty()
+            && hostname.split(|byte| *byte == b'.').all(|label| {
+                !label.is_empty()
+                    && label.len() <= 63
+                    && label
+                        .iter()
+                        .all(|byte| byte.is_ascii_alphanumeric() || *byte == b'-')
+                    && label.first() != Some(&b'-')
+                    && label.last() != Some(&b'-')
+            })
+    }
+
     pub fn validate_prometheus_data(
         prom_info: &PrometheusInfoOf,
     ) -> Result<bool, pallet::Error<T>> {
@@ -343,8 +530,11 @@ impl<T: Config> Pallet<T> {
             Error::<T>::InvalidIpAddress
         );
 
-        // Get the previous axon information.
+        // Get the previous axon information, rate limited by the last serve of either version.
         let mut prev_axon = Self::get_axon_info(netuid, hotkey_id);
+        prev_axon.block = prev_axon
+            .block
+            .max(Self::get_axon_info_v2(netuid, hotkey_id).block);
         let current_block: u64 = Self::get_current_block_as_u64();
         ensure!(
             Self::axon_passes_rate_limit(netuid, &prev_axon, current_block),
@@ -369,4 +559,35 @@ impl<T: Config> Pallet<T> {
 
         Ok(())
     }
+
+    pub fn validate_serve_axon_v2(
+        hotkey_id: &T::AccountId,
+        netuid: NetUid,
+        endpoints: &AxonEndpointsOf,
+    ) -> Result<(), Error<T>> {
+        // Ensure the hotkey is registered somewhere.
+        ensure!(
+            Self::is_hotkey_registered_on_any_network(hotkey_id),
+            Error::<T>::HotKeyNotRegisteredInNetwork
+        );
+
+        // Validate every endpoint.
+        ensure!(!endpoints.is_empty(), Error::<T>::NoAxonEndpoints);
+        for endpoint in endpoints.iter() {
+            Self::validate_axon_endpoint(endpoint)?;
+        }
+
+        // A DNS only serve leaves the legacy axon info untouched, so both versions count.
+        let mut last_serve = Self::get_axon_info(netuid, hotkey_id);
+        last_serve.block = last_serve
+            .block
+            .max(Self::get_axon_info_v2(netuid, hotkey_id).block);
+        let current_block: u64 = Self::get_current_block_as_u64();
+        ensure!(
+            Self::axon_passes_rate_limit(netuid, &last_serve, current_block),
+            Error::<T>::ServingRateLimitExceeded
+        );
+
+        Ok(())
+    }
 }
@@ -24,6 +24,17 @@ impl<T: Config> Pallet<T> {
         Consensus::<T>::mutate(netuid, |v| Self::set_element_at(v, neuron_index, 0));
         Incentive::<T>::mutate(netuid, |v| Self::set_element_at(v, neuron_index, 0));
         Dividends::<T>::mutate(netuid, |v| Self::set_element_at(v, neuron_index, 0));
+        IncentiveEma::<T>::mutate(netuid, |v| Self::set_element_at(v, neuron_index, 0));
+        IncentiveEpochs::<T>::mutate(netuid, |v| Self::set_element_at(v, neuron_index, 0));
+        // Emission recorded before the new neuron is not part of its total
+        PruningEmissionTotal::<T>::mutate(netuid, |v| Self::set_element_at(v, neuron_index, 0));
+        let recorded_epochs = PruningEmissionEpochs::<T>::get(netuid);
+        PruningEmissionStart::<T>::mutate(netuid, |v| {
+            if v.len() <= neuron_index {
+                v.resize(neuron_index.saturating_add(1), 0);
+            }
+            Self::set_element_at(v, neuron_index, recorded_epochs)
+        });
         Bonds::<T>::remove(netuid, neuron_uid); // Remove bonds for Validator.
     }
 
@@ -41,12 +52,12 @@ impl<T: Config> Pallet<T> {
             new_hotkey
         );
 
-        // 1. Get the old hotkey under this position.
-        let old_hotkey: T::AccountId = Keys::<T>::get(netuid, uid_to_replace);
+        // 1. Get the old hotkey under this position, a vacated uid has none.
+        let old_hotkey: Option<T::AccountId> = Keys::<T>::try_get(netuid, uid_to_replace).ok();
 
         // Do not replace owner hotkey from `SubnetOwnerHotkey`
         if let Ok(sn_owner_hotkey) = SubnetOwnerHotkey::<T>::try_get(netuid) {
-            if sn_owner_hotkey == old_hotkey.clone() {
+            if old_hotkey.as_ref() == Some(&sn_owner_hotkey) {
                 log::warn!(
                     "replace_neuron: Skipped replacement because neuron is the subnet owner hotkey. \
                     netuid: {:?}, uid_to_replace: {:?}, new_hotkey: {:?}, owner_hotkey: {:?}",
@@ -60,9 +71,13 @@ impl<T: Config> Pallet<T> {
         }
 
         // 2. Remove previous set memberships.
-        Uids::<T>::remove(netuid, old_hotkey.clone());
+        if let Some(old_hotkey) = old_hotkey.as_ref() {
+            Uids::<T>::remove(netuid, old_hotkey);
+            IsNetworkMember::<T>::remove(old_hotkey, netuid);
+        }
         AssociatedEvmAddress::<T>::remove(netuid, uid_to_replace);
-        IsNetworkMember::<T>::remove(old_hotkey.clone(), netuid);
+        RegistrationBurnedAlpha::<T>::remove(netuid, uid_to_replace);
+        VacantUids::<T>::mutate(netuid, |vacant| vacant.retain(|uid| *uid != uid_to_replace));
         #[allow(unknown_lints)]
         Keys::<T>::remove(netuid, uid_to_replace);
 
@@ -73,14 +88,78 @@ impl<T: Config> Pallet<T> {
         BlockAtRegistration::<T>::insert(netuid, uid_to_replace, block_number); // Fill block at registration.
         IsNetworkMember::<T>::insert(new_hotkey.clone(), netuid, true); // Fill network is member.
 
-        // 4. Clear neuron certificates
-        NeuronCertificates::<T>::remove(netuid, old_hotkey.clone());
-
-        // 5. Reset new neuron's values.
+        // 4. Reset new neuron's values.
         Self::clear_neuron(netuid, uid_to_replace);
 
-        // 5a. reset axon info for the new uid.
-        Axons::<T>::remove(netuid, old_hotkey);
+        if let Some(old_hotkey) = old_hotkey {
+            // 5. Clear neuron certificates
+            Self::clear_neuron_certificates(netuid, &old_hotkey);
+
+            // 5a. reset axon info for the new uid.
+            Axons::<T>::remove(netuid, &old_hotkey);
+            AxonsV2::<T>::remove(netuid, old_hotkey);
+        }
+    }
+
+    /// Frees the uid of a neuron leaving the network. The slot keeps its position and is filled by
+    /// the next registration, weights and bonds pointing at it are ignored meanwhile.
+    pub fn vacate_neuron(netuid: NetUid, uid_to_vacate: u16) {
+        log::debug!(
+            "vacate_neuron( netuid: {:?} | uid_to_vacate: {:?} ) ",
+            netuid,
+            uid_to_vacate
+        );
+
+        // 1. Remove the set memberships of the hotkey.
+        Self::release_uid(netuid, uid_to_vacate);
+
+        // 2. Reset the neuron's values.
+        Self::clear_neuron(netuid, uid_to_vacate);
+        Self::set_active_for_uid(netuid, uid_to_vacate, false);
+        let neuron_index: usize = uid_to_vacate.into();
+        Rank::<T>::mutate(netuid, |v| Self::set_element_at(v, neuron_index, 0));
+        PruningScores::<T>::mutate(netuid, |v| Self::set_element_at(v, neuron_index, 0));
+        ValidatorTrust::<T>::mutate(netuid, |v| Self::set_element_at(v, neuron_index, 0));
+        ValidatorPermit::<T>::mutate(netuid, |v| Self::set_element_at(v, neuron_index, false));
+
+        // 3. Drop the weights of the neuron, its bonds went with the reset. Weights and bonds
+        // pointing at the slot are left out of the epoch while it is vacant and count as outdated
+        // once it is filled again.
+        Weights::<T>::remove(netuid, uid_to_vacate);
+
+        // 4. Offer the slot to the next registration.
+        VacantUids::<T>::mutate(netuid, |vacant| {
+            if !vacant.contains(&uid_to_vacate) {
+                vacant.push(uid_to_vacate);
+            }
+        });
+    }
+
+    /// Marks the vacant uids of the network, the ones weights and bonds may not point at.
+    pub fn get_vacant_uids_mask(netuid: NetUid) -> Vec<bool> {
+        let mut vacant_mask = vec![false; Self::get_subnetwork_n(netuid) as usize];
+        for uid in VacantUids::<T>::get(netuid) {
+            if let Some(vacant) = vacant_mask.get_mut(uid as usize) {
+                *vacant = true;
+            }
+        }
+        vacant_mask
+    }
+
+    /// Removes the hotkey of the uid from the network, with its registration and serving records.
+    pub fn release_uid(netuid: NetUid, uid: u16) {
+        if let Ok(old_hotkey) = Keys::<T>::try_get(netuid, uid) {
+            Uids::<T>::remove(netuid, &old_hotkey);
+            IsNetworkMember::<T>::remove(&old_hotkey, netuid);
+            Self::clear_neuron_certificates(netuid, &old_hotkey);
+            Axons::<T>::remove(netuid, &old_hotkey);
+            AxonsV2::<T>::remove(netuid, &old_hotkey);
+        }
+        AssociatedEvmAddress::<T>::remove(netuid, uid);
+        BlockAtRegistration::<T>::remove(netuid, uid);
+        RegistrationBurnedAlpha::<T>::remove(netuid, uid);
+        #[allow(unknown_lints)]
+        Keys::<T>::remove(netuid, uid);
     }
 
     /// Appends the uid to the network.
@@ -1,6 +1,7 @@
 use super::*;
 use crate::epoch::math::*;
 use codec::Compact;
+use pallet_drand::types::BeaconId;
 use safe_math::*;
 use sp_core::{ConstU32, H256};
 use sp_runtime::{
@@ -224,6 +225,9 @@ impl<T: Config> Pallet<T> {
     /// * `TooManyUnrevealedCommits`:
     ///   - Raised if the hotkey has reached the maximum number of unrevealed commits.
     ///
+    /// * `CommitRevealRoundNotInFuture`:
+    ///   - Raised if the pulse of the reveal round is already stored.
+    ///
     /// # Events:
     /// * `WeightsCommitted`:
     ///   - Emitted upon successfully storing the weight hash.
@@ -232,10 +236,54 @@ impl<T: Config> Pallet<T> {
         netuid: NetUid,
         commit: BoundedVec<u8, ConstU32<MAX_CRV3_COMMIT_SIZE_BYTES>>,
         reveal_round: u64,
+    ) -> DispatchResult {
+        Self::do_commit_crv3_weights_for_beacon(origin, netuid, commit, reveal_round, None)
+    }
+
+    /// ---- The implementation for committing commit-reveal v3 weights timelocked to a drand
+    /// beacon registered besides the main one.
+    ///
+    /// # Args:
+    /// * `beacon_id` (`Option<BeaconId>`):
+    ///   - The id of the registered beacon the commit is revealed with, or `None` for the main
+    ///     beacon.
+    ///
+    /// The remaining args are those of `do_commit_crv3_weights`.
+    ///
+    /// # Raises:
+    /// * `BeaconNotRegistered`:
+    ///   - Raised if no drand beacon is registered with the id.
+    ///
+    /// The remaining errors are those of `do_commit_crv3_weights`.
+    pub fn do_commit_crv3_weights_for_beacon(
+        origin: T::RuntimeOrigin,
+        netuid: NetUid,
+        commit: BoundedVec<u8, ConstU32<MAX_CRV3_COMMIT_SIZE_BYTES>>,
+        reveal_round: u64,
+        beacon_id: Option<BeaconId>,
     ) -> DispatchResult {
         // 1. Verify the caller's signature (hotkey).
         let who = ensure_signed(origin)?;
 
+        // Ensure the beacon the commit is timelocked to is registered.
+        if let Some(beacon_id) = &beacon_id {
+            ensure!(
+                pallet_drand::Beacons::<T>::contains_key(beacon_id),
+                Error::<T>::BeaconNotRegistered
+            );
+        }
+
+        // Ensure the pulse of the reveal round is not stored yet, its pulse may already be
+        // pruned and the commit could be read before it is revealed.
+        let last_stored_round = match &beacon_id {
+            None => pallet_drand::LastStoredRound::<T>::get(),
+            Some(beacon_id) => pallet_drand::BeaconLastStoredRound::<T>::get(beacon_id),
+        };
+        ensure!(
+            reveal_round > last_stored_round,
+            Error::<T>::CommitRevealRoundNotInFuture
+        );
+
         log::debug!(
             "do_commit_v3_weights(hotkey: {:?}, netuid: {:?})",
             who,
@@ -274,7 +322,7 @@ impl<T: Config> Pallet<T> {
 
             let unrevealed_commits_for_who = commits
                 .iter()
-                .filter(|(account, _, _)| account == &who)
+                .filter(|(account, _, _, _)| account == &who)
                 .count();
             ensure!(
                 unrevealed_commits_for_who < 10,
@@ -284,7 +332,8 @@ impl<T: Config> Pallet<T> {
             // 7. Append the new commit with calculated reveal blocks.
             // Hash the commit before it is moved, for the event
             let commit_hash = BlakeTwo256::hash(&commit);
-            commits.push_back((who.clone(), commit, reveal_round));
+            pallet_drand::Pallet::<T>::hold_reveal_round(beacon_id.as_ref(), reveal_round);
+            commits.push_back((who.clone(), commit, reveal_round, beacon_id));
 
             // 8. Emit the WeightsCommitted event
             Self::deposit_event(Event::CRV3WeightsCommitted(
@@ -301,6 +350,104 @@ impl<T: Config> Pallet<T> {
         })
     }
 
+    /// ---- The implementation for the extrinsic batch_commit_crv3_weights.
+    ///
+    /// This call runs a batch of commit-reveal v3 commits, continuing on errors. Each commit is
+    /// checked on its own against `MAX_CRV3_COMMIT_SIZE_BYTES` and the rate limit of its subnet.
+    ///
+    /// # Args:
+    ///  * 'origin': (<T as frame_system::Config>RuntimeOrigin):
+    ///    - The signature of the calling hotkey.
+    ///
+    ///  * 'netuids' ( Vec<Compact<u16>> ):
+    ///    - The u16 network identifiers.
+    ///
+    ///  * 'commits' ( Vec<Vec<u8>> ):
+    ///    - The encrypted compressed commits, one for each netuid in the batch.
+    ///
+    ///  * 'reveal_rounds' ( Vec<u64> ):
+    ///    - The drand reveal rounds of the commits, one for each netuid in the batch.
+    ///
+    /// # Event:
+    ///  * CRV3WeightsCommitted;
+    ///    - On successfully storing each commit.
+    ///  * BatchCompletedWithErrors;
+    ///    - Emitted when at least on of the commits has an error.
+    ///  * BatchCRV3CommitItemFailed;
+    ///    - Emitted for each error within the batch, with the netuid of the commit.
+    ///  * BatchWeightsCompleted
+    ///    - Emitted when the batch is completed, with the netuids committed to.
+    ///
+    /// # Raises:
+    ///  * InputLengthsUnequal;
+    ///    - Raised when the lengths of the input vectors are not equal.
+    ///
+    ///  * TooManyBatchCommits;
+    ///    - Raised when the batch holds more than `MAX_CRV3_BATCH_COMMITS` commits.
+    ///
+    pub fn do_batch_commit_crv3_weights(
+        origin: T::RuntimeOrigin,
+        netuids: Vec<Compact<NetUid>>,
+        commits: Vec<Vec<u8>>,
+        reveal_rounds: Vec<u64>,
+    ) -> dispatch::DispatchResult {
+        // --- 1. Check the caller's signature. This is the hotkey of a registered account.
+        let hotkey = ensure_signed(origin.clone())?;
+        log::debug!(
+            "do_batch_commit_crv3_weights( origin:{:?}, netuids:{:?}, reveal_rounds:{:?} )",
+            hotkey,
+            netuids,
+            reveal_rounds
+        );
+
+        ensure!(
+            netuids.len() == commits.len() && netuids.len() == reveal_rounds.len(),
+            Error::<T>::InputLengthsUnequal
+        );
+        ensure!(
+            netuids.len() <= MAX_CRV3_BATCH_COMMITS,
+            Error::<T>::TooManyBatchCommits
+        );
+
+        // --- 2. Commit each item on its own, recording the outcome.
+        let mut committed: Vec<Compact<NetUid>> = Vec::new();
+        let mut completed_with_errors: bool = false;
+        for ((netuid, commit), reveal_round) in netuids
+            .into_iter()
+            .zip(commits.into_iter())
+            .zip(reveal_rounds.into_iter())
+        {
+            let result: DispatchResult =
+                BoundedVec::<u8, ConstU32<MAX_CRV3_COMMIT_SIZE_BYTES>>::try_from(commit)
+                    .map_err(|_| Error::<T>::CommitTooLarge.into())
+                    .and_then(|commit| {
+                        Self::do_commit_crv3_weights(origin.clone(), netuid.0, commit, reveal_round)
+                    });
+
+            match result {
+                Ok(()) => committed.push(netuid),
+                Err(err) => {
+                    if !completed_with_errors {
+                        Self::deposit_event(Event::BatchCompletedWithErrors());
+                        completed_with_errors = true;
+                    }
+                    Self::deposit_event(Event::BatchCRV3CommitItemFailed(netuid.0, err));
+                }
+            }
+        }
+
+        // --- 3. Emit the tracking event.
+        log::debug!(
+            "BatchWeightsCompleted( netuids:{:?}, hotkey:{:?} )",
+            committed,
+            hotkey
+        );
+        Self::deposit_event(Event::BatchWeightsCompleted(committed, hotkey));
+
+        // --- 4. Return ok.
+        Ok(())
+    }
+
     /// ---- The implementation for revealing committed weights.
     ///
     /// # Args:
@@ -644,6 +791,9 @@ impl<T: Config> Pallet<T> {
     ///  * 'SubNetworkDoesNotExist':
     ///    - Attempting to set weights on a non-existent network.
     ///
+    ///  * 'SubnetResizeInProgress':
+    ///    - Attempting to set weights while the subnet's uids are renumbered.
+    ///
     ///  * 'NotRegistered':
     ///    - Attempting to set weights from a non registered account.
     ///
@@ -691,99 +841,35 @@ impl<T: Config> Pallet<T> {
             values
         );
 
-        // --- Check that the netuid is not the root network.
-        ensure!(!netuid.is_root(), Error::<T>::CanNotSetRootNetworkWeights);
-
-        // --- 2. Check that the length of uid list and value list are equal for this network.
-        ensure!(
-            Self::uids_match_values(&uids, &values),
-            Error::<T>::WeightVecNotEqualSize
-        );
-
-        // --- 3. Check to see if this is a valid network.
-        ensure!(
-            Self::if_subnet_exist(netuid),
-            Error::<T>::SubNetworkDoesNotExist
-        );
-
-        // --- 4. Check to see if the number of uids is within the max allowed uids for this network.
-        ensure!(
-            Self::check_len_uids_within_allowed(netuid, &uids),
-            Error::<T>::UidsLengthExceedUidsInSubNet
-        );
-
-        // --- 5. Check to see if the hotkey is registered to the passed network.
-        ensure!(
-            Self::is_hotkey_registered_on_network(netuid, &hotkey),
-            Error::<T>::HotKeyNotRegisteredInSubNet
-        );
-
-        // --- 6. Check to see if the hotkey has enough stake to set weights.
-        ensure!(
-            Self::check_weights_min_stake(&hotkey, netuid),
-            Error::<T>::NotEnoughStakeToSetWeights
-        );
-
-        // --- 7. Ensure version_key is up-to-date.
-        ensure!(
-            Self::check_version_key(netuid, version_key),
-            Error::<T>::IncorrectWeightVersionKey
-        );
-
-        // --- 9. Ensure the uid is not setting weights faster than the weights_set_rate_limit.
+        // --- 2. Check the weights against the rules, raising the first one broken.
+        if let Some(error) =
+            Self::get_weights_violations(netuid, &hotkey, &uids, &values, version_key, true)
+                .into_iter()
+                .next()
+        {
+            return Err(error.into());
+        }
         let neuron_uid = Self::get_uid_for_net_and_hotkey(netuid, &hotkey)?;
         let current_block: u64 = Self::get_current_block_as_u64();
-        if !Self::get_commit_reveal_weights_enabled(netuid) {
-            ensure!(
-                Self::check_rate_limit(netuid, neuron_uid, current_block),
-                Error::<T>::SettingWeightsTooFast
-            );
-        }
-        // --- 10. Check that the neuron uid is an allowed validator permitted to set non-self weights.
-        ensure!(
-            Self::check_validator_permit(netuid, neuron_uid, &uids, &values),
-            Error::<T>::NeuronNoValidatorPermit
-        );
-
-        // --- 11. Ensure the passed uids contain no duplicates.
-        ensure!(!Self::has_duplicate_uids(&uids), Error::<T>::DuplicateUids);
-
-        // --- 12. Ensure that the passed uids are valid for the network.
-        ensure!(
-            !Self::contains_invalid_uids(netuid, &uids),
-            Error::<T>::UidVecContainInvalidOne
-        );
-
-        // --- 13. Ensure that the weights have the required length.
-        ensure!(
-            Self::check_length(netuid, neuron_uid, &uids, &values),
-            Error::<T>::WeightVecLengthIsLow
-        );
 
-        // --- 14. Max-upscale the weights.
+        // --- 3. Max-upscale the weights.
         let max_upscaled_weights: Vec<u16> = vec_u16_max_upscale_to_u16(&values);
 
-        // --- 15. Ensure the weights are max weight limited
-        ensure!(
-            Self::max_weight_limited(netuid, neuron_uid, &uids, &max_upscaled_weights),
-            Error::<T>::MaxWeightExceeded
-        );
-
-        // --- 16. Zip weights for sinking to storage map.
+        // --- 4. Zip weights for sinking to storage map.
         let mut zipped_weights: Vec<(u16, u16)> = vec![];
         for (uid, val) in uids.iter().zip(max_upscaled_weights.iter()) {
             zipped_weights.push((*uid, *val))
         }
 
-        // --- 17. Set weights under netuid, uid double map entry.
+        // --- 5. Set weights under netuid, uid double map entry.
         Weights::<T>::insert(netuid, neuron_uid, zipped_weights);
 
-        // --- 18. Set the activity for the weights on this network.
+        // --- 6. Set the activity for the weights on this network.
         if !Self::get_commit_reveal_weights_enabled(netuid) {
             Self::set_last_update_for_uid(netuid, neuron_uid, current_block);
         }
 
-        // --- 19. Emit the tracking event.
+        // --- 7. Emit the tracking event.
         log::debug!(
             "WeightsSet( netuid:{:?}, neuron_uid:{:?} )",
             netuid,
@@ -791,10 +877,115 @@ impl<T: Config> Pallet<T> {
         );
         Self::deposit_event(Event::WeightsSet(netuid, neuron_uid));
 
-        // --- 20. Return ok.
+        // --- 8. Return ok.
         Ok(())
     }
 
+    /// Returns every rule of `do_set_weights` the weights break for the hotkey, in the order
+    /// `do_set_weights` checks them, so the first one is the error it raises. Checks that need the
+    /// neuron uid are skipped while the hotkey is not registered, and the rest while the subnet
+    /// does not exist. With `first_only` it returns at the first rule broken, so the costlier
+    /// checks on the uids only run once the cheaper ones pass.
+    pub fn get_weights_violations(
+        netuid: NetUid,
+        hotkey: &T::AccountId,
+        uids: &[u16],
+        values: &[u16],
+        version_key: u64,
+        first_only: bool,
+    ) -> Vec<Error<T>> {
+        let mut violations = Vec::new();
+
+        // Records the rule broken, returning early when only the first one is wanted.
+        macro_rules! violation {
+            ($error:expr) => {{
+                violations.push($error);
+                if first_only {
+                    return violations;
+                }
+            }};
+        }
+
+        // --- 1. Check that the netuid is not the root network.
+        if netuid.is_root() {
+            violation!(Error::<T>::CanNotSetRootNetworkWeights);
+        }
+
+        // --- 2. Check that the length of uid list and value list are equal for this network.
+        if !Self::uids_match_values(uids, values) {
+            violation!(Error::<T>::WeightVecNotEqualSize);
+        }
+
+        // --- 3. Check to see if this is a valid network with settled uids.
+        if !Self::if_subnet_exist(netuid) {
+            violations.push(Error::<T>::SubNetworkDoesNotExist);
+            return violations;
+        }
+        if Self::is_subnet_resizing(netuid) {
+            violation!(Error::<T>::SubnetResizeInProgress);
+        }
+
+        // --- 4. Check to see if the number of uids is within the max allowed uids for this network.
+        if !Self::check_len_uids_within_allowed(netuid, uids) {
+            violation!(Error::<T>::UidsLengthExceedUidsInSubNet);
+        }
+
+        // --- 5. Check to see if the hotkey is registered to the passed network.
+        let neuron_uid = Self::get_uid_for_net_and_hotkey(netuid, hotkey).ok();
+        if neuron_uid.is_none() {
+            violation!(Error::<T>::HotKeyNotRegisteredInSubNet);
+        }
+
+        // --- 6. Check to see if the hotkey has enough stake to set weights.
+        if !Self::check_weights_min_stake(hotkey, netuid) {
+            violation!(Error::<T>::NotEnoughStakeToSetWeights);
+        }
+
+        // --- 7. Ensure version_key is up-to-date.
+        if !Self::check_version_key(netuid, version_key) {
+            violation!(Error::<T>::IncorrectWeightVersionKey);
+        }
+
+        // --- 8. Ensure the uid is not setting weights faster than the weights_set_rate_limit.
+        if let Some(neuron_uid) = neuron_uid {
+            if !Self::get_commit_reveal_weights_enabled(netuid)
+                && !Self::check_rate_limit(netuid, neuron_uid, Self::get_current_block_as_u64())
+            {
+                violation!(Error::<T>::SettingWeightsTooFast);
+            }
+
+            // --- 9. Check that the neuron uid is an allowed validator permitted to set non-self weights.
+            if !Self::check_validator_permit(netuid, neuron_uid, uids, values) {
+                violation!(Error::<T>::NeuronNoValidatorPermit);
+            }
+        }
+
+        // --- 10. Ensure the passed uids contain no duplicates.
+        if Self::has_duplicate_uids(uids) {
+            violation!(Error::<T>::DuplicateUids);
+        }
+
+        // --- 11. Ensure that the passed uids are valid for the network.
+        if Self::contains_invalid_uids(netuid, uids) {
+            violation!(Error::<T>::UidVecContainInvalidOne);
+        }
+
+        if let Some(neuron_uid) = neuron_uid {
+            // --- 12. Ensure that the weights have the required length.
+            if !Self::check_length(netuid, neuron_uid, uids, values) {
+                violation!(Error::<T>::WeightVecLengthIsLow);
+            }
+
+            // --- 13. Ensure the max-upscaled weights are max weight limited.
+            let max_upscaled_weights = vec_u16_max_upscale_to_u16(values);
+            if !Self::max_weight_limited(netuid, neuron_uid, uids, &max_upscaled_weights) {
+                violation!(Error::<T>::MaxWeightExceeded);
+            }
+        }
+
+        violations
+    }
+
     /// ---- The implementation for the extrinsic batch_set_weights.
     ///
     /// This call runs a batch of set weights calls, continuing on errors.
@@ -2,6 +2,7 @@ use super::*;
 use frame_support::weights::Weight;
 use sp_core::Get;
 use substrate_fixed::types::U64F64;
+use subtensor_swap_interface::SwapHandler;
 
 impl<T: Config> Pallet<T> {
     /// Swaps the coldkey associated with a set of hotkeys from an old coldkey to a new coldkey.
@@ -123,7 +124,10 @@ impl<T: Config> Pallet<T> {
     ///    - For each hotkey owned by the old coldkey, transfer ownership to the new coldkey.
     ///    - Update the list of owned hotkeys for both old and new coldkeys.
     ///
-    /// 7. Transfer remaining balance:
+    /// 7. Swap liquidity positions:
+    ///    - Move all liquidity positions of the old coldkey on every subnet to the new coldkey.
+    ///
+    /// 8. Transfer remaining balance:
     ///    - Transfer any remaining balance from the old coldkey to the new coldkey.
     ///
     /// Throughout the process, the function updates the transaction weight to reflect the operations performed.
@@ -223,7 +227,14 @@ impl<T: Config> Pallet<T> {
         OwnedHotkeys::<T>::insert(new_coldkey, new_owned_hotkeys);
         weight.saturating_accrue(T::DbWeight::get().reads_writes(2, 2));
 
-        // 7. Transfer remaining balance.
+        // 7. Swap liquidity positions.
+        // Positions: MAP ( netuid, coldkey, position_id ) --> Position | Liquidity positions of the coldkey.
+        weight.saturating_accrue(T::SwapInterface::swap_coldkey_positions(
+            old_coldkey,
+            new_coldkey,
+        ));
+
+        // 8. Transfer remaining balance.
         // Balance: MAP ( coldkey ) --> u64 | Balance of the coldkey.
         // Transfer any remaining balance from old_coldkey to new_coldkey
         let remaining_balance = Self::get_coldkey_balance(old_coldkey);
@@ -406,6 +406,11 @@ impl<T: Config> Pallet<T> {
                 Axons::<T>::insert(netuid, new_hotkey, old_axon_info);
                 weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 2));
             }
+            if let Ok(old_axon_info) = AxonsV2::<T>::try_get(netuid, old_hotkey) {
+                AxonsV2::<T>::remove(netuid, old_hotkey);
+                AxonsV2::<T>::insert(netuid, new_hotkey, old_axon_info);
+                weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 2));
+            }
         }
 
         // 3.5 Swap WeightCommits
@@ -443,6 +448,10 @@ impl<T: Config> Pallet<T> {
                 NeuronCertificates::<T>::insert(netuid, new_hotkey, old_neuron_certificates);
                 weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 2));
             }
+            // PublishedNeuronCertificates( netuid, hotkey ) -> the certificates kept for rotation.
+            // Their scheduled expiries move along.
+            Self::swap_neuron_certificates(netuid, old_hotkey, new_hotkey);
+            weight.saturating_accrue(T::DbWeight::get().reads_writes(2, 8));
         }
         // 4. Swap ChildKeys.
         // ChildKeys( parent, netuid ) --> Vec<(proportion,child)> -- the child keys of the parent.
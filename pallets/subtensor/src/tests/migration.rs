@@ -990,3 +990,88 @@ fn test_migrate_set_nominator_min_stake() {
         );
     });
 }
+
+#[test]
+fn test_migrate_axons_to_v2() {
+    new_test_ext(1).execute_with(|| {
+        const MIGRATION_NAME: &str = "migrate_axons_to_v2";
+
+        let netuid = NetUid::from(1);
+        let hotkey = U256::from(1);
+        let axon_info = AxonInfo {
+            block: 7,
+            version: 2,
+            ip: 1676056785,
+            port: 8091,
+            ip_type: 4,
+            protocol: 1,
+            placeholder1: 0,
+            placeholder2: 0,
+        };
+        Axons::<Test>::insert(netuid, hotkey, axon_info.clone());
+        assert!(AxonsV2::<Test>::get(netuid, hotkey).is_none());
+
+        let weight = crate::migrations::migrate_axons_to_v2::migrate_axons_to_v2::<Test>();
+
+        assert!(HasMigrationRun::<Test>::get(
+            MIGRATION_NAME.as_bytes().to_vec()
+        ));
+        assert!(!weight.is_zero(), "Migration weight should be non-zero");
+
+        // The legacy axon is kept and mirrored as a single endpoint.
+        assert_eq!(Axons::<Test>::get(netuid, hotkey), Some(axon_info));
+        let axon_info_v2 = AxonsV2::<Test>::get(netuid, hotkey).unwrap();
+        assert_eq!(axon_info_v2.block, 7);
+        assert_eq!(axon_info_v2.version, 2);
+        assert_eq!(
+            axon_info_v2.endpoints.into_inner(),
+            vec![AxonEndpoint {
+                host: AxonHost::Ip {
+                    ip_type: 4,
+                    ip: 1676056785,
+                },
+                port: 8091,
+                transport: AxonTransport::Udp,
+            }]
+        );
+    });
+}
+
+#[test]
+fn test_migrate_crv3_commits_beacon_id() {
+    new_test_ext(1).execute_with(|| {
+        use sp_std::collections::vec_deque::VecDeque;
+
+        const MIGRATION_NAME: &str = "migrate_crv3_commits_beacon_id";
+
+        let netuid = NetUid::from(1);
+        let epoch: u64 = 5;
+        let hotkey = U256::from(1);
+        let commit: BoundedVec<u8, ConstU32<MAX_CRV3_COMMIT_SIZE_BYTES>> =
+            vec![1, 2, 3].try_into().unwrap();
+
+        // Commits stored before they recorded their beacon
+        let old_commits: VecDeque<(U256, BoundedVec<u8, ConstU32<MAX_CRV3_COMMIT_SIZE_BYTES>>, u64)> =
+            VecDeque::from(vec![(hotkey, commit.clone(), 1_000)]);
+        put(
+            &CRV3WeightCommits::<Test>::hashed_key_for(netuid, epoch),
+            &old_commits,
+        );
+
+        let weight =
+            crate::migrations::migrate_crv3_commits_beacon_id::migrate_crv3_commits_beacon_id::<
+                Test,
+            >();
+
+        assert!(HasMigrationRun::<Test>::get(
+            MIGRATION_NAME.as_bytes().to_vec()
+        ));
+        assert!(!weight.is_zero(), "Migration weight should be non-zero");
+
+        // The existing commits are revealed with the main beacon
+        assert_eq!(
+            CRV3WeightCommits::<Test>::get(netuid, epoch),
+            VecDeque::from(vec![(hotkey, commit, 1_000, None)])
+        );
+    });
+}
@@ -3,7 +3,7 @@
 use core::num::NonZeroU64;
 
 use frame_support::dispatch::DispatchResultWithPostInfo;
-use frame_support::traits::{Contains, Everything, InherentBuilder, InsideBoth};
+use frame_support::traits::{Contains, Everything, InherentBuilder, InsideBoth, UnixTime};
 use frame_support::weights::Weight;
 use frame_support::weights::constants::RocksDbWeight;
 use frame_support::{PalletId, derive_impl};
@@ -464,6 +464,10 @@ parameter_types! {
     pub const SwapMaxPositions: u32 = 100;
     pub const SwapMinimumLiquidity: u64 = 1_000;
     pub const SwapMinimumReserve: NonZeroU64 = NonZeroU64::new(100).unwrap();
+    pub const SwapPositionAutomationInterval: u32 = 100;
+    pub const SwapMaxAutomatedPositionsPerRun: u32 = 20;
+    pub const SwapMinProtocolBandWidth: u32 = 2_000; // about ±10% of the price
+    pub SwapProtocolFullRangeShare: Perbill = Perbill::from_percent(20);
 }
 
 impl pallet_subtensor_swap::Config for Test {
@@ -475,6 +479,10 @@ impl pallet_subtensor_swap::Config for Test {
     type MaxPositions = SwapMaxPositions;
     type MinimumLiquidity = SwapMinimumLiquidity;
     type MinimumReserve = SwapMinimumReserve;
+    type PositionAutomationInterval = SwapPositionAutomationInterval;
+    type MaxAutomatedPositionsPerRun = SwapMaxAutomatedPositionsPerRun;
+    type MinProtocolBandWidth = SwapMinProtocolBandWidth;
+    type ProtocolFullRangeShare = SwapProtocolFullRangeShare;
     type WeightInfo = ();
 }
 
@@ -611,12 +619,28 @@ mod test_crypto {
 
 pub type TestAuthId = test_crypto::TestAuthId;
 
+parameter_types! {
+    pub static MockNow: u64 = 0;
+}
+
+/// The time of the current block, in seconds, as set in `MockNow`.
+pub struct MockUnixTime;
+
+impl UnixTime for MockUnixTime {
+    fn now() -> core::time::Duration {
+        core::time::Duration::from_secs(MockNow::get())
+    }
+}
+
 impl pallet_drand::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type AuthorityId = TestAuthId;
     type Verifier = pallet_drand::verifier::QuicknetVerifier;
     type UnsignedPriority = ConstU64<{ 1 << 20 }>;
     type HttpFetchTimeout = ConstU64<1_000>;
+    type PulseRetention = ConstU64<1_000>;
+    type OnPulsesStored = SubtensorModule;
+    type UnixTime = MockUnixTime;
 }
 
 impl frame_system::offchain::SigningTypes for Test {
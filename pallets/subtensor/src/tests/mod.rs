@@ -16,6 +16,7 @@ mod networks;
 mod neuron_info;
 mod recycle_alpha;
 mod registration;
+mod sealed_stake;
 mod senate;
 mod serving;
 mod staking;
@@ -32,12 +32,22 @@ fn test_registration_ok() {
             coldkey_account_id
         ));
 
+        CRV3RevealStatuses::<Test>::insert(
+            (netuid, 1, hotkey_account_id),
+            vec![(1_000, CRV3RevealStatus::Revealed)],
+        );
+
         assert_ok!(SubtensorModule::user_remove_network(
             coldkey_account_id,
             netuid
         ));
 
-        assert!(!SubtensorModule::if_subnet_exist(netuid))
+        assert!(!SubtensorModule::if_subnet_exist(netuid));
+        assert!(!CRV3RevealStatuses::<Test>::contains_key((
+            netuid,
+            1,
+            hotkey_account_id
+        )));
     })
 }
 
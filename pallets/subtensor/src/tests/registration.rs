@@ -6,13 +6,20 @@ use frame_support::sp_runtime::{DispatchError, transaction_validity::Transaction
 use frame_support::traits::Currency;
 use frame_support::{assert_err, assert_noop, assert_ok};
 use frame_system::{Config, RawOrigin};
-use sp_core::U256;
+use sp_core::{Pair, U256, sr25519};
 use sp_runtime::traits::{DispatchInfoOf, TransactionExtension, TxBaseImplication};
 use subtensor_runtime_common::NetUid;
 
 use super::mock;
 use super::mock::*;
-use crate::{AxonInfoOf, CustomTransactionError, Error, SubtensorTransactionExtension};
+use crate::{
+    AxonInfoOf, BlockAtRegistration, CustomTransactionError, Emission, Error, Event,
+    ImmunityPolicy, Incentive, IncentiveEma, IncentiveEpochs, MAX_REGISTRATION_VOUCHER_VALIDITY,
+    PowAlgorithm, PruningEmissionEpochs, PruningEmissionHistory, PruningEmissionTotal,
+    PruningPolicy, RegistrationBids, RegistrationVoucher, RegistrationWhitelist,
+    SpentRegistrationVouchers, SubnetOwner, SubnetOwnerHotkey, SubtensorTransactionExtension,
+    VacantUids, ValidatorPermit, Weights,
+};
 
 /********************************************
     subscribing::subscribe() tests
@@ -754,6 +761,774 @@ fn test_burn_registration_pruning_scenarios() {
     });
 }
 
+#[test]
+fn test_pruning_policy_emission_history() {
+    new_test_ext(1).execute_with(|| {
+        let netuid = NetUid::from(1);
+        add_network(netuid, 13, 0);
+        SubtensorModule::set_max_registrations_per_block(netuid, 3);
+        SubtensorModule::set_target_registrations_per_interval(netuid, 3);
+        SubtensorModule::set_immunity_period(netuid, 0);
+        for i in 0..3 {
+            register_ok_neuron(
+                netuid,
+                U256::from(i),
+                U256::from(i + 100),
+                i as u64 * 10_000,
+            );
+        }
+        SubtensorModule::set_pruning_policy(netuid, PruningPolicy::EmissionHistory { epochs: 2 });
+
+        // Pruning scores alone would pick uid 0
+        SubtensorModule::set_pruning_score_for_uid(netuid, 0, 10);
+        SubtensorModule::set_pruning_score_for_uid(netuid, 1, 50);
+        SubtensorModule::set_pruning_score_for_uid(netuid, 2, 50);
+
+        for emission in [vec![0, 900, 900], vec![300, 100, 200], vec![300, 100, 200]] {
+            Emission::<Test>::insert(netuid, emission);
+            SubtensorModule::update_pruning_history(netuid);
+        }
+
+        // Only the last two epochs are counted
+        assert_eq!(
+            PruningEmissionTotal::<Test>::get(netuid),
+            vec![600, 200, 400]
+        );
+        assert_eq!(SubtensorModule::get_neuron_to_prune(netuid), 1);
+
+        // A replaced neuron starts without emission history
+        SubtensorModule::clear_neuron(netuid, 0);
+        assert_eq!(PruningEmissionTotal::<Test>::get(netuid), vec![0, 200, 400]);
+        assert_eq!(SubtensorModule::get_neuron_to_prune(netuid), 0);
+
+        // Epochs from before the replacement leave the window without touching its total
+        Emission::<Test>::insert(netuid, vec![50, 100, 200]);
+        SubtensorModule::update_pruning_history(netuid);
+        assert_eq!(
+            PruningEmissionTotal::<Test>::get(netuid),
+            vec![50, 200, 400]
+        );
+        Emission::<Test>::insert(netuid, vec![50, 100, 200]);
+        SubtensorModule::update_pruning_history(netuid);
+        assert_eq!(
+            PruningEmissionTotal::<Test>::get(netuid),
+            vec![100, 200, 400]
+        );
+
+        // Switching policy drops the history
+        SubtensorModule::set_pruning_policy(netuid, PruningPolicy::IncentiveEma);
+        assert!(PruningEmissionTotal::<Test>::get(netuid).is_empty());
+        assert_eq!(PruningEmissionEpochs::<Test>::get(netuid), 0);
+        assert!(
+            PruningEmissionHistory::<Test>::iter_prefix(netuid)
+                .next()
+                .is_none()
+        );
+    });
+}
+
+#[test]
+fn test_pruning_policy_incentive_ema() {
+    new_test_ext(1).execute_with(|| {
+        let netuid = NetUid::from(1);
+        add_network(netuid, 13, 0);
+        SubtensorModule::set_max_registrations_per_block(netuid, 3);
+        SubtensorModule::set_target_registrations_per_interval(netuid, 3);
+        SubtensorModule::set_immunity_period(netuid, 0);
+        for i in 0..3 {
+            register_ok_neuron(
+                netuid,
+                U256::from(i),
+                U256::from(i + 100),
+                i as u64 * 10_000,
+            );
+        }
+        SubtensorModule::set_pruning_policy(netuid, PruningPolicy::IncentiveEma);
+
+        Incentive::<Test>::insert(netuid, vec![1000, 100, 500]);
+        SubtensorModule::update_pruning_history(netuid);
+        assert_eq!(IncentiveEma::<Test>::get(netuid), vec![100, 10, 50]);
+
+        // A single good epoch does not outweigh the average
+        Incentive::<Test>::insert(netuid, vec![0, 1000, 500]);
+        SubtensorModule::update_pruning_history(netuid);
+        assert_eq!(IncentiveEma::<Test>::get(netuid), vec![90, 109, 95]);
+        assert_eq!(SubtensorModule::get_neuron_to_prune(netuid), 0);
+    });
+}
+
+#[test]
+fn test_pruning_policy_protect_validators() {
+    new_test_ext(1).execute_with(|| {
+        let netuid = NetUid::from(1);
+        add_network(netuid, 13, 0);
+        SubtensorModule::set_max_registrations_per_block(netuid, 3);
+        SubtensorModule::set_target_registrations_per_interval(netuid, 3);
+        SubtensorModule::set_immunity_period(netuid, 0);
+        for i in 0..3 {
+            register_ok_neuron(
+                netuid,
+                U256::from(i),
+                U256::from(i + 100),
+                i as u64 * 10_000,
+            );
+        }
+        ValidatorPermit::<Test>::insert(netuid, vec![true, false, true]);
+
+        SubtensorModule::set_pruning_score_for_uid(netuid, 0, 10);
+        SubtensorModule::set_pruning_score_for_uid(netuid, 1, 50);
+        SubtensorModule::set_pruning_score_for_uid(netuid, 2, 20);
+        assert_eq!(SubtensorModule::get_neuron_to_prune(netuid), 0);
+
+        SubtensorModule::set_pruning_policy(netuid, PruningPolicy::ProtectValidators);
+        SubtensorModule::set_pruning_score_for_uid(netuid, 0, 10);
+        assert_eq!(SubtensorModule::get_neuron_to_prune(netuid), 1);
+
+        // With every neuron holding a permit the lowest pruning score is pruned
+        ValidatorPermit::<Test>::insert(netuid, vec![true, true, true]);
+        SubtensorModule::set_pruning_score_for_uid(netuid, 1, 50);
+        assert_eq!(SubtensorModule::get_neuron_to_prune(netuid), 0);
+    });
+}
+
+#[test]
+fn test_pruning_preview_matches_neuron_to_prune() {
+    new_test_ext(1).execute_with(|| {
+        let netuid = NetUid::from(1);
+        add_network(netuid, 13, 0);
+        SubtensorModule::set_max_registrations_per_block(netuid, 3);
+        SubtensorModule::set_target_registrations_per_interval(netuid, 3);
+        SubtensorModule::set_max_allowed_uids(netuid, 3);
+        for i in 0..3 {
+            register_ok_neuron(
+                netuid,
+                U256::from(i),
+                U256::from(i + 100),
+                i as u64 * 10_000,
+            );
+        }
+        assert_eq!(
+            SubtensorModule::get_pruning_preview(NetUid::from(2), 3),
+            None
+        );
+
+        // Only uid 2 is still immune, for 5 more blocks
+        System::set_block_number(100);
+        SubtensorModule::set_immunity_period(netuid, 10);
+        BlockAtRegistration::<Test>::insert(netuid, 2, 95);
+
+        SubtensorModule::set_pruning_score_for_uid(netuid, 0, 50);
+        SubtensorModule::set_pruning_score_for_uid(netuid, 1, 20);
+        SubtensorModule::set_pruning_score_for_uid(netuid, 2, 5);
+
+        let preview = SubtensorModule::get_pruning_preview(netuid, 2).unwrap();
+        assert!(preview.subnet_full);
+        assert_eq!(preview.uid_to_prune.map(|uid| uid.0), Some(1));
+        assert_eq!(
+            preview
+                .candidates
+                .iter()
+                .map(|candidate| (candidate.uid.0, candidate.pruning_score.0))
+                .collect::<Vec<_>>(),
+            vec![(1, 20), (0, 50)]
+        );
+
+        let preview = SubtensorModule::get_pruning_preview(netuid, u16::MAX).unwrap();
+        let immune_candidate = preview.candidates.last().unwrap();
+        assert_eq!(preview.candidates.len(), 3);
+        assert_eq!(immune_candidate.uid.0, 2);
+        assert!(immune_candidate.immune);
+        assert_eq!(immune_candidate.immunity_blocks_remaining.0, 5);
+
+        // The remaining immunity follows the immunity policy
+        SubtensorModule::set_immunity_policy(
+            netuid,
+            ImmunityPolicy::AwaitFirstWeights { max_extension: 20 },
+        );
+        let preview = SubtensorModule::get_pruning_preview(netuid, u16::MAX).unwrap();
+        let immune_candidate = preview.candidates.last().unwrap();
+        assert!(immune_candidate.immune);
+        assert_eq!(immune_candidate.immunity_blocks_remaining.0, 25);
+
+        SubtensorModule::set_immunity_policy(netuid, ImmunityPolicy::EarnedIncentive { epochs: 1 });
+        IncentiveEpochs::<Test>::insert(netuid, vec![0, 0, 1]);
+        let preview = SubtensorModule::get_pruning_preview(netuid, u16::MAX).unwrap();
+        assert!(
+            preview
+                .candidates
+                .iter()
+                .all(|candidate| !candidate.immune && candidate.immunity_blocks_remaining.0 == 0)
+        );
+
+        // The preview leaves the pruning scores untouched
+        assert_eq!(SubtensorModule::get_pruning_score_for_uid(netuid, 1), 20);
+        assert_eq!(SubtensorModule::get_neuron_to_prune(netuid), 1);
+    });
+}
+
+#[test]
+fn test_registration_auction_settles_at_clearing_price() {
+    new_test_ext(1).execute_with(|| {
+        let netuid = NetUid::from(1);
+        let burn_cost = 1000;
+        let initial_balance = 1_000_000;
+        add_network(netuid, 13, 0);
+        mock::setup_reserves(netuid, 1_000_000_000_000, 1_000_000_000_000);
+        SubtensorModule::set_burn(netuid, burn_cost);
+        SubtensorModule::set_target_registrations_per_interval(netuid, 2);
+        SubtensorModule::set_registration_auction_enabled(netuid, true);
+
+        let bidders: Vec<(U256, U256)> = (0..4)
+            .map(|i| (U256::from(i + 100), U256::from(i)))
+            .collect();
+        for (coldkey, _) in bidders.iter() {
+            SubtensorModule::add_balance_to_coldkey_account(coldkey, initial_balance);
+        }
+
+        // Burned registrations go through the auction
+        assert_noop!(
+            SubtensorModule::burned_register(
+                <<Test as Config>::RuntimeOrigin>::signed(bidders[0].0),
+                netuid,
+                bidders[0].1
+            ),
+            Error::<Test>::RegistrationAuctionActive
+        );
+        assert_noop!(
+            SubtensorModule::bid_registration(
+                <<Test as Config>::RuntimeOrigin>::signed(bidders[0].0),
+                netuid,
+                bidders[0].1,
+                burn_cost - 1
+            ),
+            Error::<Test>::RegistrationBidTooLow
+        );
+
+        for ((coldkey, hotkey), bid) in bidders.iter().zip([3000, 2000, 1500, 500 + burn_cost]) {
+            assert_ok!(SubtensorModule::bid_registration(
+                <<Test as Config>::RuntimeOrigin>::signed(*coldkey),
+                netuid,
+                *hotkey,
+                bid
+            ));
+        }
+
+        // A bid can only be raised
+        assert_noop!(
+            SubtensorModule::bid_registration(
+                <<Test as Config>::RuntimeOrigin>::signed(bidders[2].0),
+                netuid,
+                bidders[2].1,
+                1500
+            ),
+            Error::<Test>::RegistrationBidTooLow
+        );
+        assert_ok!(SubtensorModule::bid_registration(
+            <<Test as Config>::RuntimeOrigin>::signed(bidders[2].0),
+            netuid,
+            bidders[2].1,
+            1800
+        ));
+        assert_eq!(
+            SubtensorModule::get_coldkey_balance(&bidders[2].0),
+            initial_balance - 1800
+        );
+        // Raising a bid does not count as a new one
+        assert_eq!(RegistrationBidCount::<Test>::get(netuid), 4);
+
+        SubtensorModule::settle_registration_auction(netuid);
+
+        // The two highest bids register paying the highest losing bid
+        let clearing_price = 1800;
+        for (i, (coldkey, hotkey)) in bidders.iter().enumerate() {
+            let registered = i < 2;
+            assert_eq!(
+                SubtensorModule::is_hotkey_registered_on_network(netuid, hotkey),
+                registered
+            );
+            let expected_balance = if registered {
+                initial_balance - clearing_price
+            } else {
+                initial_balance
+            };
+            assert_eq!(
+                SubtensorModule::get_coldkey_balance(coldkey),
+                expected_balance
+            );
+        }
+        assert_eq!(RegistrationBids::<Test>::iter_prefix(netuid).count(), 0);
+        assert_eq!(RegistrationBidCount::<Test>::get(netuid), 0);
+        assert_eq!(
+            SubtensorModule::get_burn_registrations_this_interval(netuid),
+            2
+        );
+        assert!(System::events().iter().any(|record| {
+            record.event
+                == RuntimeEvent::SubtensorModule(Event::RegistrationAuctionSettled {
+                    netuid,
+                    registered: 2,
+                    clearing_price,
+                })
+        }));
+    });
+}
+
+#[test]
+fn test_registration_auction_counts_against_registrations_per_block() {
+    new_test_ext(1).execute_with(|| {
+        let netuid = NetUid::from(1);
+        let burn_cost = 1000;
+        let initial_balance = 1_000_000;
+        add_network(netuid, 13, 0);
+        mock::setup_reserves(netuid, 1_000_000_000_000, 1_000_000_000_000);
+        SubtensorModule::set_burn(netuid, burn_cost);
+        SubtensorModule::set_target_registrations_per_interval(netuid, 3);
+        SubtensorModule::set_max_registrations_per_block(netuid, 2);
+        SubtensorModule::set_registration_auction_enabled(netuid, true);
+
+        let bidders: Vec<(U256, U256)> = (0..3)
+            .map(|i| (U256::from(i + 100), U256::from(i)))
+            .collect();
+        for ((coldkey, hotkey), bid) in bidders.iter().zip([3000, 2000, 1500]) {
+            SubtensorModule::add_balance_to_coldkey_account(coldkey, initial_balance);
+            assert_ok!(SubtensorModule::bid_registration(
+                <<Test as Config>::RuntimeOrigin>::signed(*coldkey),
+                netuid,
+                *hotkey,
+                bid
+            ));
+        }
+
+        let weight = SubtensorModule::settle_registration_auction(netuid);
+
+        // Only two winners fit in the block, the third bid sets the price and is refunded
+        for (i, (coldkey, hotkey)) in bidders.iter().enumerate() {
+            let registered = i < 2;
+            assert_eq!(
+                SubtensorModule::is_hotkey_registered_on_network(netuid, hotkey),
+                registered
+            );
+            let expected_balance = if registered {
+                initial_balance - 1500
+            } else {
+                initial_balance
+            };
+            assert_eq!(
+                SubtensorModule::get_coldkey_balance(coldkey),
+                expected_balance
+            );
+        }
+        assert_eq!(SubtensorModule::get_registrations_this_block(netuid), 2);
+        assert!(weight.ref_time() > 2 * 354_400_000);
+
+        // Registrations in the same block count against what the winners used
+        let coldkey = U256::from(200);
+        SubtensorModule::add_balance_to_coldkey_account(&coldkey, initial_balance);
+        SubtensorModule::set_registration_auction_enabled(netuid, false);
+        assert_noop!(
+            SubtensorModule::burned_register(
+                <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+                netuid,
+                U256::from(10)
+            ),
+            Error::<Test>::TooManyRegistrationsThisBlock
+        );
+    });
+}
+
+#[test]
+fn test_registration_auction_refunds_bids_when_disabled() {
+    new_test_ext(1).execute_with(|| {
+        let netuid = NetUid::from(1);
+        let coldkey = U256::from(100);
+        let hotkey = U256::from(1);
+        let initial_balance = 1_000_000;
+        add_network(netuid, 13, 0);
+        SubtensorModule::set_burn(netuid, 1000);
+        SubtensorModule::add_balance_to_coldkey_account(&coldkey, initial_balance);
+
+        assert_noop!(
+            SubtensorModule::bid_registration(
+                <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+                netuid,
+                hotkey,
+                5000
+            ),
+            Error::<Test>::RegistrationAuctionDisabled
+        );
+
+        SubtensorModule::set_registration_auction_enabled(netuid, true);
+        assert_ok!(SubtensorModule::bid_registration(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+            netuid,
+            hotkey,
+            5000
+        ));
+        assert_eq!(
+            SubtensorModule::get_coldkey_balance(&coldkey),
+            initial_balance - 5000
+        );
+
+        SubtensorModule::set_registration_auction_enabled(netuid, false);
+        assert_eq!(
+            SubtensorModule::get_coldkey_balance(&coldkey),
+            initial_balance
+        );
+        assert!(RegistrationBids::<Test>::get(netuid, hotkey).is_none());
+        assert!(!SubtensorModule::is_hotkey_registered_on_network(
+            netuid, &hotkey
+        ));
+    });
+}
+
+#[test]
+fn test_deregister_frees_uid_and_refunds_burn() {
+    new_test_ext(1).execute_with(|| {
+        let netuid = NetUid::from(1);
+        let burn_cost = 1000;
+        let coldkey = U256::from(667);
+        let hotkeys = [U256::from(1), U256::from(2)];
+        add_network(netuid, 13, 0);
+        mock::setup_reserves(netuid, 1_000_000_000_000, 1_000_000_000_000);
+        SubtensorModule::set_burn(netuid, burn_cost);
+        SubtensorModule::set_max_registrations_per_block(netuid, 3);
+        SubtensorModule::set_target_registrations_per_interval(netuid, 3);
+        SubtensorModule::set_deregistration_refund(netuid, u16::MAX, 100);
+        SubtensorModule::add_balance_to_coldkey_account(&coldkey, 1_000_000);
+
+        for hotkey in hotkeys {
+            assert_ok!(SubtensorModule::burned_register(
+                <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+                netuid,
+                hotkey
+            ));
+        }
+        Weights::<Test>::insert(netuid, 1, vec![(0, 100), (1, 50)]);
+        let balance_before = SubtensorModule::get_coldkey_balance(&coldkey);
+
+        // Half of the decay period has passed
+        System::set_block_number(51);
+        assert_ok!(SubtensorModule::deregister(
+            <<Test as Config>::RuntimeOrigin>::signed(hotkeys[0]),
+            netuid
+        ));
+
+        assert_eq!(
+            SubtensorModule::get_coldkey_balance(&coldkey),
+            balance_before + burn_cost / 2
+        );
+        assert!(!SubtensorModule::is_hotkey_registered_on_network(
+            netuid,
+            &hotkeys[0]
+        ));
+        assert!(!SubtensorModule::is_uid_exist_on_network(netuid, 0));
+        assert_eq!(VacantUids::<Test>::get(netuid), vec![0]);
+        // Weights pointing at the vacant uid are left out of the epoch
+        assert_eq!(
+            SubtensorModule::get_weights_sparse(netuid)[1]
+                .iter()
+                .map(|(uid, _)| *uid)
+                .collect::<Vec<_>>(),
+            vec![1]
+        );
+        assert!(System::events().iter().any(|record| {
+            record.event
+                == RuntimeEvent::SubtensorModule(Event::NeuronDeregistered {
+                    netuid,
+                    uid: 0,
+                    hotkey: hotkeys[0],
+                    refund: burn_cost / 2,
+                })
+        }));
+
+        // The next registration fills the freed uid
+        let new_hotkey = U256::from(3);
+        assert_ok!(SubtensorModule::burned_register(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+            netuid,
+            new_hotkey
+        ));
+        assert_eq!(
+            SubtensorModule::get_uid_for_net_and_hotkey(netuid, &new_hotkey),
+            Ok(0)
+        );
+        assert_eq!(SubtensorModule::get_subnetwork_n(netuid), 2);
+        assert!(VacantUids::<Test>::get(netuid).is_empty());
+    });
+}
+
+#[test]
+fn test_deregister_not_permitted() {
+    new_test_ext(1).execute_with(|| {
+        let netuid = NetUid::from(1);
+        let owner_hotkey = U256::from(1);
+        add_network(netuid, 13, 0);
+        register_ok_neuron(netuid, owner_hotkey, U256::from(100), 0);
+
+        assert_noop!(
+            SubtensorModule::deregister(
+                <<Test as Config>::RuntimeOrigin>::signed(U256::from(2)),
+                netuid
+            ),
+            Error::<Test>::HotKeyNotRegisteredInSubNet
+        );
+        assert_noop!(
+            SubtensorModule::deregister(
+                <<Test as Config>::RuntimeOrigin>::signed(owner_hotkey),
+                NetUid::ROOT
+            ),
+            Error::<Test>::DeregistrationNotPermitted
+        );
+
+        SubnetOwnerHotkey::<Test>::insert(netuid, owner_hotkey);
+        assert_noop!(
+            SubtensorModule::deregister(
+                <<Test as Config>::RuntimeOrigin>::signed(owner_hotkey),
+                netuid
+            ),
+            Error::<Test>::DeregistrationNotPermitted
+        );
+
+        // Without a recorded burn nothing is refunded
+        SubnetOwnerHotkey::<Test>::remove(netuid);
+        SubtensorModule::set_deregistration_refund(netuid, u16::MAX, 100);
+        assert_ok!(SubtensorModule::deregister(
+            <<Test as Config>::RuntimeOrigin>::signed(owner_hotkey),
+            netuid
+        ));
+        assert_eq!(SubtensorModule::get_coldkey_balance(&U256::from(100)), 0);
+    });
+}
+
+#[test]
+fn test_registration_whitelist() {
+    new_test_ext(1).execute_with(|| {
+        let netuid = NetUid::from(1);
+        let coldkey = U256::from(667);
+        let hotkey = U256::from(1);
+        add_network(netuid, 13, 0);
+        mock::setup_reserves(netuid, 1_000_000_000_000, 1_000_000_000_000);
+        SubtensorModule::set_burn(netuid, 1000);
+        SubtensorModule::add_balance_to_coldkey_account(&coldkey, 10000);
+        SubtensorModule::set_registration_whitelist_enabled(netuid, true);
+
+        assert_noop!(
+            SubtensorModule::burned_register(
+                <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+                netuid,
+                hotkey
+            ),
+            Error::<Test>::HotKeyNotWhitelisted
+        );
+        let (nonce, work) =
+            SubtensorModule::create_work_for_block_number(netuid, 0, 0, &U256::from(2));
+        assert_noop!(
+            SubtensorModule::register(
+                <<Test as Config>::RuntimeOrigin>::signed(U256::from(2)),
+                netuid,
+                0,
+                nonce,
+                work,
+                U256::from(2),
+                coldkey,
+            ),
+            Error::<Test>::HotKeyNotWhitelisted
+        );
+
+        // The whitelist names the coldkey registering the hotkey
+        SubtensorModule::set_registration_whitelisted(netuid, &hotkey, Some(coldkey));
+        SubtensorModule::add_balance_to_coldkey_account(&U256::from(668), 10000);
+        assert_noop!(
+            SubtensorModule::burned_register(
+                <<Test as Config>::RuntimeOrigin>::signed(U256::from(668)),
+                netuid,
+                hotkey
+            ),
+            Error::<Test>::HotKeyNotWhitelisted
+        );
+        assert_ok!(SubtensorModule::burned_register(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+            netuid,
+            hotkey
+        ));
+        assert!(SubtensorModule::is_hotkey_registered_on_network(
+            netuid, &hotkey
+        ));
+
+        // Removing the hotkey closes the subnet to it again
+        SubtensorModule::set_registration_whitelisted(netuid, &U256::from(3), Some(coldkey));
+        SubtensorModule::set_registration_whitelisted(netuid, &U256::from(3), None);
+        assert!(!RegistrationWhitelist::<Test>::contains_key(
+            netuid,
+            U256::from(3)
+        ));
+        assert_noop!(
+            SubtensorModule::burned_register(
+                <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+                netuid,
+                U256::from(3)
+            ),
+            Error::<Test>::HotKeyNotWhitelisted
+        );
+    });
+}
+
+#[test]
+fn test_registration_whitelist_voucher() {
+    new_test_ext(1).execute_with(|| {
+        let netuid = NetUid::from(1);
+        let coldkey = U256::from(667);
+        let hotkey = U256::from(1);
+        let owner_pair = sr25519::Pair::from_seed(&[1; 32]);
+        let owner = U256::from_little_endian(&owner_pair.public().0);
+        let expires_at = 10;
+        add_network(netuid, 13, 0);
+        mock::setup_reserves(netuid, 1_000_000_000_000, 1_000_000_000_000);
+        SubtensorModule::set_burn(netuid, 1000);
+        SubtensorModule::add_balance_to_coldkey_account(&coldkey, 10000);
+        SubtensorModule::set_registration_whitelist_enabled(netuid, true);
+        SubnetOwner::<Test>::insert(netuid, owner);
+
+        let voucher_with = |pair: &sr25519::Pair, hotkey: &U256, expires_at: u64, nonce: u64| {
+            RegistrationVoucher {
+                expires_at,
+                nonce,
+                signature: pair.sign(&SubtensorModule::registration_voucher_message(
+                    netuid, hotkey, &coldkey, expires_at, nonce,
+                )),
+            }
+        };
+        let voucher_for =
+            |pair: &sr25519::Pair, hotkey: &U256| voucher_with(pair, hotkey, expires_at, 0);
+
+        // Only the subnet owner vouches
+        assert_noop!(
+            SubtensorModule::burned_register_with_voucher(
+                <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+                netuid,
+                hotkey,
+                voucher_for(&sr25519::Pair::from_seed(&[2; 32]), &hotkey)
+            ),
+            Error::<Test>::InvalidRegistrationVoucher
+        );
+        // A voucher is bound to its hotkey
+        assert_noop!(
+            SubtensorModule::burned_register_with_voucher(
+                <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+                netuid,
+                hotkey,
+                voucher_for(&owner_pair, &U256::from(2))
+            ),
+            Error::<Test>::InvalidRegistrationVoucher
+        );
+        // and to its coldkey
+        SubtensorModule::add_balance_to_coldkey_account(&U256::from(668), 10000);
+        assert_noop!(
+            SubtensorModule::burned_register_with_voucher(
+                <<Test as Config>::RuntimeOrigin>::signed(U256::from(668)),
+                netuid,
+                hotkey,
+                voucher_for(&owner_pair, &hotkey)
+            ),
+            Error::<Test>::InvalidRegistrationVoucher
+        );
+
+        // A voucher is valid for at most MAX_REGISTRATION_VOUCHER_VALIDITY blocks
+        assert_noop!(
+            SubtensorModule::burned_register_with_voucher(
+                <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+                netuid,
+                hotkey,
+                voucher_with(
+                    &owner_pair,
+                    &hotkey,
+                    System::block_number() + MAX_REGISTRATION_VOUCHER_VALIDITY + 1,
+                    0
+                )
+            ),
+            Error::<Test>::RegistrationVoucherValidityTooLong
+        );
+
+        // Only the subnet owner revokes its vouchers, a revoked voucher no longer registers
+        assert_noop!(
+            SubtensorModule::revoke_registration_voucher(
+                <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+                netuid,
+                1,
+                expires_at
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(SubtensorModule::revoke_registration_voucher(
+            <<Test as Config>::RuntimeOrigin>::signed(owner),
+            netuid,
+            1,
+            expires_at
+        ));
+        assert_noop!(
+            SubtensorModule::burned_register_with_voucher(
+                <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+                netuid,
+                hotkey,
+                voucher_with(&owner_pair, &hotkey, expires_at, 1)
+            ),
+            Error::<Test>::RegistrationVoucherSpent
+        );
+
+        step_block(expires_at as u16 + 1);
+        assert_noop!(
+            SubtensorModule::burned_register_with_voucher(
+                <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+                netuid,
+                hotkey,
+                voucher_for(&owner_pair, &hotkey)
+            ),
+            Error::<Test>::RegistrationVoucherExpired
+        );
+        // Spent vouchers are forgotten once they expire
+        assert!(
+            SpentRegistrationVouchers::<Test>::iter_prefix(expires_at)
+                .next()
+                .is_none()
+        );
+
+        System::set_block_number(expires_at);
+        assert_ok!(SubtensorModule::burned_register_with_voucher(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+            netuid,
+            hotkey,
+            voucher_for(&owner_pair, &hotkey)
+        ));
+        assert!(SubtensorModule::is_hotkey_registered_on_network(
+            netuid, &hotkey
+        ));
+        assert!(System::events().iter().any(|record| {
+            record.event
+                == RuntimeEvent::SubtensorModule(Event::RegistrationVoucherUsed {
+                    netuid,
+                    hotkey,
+                    expires_at,
+                    nonce: 0,
+                })
+        }));
+
+        // A voucher registers once
+        assert!(SpentRegistrationVouchers::<Test>::contains_key(
+            expires_at,
+            (netuid, 0)
+        ));
+        assert_noop!(
+            SubtensorModule::ensure_registration_whitelisted(
+                netuid,
+                &hotkey,
+                &coldkey,
+                Some(&voucher_for(&owner_pair, &hotkey))
+            ),
+            Error::<Test>::RegistrationVoucherSpent
+        );
+    });
+}
+
 #[test]
 fn test_registration_too_many_registrations_per_block() {
     new_test_ext(1).execute_with(|| {
@@ -1221,6 +1996,94 @@ fn test_registration_invalid_seal() {
     });
 }
 
+#[test]
+fn test_registration_cuckoo_cycle() {
+    new_test_ext(1).execute_with(|| {
+        let block_number: u64 = 0;
+        let netuid = NetUid::from(1);
+        let tempo: u16 = 13;
+        let hotkey_account_id = U256::from(1);
+        let coldkey_account_id = U256::from(667);
+        let edge_bits = crate::MIN_CUCKOO_EDGE_BITS;
+
+        add_network(netuid, tempo, 0);
+        SubtensorModule::set_difficulty(netuid, 1);
+        SubtensorModule::set_pow_algorithm(netuid, PowAlgorithm::CuckooCycle { edge_bits });
+
+        // A seal is not a cycle.
+        let (seal_nonce, seal) = SubtensorModule::create_work_for_block_number(
+            netuid,
+            block_number,
+            0,
+            &hotkey_account_id,
+        );
+        assert_noop!(
+            SubtensorModule::register(
+                <<Test as Config>::RuntimeOrigin>::signed(hotkey_account_id),
+                netuid,
+                block_number,
+                seal_nonce,
+                seal,
+                hotkey_account_id,
+                coldkey_account_id,
+            ),
+            Error::<Test>::InvalidSeal
+        );
+
+        let (nonce, work) = SubtensorModule::create_cuckoo_work_for_block_number(
+            netuid,
+            block_number,
+            0,
+            &hotkey_account_id,
+            edge_bits,
+        );
+
+        // The cycle belongs to the graph of its nonce only.
+        assert_noop!(
+            SubtensorModule::register(
+                <<Test as Config>::RuntimeOrigin>::signed(hotkey_account_id),
+                netuid,
+                block_number,
+                nonce + 1,
+                work.clone(),
+                hotkey_account_id,
+                coldkey_account_id,
+            ),
+            Error::<Test>::InvalidSeal
+        );
+
+        // The hash of the cycle has to meet the difficulty.
+        SubtensorModule::set_difficulty(netuid, u64::MAX);
+        assert_noop!(
+            SubtensorModule::register(
+                <<Test as Config>::RuntimeOrigin>::signed(hotkey_account_id),
+                netuid,
+                block_number,
+                nonce,
+                work.clone(),
+                hotkey_account_id,
+                coldkey_account_id,
+            ),
+            Error::<Test>::InvalidDifficulty
+        );
+        SubtensorModule::set_difficulty(netuid, 1);
+
+        assert_ok!(SubtensorModule::register(
+            <<Test as Config>::RuntimeOrigin>::signed(hotkey_account_id),
+            netuid,
+            block_number,
+            nonce,
+            work,
+            hotkey_account_id,
+            coldkey_account_id,
+        ));
+        assert!(SubtensorModule::is_hotkey_registered_on_network(
+            netuid,
+            &hotkey_account_id
+        ));
+    });
+}
+
 #[test]
 fn test_registration_invalid_block_number() {
     new_test_ext(1).execute_with(|| {
@@ -1364,6 +2227,76 @@ fn test_registration_get_uid_to_prune_none_in_immunity_period() {
     });
 }
 
+#[test]
+fn test_immunity_policy_earned_incentive() {
+    new_test_ext(1).execute_with(|| {
+        System::set_block_number(0);
+        let netuid = NetUid::from(1);
+        add_network(netuid, 13, 0);
+        register_ok_neuron(netuid, U256::from(0), U256::from(0), 39420842);
+        register_ok_neuron(netuid, U256::from(1), U256::from(1), 12412392);
+        SubtensorModule::set_immunity_period(netuid, 100);
+        SubtensorModule::set_immunity_policy(netuid, ImmunityPolicy::EarnedIncentive { epochs: 2 });
+        Incentive::<Test>::insert(netuid, vec![u16::MAX, 0]);
+
+        SubtensorModule::update_immunity_history(netuid);
+        assert_eq!(SubtensorModule::get_incentive_epochs_for_uid(netuid, 0), 1);
+        assert!(SubtensorModule::get_neuron_is_immune(netuid, 0));
+
+        // The second epoch with incentive ends the immunity early
+        SubtensorModule::update_immunity_history(netuid);
+        assert!(!SubtensorModule::get_neuron_is_immune(netuid, 0));
+        assert!(SubtensorModule::get_neuron_is_immune(netuid, 1));
+        assert_eq!(SubtensorModule::get_neuron_to_prune(netuid), 0);
+
+        // The immunity period still ends neurons without incentive
+        System::set_block_number(100);
+        assert!(!SubtensorModule::get_neuron_is_immune(netuid, 1));
+
+        // A replaced neuron starts over
+        SubtensorModule::clear_neuron(netuid, 0);
+        assert_eq!(SubtensorModule::get_incentive_epochs_for_uid(netuid, 0), 0);
+    });
+}
+
+#[test]
+fn test_immunity_policy_await_first_weights() {
+    new_test_ext(1).execute_with(|| {
+        System::set_block_number(0);
+        let netuid = NetUid::from(1);
+        add_network(netuid, 13, 0);
+        register_ok_neuron(netuid, U256::from(0), U256::from(0), 39420842);
+        register_ok_neuron(netuid, U256::from(1), U256::from(1), 12412392);
+        SubtensorModule::set_immunity_period(netuid, 10);
+        SubtensorModule::set_immunity_policy(
+            netuid,
+            ImmunityPolicy::AwaitFirstWeights { max_extension: 50 },
+        );
+
+        // Without weights the immunity extends past the immunity period
+        System::set_block_number(20);
+        assert!(SubtensorModule::get_neuron_is_immune(netuid, 0));
+        assert!(SubtensorModule::get_neuron_is_immune(netuid, 1));
+
+        Incentive::<Test>::insert(netuid, vec![0, u16::MAX]);
+        SubtensorModule::update_immunity_history(netuid);
+        assert!(SubtensorModule::get_neuron_is_immune(netuid, 0));
+        assert!(!SubtensorModule::get_neuron_is_immune(netuid, 1));
+        assert_eq!(SubtensorModule::get_neuron_to_prune(netuid), 1);
+
+        // Up to the maximum extension
+        System::set_block_number(60);
+        assert!(!SubtensorModule::get_neuron_is_immune(netuid, 0));
+
+        // Block based immunity does not count epochs
+        SubtensorModule::set_immunity_policy(netuid, ImmunityPolicy::Blocks);
+        SubtensorModule::update_immunity_history(netuid);
+        assert_eq!(SubtensorModule::get_incentive_epochs_for_uid(netuid, 1), 1);
+        SubtensorModule::set_immunity_policy(netuid, ImmunityPolicy::EarnedIncentive { epochs: 1 });
+        assert!(IncentiveEpochs::<Test>::get(netuid).is_empty());
+    });
+}
+
 #[test]
 fn test_registration_pruning() {
     new_test_ext(1).execute_with(|| {
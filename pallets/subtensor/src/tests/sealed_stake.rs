@@ -0,0 +1,373 @@
+#![allow(clippy::unwrap_used)]
+
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use frame_support::{assert_noop, assert_ok};
+use rand_chacha::{ChaCha20Rng, rand_core::SeedableRng};
+use sha2::Digest;
+use sp_core::{Encode, U256};
+use subtensor_runtime_common::NetUid;
+use tle::{
+    curves::drand::TinyBLS381, ibe::fullident::Identity,
+    stream_ciphers::AESGCMStreamCipherProvider, tlock::tle,
+};
+use w3f_bls::EngineBLS;
+
+use super::mock;
+use super::mock::*;
+use crate::*;
+
+/// Timelocks the call to the round of the mock beacon.
+fn seal(call: &Call<Test>, round: u64) -> BoundedVec<u8, ConstU32<MAX_SEALED_CALL_SIZE_BYTES>> {
+    let public_key = <TinyBLS381 as EngineBLS>::PublicKeyGroup::deserialize_compressed(
+        &pallet_drand::mock_beacon::public_key()[..],
+    )
+    .unwrap();
+    let identity = Identity::new(
+        b"",
+        vec![sha2::Sha256::digest(round.to_be_bytes()).to_vec()],
+    );
+    let ciphertext = tle::<TinyBLS381, AESGCMStreamCipherProvider, ChaCha20Rng>(
+        public_key,
+        [3; 32],
+        &call.encode(),
+        identity,
+        ChaCha20Rng::seed_from_u64(0),
+    )
+    .unwrap();
+
+    let mut sealed_call = Vec::new();
+    ciphertext.serialize_compressed(&mut sealed_call).unwrap();
+    sealed_call.try_into().unwrap()
+}
+
+/// Stores the pulses of the mock beacon up to the round.
+fn store_pulses_until(round: u64) {
+    for stored_round in pallet_drand::LastStoredRound::<Test>::get() + 1..=round {
+        pallet_drand::Pulses::<Test>::insert(
+            stored_round,
+            pallet_drand::mock_beacon::pulse(stored_round).unwrap(),
+        );
+    }
+    pallet_drand::LastStoredRound::<Test>::put(round);
+}
+
+/// Stores the pulses of the mock beacon up to the round in a `write_pulse` extrinsic, as the
+/// offchain worker does.
+fn write_pulses_until(round: u64) {
+    let pulses = (pallet_drand::LastStoredRound::<Test>::get() + 1..=round)
+        .map(|stored_round| pallet_drand::mock_beacon::pulse(stored_round).unwrap())
+        .collect();
+    assert_ok!(pallet_drand::Pallet::<Test>::write_pulse(
+        RuntimeOrigin::none(),
+        pallet_drand::types::PulsesPayload {
+            block_number: System::block_number(),
+            pulses,
+            public: sp_core::sr25519::Public::from_raw([1; 32]).into(),
+        },
+        None
+    ));
+}
+
+/// The index among the events of the block of the first event matching.
+fn event_index(matches: impl Fn(&Event<Test>) -> bool) -> Option<usize> {
+    System::events()
+        .iter()
+        .position(|record| match &record.event {
+            RuntimeEvent::SubtensorModule(event) => matches(event),
+            _ => false,
+        })
+}
+
+#[test]
+fn test_submit_sealed_stake_call_rounds() {
+    new_test_ext(1).execute_with(|| {
+        let coldkey = U256::from(1);
+        let hotkey = U256::from(2);
+        let last_stored_round = 1_000;
+        store_pulses_until(last_stored_round);
+
+        let call = Call::<Test>::add_stake {
+            hotkey,
+            netuid: NetUid::from(1),
+            amount_staked: 1_000_000_000,
+        };
+
+        // The pulse of the round is already known
+        assert_noop!(
+            SubtensorModule::submit_sealed_stake_call(
+                RuntimeOrigin::signed(coldkey),
+                seal(&call, last_stored_round),
+                last_stored_round
+            ),
+            Error::<Test>::SealedCallRoundNotInFuture
+        );
+        assert_noop!(
+            SubtensorModule::submit_sealed_stake_call(
+                RuntimeOrigin::signed(coldkey),
+                seal(&call, last_stored_round + MAX_SEALED_CALL_ROUND_DELAY + 1),
+                last_stored_round + MAX_SEALED_CALL_ROUND_DELAY + 1
+            ),
+            Error::<Test>::SealedCallRoundTooFar
+        );
+
+        let reveal_round = last_stored_round + 1;
+        for _ in 0..MAX_SEALED_CALLS_PER_ROUND {
+            assert_ok!(SubtensorModule::submit_sealed_stake_call(
+                RuntimeOrigin::signed(coldkey),
+                seal(&call, reveal_round),
+                reveal_round
+            ));
+        }
+        assert_noop!(
+            SubtensorModule::submit_sealed_stake_call(
+                RuntimeOrigin::signed(coldkey),
+                seal(&call, reveal_round),
+                reveal_round
+            ),
+            Error::<Test>::TooManySealedCalls
+        );
+        System::assert_last_event(Event::SealedStakeCallSubmitted(coldkey, reveal_round).into());
+    });
+}
+
+#[test]
+fn test_submit_sealed_stake_call_round_margin() {
+    new_test_ext(1).execute_with(|| {
+        let coldkey = U256::from(1);
+        let last_stored_round = 1_000;
+        store_pulses_until(last_stored_round);
+
+        // Drand already published round 1_005, the chain stored only up to 1_000
+        let config = pallet_drand::BeaconConfig::<Test>::get();
+        let current_round = last_stored_round + 5;
+        MockNow::set(
+            u64::from(config.genesis_time) + (current_round - 1) * u64::from(config.period),
+        );
+        assert_eq!(pallet_drand::Pallet::<Test>::current_round(), current_round);
+
+        let call = Call::<Test>::add_stake {
+            hotkey: U256::from(2),
+            netuid: NetUid::from(1),
+            amount_staked: 1_000_000_000,
+        };
+        let reveal_round = current_round + MIN_SEALED_CALL_ROUND_MARGIN;
+        assert_noop!(
+            SubtensorModule::submit_sealed_stake_call(
+                RuntimeOrigin::signed(coldkey),
+                seal(&call, reveal_round - 1),
+                reveal_round - 1
+            ),
+            Error::<Test>::SealedCallRoundTooSoon
+        );
+        assert_ok!(SubtensorModule::submit_sealed_stake_call(
+            RuntimeOrigin::signed(coldkey),
+            seal(&call, reveal_round),
+            reveal_round
+        ));
+
+        MockNow::set(0);
+    });
+}
+
+#[test]
+fn test_sealed_stake_call_dispatched_with_pulse() {
+    new_test_ext(1).execute_with(|| {
+        let coldkey = U256::from(1);
+        let hotkey = U256::from(2);
+        let owner_coldkey = U256::from(1001);
+        let owner_hotkey = U256::from(1002);
+        let amount = DefaultMinStake::<Test>::get() * 10;
+
+        let netuid = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+        mock::setup_reserves(netuid, amount * 1_000_000, amount * 10_000_000);
+        register_ok_neuron(netuid, hotkey, coldkey, 0);
+        SubtensorModule::add_balance_to_coldkey_account(&coldkey, amount);
+        pallet_drand::BeaconConfig::<Test>::put(pallet_drand::Pallet::<Test>::mock_beacon_config());
+        store_pulses_until(1_000);
+
+        let reveal_round = 1_002;
+        let stake_call = Call::<Test>::add_stake {
+            hotkey,
+            netuid,
+            amount_staked: amount,
+        };
+        let other_call = Call::<Test>::set_weights {
+            netuid,
+            dests: vec![0],
+            weights: vec![1],
+            version_key: 0,
+        };
+        assert_ok!(SubtensorModule::submit_sealed_stake_call(
+            RuntimeOrigin::signed(coldkey),
+            seal(&stake_call, reveal_round),
+            reveal_round
+        ));
+        assert_ok!(SubtensorModule::submit_sealed_stake_call(
+            RuntimeOrigin::signed(coldkey),
+            seal(&other_call, reveal_round),
+            reveal_round
+        ));
+        // Sealed to another round than the one it is submitted for
+        assert_ok!(SubtensorModule::submit_sealed_stake_call(
+            RuntimeOrigin::signed(coldkey),
+            seal(&stake_call, reveal_round + 1),
+            reveal_round
+        ));
+
+        // Nothing is dispatched before the pulse of the round is stored
+        let stake_before = SubtensorModule::get_total_stake_for_hotkey(&hotkey);
+        write_pulses_until(reveal_round - 1);
+        assert_eq!(SealedStakeCalls::<Test>::get(reveal_round).len(), 3);
+        assert_eq!(
+            SubtensorModule::get_total_stake_for_hotkey(&hotkey),
+            stake_before
+        );
+
+        // The extrinsic storing the pulse dispatches the calls
+        write_pulses_until(reveal_round);
+        assert!(SealedStakeCalls::<Test>::get(reveal_round).is_empty());
+        assert!(SubtensorModule::get_total_stake_for_hotkey(&hotkey) > stake_before);
+        assert!(!pallet_drand::PendingReveals::<Test>::contains_key(
+            None::<pallet_drand::types::BeaconId>,
+            reveal_round
+        ));
+
+        let outcomes: Vec<DispatchResult> = System::events()
+            .into_iter()
+            .filter_map(|record| match record.event {
+                RuntimeEvent::SubtensorModule(Event::SealedStakeCallDispatched(
+                    who,
+                    round,
+                    result,
+                )) if who == coldkey && round == reveal_round => Some(result),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                Ok(()),
+                Err(Error::<Test>::InvalidSealedCall.into()),
+                Err(Error::<Test>::SealedCallDecryptionFailed.into()),
+            ]
+        );
+    });
+}
+
+#[test]
+fn test_sealed_stake_call_runs_before_clear_stake_of_the_pulse_block() {
+    new_test_ext(1).execute_with(|| {
+        let coldkey = U256::from(1);
+        let front_runner = U256::from(3);
+        let hotkey = U256::from(2);
+        let owner_coldkey = U256::from(1001);
+        let owner_hotkey = U256::from(1002);
+        let amount = DefaultMinStake::<Test>::get() * 10;
+
+        let netuid = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+        mock::setup_reserves(netuid, amount * 1_000_000, amount * 10_000_000);
+        register_ok_neuron(netuid, hotkey, coldkey, 0);
+        SubtensorModule::add_balance_to_coldkey_account(&coldkey, amount);
+        SubtensorModule::add_balance_to_coldkey_account(&front_runner, amount);
+        pallet_drand::BeaconConfig::<Test>::put(pallet_drand::Pallet::<Test>::mock_beacon_config());
+        store_pulses_until(1_000);
+
+        let reveal_round = 1_001;
+        let stake_call = Call::<Test>::add_stake {
+            hotkey,
+            netuid,
+            amount_staked: amount,
+        };
+        let sealed_call = seal(&stake_call, reveal_round);
+        assert_ok!(SubtensorModule::submit_sealed_stake_call(
+            RuntimeOrigin::signed(coldkey),
+            sealed_call.clone(),
+            reveal_round
+        ));
+
+        step_block(1);
+        System::reset_events();
+
+        // Before the pulse is stored in the block, nobody can open the call on chain
+        assert_eq!(
+            pallet_drand::Pallet::<Test>::timelock_decrypt(None, reveal_round, &sealed_call),
+            Err(pallet_drand::timelock::TimelockError::MissingPulse)
+        );
+
+        // Once it is, the sealed call already ran, ahead of a stake in the clear placed right
+        // after the pulse in the same block
+        write_pulses_until(reveal_round);
+        assert_ok!(SubtensorModule::add_stake(
+            RuntimeOrigin::signed(front_runner),
+            hotkey,
+            netuid,
+            amount
+        ));
+
+        let sealed_index = event_index(|event| {
+            matches!(
+                event,
+                Event::SealedStakeCallDispatched(who, round, Ok(()))
+                    if *who == coldkey && *round == reveal_round
+            )
+        })
+        .unwrap();
+        let sealed_stake_index =
+            event_index(|event| matches!(event, Event::StakeAdded(who, ..) if *who == coldkey))
+                .unwrap();
+        let clear_stake_index = event_index(
+            |event| matches!(event, Event::StakeAdded(who, ..) if *who == front_runner),
+        )
+        .unwrap();
+        assert!(sealed_stake_index < sealed_index);
+        assert!(sealed_index < clear_stake_index);
+    });
+}
+
+#[test]
+fn test_sealed_stake_calls_all_opened_with_most_pulses_written() {
+    new_test_ext(1).execute_with(|| {
+        let coldkey = U256::from(1);
+        pallet_drand::BeaconConfig::<Test>::put(pallet_drand::Pallet::<Test>::mock_beacon_config());
+        store_pulses_until(1_000);
+
+        // Fill every round a single write stores with garbage
+        let rounds = pallet_drand::MAX_PULSES_TO_FETCH;
+        for round in 1_001..=1_000 + rounds {
+            for _ in 0..MAX_SEALED_CALLS_PER_ROUND {
+                assert_ok!(SubtensorModule::submit_sealed_stake_call(
+                    RuntimeOrigin::signed(coldkey),
+                    vec![0; 64].try_into().unwrap(),
+                    round
+                ));
+            }
+        }
+
+        write_pulses_until(1_000 + rounds);
+
+        // Every call is opened, none is dropped for lack of weight
+        let opened = System::events()
+            .into_iter()
+            .filter(|record| {
+                matches!(
+                    record.event,
+                    RuntimeEvent::SubtensorModule(Event::SealedStakeCallDispatched(
+                        _,
+                        _,
+                        Err(error)
+                    )) if error == Error::<Test>::SealedCallDecryptionFailed.into()
+                )
+            })
+            .count() as u64;
+        assert_eq!(opened, rounds * u64::from(MAX_SEALED_CALLS_PER_ROUND));
+
+        // Every call leaves with the pulse, none waits to be opened in the clear
+        for round in 1_001..=1_000 + rounds {
+            assert!(SealedStakeCalls::<Test>::get(round).is_empty());
+            assert!(!pallet_drand::PendingReveals::<Test>::contains_key(
+                None::<pallet_drand::types::BeaconId>,
+                round
+            ));
+        }
+    });
+}
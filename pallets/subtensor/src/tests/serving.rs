@@ -1453,3 +1453,509 @@ fn test_serve_axon_validate() {
         assert_ok!(result_ok);
     });
 }
+
+#[test]
+fn test_serve_axon_v2_ok() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey_account_id = U256::from(1);
+        let netuid = NetUid::from(1);
+        let version: u32 = 2;
+        let ip: u128 = 1676056785;
+        add_network(netuid, 13, 0);
+        register_ok_neuron(netuid, hotkey_account_id, U256::from(66), 0);
+
+        let endpoints = vec![
+            AxonEndpoint {
+                host: AxonHost::Dns(b"miner.example.com".to_vec().try_into().unwrap()),
+                port: 443,
+                transport: AxonTransport::Http2,
+            },
+            AxonEndpoint {
+                host: AxonHost::Ip { ip_type: 4, ip },
+                port: 8091,
+                transport: AxonTransport::Quic,
+            },
+        ];
+        assert_ok!(SubtensorModule::serve_axon_v2(
+            <<Test as Config>::RuntimeOrigin>::signed(hotkey_account_id),
+            netuid,
+            version,
+            endpoints.clone().try_into().unwrap(),
+            None
+        ));
+
+        let axon_info_v2 = SubtensorModule::get_axon_info_v2(netuid, &hotkey_account_id);
+        assert_eq!(axon_info_v2.version, version);
+        assert_eq!(axon_info_v2.endpoints.into_inner(), endpoints);
+
+        // The first ip endpoint is mirrored to the legacy axon info.
+        let axon_info = SubtensorModule::get_axon_info(netuid, &hotkey_account_id);
+        assert_eq!(axon_info.version, version);
+        assert_eq!(axon_info.ip, ip);
+        assert_eq!(axon_info.port, 8091);
+        assert_eq!(axon_info.ip_type, 4);
+        assert_eq!(axon_info.protocol, 1);
+
+        // Serving through the legacy call replaces the preferred ip endpoint only.
+        System::set_block_number(100);
+        assert_ok!(SubtensorModule::serve_axon(
+            <<Test as Config>::RuntimeOrigin>::signed(hotkey_account_id),
+            netuid,
+            version,
+            ip,
+            128,
+            4,
+            0,
+            0,
+            0
+        ));
+        let axon_info_v2 = SubtensorModule::get_axon_info_v2(netuid, &hotkey_account_id);
+        assert_eq!(
+            axon_info_v2.endpoints.into_inner(),
+            vec![
+                endpoints[0].clone(),
+                AxonEndpoint {
+                    host: AxonHost::Ip { ip_type: 4, ip },
+                    port: 128,
+                    transport: AxonTransport::Tcp,
+                }
+            ]
+        );
+
+        // Serving DNS endpoints only leaves the legacy axon info as is.
+        System::set_block_number(200);
+        assert_ok!(SubtensorModule::serve_axon_v2(
+            <<Test as Config>::RuntimeOrigin>::signed(hotkey_account_id),
+            netuid,
+            version,
+            vec![endpoints[0].clone()].try_into().unwrap(),
+            None
+        ));
+        let axon_info = SubtensorModule::get_axon_info(netuid, &hotkey_account_id);
+        assert_eq!(axon_info.block, 100);
+        assert_eq!(axon_info.port, 128);
+        assert_eq!(
+            SubtensorModule::get_axon_info_v2(netuid, &hotkey_account_id).block,
+            200
+        );
+    });
+}
+
+#[test]
+fn test_serve_axon_v2_dns_only_is_rate_limited() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey_account_id = U256::from(1);
+        let netuid = NetUid::from(1);
+        add_network(netuid, 13, 0);
+        register_ok_neuron(netuid, hotkey_account_id, U256::from(66), 0);
+        SubtensorModule::set_serving_rate_limit(netuid, 10);
+
+        System::set_block_number(100);
+        assert_ok!(SubtensorModule::serve_axon_v2(
+            <<Test as Config>::RuntimeOrigin>::signed(hotkey_account_id),
+            netuid,
+            2,
+            vec![AxonEndpoint {
+                host: AxonHost::Dns(b"miner.example.com".to_vec().try_into().unwrap()),
+                port: 443,
+                transport: AxonTransport::Http2,
+            }]
+            .try_into()
+            .unwrap(),
+            None
+        ));
+
+        // The legacy call counts the last serve of either version
+        assert_noop!(
+            SubtensorModule::serve_axon(
+                <<Test as Config>::RuntimeOrigin>::signed(hotkey_account_id),
+                netuid,
+                2,
+                1676056785,
+                128,
+                4,
+                0,
+                0,
+                0
+            ),
+            Error::<Test>::ServingRateLimitExceeded
+        );
+    });
+}
+
+#[test]
+fn test_serve_axon_v2_invalid_endpoints() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey_account_id = U256::from(1);
+        let netuid = NetUid::from(1);
+        add_network(netuid, 13, 0);
+        register_ok_neuron(netuid, hotkey_account_id, U256::from(66), 0);
+
+        let serve = |host: AxonHost, port: u16| {
+            SubtensorModule::serve_axon_v2(
+                <<Test as Config>::RuntimeOrigin>::signed(hotkey_account_id),
+                netuid,
+                2,
+                vec![AxonEndpoint {
+                    host,
+                    port,
+                    transport: AxonTransport::Tcp,
+                }]
+                .try_into()
+                .unwrap(),
+                None,
+            )
+        };
+
+        assert_noop!(
+            SubtensorModule::serve_axon_v2(
+                <<Test as Config>::RuntimeOrigin>::signed(hotkey_account_id),
+                netuid,
+                2,
+                Default::default(),
+                None
+            ),
+            Error::<Test>::NoAxonEndpoints
+        );
+        assert_noop!(
+            serve(AxonHost::Ip { ip_type: 5, ip: 1 }, 8091),
+            Error::<Test>::InvalidIpType
+        );
+        assert_noop!(
+            serve(AxonHost::Ip { ip_type: 4, ip: 0 }, 8091),
+            Error::<Test>::InvalidIpAddress
+        );
+        assert_noop!(
+            serve(
+                AxonHost::Dns(b"-bad.example.com".to_vec().try_into().unwrap()),
+                8091
+            ),
+            Error::<Test>::InvalidAxonHostname
+        );
+        assert_noop!(
+            serve(
+                AxonHost::Dns(b"miner.example.com".to_vec().try_into().unwrap()),
+                0
+            ),
+            Error::<Test>::InvalidPort
+        );
+        assert_noop!(
+            SubtensorModule::serve_axon_v2(
+                <<Test as Config>::RuntimeOrigin>::signed(U256::from(2)),
+                netuid,
+                2,
+                Default::default(),
+                None
+            ),
+            Error::<Test>::HotKeyNotRegisteredInNetwork
+        );
+    });
+}
+
+#[test]
+fn test_serving_is_valid_hostname() {
+    assert!(SubtensorModule::is_valid_hostname(b"localhost"));
+    assert!(SubtensorModule::is_valid_hostname(b"miner-1.example.com"));
+    assert!(SubtensorModule::is_valid_hostname(b"8.8.8.8"));
+    assert!(!SubtensorModule::is_valid_hostname(b""));
+    assert!(!SubtensorModule::is_valid_hostname(b"example..com"));
+    assert!(!SubtensorModule::is_valid_hostname(b"example.com."));
+    assert!(!SubtensorModule::is_valid_hostname(b"miner-.example.com"));
+    assert!(!SubtensorModule::is_valid_hostname(b"miner_1.example.com"));
+    assert!(!SubtensorModule::is_valid_hostname(
+        &[b'a'; 64]
+            .iter()
+            .chain(b".com")
+            .copied()
+            .collect::<Vec<u8>>()
+    ));
+}
+
+#[test]
+fn test_publish_certificate_rotation() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey_account_id = U256::from(1);
+        let netuid = NetUid::from(1);
+        add_network(netuid, 13, 0);
+        register_ok_neuron(netuid, hotkey_account_id, U256::from(66), 0);
+
+        let publish = |certificate: &[u8], not_after: Option<u64>| {
+            SubtensorModule::publish_certificate(
+                <<Test as Config>::RuntimeOrigin>::signed(hotkey_account_id),
+                netuid,
+                certificate.to_vec(),
+                not_after,
+            )
+        };
+        let fingerprints = || {
+            PublishedNeuronCertificates::<Test>::get(netuid, hotkey_account_id)
+                .iter()
+                .map(|published| published.fingerprint)
+                .collect::<Vec<_>>()
+        };
+        let fingerprint = |certificate: &[u8]| {
+            SubtensorModule::get_certificate_fingerprint(
+                &NeuronCertificateOf::try_from(certificate.to_vec()).unwrap(),
+            )
+        };
+
+        assert_ok!(publish(b"\x01OLD_KEY", None));
+        assert_ok!(publish(b"\x01NEW_KEY", Some(1_000)));
+        assert_eq!(
+            fingerprints(),
+            vec![fingerprint(b"\x01OLD_KEY"), fingerprint(b"\x01NEW_KEY")]
+        );
+
+        // Republishing refreshes the expiry without dropping the rotation certificate.
+        assert_ok!(publish(b"\x01OLD_KEY", Some(2_000)));
+        assert_eq!(
+            fingerprints(),
+            vec![fingerprint(b"\x01NEW_KEY"), fingerprint(b"\x01OLD_KEY")]
+        );
+
+        // A third certificate drops the oldest one.
+        assert_ok!(publish(b"\x01NEXT_KEY", None));
+        assert_eq!(
+            fingerprints(),
+            vec![fingerprint(b"\x01OLD_KEY"), fingerprint(b"\x01NEXT_KEY")]
+        );
+
+        // The newest certificate is mirrored for single certificate clients.
+        assert_eq!(
+            NeuronCertificates::<Test>::get(netuid, hotkey_account_id)
+                .unwrap()
+                .public_key
+                .into_inner(),
+            b"NEXT_KEY".to_vec()
+        );
+
+        assert_noop!(publish(b"", None), Error::<Test>::InvalidCertificate);
+        assert_noop!(
+            publish(b"\x01KEY", Some(1)),
+            Error::<Test>::InvalidCertificateExpiry
+        );
+        assert_noop!(
+            SubtensorModule::publish_certificate(
+                <<Test as Config>::RuntimeOrigin>::signed(U256::from(2)),
+                netuid,
+                b"\x01KEY".to_vec(),
+                None
+            ),
+            Error::<Test>::HotKeyNotRegisteredInSubNet
+        );
+    });
+}
+
+#[test]
+fn test_revoke_certificate() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey_account_id = U256::from(1);
+        let netuid = NetUid::from(1);
+        add_network(netuid, 13, 0);
+        register_ok_neuron(netuid, hotkey_account_id, U256::from(66), 0);
+
+        for certificate in [b"\x01OLD_KEY".to_vec(), b"\x01NEW_KEY".to_vec()] {
+            assert_ok!(SubtensorModule::publish_certificate(
+                <<Test as Config>::RuntimeOrigin>::signed(hotkey_account_id),
+                netuid,
+                certificate,
+                None
+            ));
+        }
+        let [old, new] = [b"\x01OLD_KEY", b"\x01NEW_KEY"].map(|certificate| {
+            SubtensorModule::get_certificate_fingerprint(
+                &NeuronCertificateOf::try_from(certificate.to_vec()).unwrap(),
+            )
+        });
+
+        // Revoking the newest certificate mirrors the remaining one.
+        assert_ok!(SubtensorModule::revoke_certificate(
+            <<Test as Config>::RuntimeOrigin>::signed(hotkey_account_id),
+            netuid,
+            new
+        ));
+        assert_eq!(
+            NeuronCertificates::<Test>::get(netuid, hotkey_account_id)
+                .unwrap()
+                .public_key
+                .into_inner(),
+            b"OLD_KEY".to_vec()
+        );
+        assert_noop!(
+            SubtensorModule::revoke_certificate(
+                <<Test as Config>::RuntimeOrigin>::signed(hotkey_account_id),
+                netuid,
+                new
+            ),
+            Error::<Test>::CertificateNotFound
+        );
+
+        assert_ok!(SubtensorModule::revoke_certificate(
+            <<Test as Config>::RuntimeOrigin>::signed(hotkey_account_id),
+            netuid,
+            old
+        ));
+        assert!(NeuronCertificates::<Test>::get(netuid, hotkey_account_id).is_none());
+        assert!(PublishedNeuronCertificates::<Test>::get(netuid, hotkey_account_id).is_empty());
+    });
+}
+
+#[test]
+fn test_expired_certificates_are_removed() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey_account_id = U256::from(1);
+        let netuid = NetUid::from(1);
+        add_network(netuid, 13, 0);
+        register_ok_neuron(netuid, hotkey_account_id, U256::from(66), 0);
+
+        assert_ok!(SubtensorModule::publish_certificate(
+            <<Test as Config>::RuntimeOrigin>::signed(hotkey_account_id),
+            netuid,
+            b"\x01OLD_KEY".to_vec(),
+            None
+        ));
+        assert_ok!(SubtensorModule::publish_certificate(
+            <<Test as Config>::RuntimeOrigin>::signed(hotkey_account_id),
+            netuid,
+            b"\x01NEW_KEY".to_vec(),
+            Some(10)
+        ));
+        // Republishing schedules the expiry once
+        assert_ok!(SubtensorModule::publish_certificate(
+            <<Test as Config>::RuntimeOrigin>::signed(hotkey_account_id),
+            netuid,
+            b"\x01NEW_KEY".to_vec(),
+            Some(10)
+        ));
+        assert_eq!(
+            NeuronCertificateExpiries::<Test>::iter_prefix(10).count(),
+            1
+        );
+        assert_eq!(
+            SubtensorModule::get_neuron_certificates(netuid)
+                .first()
+                .unwrap()
+                .certificates
+                .len(),
+            2
+        );
+
+        // Expired certificates are no longer served, even before being removed.
+        System::set_block_number(10);
+        let certificates = SubtensorModule::get_neuron_certificates(netuid);
+        assert_eq!(certificates.len(), 1);
+        assert_eq!(
+            certificates
+                .first()
+                .unwrap()
+                .certificates
+                .first()
+                .unwrap()
+                .certificate
+                .public_key
+                .clone()
+                .into_inner(),
+            b"OLD_KEY".to_vec()
+        );
+
+        SubtensorModule::remove_expired_neuron_certificates(10);
+        assert_eq!(
+            PublishedNeuronCertificates::<Test>::get(netuid, hotkey_account_id).len(),
+            1
+        );
+        assert_eq!(
+            NeuronCertificates::<Test>::get(netuid, hotkey_account_id)
+                .unwrap()
+                .public_key
+                .into_inner(),
+            b"OLD_KEY".to_vec()
+        );
+        assert!(
+            NeuronCertificateExpiries::<Test>::iter_prefix(10)
+                .next()
+                .is_none()
+        );
+    });
+}
+
+#[test]
+fn test_certificate_expiries_follow_the_certificates() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey_account_id = U256::from(1);
+        let new_hotkey_account_id = U256::from(2);
+        let netuid = NetUid::from(1);
+        add_network(netuid, 13, 0);
+        register_ok_neuron(netuid, hotkey_account_id, U256::from(66), 0);
+
+        let publish = |not_after: u64| {
+            SubtensorModule::publish_certificate(
+                <<Test as Config>::RuntimeOrigin>::signed(hotkey_account_id),
+                netuid,
+                b"\x01KEY".to_vec(),
+                Some(not_after),
+            )
+        };
+        let scheduled = |hotkey: U256| {
+            NeuronCertificateExpiries::<Test>::iter()
+                .filter(|(_, (_, scheduled_hotkey), ())| *scheduled_hotkey == hotkey)
+                .map(|(not_after, _, ())| not_after)
+                .collect::<Vec<_>>()
+        };
+
+        // The expiry is bounded
+        let current_block = SubtensorModule::get_current_block_as_u64();
+        assert_noop!(
+            publish(current_block + MAX_CERTIFICATE_VALIDITY + 1),
+            Error::<Test>::InvalidCertificateExpiry
+        );
+
+        // Republishing moves the expiry
+        assert_ok!(publish(10));
+        assert_ok!(publish(20));
+        assert_eq!(scheduled(hotkey_account_id), vec![20]);
+
+        // Swapping the hotkey moves the expiry too
+        SubtensorModule::swap_neuron_certificates(
+            netuid,
+            &hotkey_account_id,
+            &new_hotkey_account_id,
+        );
+        assert!(scheduled(hotkey_account_id).is_empty());
+        assert_eq!(scheduled(new_hotkey_account_id), vec![20]);
+
+        // Clearing the certificates clears their expiry
+        SubtensorModule::clear_neuron_certificates(netuid, &new_hotkey_account_id);
+        assert!(NeuronCertificateExpiries::<Test>::iter().next().is_none());
+    });
+}
+
+#[test]
+fn test_certificate_expiries_removal_is_bounded() {
+    new_test_ext(1).execute_with(|| {
+        let expiring = MAX_CERTIFICATE_EXPIRIES_PER_BLOCK + 10;
+        for i in 0..expiring {
+            NeuronCertificateExpiries::<Test>::insert(
+                10,
+                (NetUid::from(1), U256::from(1_000 + i)),
+                (),
+            );
+        }
+
+        // The first block removes as many expiries as it may and keeps its place
+        SubtensorModule::remove_expired_neuron_certificates(10);
+        assert_eq!(
+            NeuronCertificateExpiries::<Test>::iter_prefix(10).count(),
+            10
+        );
+        assert_eq!(NeuronCertificateExpiryCursor::<Test>::get(), Some(10));
+
+        // The next block picks up the rest
+        SubtensorModule::remove_expired_neuron_certificates(11);
+        assert!(
+            NeuronCertificateExpiries::<Test>::iter_prefix(10)
+                .next()
+                .is_none()
+        );
+        assert_eq!(NeuronCertificateExpiryCursor::<Test>::get(), Some(12));
+    });
+}
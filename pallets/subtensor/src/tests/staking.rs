@@ -5661,3 +5661,86 @@ fn test_stake_rate_limits() {
         )));
     });
 }
+
+/// cargo test --package pallet-subtensor --lib -- tests::staking::test_stake_flows_emit_swapped_event --exact --show-output
+#[test]
+fn test_stake_flows_emit_swapped_event() {
+    new_test_ext(1).execute_with(|| {
+        let subnet_owner_coldkey = U256::from(1);
+        let subnet_owner_hotkey = U256::from(2);
+        let coldkey = U256::from(3);
+        let hotkey = U256::from(4);
+        let amount = DefaultMinStake::<Test>::get() * 10;
+
+        let netuid = add_dynamic_network(&subnet_owner_hotkey, &subnet_owner_coldkey);
+        mock::setup_reserves(netuid, 100_000_000_000, 400_000_000_000);
+        register_ok_neuron(netuid, hotkey, coldkey, 0);
+        SubtensorModule::add_balance_to_coldkey_account(&coldkey, amount * 10);
+
+        let swapped_events = || {
+            System::events()
+                .into_iter()
+                .filter_map(|record| match record.event {
+                    RuntimeEvent::Swap(pallet_subtensor_swap::Event::Swapped {
+                        netuid: event_netuid,
+                        order_type,
+                        amount_paid_out,
+                        lp_fee,
+                        protocol_fee,
+                        ..
+                    }) if event_netuid == netuid => {
+                        Some((order_type, amount_paid_out, lp_fee + protocol_fee))
+                    }
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // Staking buys alpha through the pool
+        System::reset_events();
+        assert_ok!(SubtensorModule::add_stake(
+            RuntimeOrigin::signed(coldkey),
+            hotkey,
+            netuid,
+            amount,
+        ));
+        let (alpha, stake_added_fee) = System::events()
+            .into_iter()
+            .find_map(|record| match record.event {
+                RuntimeEvent::SubtensorModule(Event::StakeAdded(_, _, _, alpha, _, fee)) => {
+                    Some((alpha, fee))
+                }
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(
+            swapped_events(),
+            vec![(OrderType::Buy, alpha, stake_added_fee)]
+        );
+
+        // Unstaking sells it back
+        remove_stake_rate_limit_for_tests(&hotkey, &coldkey, netuid);
+        System::reset_events();
+        let stake =
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &coldkey, netuid);
+        assert_ok!(SubtensorModule::remove_stake(
+            RuntimeOrigin::signed(coldkey),
+            hotkey,
+            netuid,
+            stake,
+        ));
+        let (tao, stake_removed_fee) = System::events()
+            .into_iter()
+            .find_map(|record| match record.event {
+                RuntimeEvent::SubtensorModule(Event::StakeRemoved(_, _, tao, _, _, fee)) => {
+                    Some((tao, fee))
+                }
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(
+            swapped_events(),
+            vec![(OrderType::Sell, tao, stake_removed_fee)]
+        );
+    });
+}
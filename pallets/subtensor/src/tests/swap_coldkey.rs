@@ -2,6 +2,7 @@
 
 use approx::assert_abs_diff_eq;
 use codec::Encode;
+use core::marker::PhantomData;
 use frame_support::error::BadOrigin;
 use frame_support::traits::OnInitialize;
 use frame_support::traits::schedule::DispatchTime;
@@ -9,9 +10,13 @@ use frame_support::traits::schedule::v3::Named as ScheduleNamed;
 use frame_support::weights::Weight;
 use frame_support::{assert_err, assert_noop, assert_ok};
 use frame_system::{Config, RawOrigin};
+use pallet_subtensor_swap::{
+    position::{Position, PositionId},
+    tick::TickIndex,
+};
 use sp_core::{Get, H256, U256};
 use sp_runtime::{DispatchError, traits::TxBaseImplication};
-use substrate_fixed::types::U96F32;
+use substrate_fixed::types::{I64F64, U96F32};
 use subtensor_runtime_common::SubnetInfo;
 use subtensor_swap_interface::{OrderType, SwapHandler};
 
@@ -192,6 +197,54 @@ fn test_transfer_remaining_balance() {
     });
 }
 
+// SKIP_WASM_BUILD=1 RUST_LOG=debug cargo test --package pallet-subtensor --lib -- tests::swap_coldkey::test_swap_liquidity_positions --exact --show-output
+#[test]
+fn test_swap_liquidity_positions() {
+    new_test_ext(1).execute_with(|| {
+        let old_coldkey = U256::from(1);
+        let new_coldkey = U256::from(2);
+        let netuid = NetUid::from(1u16);
+        let position_id = PositionId::from(1u128);
+
+        pallet_subtensor_swap::SwapV3Initialized::<Test>::insert(netuid, true);
+        pallet_subtensor_swap::Positions::<Test>::insert(
+            (netuid, old_coldkey, position_id),
+            Position {
+                id: position_id,
+                netuid,
+                tick_low: TickIndex::new(-10000).unwrap(),
+                tick_high: TickIndex::new(10000).unwrap(),
+                liquidity: 1_000_000,
+                fees_tao: I64F64::from_num(0),
+                fees_alpha: I64F64::from_num(0),
+                _phantom: PhantomData,
+            },
+        );
+
+        let mut weight = Weight::zero();
+        assert_ok!(SubtensorModule::perform_swap_coldkey(
+            &old_coldkey,
+            &new_coldkey,
+            &mut weight
+        ));
+
+        assert!(!pallet_subtensor_swap::Positions::<Test>::contains_key((
+            netuid,
+            old_coldkey,
+            position_id
+        )));
+        assert_eq!(
+            pallet_subtensor_swap::Positions::<Test>::get((netuid, new_coldkey, position_id))
+                .map(|position| position.liquidity),
+            Some(1_000_000)
+        );
+        assert_eq!(
+            pallet_subtensor_swap::PositionOwners::<Test>::get(position_id),
+            Some((netuid, new_coldkey))
+        );
+    });
+}
+
 // SKIP_WASM_BUILD=1 RUST_LOG=debug cargo test --package pallet-subtensor --lib -- tests::swap_coldkey::test_swap_with_no_stake --exact --show-output
 #[test]
 fn test_swap_with_no_stake() {
@@ -2,9 +2,12 @@
 
 use super::mock::*;
 use crate::*;
+use frame_support::sp_runtime::DispatchError;
+use frame_support::weights::Weight;
 use frame_support::{assert_err, assert_ok};
-use frame_system::Config;
-use sp_core::{H160, U256};
+use frame_system::{Config, RawOrigin};
+use sp_core::{H160, H256, U256};
+use sp_std::collections::vec_deque::VecDeque;
 
 /********************************************
     tests for uids.rs file
@@ -559,3 +562,204 @@ fn test_get_neuron_to_prune_owner_pruned_if_not_in_sn_owner_hotkey_map() {
         );
     });
 }
+
+/********************************************
+    tests resize::do_resize_subnet()
+*********************************************/
+
+/// Steps the resize of the subnet until it is done, returning the blocks it took.
+fn run_subnet_resize(netuid: NetUid) -> u32 {
+    let mut blocks = 0;
+    while SubtensorModule::is_subnet_resizing(netuid) {
+        SubtensorModule::step_subnet_resizes();
+        blocks += 1;
+        assert!(blocks < 100, "the resize does not finish");
+    }
+    blocks
+}
+
+#[test]
+fn test_resize_subnet_prunes_and_compacts() {
+    new_test_ext(1).execute_with(|| {
+        let netuid = NetUid::from(1);
+        let coldkey = U256::from(1234);
+        let evm_address = H160::from_slice(&[1_u8; 20]);
+        add_network(netuid, 13, 0);
+        SubtensorModule::set_immunity_period(netuid, 0);
+        for hotkey in 0..6 {
+            SubtensorModule::append_neuron(netuid, &U256::from(hotkey), 0);
+        }
+        // Uid 1 is free, uids 2 and 3 rank lowest
+        SubtensorModule::vacate_neuron(netuid, 1);
+        for (uid, pruning_score) in [(0, 100), (2, 5), (3, 50), (4, 60), (5, 70)] {
+            SubtensorModule::set_pruning_score_for_uid(netuid, uid, pruning_score);
+        }
+        Weights::<Test>::insert(netuid, 5, vec![(0, 1), (2, 2), (3, 3), (5, 4)]);
+        Bonds::<Test>::insert(netuid, 4, vec![(3, 6), (5, 7)]);
+        AssociatedEvmAddress::<Test>::insert(netuid, 5, (evm_address, 1));
+        WeightCommits::<Test>::insert(
+            netuid,
+            U256::from(0),
+            VecDeque::from([(H256::zero(), 0, 1, 2)]),
+        );
+        CRV3WeightCommits::<Test>::insert(
+            netuid,
+            7,
+            VecDeque::from([(U256::from(0), vec![0; 5].try_into().unwrap(), 1_000, None)]),
+        );
+
+        assert_err!(
+            SubtensorModule::resize_subnet(
+                <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+                netuid,
+                3
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_err!(
+            SubtensorModule::resize_subnet(RawOrigin::Root.into(), netuid, 0),
+            Error::<Test>::InvalidSubnetResize
+        );
+        assert_ok!(SubtensorModule::resize_subnet(
+            RawOrigin::Root.into(),
+            netuid,
+            3
+        ));
+        assert!(SubtensorModule::is_subnet_resizing(netuid));
+        assert_eq!(
+            SubnetResizes::<Test>::get(netuid).map(|resize| resize.prune),
+            Some(vec![2, 3])
+        );
+
+        // The uids are not settled until the resize is done
+        SubtensorModule::add_balance_to_coldkey_account(&coldkey, 10000);
+        assert_err!(
+            SubtensorModule::burned_register(
+                <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+                netuid,
+                U256::from(10)
+            ),
+            Error::<Test>::SubnetResizeInProgress
+        );
+        assert_err!(
+            SubtensorModule::set_weights(
+                <<Test as Config>::RuntimeOrigin>::signed(U256::from(0)),
+                netuid,
+                vec![0],
+                vec![1],
+                0
+            ),
+            Error::<Test>::SubnetResizeInProgress
+        );
+
+        run_subnet_resize(netuid);
+
+        assert!(!SubtensorModule::is_subnet_resizing(netuid));
+        assert!(!WeightCommits::<Test>::contains_key(netuid, U256::from(0)));
+        assert!(!CRV3WeightCommits::<Test>::contains_key(netuid, 7));
+        assert_eq!(
+            CRV3RevealStatuses::<Test>::get((netuid, 7, U256::from(0))),
+            vec![(1_000, CRV3RevealStatus::Dropped)]
+        );
+        assert_eq!(SubtensorModule::get_subnetwork_n(netuid), 3);
+        assert_eq!(SubtensorModule::get_max_allowed_uids(netuid), 3);
+        for (uid, hotkey) in [(0, 0), (1, 4), (2, 5)] {
+            assert_eq!(Keys::<Test>::get(netuid, uid), U256::from(hotkey));
+            assert_eq!(Uids::<Test>::get(netuid, U256::from(hotkey)), Some(uid));
+        }
+        for hotkey in [1, 2, 3] {
+            assert!(!SubtensorModule::is_hotkey_registered_on_network(
+                netuid,
+                &U256::from(hotkey)
+            ));
+        }
+        assert!(!Keys::<Test>::contains_key(netuid, 3));
+        assert!(!Keys::<Test>::contains_key(netuid, 5));
+        assert_eq!(Weights::<Test>::get(netuid, 2), vec![(0, 1), (2, 4)]);
+        assert!(!Weights::<Test>::contains_key(netuid, 5));
+        assert_eq!(Bonds::<Test>::get(netuid, 1), vec![(2, 7)]);
+        assert_eq!(
+            AssociatedEvmAddress::<Test>::get(netuid, 2),
+            Some((evm_address, 1))
+        );
+        assert_eq!(PruningScores::<Test>::get(netuid), vec![100, 60, 70]);
+        assert_eq!(Active::<Test>::get(netuid).len(), 3);
+        assert!(VacantUids::<Test>::get(netuid).is_empty());
+        assert!(System::events().iter().any(|record| {
+            record.event
+                == RuntimeEvent::SubtensorModule(Event::SubnetResized {
+                    netuid,
+                    max_allowed_uids: 3,
+                    subnetwork_n: 3,
+                })
+        }));
+    });
+}
+
+#[test]
+fn test_resize_subnet_runs_over_several_blocks() {
+    new_test_ext(1).execute_with(|| {
+        let netuid = NetUid::from(1);
+        add_network(netuid, 13, 0);
+        SubtensorModule::set_immunity_period(netuid, 0);
+        SubtensorModule::set_max_allowed_uids(netuid, 100);
+        for hotkey in 0..100 {
+            SubtensorModule::append_neuron(netuid, &U256::from(hotkey), 0);
+            SubtensorModule::set_pruning_score_for_uid(netuid, hotkey, hotkey);
+        }
+
+        assert_ok!(SubtensorModule::resize_subnet(
+            RawOrigin::Root.into(),
+            netuid,
+            50
+        ));
+
+        // 50 uids are pruned and 14 checked for vacancy, then 64 checked, then the last 22
+        // checked and 42 moved, then the last ones moved
+        for stage in [
+            SubnetResizeStage::CollectVacant { next_uid: 14 },
+            SubnetResizeStage::CollectVacant { next_uid: 78 },
+            SubnetResizeStage::Compact { next_uid: 42 },
+            SubnetResizeStage::ShrinkVectors { next_vector: 0 },
+        ] {
+            assert!(SubtensorModule::step_subnet_resizes() != Weight::zero());
+            assert_eq!(
+                SubnetResizes::<Test>::get(netuid).map(|resize| resize.stage),
+                Some(stage)
+            );
+        }
+        // One vector per block, then the pending commits
+        assert_eq!(run_subnet_resize(netuid), 16);
+
+        // The highest pruning scores are kept, in their uid order
+        assert_eq!(SubtensorModule::get_subnetwork_n(netuid), 50);
+        for uid in 0..50 {
+            assert_eq!(Keys::<Test>::get(netuid, uid), U256::from(uid + 50));
+        }
+        assert!(!Keys::<Test>::contains_key(netuid, 50));
+    });
+}
+
+#[test]
+fn test_resize_subnet_grows_in_place() {
+    new_test_ext(1).execute_with(|| {
+        let netuid = NetUid::from(1);
+        add_network(netuid, 13, 0);
+        SubtensorModule::append_neuron(netuid, &U256::from(0), 0);
+        SubtensorModule::append_neuron(netuid, &U256::from(1), 0);
+
+        assert_err!(
+            SubtensorModule::resize_subnet(RawOrigin::Root.into(), NetUid::ROOT, 10),
+            Error::<Test>::InvalidSubnetResize
+        );
+        assert_ok!(SubtensorModule::resize_subnet(
+            RawOrigin::Root.into(),
+            netuid,
+            1024
+        ));
+
+        assert!(!SubtensorModule::is_subnet_resizing(netuid));
+        assert_eq!(SubtensorModule::get_max_allowed_uids(netuid), 1024);
+        assert_eq!(SubtensorModule::get_subnetwork_n(netuid), 2);
+    });
+}
@@ -1,12 +1,14 @@
 #![allow(clippy::indexing_slicing, clippy::unwrap_used)]
 
 use ark_serialize::CanonicalDeserialize;
+use codec::Compact;
 use frame_support::{
     assert_err, assert_ok,
     dispatch::{DispatchClass, DispatchResult, GetDispatchInfo, Pays},
+    weights::Weight,
 };
 use frame_system::RawOrigin;
-use pallet_drand::types::Pulse;
+use pallet_drand::types::{BeaconId, Pulse};
 use rand_chacha::{ChaCha20Rng, rand_core::SeedableRng};
 use scale_info::prelude::collections::HashMap;
 use sha2::Digest;
@@ -5044,6 +5046,7 @@ fn test_do_commit_crv3_weights_success() {
         assert_eq!(commits[0].0, hotkey);
         assert_eq!(commits[0].1, commit_data);
         assert_eq!(commits[0].2, reveal_round);
+        assert_eq!(commits[0].3, None);
     });
 }
 
@@ -5918,7 +5921,7 @@ fn test_reveal_crv3_commits_removes_past_epoch_commits() {
                 netuid,
                 *epoch,
                 |commits| -> DispatchResult {
-                    commits.push_back((hotkey, bounded_commit_data, reveal_round));
+                    commits.push_back((hotkey, bounded_commit_data, reveal_round, None));
                     Ok(())
                 }
             ));
@@ -5942,6 +5945,10 @@ fn test_reveal_crv3_commits_removes_past_epoch_commits() {
                 "Expected commits for past epoch {} to be removed",
                 epoch
             );
+            assert_eq!(
+                CRV3RevealStatuses::<Test>::get((netuid, *epoch, hotkey)),
+                vec![(reveal_round, CRV3RevealStatus::Expired)]
+            );
         }
 
         let current_epoch_commits = CRV3WeightCommits::<Test>::get(netuid, current_epoch);
@@ -6328,3 +6335,780 @@ fn test_reveal_crv3_commits_max_neurons() {
         );
     });
 }
+
+#[test]
+fn test_crv3_pending_commit_keeps_reveal_pulse() {
+    new_test_ext(1).execute_with(|| {
+        let netuid = NetUid::from(1);
+        let hotkey = U256::from(1);
+        let reveal_round: u64 = 200;
+
+        add_network(netuid, 5, 0);
+        register_ok_neuron(netuid, hotkey, U256::from(2), 100_000);
+        SubtensorModule::set_weights_set_rate_limit(netuid, 0);
+        SubtensorModule::set_commit_reveal_weights_enabled(netuid, true);
+        SubtensorModule::set_reveal_period(netuid, 1);
+
+        // The pulse of the round is stored, anyone could read the commit
+        pallet_drand::LastStoredRound::<Test>::put(reveal_round);
+        assert_err!(
+            SubtensorModule::do_commit_crv3_weights(
+                RuntimeOrigin::signed(hotkey),
+                netuid,
+                vec![0; 5]
+                    .try_into()
+                    .expect("Failed to convert commit data"),
+                reveal_round
+            ),
+            Error::<Test>::CommitRevealRoundNotInFuture
+        );
+
+        pallet_drand::LastStoredRound::<Test>::put(reveal_round - 1);
+        assert_ok!(SubtensorModule::do_commit_crv3_weights(
+            RuntimeOrigin::signed(hotkey),
+            netuid,
+            vec![0; 5]
+                .try_into()
+                .expect("Failed to convert commit data"),
+            reveal_round
+        ));
+        assert_eq!(
+            pallet_drand::PendingReveals::<Test>::get(None::<BeaconId>, reveal_round),
+            1
+        );
+
+        for round in 1..=1_500 {
+            pallet_drand::Pulses::<Test>::insert(round, Pulse::default());
+        }
+        pallet_drand::OldestStoredRound::<Test>::put(1);
+        pallet_drand::LastStoredRound::<Test>::put(1_500);
+
+        // The mock keeps the last 1_000 rounds, but the pending commit reveals with round 200
+        pallet_drand::Pallet::<Test>::prune_pulses(Weight::MAX);
+        assert_eq!(pallet_drand::OldestStoredRound::<Test>::get(), 500);
+        assert!(!pallet_drand::Pulses::<Test>::contains_key(
+            reveal_round - 1
+        ));
+        assert!(pallet_drand::Pulses::<Test>::contains_key(reveal_round));
+        assert!(!pallet_drand::Pulses::<Test>::contains_key(
+            reveal_round + 1
+        ));
+
+        // Once the commit is revealed, the pulse is pruned with it
+        step_epochs(2, netuid);
+        assert!(!pallet_drand::PendingReveals::<Test>::contains_key(
+            None::<BeaconId>,
+            reveal_round
+        ));
+        assert!(!pallet_drand::Pulses::<Test>::contains_key(reveal_round));
+    });
+}
+
+#[test]
+fn test_reveal_crv3_commits_with_mock_beacon() {
+    new_test_ext(100).execute_with(|| {
+        use ark_serialize::CanonicalSerialize;
+
+        let netuid = NetUid::from(1);
+        let hotkey1: AccountId = U256::from(1);
+        let hotkey2: AccountId = U256::from(2);
+        let reveal_round: u64 = 4321;
+
+        add_network(netuid, 5, 0);
+        register_ok_neuron(netuid, hotkey1, U256::from(3), 100_000);
+        register_ok_neuron(netuid, hotkey2, U256::from(4), 100_000);
+        SubtensorModule::set_stake_threshold(0);
+        SubtensorModule::set_weights_set_rate_limit(netuid, 0);
+        SubtensorModule::set_commit_reveal_weights_enabled(netuid, true);
+        SubtensorModule::set_reveal_period(netuid, 3);
+
+        let neuron_uid1 = SubtensorModule::get_uid_for_net_and_hotkey(netuid, &hotkey1)
+            .expect("Failed to get neuron UID for hotkey1");
+        let neuron_uid2 = SubtensorModule::get_uid_for_net_and_hotkey(netuid, &hotkey2)
+            .expect("Failed to get neuron UID for hotkey2");
+        SubtensorModule::set_validator_permit_for_uid(netuid, neuron_uid1, true);
+        SubtensorModule::set_validator_permit_for_uid(netuid, neuron_uid2, true);
+        SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey1,
+            &(U256::from(3)),
+            netuid,
+            1,
+        );
+
+        let payload = WeightsTlockPayload {
+            values: vec![10, 20],
+            uids: vec![neuron_uid1, neuron_uid2],
+            version_key: SubtensorModule::get_weights_version_key(netuid),
+        };
+
+        // Encrypt to the mock beacon instead of quicknet
+        let pub_key = <TinyBLS381 as EngineBLS>::PublicKeyGroup::deserialize_compressed(
+            &pallet_drand::mock_beacon::public_key()[..],
+        )
+        .expect("Failed to deserialize public key");
+        let message = {
+            let mut hasher = sha2::Sha256::new();
+            hasher.update(reveal_round.to_be_bytes());
+            hasher.finalize().to_vec()
+        };
+        let ct = tle::<TinyBLS381, AESGCMStreamCipherProvider, ChaCha20Rng>(
+            pub_key,
+            [2; 32],
+            &payload.encode(),
+            Identity::new(b"", vec![message]),
+            ChaCha20Rng::seed_from_u64(0),
+        )
+        .expect("Encryption failed");
+        let mut commit_bytes = Vec::new();
+        ct.serialize_compressed(&mut commit_bytes)
+            .expect("Failed to serialize commit");
+
+        assert_ok!(SubtensorModule::do_commit_crv3_weights(
+            RuntimeOrigin::signed(hotkey1),
+            netuid,
+            commit_bytes
+                .try_into()
+                .expect("Failed to convert commit bytes into bounded vector"),
+            reveal_round
+        ));
+
+        pallet_drand::Pulses::<Test>::insert(
+            reveal_round,
+            pallet_drand::mock_beacon::pulse(reveal_round).expect("Mock beacon pulse"),
+        );
+
+        step_epochs(3, netuid);
+
+        let weights = SubtensorModule::get_weights_sparse(netuid)
+            .get(neuron_uid1 as usize)
+            .cloned()
+            .unwrap_or_default();
+        assert!(
+            !weights.is_empty(),
+            "Weights for neuron_uid1 are empty, expected weights to be set."
+        );
+    });
+}
+
+#[test]
+fn test_reveal_crv3_commits_for_registered_beacon() {
+    new_test_ext(100).execute_with(|| {
+        use ark_serialize::CanonicalSerialize;
+
+        let netuid = NetUid::from(1);
+        let hotkey1: AccountId = U256::from(1);
+        let hotkey2: AccountId = U256::from(2);
+        let reveal_round: u64 = 4321;
+
+        add_network(netuid, 5, 0);
+        register_ok_neuron(netuid, hotkey1, U256::from(3), 100_000);
+        register_ok_neuron(netuid, hotkey2, U256::from(4), 100_000);
+        SubtensorModule::set_stake_threshold(0);
+        SubtensorModule::set_weights_set_rate_limit(netuid, 0);
+        SubtensorModule::set_commit_reveal_weights_enabled(netuid, true);
+        SubtensorModule::set_reveal_period(netuid, 3);
+
+        let neuron_uid1 = SubtensorModule::get_uid_for_net_and_hotkey(netuid, &hotkey1)
+            .expect("Failed to get neuron UID for hotkey1");
+        let neuron_uid2 = SubtensorModule::get_uid_for_net_and_hotkey(netuid, &hotkey2)
+            .expect("Failed to get neuron UID for hotkey2");
+        SubtensorModule::set_validator_permit_for_uid(netuid, neuron_uid1, true);
+        SubtensorModule::set_validator_permit_for_uid(netuid, neuron_uid2, true);
+        SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey1,
+            &(U256::from(3)),
+            netuid,
+            1,
+        );
+
+        let payload = WeightsTlockPayload {
+            values: vec![10, 20],
+            uids: vec![neuron_uid1, neuron_uid2],
+            version_key: SubtensorModule::get_weights_version_key(netuid),
+        };
+
+        // Encrypt to the mock beacon, registered besides quicknet
+        let pub_key = <TinyBLS381 as EngineBLS>::PublicKeyGroup::deserialize_compressed(
+            &pallet_drand::mock_beacon::public_key()[..],
+        )
+        .expect("Failed to deserialize public key");
+        let message = {
+            let mut hasher = sha2::Sha256::new();
+            hasher.update(reveal_round.to_be_bytes());
+            hasher.finalize().to_vec()
+        };
+        let ct = tle::<TinyBLS381, AESGCMStreamCipherProvider, ChaCha20Rng>(
+            pub_key,
+            [2; 32],
+            &payload.encode(),
+            Identity::new(b"", vec![message]),
+            ChaCha20Rng::seed_from_u64(0),
+        )
+        .expect("Encryption failed");
+        let mut commit_bytes = Vec::new();
+        ct.serialize_compressed(&mut commit_bytes)
+            .expect("Failed to serialize commit");
+        let commit: BoundedVec<u8, ConstU32<MAX_CRV3_COMMIT_SIZE_BYTES>> = commit_bytes
+            .try_into()
+            .expect("Failed to convert commit bytes into bounded vector");
+
+        let beacon_config = pallet_drand::Pallet::<Test>::mock_beacon_config();
+        let beacon_id = beacon_config.metadata.beacon_id.clone();
+
+        // The beacon must be registered first
+        assert_err!(
+            SubtensorModule::commit_crv3_weights_for_beacon(
+                RuntimeOrigin::signed(hotkey1),
+                netuid,
+                commit.clone(),
+                reveal_round,
+                beacon_id.clone()
+            ),
+            Error::<Test>::BeaconNotRegistered
+        );
+
+        assert_ok!(pallet_drand::Pallet::<Test>::register_beacon(
+            RuntimeOrigin::root(),
+            beacon_config
+        ));
+
+        // The round must be past the last stored one of the beacon, not of the main beacon
+        pallet_drand::BeaconLastStoredRound::<Test>::insert(&beacon_id, reveal_round);
+        assert_err!(
+            SubtensorModule::commit_crv3_weights_for_beacon(
+                RuntimeOrigin::signed(hotkey1),
+                netuid,
+                commit.clone(),
+                reveal_round,
+                beacon_id.clone()
+            ),
+            Error::<Test>::CommitRevealRoundNotInFuture
+        );
+        pallet_drand::BeaconLastStoredRound::<Test>::insert(&beacon_id, reveal_round - 1);
+        pallet_drand::LastStoredRound::<Test>::put(reveal_round);
+
+        assert_ok!(SubtensorModule::commit_crv3_weights_for_beacon(
+            RuntimeOrigin::signed(hotkey1),
+            netuid,
+            commit,
+            reveal_round,
+            beacon_id.clone()
+        ));
+
+        // Only the pulse of the registered beacon is stored for the round
+        pallet_drand::BeaconPulses::<Test>::insert(
+            &beacon_id,
+            reveal_round,
+            pallet_drand::mock_beacon::pulse(reveal_round).expect("Mock beacon pulse"),
+        );
+        assert!(!pallet_drand::Pulses::<Test>::contains_key(reveal_round));
+
+        step_epochs(3, netuid);
+
+        let weights = SubtensorModule::get_weights_sparse(netuid)
+            .get(neuron_uid1 as usize)
+            .cloned()
+            .unwrap_or_default();
+        assert!(
+            !weights.is_empty(),
+            "Weights for neuron_uid1 are empty, expected weights to be set."
+        );
+    });
+}
+
+#[test]
+fn test_reveal_crv3_commits_records_statuses() {
+    new_test_ext(100).execute_with(|| {
+        use ark_serialize::CanonicalSerialize;
+
+        let netuid = NetUid::from(1);
+        let hotkey1: AccountId = U256::from(1);
+        let hotkey2: AccountId = U256::from(2);
+        let reveal_round: u64 = 4321;
+        let missing_round: u64 = 5000;
+
+        add_network(netuid, 5, 0);
+        register_ok_neuron(netuid, hotkey1, U256::from(3), 100_000);
+        register_ok_neuron(netuid, hotkey2, U256::from(4), 100_000);
+        SubtensorModule::set_stake_threshold(0);
+        SubtensorModule::set_weights_set_rate_limit(netuid, 0);
+        SubtensorModule::set_commit_reveal_weights_enabled(netuid, true);
+        SubtensorModule::set_reveal_period(netuid, 3);
+
+        let neuron_uid1 = SubtensorModule::get_uid_for_net_and_hotkey(netuid, &hotkey1)
+            .expect("Failed to get neuron UID for hotkey1");
+        let neuron_uid2 = SubtensorModule::get_uid_for_net_and_hotkey(netuid, &hotkey2)
+            .expect("Failed to get neuron UID for hotkey2");
+
+        // Timelock a payload to the mock beacon
+        let encrypt = |version_key: u64, round: u64| {
+            let payload = WeightsTlockPayload {
+                values: vec![10, 20],
+                uids: vec![neuron_uid1, neuron_uid2],
+                version_key,
+            };
+            let pub_key = <TinyBLS381 as EngineBLS>::PublicKeyGroup::deserialize_compressed(
+                &pallet_drand::mock_beacon::public_key()[..],
+            )
+            .expect("Failed to deserialize public key");
+            let message = {
+                let mut hasher = sha2::Sha256::new();
+                hasher.update(round.to_be_bytes());
+                hasher.finalize().to_vec()
+            };
+            let ct = tle::<TinyBLS381, AESGCMStreamCipherProvider, ChaCha20Rng>(
+                pub_key,
+                [2; 32],
+                &payload.encode(),
+                Identity::new(b"", vec![message]),
+                ChaCha20Rng::seed_from_u64(0),
+            )
+            .expect("Encryption failed");
+            let mut commit_bytes = Vec::new();
+            ct.serialize_compressed(&mut commit_bytes)
+                .expect("Failed to serialize commit");
+            BoundedVec::<u8, ConstU32<MAX_CRV3_COMMIT_SIZE_BYTES>>::try_from(commit_bytes)
+                .expect("Failed to convert commit bytes into bounded vector")
+        };
+
+        // The version key is older than the subnet's
+        SubtensorModule::set_weights_version_key(netuid, 5);
+        let version_key = SubtensorModule::get_weights_version_key(netuid);
+        assert_ok!(SubtensorModule::do_commit_crv3_weights(
+            RuntimeOrigin::signed(hotkey1),
+            netuid,
+            encrypt(version_key - 1, reveal_round),
+            reveal_round
+        ));
+        // No pulse is stored for the round
+        assert_ok!(SubtensorModule::do_commit_crv3_weights(
+            RuntimeOrigin::signed(hotkey2),
+            netuid,
+            encrypt(version_key, missing_round),
+            missing_round
+        ));
+        let (commit_epoch, _) = CRV3WeightCommits::<Test>::iter_prefix(netuid)
+            .next()
+            .expect("The commits should be stored");
+
+        pallet_drand::Pulses::<Test>::insert(
+            reveal_round,
+            pallet_drand::mock_beacon::pulse(reveal_round).expect("Mock beacon pulse"),
+        );
+
+        step_epochs(3, netuid);
+
+        assert_eq!(
+            CRV3RevealStatuses::<Test>::get((netuid, commit_epoch, hotkey1)),
+            vec![(reveal_round, CRV3RevealStatus::VersionKeyMismatch)]
+        );
+        assert_eq!(
+            CRV3RevealStatuses::<Test>::get((netuid, commit_epoch, hotkey2)),
+            vec![(missing_round, CRV3RevealStatus::MissingPulse)]
+        );
+
+        let statuses =
+            SubtensorModule::get_crv3_reveal_statuses(netuid, commit_epoch, Some(hotkey2));
+        assert_eq!(statuses.len(), 1);
+        assert_eq!(statuses[0].hotkey, hotkey2);
+        assert_eq!(statuses[0].status, CRV3RevealStatus::MissingPulse);
+        assert_eq!(
+            SubtensorModule::get_crv3_reveal_statuses(netuid, commit_epoch, None).len(),
+            2
+        );
+
+        // The statuses are pruned `CRV3_REVEAL_STATUS_EPOCHS` epochs after the reveal
+        step_epochs((CRV3_REVEAL_STATUS_EPOCHS + 2) as u16, netuid);
+        assert!(SubtensorModule::get_crv3_reveal_statuses(netuid, commit_epoch, None).is_empty());
+    });
+}
+
+#[test]
+fn test_crv3_commit_sdk_round_trip() {
+    new_test_ext(100).execute_with(|| {
+        let netuid = NetUid::from(1);
+        let hotkey1: AccountId = U256::from(1);
+        let hotkey2: AccountId = U256::from(2);
+
+        add_network(netuid, 5, 0);
+        register_ok_neuron(netuid, hotkey1, U256::from(3), 100_000);
+        register_ok_neuron(netuid, hotkey2, U256::from(4), 100_000);
+        SubtensorModule::set_stake_threshold(0);
+        SubtensorModule::set_weights_set_rate_limit(netuid, 0);
+        SubtensorModule::set_commit_reveal_weights_enabled(netuid, true);
+        SubtensorModule::set_reveal_period(netuid, 3);
+
+        let neuron_uid1 = SubtensorModule::get_uid_for_net_and_hotkey(netuid, &hotkey1)
+            .expect("Failed to get neuron UID for hotkey1");
+        let neuron_uid2 = SubtensorModule::get_uid_for_net_and_hotkey(netuid, &hotkey2)
+            .expect("Failed to get neuron UID for hotkey2");
+        SubtensorModule::set_validator_permit_for_uid(netuid, neuron_uid1, true);
+        SubtensorModule::set_validator_permit_for_uid(netuid, neuron_uid2, true);
+        SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey1,
+            &(U256::from(3)),
+            netuid,
+            1,
+        );
+
+        // Build the commit as a validator would, timelocked to the mock beacon
+        let subnet = crv3_commit::SubnetTiming {
+            netuid: u16::from(netuid),
+            tempo: SubtensorModule::get_tempo(netuid),
+            reveal_period_epochs: SubtensorModule::get_reveal_period(netuid),
+        };
+        let drand = crv3_commit::DrandTiming::QUICKNET;
+        let commit_block = SubtensorModule::get_current_block_as_u64();
+        let now = drand.genesis_time + 3_000;
+        let reveal_block = subnet
+            .reveal_block(commit_block)
+            .expect("Commits of the subnet are revealed");
+        let reveal_round =
+            crv3_commit::reveal_round(&subnet, &drand, commit_block, commit_block, now)
+                .expect("Failed to compute the reveal round");
+
+        let payload = WeightsTlockPayload {
+            values: vec![10, 20],
+            uids: vec![neuron_uid1, neuron_uid2],
+            version_key: SubtensorModule::get_weights_version_key(netuid),
+        };
+        let commit_bytes = crv3_commit::encrypt_commit(
+            &payload,
+            reveal_round,
+            &pallet_drand::mock_beacon::public_key()[..],
+            ChaCha20Rng::seed_from_u64(0),
+        )
+        .expect("Failed to encrypt the commit");
+        let commit: BoundedVec<u8, ConstU32<MAX_CRV3_COMMIT_SIZE_BYTES>> = commit_bytes
+            .clone()
+            .try_into()
+            .expect("Failed to convert commit bytes into bounded vector");
+
+        assert_ok!(SubtensorModule::commit_crv3_weights(
+            RuntimeOrigin::signed(hotkey1),
+            netuid,
+            commit,
+            reveal_round
+        ));
+
+        // The commit is stored as built, under the epoch the client computed
+        let commits = CRV3WeightCommits::<Test>::get(netuid, subnet.commit_epoch(commit_block));
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].1.to_vec(), commit_bytes);
+        assert_eq!(commits[0].2, reveal_round);
+
+        // The latest round published when the block is imported, which the offchain worker run
+        // after it fetches and the next block stores
+        let published_round = |block: u64| {
+            drand.round_at(now + (block - commit_block) * crv3_commit::BLOCK_TIME_SECONDS)
+        };
+        for block in commit_block + 1..reveal_block {
+            run_to_block(block);
+
+            // Nothing is revealed before the block the client computed
+            assert!(
+                SubtensorModule::get_weights_sparse(netuid)
+                    .get(neuron_uid1 as usize)
+                    .cloned()
+                    .unwrap_or_default()
+                    .is_empty()
+            );
+
+            // The extrinsics of the block store the pulse fetched after the previous one
+            let round = published_round(block - 1);
+            if round > pallet_drand::LastStoredRound::<Test>::get() {
+                pallet_drand::Pulses::<Test>::insert(
+                    round,
+                    pallet_drand::mock_beacon::pulse(round).expect("Mock beacon pulse"),
+                );
+                pallet_drand::LastStoredRound::<Test>::put(round);
+            }
+        }
+
+        // The round published at the block before the reveal is only stored after it
+        assert!(pallet_drand::Pulses::<Test>::contains_key(reveal_round));
+        assert!(!pallet_drand::Pulses::<Test>::contains_key(
+            published_round(reveal_block - 1)
+        ));
+
+        run_to_block(reveal_block);
+        let weights = SubtensorModule::get_weights_sparse(netuid)
+            .get(neuron_uid1 as usize)
+            .cloned()
+            .unwrap_or_default();
+        assert!(
+            !weights.is_empty(),
+            "Weights for neuron_uid1 are empty, expected weights to be set."
+        );
+        assert_eq!(
+            SubtensorModule::get_crv3_reveal_statuses(
+                netuid,
+                subnet.commit_epoch(commit_block),
+                Some(hotkey1)
+            )
+            .into_iter()
+            .map(|info| info.status)
+            .collect::<Vec<_>>(),
+            vec![CRV3RevealStatus::Revealed]
+        );
+    });
+}
+
+#[test]
+fn test_batch_commit_crv3_weights() {
+    new_test_ext(1).execute_with(|| {
+        let netuid1 = NetUid::from(1);
+        let netuid2 = NetUid::from(2);
+        let hotkey: AccountId = U256::from(1);
+        let reveal_round: u64 = 1000;
+
+        for netuid in [netuid1, netuid2] {
+            add_network(netuid, 5, 0);
+            register_ok_neuron(netuid, hotkey, U256::from(2), 100_000);
+            SubtensorModule::set_commit_reveal_weights_enabled(netuid, true);
+        }
+        SubtensorModule::set_weights_set_rate_limit(netuid1, 0);
+        SubtensorModule::set_weights_set_rate_limit(netuid2, 100);
+
+        let commit: Vec<u8> = vec![1; 100];
+        let oversized_commit: Vec<u8> = vec![1; MAX_CRV3_COMMIT_SIZE_BYTES as usize + 1];
+
+        assert_err!(
+            SubtensorModule::batch_commit_crv3_weights(
+                RuntimeOrigin::signed(hotkey),
+                vec![Compact(netuid1), Compact(netuid2)],
+                vec![commit.clone()],
+                vec![reveal_round, reveal_round],
+            ),
+            Error::<Test>::InputLengthsUnequal
+        );
+
+        assert_err!(
+            SubtensorModule::batch_commit_crv3_weights(
+                RuntimeOrigin::signed(hotkey),
+                vec![Compact(netuid1); MAX_CRV3_BATCH_COMMITS + 1],
+                vec![commit.clone(); MAX_CRV3_BATCH_COMMITS + 1],
+                vec![reveal_round; MAX_CRV3_BATCH_COMMITS + 1],
+            ),
+            Error::<Test>::TooManyBatchCommits
+        );
+
+        // Each commit is checked on its own, the failures do not fail the batch
+        assert_ok!(SubtensorModule::batch_commit_crv3_weights(
+            RuntimeOrigin::signed(hotkey),
+            vec![Compact(netuid1), Compact(netuid2), Compact(netuid1)],
+            vec![commit.clone(), commit.clone(), oversized_commit],
+            vec![reveal_round, reveal_round, reveal_round],
+        ));
+
+        let commits: Vec<_> = CRV3WeightCommits::<Test>::iter_prefix(netuid1)
+            .flat_map(|(_, commits)| commits)
+            .collect();
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].0, hotkey);
+        assert_eq!(commits[0].1.to_vec(), commit);
+        assert_eq!(commits[0].2, reveal_round);
+        assert!(
+            CRV3WeightCommits::<Test>::iter_prefix(netuid2)
+                .next()
+                .is_none()
+        );
+
+        let events: Vec<RuntimeEvent> = System::events()
+            .into_iter()
+            .map(|record| record.event)
+            .collect();
+        assert!(events.contains(&RuntimeEvent::SubtensorModule(
+            Event::BatchCRV3CommitItemFailed(
+                netuid2,
+                Error::<Test>::CommittingWeightsTooFast.into()
+            )
+        )));
+        assert!(events.contains(&RuntimeEvent::SubtensorModule(
+            Event::BatchCRV3CommitItemFailed(netuid1, Error::<Test>::CommitTooLarge.into())
+        )));
+        assert!(events.contains(&RuntimeEvent::SubtensorModule(
+            Event::BatchCompletedWithErrors()
+        )));
+        assert!(events.contains(&RuntimeEvent::SubtensorModule(
+            Event::BatchWeightsCompleted(vec![Compact(netuid1)], hotkey)
+        )));
+    });
+}
+
+#[test]
+fn test_batch_commit_crv3_weights_validate() {
+    new_test_ext(1).execute_with(|| {
+        let netuid1 = NetUid::from(1);
+        let netuid2 = NetUid::from(2);
+        let hotkey: AccountId = U256::from(1);
+
+        for netuid in [netuid1, netuid2] {
+            add_network(netuid, 5, 0);
+            register_ok_neuron(netuid, hotkey, U256::from(2), 100_000);
+        }
+
+        let call = RuntimeCall::SubtensorModule(SubtensorCall::batch_commit_crv3_weights {
+            netuids: vec![Compact(netuid1), Compact(netuid2)],
+            commits: vec![vec![1; 100], vec![1; 100]],
+            reveal_rounds: vec![1000, 1000],
+        });
+        let info = crate::DispatchInfoOf::<<Test as frame_system::Config>::RuntimeCall>::default();
+        let extension = crate::SubtensorTransactionExtension::<Test>::new();
+
+        // The hotkey has the minimum stake on only one of the subnets
+        SubtensorModule::set_stake_threshold(1);
+        SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey,
+            &U256::from(2),
+            netuid1,
+            1,
+        );
+        assert_eq!(
+            extension
+                .validate(
+                    RawOrigin::Signed(hotkey).into(),
+                    &call.clone(),
+                    &info,
+                    10,
+                    (),
+                    &TxBaseImplication(()),
+                    TransactionSource::External,
+                )
+                .unwrap_err(),
+            CustomTransactionError::StakeAmountTooLow.into()
+        );
+
+        // Too many commits
+        let oversized_call =
+            RuntimeCall::SubtensorModule(SubtensorCall::batch_commit_crv3_weights {
+                netuids: vec![Compact(netuid1); MAX_CRV3_BATCH_COMMITS + 1],
+                commits: vec![vec![1; 100]; MAX_CRV3_BATCH_COMMITS + 1],
+                reveal_rounds: vec![1000; MAX_CRV3_BATCH_COMMITS + 1],
+            });
+        assert_eq!(
+            extension
+                .validate(
+                    RawOrigin::Signed(hotkey).into(),
+                    &oversized_call,
+                    &info,
+                    10,
+                    (),
+                    &TxBaseImplication(()),
+                    TransactionSource::External,
+                )
+                .unwrap_err(),
+            CustomTransactionError::BadRequest.into()
+        );
+
+        SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey,
+            &U256::from(2),
+            netuid2,
+            1,
+        );
+        assert_ok!(extension.validate(
+            RawOrigin::Signed(hotkey).into(),
+            &call.clone(),
+            &info,
+            10,
+            (),
+            &TxBaseImplication(()),
+            TransactionSource::External,
+        ));
+    });
+}
+
+// SKIP_WASM_BUILD=1 RUST_LOG=debug cargo test --package pallet-subtensor --lib -- tests::weights::test_validate_weights --exact --show-output --nocapture
+#[test]
+fn test_validate_weights() {
+    new_test_ext(0).execute_with(|| {
+        let netuid = NetUid::from(1);
+        let hotkey = U256::from(1);
+        let version_key: u64 = 5;
+
+        add_network(netuid, 100, 0);
+        SubtensorModule::set_max_allowed_uids(netuid, 3);
+        SubtensorModule::set_target_registrations_per_interval(netuid, 3);
+        SubtensorModule::set_weights_set_rate_limit(netuid, 0);
+        SubtensorModule::set_max_weight_limit(netuid, u16::MAX / 5);
+        SubtensorModule::set_min_allowed_weights(netuid, 0);
+        SubtensorModule::set_weights_version_key(netuid, version_key);
+        for i in 1..=3 {
+            register_ok_neuron(netuid, U256::from(i), U256::from(i), 1000 * i as u64);
+            step_block(1);
+        }
+        let neuron_uid = SubtensorModule::get_uid_for_net_and_hotkey(netuid, &hotkey).unwrap();
+        SubtensorModule::set_validator_permit_for_uid(netuid, neuron_uid, true);
+
+        // Every broken rule is reported, in the order set_weights checks them.
+        let uids: Vec<u16> = vec![1, 1, 2];
+        let values: Vec<u16> = vec![5, 3, 15];
+        let info = SubtensorModule::validate_weights(
+            netuid,
+            hotkey,
+            uids.clone(),
+            values.clone(),
+            version_key + 1,
+        );
+        assert_eq!(
+            info.violations,
+            vec![
+                Error::<Test>::IncorrectWeightVersionKey.into(),
+                Error::<Test>::DuplicateUids.into(),
+                Error::<Test>::MaxWeightExceeded.into(),
+            ]
+        );
+        assert_eq!(
+            info.weights,
+            vec![
+                (1.into(), 21845.into()),
+                (1.into(), 13107.into()),
+                (2.into(), u16::MAX.into()),
+            ]
+        );
+        assert_err!(
+            SubtensorModule::set_weights(
+                RuntimeOrigin::signed(hotkey),
+                netuid,
+                uids,
+                values,
+                version_key + 1
+            ),
+            Error::<Test>::IncorrectWeightVersionKey
+        );
+
+        // Unregistered hotkeys skip the checks needing a uid.
+        let info =
+            SubtensorModule::validate_weights(netuid, U256::from(4), vec![0], vec![1], version_key);
+        assert_eq!(
+            info.violations,
+            vec![Error::<Test>::HotKeyNotRegisteredInSubNet.into()]
+        );
+
+        // Valid weights are stored as reported.
+        SubtensorModule::set_max_weight_limit(netuid, u16::MAX);
+        let uids: Vec<u16> = vec![0, 2];
+        let values: Vec<u16> = vec![5, 15];
+        let info = SubtensorModule::validate_weights(
+            netuid,
+            hotkey,
+            uids.clone(),
+            values.clone(),
+            version_key,
+        );
+        assert!(info.violations.is_empty());
+        assert_ok!(SubtensorModule::set_weights(
+            RuntimeOrigin::signed(hotkey),
+            netuid,
+            uids,
+            values,
+            version_key
+        ));
+        let stored: Vec<(Compact<u16>, Compact<u16>)> = Weights::<Test>::get(netuid, neuron_uid)
+            .into_iter()
+            .map(|(uid, weight)| (uid.into(), weight.into()))
+            .collect();
+        assert_eq!(info.weights, stored);
+    });
+}
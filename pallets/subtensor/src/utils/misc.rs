@@ -187,6 +187,10 @@ impl<T: Config> Pallet<T> {
         let vec = Incentive::<T>::get(netuid);
         vec.get(uid as usize).copied().unwrap_or(0)
     }
+    pub fn get_incentive_epochs_for_uid(netuid: NetUid, uid: u16) -> u16 {
+        let vec = IncentiveEpochs::<T>::get(netuid);
+        vec.get(uid as usize).copied().unwrap_or(0)
+    }
     pub fn get_dividends_for_uid(netuid: NetUid, uid: u16) -> u16 {
         let vec = Dividends::<T>::get(netuid);
         vec.get(uid as usize).copied().unwrap_or(0)
@@ -445,10 +449,34 @@ impl<T: Config> Pallet<T> {
     }
     /// Check if a neuron is in immunity based on the current block
     pub fn get_neuron_is_immune(netuid: NetUid, uid: u16) -> bool {
+        Self::get_neuron_immunity_blocks_remaining(netuid, uid) > 0
+    }
+    /// Blocks left until the immunity of a neuron ends under the subnet's immunity policy, zero
+    /// once it is no longer immune. Immunity ending on earned incentive may end sooner.
+    pub fn get_neuron_immunity_blocks_remaining(netuid: NetUid, uid: u16) -> u64 {
         let registered_at = Self::get_neuron_block_at_registration(netuid, uid);
         let current_block = Self::get_current_block_as_u64();
-        let immunity_period = Self::get_immunity_period(netuid);
-        current_block.saturating_sub(registered_at) < u64::from(immunity_period)
+        let immunity_period = u64::from(Self::get_immunity_period(netuid));
+        let immune_for = match Self::get_immunity_policy(netuid) {
+            ImmunityPolicy::Blocks => immunity_period,
+            ImmunityPolicy::EarnedIncentive { epochs } => {
+                if Self::get_incentive_epochs_for_uid(netuid, uid) < epochs {
+                    immunity_period
+                } else {
+                    0
+                }
+            }
+            ImmunityPolicy::AwaitFirstWeights { max_extension } => {
+                if Self::get_incentive_epochs_for_uid(netuid, uid) == 0 {
+                    immunity_period.saturating_add(u64::from(max_extension))
+                } else {
+                    immunity_period
+                }
+            }
+        };
+        registered_at
+            .saturating_add(immune_for)
+            .saturating_sub(current_block)
     }
 
     pub fn get_min_allowed_weights(netuid: NetUid) -> u16 {
@@ -594,6 +622,102 @@ impl<T: Config> Pallet<T> {
         Self::deposit_event(Event::BondsResetOnSet(netuid, bonds_reset));
     }
 
+    pub fn get_pruning_policy(netuid: NetUid) -> PruningPolicy {
+        UidPruningPolicy::<T>::get(netuid)
+    }
+    pub fn set_pruning_policy(netuid: NetUid, policy: PruningPolicy) {
+        if UidPruningPolicy::<T>::get(netuid) != policy {
+            // The history is only tracked for the active policy, start it over
+            Self::clear_pruning_emission_history(netuid);
+            IncentiveEma::<T>::remove(netuid);
+        }
+        UidPruningPolicy::<T>::insert(netuid, policy);
+        Self::deposit_event(Event::PruningPolicySet(netuid, policy));
+    }
+
+    pub fn get_immunity_policy(netuid: NetUid) -> ImmunityPolicy {
+        SubnetImmunityPolicy::<T>::get(netuid)
+    }
+    pub fn set_immunity_policy(netuid: NetUid, policy: ImmunityPolicy) {
+        if SubnetImmunityPolicy::<T>::get(netuid) == ImmunityPolicy::Blocks {
+            // Epochs with incentive are not counted under block based immunity, start them over
+            IncentiveEpochs::<T>::remove(netuid);
+        }
+        SubnetImmunityPolicy::<T>::insert(netuid, policy);
+        Self::deposit_event(Event::ImmunityPolicySet(netuid, policy));
+    }
+
+    pub fn get_registration_auction_enabled(netuid: NetUid) -> bool {
+        RegistrationAuctionEnabled::<T>::get(netuid)
+    }
+    pub fn set_registration_auction_enabled(netuid: NetUid, enabled: bool) {
+        if !enabled {
+            Self::refund_registration_bids(netuid);
+        }
+        RegistrationAuctionEnabled::<T>::insert(netuid, enabled);
+        Self::deposit_event(Event::RegistrationAuctionSet(netuid, enabled));
+    }
+
+    pub fn get_deregistration_refund(netuid: NetUid) -> u16 {
+        DeregistrationRefund::<T>::get(netuid)
+    }
+    pub fn get_deregistration_refund_decay(netuid: NetUid) -> u64 {
+        DeregistrationRefundDecay::<T>::get(netuid)
+    }
+    pub fn set_deregistration_refund(netuid: NetUid, refund: u16, decay: u64) {
+        DeregistrationRefund::<T>::insert(netuid, refund);
+        DeregistrationRefundDecay::<T>::insert(netuid, decay);
+        Self::deposit_event(Event::DeregistrationRefundSet(netuid, refund, decay));
+    }
+
+    pub fn get_pow_algorithm(netuid: NetUid) -> PowAlgorithm {
+        SubnetPowAlgorithm::<T>::get(netuid)
+    }
+    /// Sets the proof of work of the subnet and rescales its difficulty to the new algorithm.
+    ///
+    /// The difficulty is the number of attempts a solution takes on average, and an attempt at a
+    /// cuckoo cycle costs about `2^edge_bits` hashes where a seal costs one. The difficulty and
+    /// its bounds are scaled by the ratio of the attempt costs, so a registration takes about the
+    /// same work after the change, and keep adjusting to the registrations from there.
+    pub fn set_pow_algorithm(netuid: NetUid, algorithm: PowAlgorithm) {
+        let previous = SubnetPowAlgorithm::<T>::get(netuid);
+        if previous != algorithm {
+            let (previous_cost, cost) = (
+                Self::pow_attempt_cost(previous),
+                Self::pow_attempt_cost(algorithm),
+            );
+            let rescale = |difficulty: u64| -> u64 {
+                u128::from(difficulty)
+                    .saturating_mul(previous_cost)
+                    .safe_div(cost)
+                    .clamp(1, u128::from(u64::MAX)) as u64
+            };
+            Self::set_difficulty(netuid, rescale(Self::get_difficulty_as_u64(netuid)));
+            Self::set_min_difficulty(netuid, rescale(Self::get_min_difficulty(netuid)));
+            Self::set_max_difficulty(netuid, rescale(Self::get_max_difficulty(netuid)));
+        }
+        SubnetPowAlgorithm::<T>::insert(netuid, algorithm);
+        Self::deposit_event(Event::PowAlgorithmSet(netuid, algorithm));
+    }
+
+    /// Hashes an attempt at solving the proof of work costs, relative to a seal.
+    pub fn pow_attempt_cost(algorithm: PowAlgorithm) -> u128 {
+        match algorithm {
+            PowAlgorithm::Seal => 1,
+            PowAlgorithm::CuckooCycle { edge_bits } => {
+                1u128.checked_shl(u32::from(edge_bits)).unwrap_or(u128::MAX)
+            }
+        }
+    }
+
+    pub fn get_registration_whitelist_enabled(netuid: NetUid) -> bool {
+        RegistrationWhitelistEnabled::<T>::get(netuid)
+    }
+    pub fn set_registration_whitelist_enabled(netuid: NetUid, enabled: bool) {
+        RegistrationWhitelistEnabled::<T>::insert(netuid, enabled);
+        Self::deposit_event(Event::RegistrationWhitelistSet(netuid, enabled));
+    }
+
     pub fn get_max_registrations_per_block(netuid: NetUid) -> u16 {
         MaxRegistrationsPerBlock::<T>::get(netuid)
     }
@@ -2,9 +2,13 @@
 
 use frame_support::pallet_prelude::*;
 use substrate_fixed::types::U96F32;
+use subtensor_macros::freeze_struct;
 use subtensor_runtime_common::NetUid;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The maximum number of bands in a multi-band protocol liquidity policy
+pub const MAX_PROTOCOL_LIQUIDITY_BANDS: u32 = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
 pub enum OrderType {
     Sell,
     Buy,
@@ -30,6 +34,36 @@ pub trait SwapHandler<AccountId> {
     fn min_price() -> u64;
     fn adjust_protocol_liquidity(netuid: NetUid, tao_delta: u64, alpha_delta: u64);
     fn is_user_liquidity_enabled(netuid: NetUid) -> bool;
+    fn swap_coldkey_positions(old_coldkey: &AccountId, new_coldkey: &AccountId) -> Weight;
+    fn set_protocol_liquidity_policy(
+        netuid: NetUid,
+        policy: ProtocolLiquidityPolicy,
+    ) -> DispatchResult;
+}
+
+/// Shape of the protocol owned liquidity of a subnet pool.
+///
+/// Apart from `FullRange`, a fixed share of the protocol liquidity always stays full range so
+/// that the pool never runs out of liquidity when the price leaves the bands.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, Default, TypeInfo, MaxEncodedLen)]
+pub enum ProtocolLiquidityPolicy {
+    /// All protocol liquidity is spread from the minimum to the maximum price
+    #[default]
+    FullRange,
+    /// Protocol liquidity is concentrated in a band of `width` ticks around the current price
+    Concentrated { width: u32 },
+    /// Protocol liquidity is split between several bands around the current price
+    MultiBand(BoundedVec<ProtocolLiquidityBand, ConstU32<MAX_PROTOCOL_LIQUIDITY_BANDS>>),
+}
+
+/// A band of protocol liquidity centered on the current price
+#[freeze_struct("cb860b1f6cc4c870")]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+pub struct ProtocolLiquidityBand {
+    /// Width of the band in ticks
+    pub width: u32,
+    /// Share of the concentrated protocol liquidity relative to the other bands
+    pub weight: u16,
 }
 
 #[derive(Debug, PartialEq)]
@@ -0,0 +1,149 @@
+//! Drives random sequences of liquidity and swap operations against the mock runtime and checks
+//! the pool invariants after every step.
+//!
+//! Run with `cargo +nightly fuzz run swap_operations` from `pallets/swap`.
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use frame_support::assert_ok;
+use libfuzzer_sys::fuzz_target;
+use pallet_subtensor_swap::{
+    LastPositionId, Pallet, Positions, mock::*, position::PositionId, tick::TickIndex,
+};
+use subtensor_runtime_common::NetUid;
+use subtensor_swap_interface::OrderType;
+
+const ACCOUNTS: [(AccountId, AccountId); 3] = [
+    (OK_COLDKEY_ACCOUNT_ID, OK_HOTKEY_ACCOUNT_ID),
+    (OK_COLDKEY_ACCOUNT_ID_2, OK_HOTKEY_ACCOUNT_ID_2),
+    (OK_COLDKEY_ACCOUNT_ID_RICH, OK_HOTKEY_ACCOUNT_ID_RICH),
+];
+
+#[derive(Arbitrary, Debug)]
+enum Operation {
+    AddLiquidity {
+        account: u8,
+        tick_low: i32,
+        tick_high: i32,
+        liquidity: u64,
+    },
+    RemoveLiquidity {
+        position: u8,
+    },
+    ModifyPosition {
+        position: u8,
+        liquidity_delta: i64,
+    },
+    Swap {
+        buy: bool,
+        amount: u64,
+    },
+}
+
+/// Maps an arbitrary value onto the valid tick range
+fn bounded_tick(value: i32) -> TickIndex {
+    let span = TickIndex::MAX.get().saturating_sub(TickIndex::MIN.get());
+    TickIndex::new_unchecked(
+        TickIndex::MIN
+            .get()
+            .saturating_add(value.rem_euclid(span.saturating_add(1))),
+    )
+}
+
+fn pick<T: Copy>(items: &[T], index: u8) -> Option<T> {
+    usize::from(index)
+        .checked_rem(items.len())
+        .and_then(|index| items.get(index).copied())
+}
+
+fn apply(netuid: NetUid, positions: &mut Vec<(AccountId, AccountId, PositionId)>, op: Operation) {
+    match op {
+        Operation::AddLiquidity {
+            account,
+            tick_low,
+            tick_high,
+            liquidity,
+        } => {
+            let Some((coldkey, hotkey)) = pick(&ACCOUNTS, account) else {
+                return;
+            };
+            let result = Swap::add_liquidity(
+                RuntimeOrigin::signed(coldkey),
+                hotkey,
+                netuid,
+                bounded_tick(tick_low),
+                bounded_tick(tick_high),
+                liquidity,
+            );
+            if result.is_ok() {
+                let position_id = PositionId::from(LastPositionId::<Test>::get());
+                positions.push((coldkey, hotkey, position_id));
+            }
+        }
+        Operation::RemoveLiquidity { position } => {
+            let Some((coldkey, hotkey, position_id)) = pick(positions, position) else {
+                return;
+            };
+            if Swap::remove_liquidity(RuntimeOrigin::signed(coldkey), hotkey, netuid, position_id)
+                .is_ok()
+            {
+                positions.retain(|(_, _, id)| *id != position_id);
+            }
+        }
+        Operation::ModifyPosition {
+            position,
+            liquidity_delta,
+        } => {
+            let Some((coldkey, hotkey, position_id)) = pick(positions, position) else {
+                return;
+            };
+            let _ = Swap::modify_position(
+                RuntimeOrigin::signed(coldkey),
+                hotkey,
+                netuid,
+                position_id,
+                liquidity_delta,
+            );
+            if !Positions::<Test>::contains_key((netuid, coldkey, position_id)) {
+                positions.retain(|(_, _, id)| *id != position_id);
+            }
+        }
+        Operation::Swap { buy, amount } => {
+            let (order_type, limit) = if buy {
+                (OrderType::Buy, TickIndex::max_sqrt_price())
+            } else {
+                (OrderType::Sell, TickIndex::min_sqrt_price())
+            };
+            // Apply the swap result to the reserves the way subtensor does
+            if let Ok(result) =
+                Pallet::<Test>::do_swap(netuid, order_type, amount, limit, false, false)
+            {
+                adjust_reserves(netuid, result.tao_reserve_delta, result.alpha_reserve_delta);
+            }
+        }
+    }
+}
+
+fuzz_target!(|ops: Vec<Operation>| {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+
+        // An empty swap switches the subnet to V3 and creates the protocol position
+        assert_ok!(Pallet::<Test>::do_swap(
+            netuid,
+            OrderType::Buy,
+            0,
+            TickIndex::max_sqrt_price(),
+            false,
+            false,
+        ));
+        assert_ok!(Pallet::<Test>::do_try_state());
+
+        let mut positions = Vec::new();
+        for op in ops {
+            apply(netuid, &mut positions, op);
+            assert_ok!(Pallet::<Test>::do_try_state());
+        }
+    });
+});
@@ -1,7 +1,42 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
+use alloc::vec::Vec;
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use subtensor_macros::freeze_struct;
+use subtensor_swap_interface::ProtocolLiquidityPolicy;
+
+/// Composition of the protocol owned liquidity of a subnet pool
+#[freeze_struct("c026341c6cf98de4")]
+#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug, TypeInfo)]
+pub struct ProtocolLiquidityInfo {
+    /// Policy the protocol liquidity is shaped by
+    pub policy: ProtocolLiquidityPolicy,
+    /// Protocol positions the liquidity is currently split into
+    pub positions: Vec<ProtocolPositionInfo>,
+}
+
+/// A single protocol owned position
+#[freeze_struct("d739ce0d8c00fa3e")]
+#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug, TypeInfo)]
+pub struct ProtocolPositionInfo {
+    /// Lower tick of the position
+    pub tick_low: i32,
+    /// Upper tick of the position
+    pub tick_high: i32,
+    /// Position liquidity
+    pub liquidity: u64,
+    /// TAO provided by the position at the current price
+    pub tao: u64,
+    /// Alpha provided by the position at the current price
+    pub alpha: u64,
+}
+
 sp_api::decl_runtime_apis! {
     pub trait SwapRuntimeApi {
         fn current_alpha_price(netuid: u16) -> u64;
+        fn protocol_liquidity(netuid: u16) -> ProtocolLiquidityInfo;
     }
 }
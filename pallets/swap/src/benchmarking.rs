@@ -13,7 +13,7 @@ use subtensor_runtime_common::NetUid;
 use crate::{
     pallet::{
         AlphaSqrtPrice, Call, Config, CurrentLiquidity, CurrentTick, EnabledUserLiquidity, Pallet,
-        Positions, SwapV3Initialized,
+        PositionAutomations, PositionOwners, Positions, SwapV3Initialized,
     },
     position::{Position, PositionId},
     tick::TickIndex,
@@ -131,6 +131,75 @@ mod benchmarks {
         );
     }
 
+    #[benchmark]
+    fn transfer_position() {
+        let netuid = NetUid::from(1);
+
+        let caller: T::AccountId = whitelisted_caller();
+        let new_coldkey: T::AccountId = account("new_coldkey", 0, 0);
+        let id = PositionId::from(1u128);
+
+        Positions::<T>::insert(
+            (netuid, caller.clone(), id),
+            Position {
+                id,
+                netuid,
+                tick_low: TickIndex::new(-10000).unwrap(),
+                tick_high: TickIndex::new(10000).unwrap(),
+                liquidity: 10000,
+                fees_tao: I64F64::from_num(0),
+                fees_alpha: I64F64::from_num(0),
+                _phantom: PhantomData,
+            },
+        );
+        PositionOwners::<T>::insert(id, (netuid, caller.clone()));
+
+        #[extrinsic_call]
+        transfer_position(
+            RawOrigin::Signed(caller),
+            netuid.into(),
+            id.into(),
+            new_coldkey.clone(),
+        );
+
+        assert!(Positions::<T>::contains_key((netuid, new_coldkey, id)));
+    }
+
+    #[benchmark]
+    fn set_position_automation() {
+        let netuid = NetUid::from(1);
+
+        let caller: T::AccountId = whitelisted_caller();
+        let hotkey: T::AccountId = account("hotkey", 0, 0);
+        let id = PositionId::from(1u128);
+
+        Positions::<T>::insert(
+            (netuid, caller.clone(), id),
+            Position {
+                id,
+                netuid,
+                tick_low: TickIndex::new(-10000).unwrap(),
+                tick_high: TickIndex::new(10000).unwrap(),
+                liquidity: 10000,
+                fees_tao: I64F64::from_num(0),
+                fees_alpha: I64F64::from_num(0),
+                _phantom: PhantomData,
+            },
+        );
+
+        #[extrinsic_call]
+        set_position_automation(
+            RawOrigin::Signed(caller),
+            hotkey,
+            netuid.into(),
+            id.into(),
+            true,
+            true,
+        );
+
+        assert!(PositionAutomations::<T>::contains_key(id));
+    }
+
     #[benchmark]
     fn toggle_user_liquidity() {
         let netuid = NetUid::from(101);
@@ -1,8 +1,11 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
 use substrate_fixed::types::U64F64;
 use subtensor_swap_interface::OrderType;
 
+pub mod migrations;
 pub mod pallet;
 pub mod position;
 pub mod tick;
@@ -13,7 +16,7 @@ pub use pallet::*;
 #[cfg(feature = "runtime-benchmarks")]
 pub mod benchmarking;
 
-#[cfg(test)]
-pub(crate) mod mock;
+#[cfg(any(test, feature = "fuzzing"))]
+pub mod mock;
 
 type SqrtPrice = U64F64;
@@ -0,0 +1,97 @@
+use alloc::string::String;
+use frame_support::{BoundedVec, traits::Get, weights::Weight};
+
+use crate::pallet::{Config, HasMigrationRun, PositionOwners, Positions};
+
+/// Fills the `PositionOwners` lookup index for positions created before the index existed.
+pub fn migrate_populate_position_owners<T: Config>() -> Weight {
+    let migration_name = BoundedVec::truncate_from(b"migrate_populate_position_owners".to_vec());
+    let mut weight = T::DbWeight::get().reads(1);
+
+    if HasMigrationRun::<T>::get(&migration_name) {
+        log::info!(
+            "Migration '{:?}' has already run. Skipping.",
+            String::from_utf8_lossy(&migration_name)
+        );
+        return weight;
+    }
+
+    log::info!(
+        "Running migration '{}'",
+        String::from_utf8_lossy(&migration_name)
+    );
+
+    let mut positions_count: u64 = 0;
+    for ((netuid, account_id, position_id), _) in Positions::<T>::iter() {
+        PositionOwners::<T>::insert(position_id, (netuid, account_id));
+        positions_count = positions_count.saturating_add(1);
+    }
+    weight =
+        weight.saturating_add(T::DbWeight::get().reads_writes(positions_count, positions_count));
+
+    HasMigrationRun::<T>::insert(&migration_name, true);
+    weight = weight.saturating_add(T::DbWeight::get().writes(1));
+
+    log::info!(
+        "Migration '{:?}' completed successfully. {} positions indexed.",
+        String::from_utf8_lossy(&migration_name),
+        positions_count
+    );
+
+    weight
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use core::marker::PhantomData;
+
+    use substrate_fixed::types::I64F64;
+    use subtensor_runtime_common::NetUid;
+
+    use super::*;
+    use crate::{
+        mock::*,
+        position::{Position, PositionId},
+        tick::TickIndex,
+    };
+
+    #[test]
+    fn test_migrate_populate_position_owners_works() {
+        new_test_ext().execute_with(|| {
+            let netuid = NetUid::from(1);
+            let items = [
+                (OK_COLDKEY_ACCOUNT_ID, PositionId::from(1u128)),
+                (OK_COLDKEY_ACCOUNT_ID, PositionId::from(2u128)),
+                (OK_COLDKEY_ACCOUNT_ID_2, PositionId::from(3u128)),
+            ];
+
+            for (account_id, id) in items {
+                Positions::<Test>::insert(
+                    (netuid, account_id, id),
+                    Position {
+                        id,
+                        netuid,
+                        tick_low: TickIndex::new(-10000).unwrap(),
+                        tick_high: TickIndex::new(10000).unwrap(),
+                        liquidity: 1000,
+                        fees_tao: I64F64::from_num(0),
+                        fees_alpha: I64F64::from_num(0),
+                        _phantom: PhantomData,
+                    },
+                );
+            }
+
+            migrate_populate_position_owners::<Test>();
+
+            for (account_id, id) in items {
+                assert_eq!(PositionOwners::<Test>::get(id), Some((netuid, account_id)));
+            }
+
+            assert!(HasMigrationRun::<Test>::get(BoundedVec::truncate_from(
+                b"migrate_populate_position_owners".to_vec()
+            )));
+        });
+    }
+}
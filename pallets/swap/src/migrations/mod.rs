@@ -0,0 +1,2 @@
+mod migrate_populate_position_owners;
+pub use migrate_populate_position_owners::*;
@@ -1,6 +1,8 @@
 #![allow(clippy::unwrap_used)]
 
 use core::num::NonZeroU64;
+use core::ops::Neg;
+use std::{cell::RefCell, collections::BTreeMap};
 
 use frame_support::construct_runtime;
 use frame_support::pallet_prelude::*;
@@ -11,7 +13,7 @@ use frame_support::{
 use frame_system::{self as system};
 use sp_core::H256;
 use sp_runtime::{
-    BuildStorage,
+    BuildStorage, Perbill,
     traits::{BlakeTwo256, IdentityLookup},
 };
 use subtensor_runtime_common::{BalanceOps, NetUid, SubnetInfo};
@@ -34,6 +36,7 @@ pub const OK_HOTKEY_ACCOUNT_ID_2: AccountId = 1001;
 pub const OK_COLDKEY_ACCOUNT_ID_RICH: AccountId = 5;
 pub const OK_HOTKEY_ACCOUNT_ID_RICH: AccountId = 1005;
 pub const NOT_SUBNET_OWNER: AccountId = 666;
+pub const NOT_OWNED_HOTKEY_ACCOUNT_ID: AccountId = 667;
 pub const NON_EXISTENT_NETUID: u16 = 999;
 pub const WRAPPING_FEES_NETUID: u16 = 124;
 
@@ -81,6 +84,30 @@ parameter_types! {
     pub const MaxPositions: u32 = 100;
     pub const MinimumLiquidity: u64 = 1_000;
     pub const MinimumReserves: NonZeroU64 = NonZeroU64::new(1).unwrap();
+    pub const PositionAutomationInterval: u32 = 10;
+    pub const MaxAutomatedPositionsPerRun: u32 = 2;
+    pub const MinProtocolBandWidth: u32 = 1_000;
+    pub ProtocolFullRangeShare: Perbill = Perbill::from_percent(20);
+}
+
+thread_local! {
+    // Changes of the mocked reserves made by provided liquidity and applied swaps
+    static RESERVE_DELTAS: RefCell<BTreeMap<NetUid, (i64, i64)>> =
+        const { RefCell::new(BTreeMap::new()) };
+}
+
+/// Moves the mocked subnet reserves by the given deltas, e.g. to apply a swap result the way
+/// subtensor does
+pub fn adjust_reserves(netuid: NetUid, tao_delta: i64, alpha_delta: i64) {
+    RESERVE_DELTAS.with_borrow_mut(|deltas| {
+        let (tao, alpha) = deltas.entry(netuid).or_default();
+        *tao = tao.saturating_add(tao_delta);
+        *alpha = alpha.saturating_add(alpha_delta);
+    });
+}
+
+fn reserve_deltas(netuid: NetUid) -> (i64, i64) {
+    RESERVE_DELTAS.with_borrow(|deltas| deltas.get(&netuid).copied().unwrap_or_default())
 }
 
 // Mock implementor of SubnetInfo trait
@@ -88,19 +115,21 @@ pub struct MockLiquidityProvider;
 
 impl SubnetInfo<AccountId> for MockLiquidityProvider {
     fn tao_reserve(netuid: NetUid) -> u64 {
-        match netuid.into() {
+        let reserve: u64 = match netuid.into() {
             123u16 => 10_000,
             WRAPPING_FEES_NETUID => 100_000_000_000,
             _ => 1_000_000_000_000,
-        }
+        };
+        reserve.saturating_add_signed(reserve_deltas(netuid).0)
     }
 
     fn alpha_reserve(netuid: NetUid) -> u64 {
-        match netuid.into() {
+        let reserve: u64 = match netuid.into() {
             123u16 => 10_000,
             WRAPPING_FEES_NETUID => 400_000_000_000,
             _ => 4_000_000_000_000,
-        }
+        };
+        reserve.saturating_add_signed(reserve_deltas(netuid).1)
     }
 
     fn exists(netuid: NetUid) -> bool {
@@ -143,6 +172,10 @@ impl BalanceOps<AccountId> for MockBalanceOps {
         }
     }
 
+    fn coldkey_owns_hotkey(_coldkey: &AccountId, hotkey: &AccountId) -> bool {
+        *hotkey != NOT_OWNED_HOTKEY_ACCOUNT_ID
+    }
+
     fn increase_balance(_coldkey: &AccountId, _tao: u64) {}
 
     fn decrease_balance(_coldkey: &AccountId, tao: u64) -> Result<u64, DispatchError> {
@@ -167,10 +200,21 @@ impl BalanceOps<AccountId> for MockBalanceOps {
         Ok(alpha)
     }
 
-    fn increase_provided_tao_reserve(_netuid: NetUid, _tao: u64) {}
-    fn decrease_provided_tao_reserve(_netuid: NetUid, _tao: u64) {}
-    fn increase_provided_alpha_reserve(_netuid: NetUid, _alpha: u64) {}
-    fn decrease_provided_alpha_reserve(_netuid: NetUid, _alpha: u64) {}
+    fn increase_provided_tao_reserve(netuid: NetUid, tao: u64) {
+        adjust_reserves(netuid, tao as i64, 0);
+    }
+
+    fn decrease_provided_tao_reserve(netuid: NetUid, tao: u64) {
+        adjust_reserves(netuid, (tao as i64).neg(), 0);
+    }
+
+    fn increase_provided_alpha_reserve(netuid: NetUid, alpha: u64) {
+        adjust_reserves(netuid, 0, alpha as i64);
+    }
+
+    fn decrease_provided_alpha_reserve(netuid: NetUid, alpha: u64) {
+        adjust_reserves(netuid, 0, (alpha as i64).neg());
+    }
 }
 
 impl crate::pallet::Config for Test {
@@ -182,12 +226,19 @@ impl crate::pallet::Config for Test {
     type MaxPositions = MaxPositions;
     type MinimumLiquidity = MinimumLiquidity;
     type MinimumReserve = MinimumReserves;
+    type PositionAutomationInterval = PositionAutomationInterval;
+    type MaxAutomatedPositionsPerRun = MaxAutomatedPositionsPerRun;
+    type MinProtocolBandWidth = MinProtocolBandWidth;
+    type ProtocolFullRangeShare = ProtocolFullRangeShare;
     type WeightInfo = ();
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
+    #[cfg(test)]
     sp_tracing::try_init_simple();
+    RESERVE_DELTAS.with_borrow_mut(|deltas| deltas.clear());
+
     let storage = system::GenesisConfig::<Test>::default()
         .build_storage()
         .unwrap();
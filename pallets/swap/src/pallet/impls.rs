@@ -2,18 +2,26 @@ use core::marker::PhantomData;
 use core::ops::Neg;
 
 use frame_support::storage::{TransactionOutcome, transactional};
-use frame_support::{ensure, pallet_prelude::DispatchError, traits::Get};
+use frame_support::{
+    ensure,
+    pallet_prelude::{DispatchError, DispatchResult, Weight},
+    traits::Get,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use pallet_subtensor_swap_runtime_api::{ProtocolLiquidityInfo, ProtocolPositionInfo};
 use safe_math::*;
 use sp_arithmetic::helpers_128bit;
-use sp_runtime::traits::AccountIdConversion;
+use sp_runtime::{PerThing, Perbill, SaturatedConversion, Saturating, traits::AccountIdConversion};
 use substrate_fixed::types::{I64F64, U64F64, U96F32};
 use subtensor_runtime_common::{BalanceOps, NetUid, SubnetInfo};
-use subtensor_swap_interface::{SwapHandler, SwapResult};
+use subtensor_swap_interface::{
+    ProtocolLiquidityBand, ProtocolLiquidityPolicy, SwapHandler, SwapResult,
+};
 
 use super::pallet::*;
 use crate::{
     OrderType, SqrtPrice,
-    position::{Position, PositionId},
+    position::{Position, PositionAutomation, PositionId},
     tick::{ActiveTickIndexManager, Tick, TickIndex},
 };
 
@@ -230,6 +238,7 @@ impl<T: Config> SwapStep<T> {
     /// Process a single step of a swap
     fn process_swap(&self) -> Result<SwapStepResult, Error<T>> {
         // Hold the fees
+        let protocol_fee = Pallet::<T>::protocol_fee_share(self.netuid, self.fee);
         Pallet::<T>::add_fees(self.netuid, self.order_type, self.fee);
         let delta_out = Pallet::<T>::convert_deltas(self.netuid, self.order_type, self.delta_in);
         log::trace!("\tDelta Out        : {:?}", delta_out);
@@ -255,6 +264,7 @@ impl<T: Config> SwapStep<T> {
         Ok(SwapStepResult {
             amount_to_take: self.delta_in.saturating_add(self.fee),
             fee_paid: self.fee,
+            protocol_fee,
             delta_in: self.delta_in,
             delta_out,
         })
@@ -348,40 +358,113 @@ impl<T: Config> Pallet<T> {
             liquidity,
         )?;
 
+        PositionOwners::<T>::insert(position.id, (netuid, protocol_account_id.clone()));
         Positions::<T>::insert(&(netuid, protocol_account_id, position.id), position);
 
+        CurrentProtocolLiquidity::<T>::remove(netuid);
+
+        // The subnet owner may have chosen the shape of protocol liquidity before the switch
+        if ProtocolLiquidityPolicies::<T>::get(netuid) != ProtocolLiquidityPolicy::FullRange {
+            Self::rebuild_protocol_liquidity(netuid, 0, 0);
+        }
+
         Ok(())
     }
 
     /// Adjusts protocol liquidity with new values of TAO and Alpha reserve
+    ///
+    /// New reserves are split between protocol positions in proportion to their value. Once the
+    /// price leaves a concentrated band, protocol liquidity is rebuilt around the new price.
     pub(super) fn adjust_protocol_liquidity(netuid: NetUid, tao_delta: u64, alpha_delta: u64) {
-        // Update protocol position with new liquidity
+        CurrentProtocolLiquidity::<T>::remove(netuid);
+
+        // Update protocol positions with new liquidity
         let protocol_account_id = Self::protocol_account_id();
-        let mut positions =
-            Positions::<T>::iter_prefix_values((netuid, protocol_account_id.clone()))
-                .collect::<sp_std::vec::Vec<_>>();
+        let positions = Positions::<T>::iter_prefix_values((netuid, protocol_account_id.clone()))
+            .collect::<sp_std::vec::Vec<_>>();
+
+        let current_tick = TickIndex::current_bounded::<T>(netuid);
+        if positions.iter().any(|position| {
+            !Self::is_full_range(position)
+                && (current_tick < position.tick_low || current_tick >= position.tick_high)
+        }) {
+            Self::rebuild_protocol_liquidity(netuid, tao_delta, alpha_delta);
+            return;
+        }
+
+        let current_sqrt_price = Pallet::<T>::current_price_sqrt(netuid);
+        let price = current_sqrt_price.saturating_mul(current_sqrt_price);
+        let value = |tao: u64, alpha: u64| {
+            U64F64::saturating_from_num(tao)
+                .saturating_add(U64F64::saturating_from_num(alpha).saturating_mul(price))
+        };
+        let total_value = positions
+            .iter()
+            .filter_map(|position| position.to_token_amounts(current_sqrt_price).ok())
+            .fold(U64F64::saturating_from_num(0), |total, (tao, alpha)| {
+                total.saturating_add(value(tao, alpha))
+            });
+        let single_position = positions.len() == 1;
 
-        if let Some(position) = positions.get_mut(0) {
-            let current_sqrt_price = Pallet::<T>::current_price_sqrt(netuid);
+        for mut position in positions {
             let maybe_token_amounts = position.to_token_amounts(current_sqrt_price);
             if let Ok((tao, alpha)) = maybe_token_amounts {
-                // Get updated reserves, calculate liquidity
-                let new_tao_reserve = tao.saturating_add(tao_delta);
-                let new_alpha_reserve = alpha.saturating_add(alpha_delta);
-                let new_liquidity = helpers_128bit::sqrt(
-                    (new_tao_reserve as u128).saturating_mul(new_alpha_reserve as u128),
-                ) as u64;
-                let liquidity_delta = new_liquidity.saturating_sub(position.liquidity);
-
-                // Update current liquidity
-                CurrentLiquidity::<T>::mutate(netuid, |current_liquidity| {
-                    *current_liquidity = current_liquidity.saturating_add(liquidity_delta);
-                });
+                // Get updated reserves of the position
+                let share = if single_position {
+                    U64F64::saturating_from_num(1)
+                } else {
+                    value(tao, alpha).safe_div(total_value)
+                };
+                let new_tao_reserve = tao.saturating_add(
+                    U64F64::saturating_from_num(tao_delta)
+                        .saturating_mul(share)
+                        .saturating_to_num::<u64>(),
+                );
+                let new_alpha_reserve = alpha.saturating_add(
+                    U64F64::saturating_from_num(alpha_delta)
+                        .saturating_mul(share)
+                        .saturating_to_num::<u64>(),
+                );
+
+                let liquidity_delta = if Self::is_full_range(&position) {
+                    // Calculate liquidity
+                    let new_liquidity = helpers_128bit::sqrt(
+                        (new_tao_reserve as u128).saturating_mul(new_alpha_reserve as u128),
+                    ) as u64;
+                    let liquidity_delta = new_liquidity.saturating_sub(position.liquidity);
+
+                    // Update current liquidity
+                    CurrentLiquidity::<T>::mutate(netuid, |current_liquidity| {
+                        *current_liquidity = current_liquidity.saturating_add(liquidity_delta);
+                    });
+
+                    position.liquidity = new_liquidity;
+                    liquidity_delta
+                } else {
+                    let new_liquidity = Self::liquidity_for_amounts(
+                        position.tick_low,
+                        position.tick_high,
+                        current_sqrt_price,
+                        new_tao_reserve,
+                        new_alpha_reserve,
+                    )
+                    .unwrap_or(position.liquidity);
+                    let liquidity_delta = new_liquidity.saturating_sub(position.liquidity);
+
+                    Self::update_liquidity_if_needed(
+                        netuid,
+                        position.tick_low,
+                        position.tick_high,
+                        liquidity_delta as i128,
+                    );
+
+                    position.liquidity = position.liquidity.saturating_add(liquidity_delta);
+                    liquidity_delta
+                };
 
                 // Update protocol position
-                position.liquidity = new_liquidity;
                 Positions::<T>::insert(
-                    (netuid, protocol_account_id, position.id),
+                    (netuid, protocol_account_id.clone(), position.id),
                     position.clone(),
                 );
 
@@ -392,6 +475,193 @@ impl<T: Config> Pallet<T> {
         }
     }
 
+    /// Withdraws all protocol positions and provides their tokens, together with the new
+    /// reserves, again according to the protocol liquidity policy of the subnet.
+    fn rebuild_protocol_liquidity(netuid: NetUid, tao_delta: u64, alpha_delta: u64) {
+        CurrentProtocolLiquidity::<T>::remove(netuid);
+
+        let protocol_account_id = Self::protocol_account_id();
+        let current_sqrt_price = Pallet::<T>::current_price_sqrt(netuid);
+
+        let mut tao_total = tao_delta;
+        let mut alpha_total = alpha_delta;
+        let positions = Positions::<T>::iter_prefix_values((netuid, protocol_account_id.clone()))
+            .collect::<sp_std::vec::Vec<_>>();
+        for position in positions {
+            if let Ok((tao, alpha)) = position.to_token_amounts(current_sqrt_price) {
+                tao_total = tao_total.saturating_add(tao);
+                alpha_total = alpha_total.saturating_add(alpha);
+            }
+
+            Self::remove_liquidity_at_index(netuid, position.tick_low, position.liquidity, false);
+            Self::remove_liquidity_at_index(netuid, position.tick_high, position.liquidity, true);
+            Self::update_liquidity_if_needed(
+                netuid,
+                position.tick_low,
+                position.tick_high,
+                (position.liquidity as i128).neg(),
+            );
+
+            Positions::<T>::remove((netuid, &protocol_account_id, position.id));
+            PositionOwners::<T>::remove(position.id);
+        }
+
+        // Concentrated bands are provided first and the full range position takes whatever they
+        // leave over, as they can only use both tokens in the ratio of their range.
+        let mut tao_left = tao_total;
+        let mut alpha_left = alpha_total;
+        for (tick_low, tick_high, share) in Self::protocol_liquidity_layout(netuid) {
+            if tick_low == TickIndex::MIN && tick_high == TickIndex::MAX {
+                continue;
+            }
+
+            let tao = share.mul_floor(tao_total).min(tao_left);
+            let alpha = share.mul_floor(alpha_total).min(alpha_left);
+            let liquidity =
+                Self::liquidity_for_amounts(tick_low, tick_high, current_sqrt_price, tao, alpha)
+                    .unwrap_or_default();
+            if let Some((tao_used, alpha_used)) =
+                Self::insert_protocol_position(netuid, tick_low, tick_high, liquidity)
+            {
+                tao_left = tao_left.saturating_sub(tao_used);
+                alpha_left = alpha_left.saturating_sub(alpha_used);
+            }
+        }
+
+        let liquidity = helpers_128bit::sqrt((tao_left as u128).saturating_mul(alpha_left as u128));
+        Self::insert_protocol_position(netuid, TickIndex::MIN, TickIndex::MAX, liquidity as u64);
+    }
+
+    /// Provides the liquidity as a new protocol position, returning the tokens it holds.
+    fn insert_protocol_position(
+        netuid: NetUid,
+        tick_low: TickIndex,
+        tick_high: TickIndex,
+        liquidity: u64,
+    ) -> Option<(u64, u64)> {
+        if liquidity == 0 {
+            return None;
+        }
+
+        let protocol_account_id = Self::protocol_account_id();
+        let (position, tao, alpha) = Self::add_liquidity_not_insert(
+            netuid,
+            &protocol_account_id,
+            tick_low,
+            tick_high,
+            liquidity,
+        )
+        .ok()?;
+        PositionOwners::<T>::insert(position.id, (netuid, protocol_account_id.clone()));
+        Positions::<T>::insert(&(netuid, protocol_account_id, position.id), position);
+
+        Some((tao, alpha))
+    }
+
+    /// Returns tick ranges of protocol positions of a subnet together with their shares of
+    /// protocol liquidity. Concentrated bands are centered on the current tick.
+    fn protocol_liquidity_layout(
+        netuid: NetUid,
+    ) -> sp_std::vec::Vec<(TickIndex, TickIndex, Perbill)> {
+        let bands = match ProtocolLiquidityPolicies::<T>::get(netuid) {
+            ProtocolLiquidityPolicy::FullRange => {
+                return sp_std::vec![(TickIndex::MIN, TickIndex::MAX, Perbill::one())];
+            }
+            ProtocolLiquidityPolicy::Concentrated { width } => {
+                sp_std::vec![ProtocolLiquidityBand { width, weight: 1 }]
+            }
+            ProtocolLiquidityPolicy::MultiBand(bands) => bands.into_inner(),
+        };
+
+        let full_range_share = T::ProtocolFullRangeShare::get();
+        let concentrated_share = full_range_share.left_from_one();
+        let total_weight = bands.iter().fold(0_u32, |total, band| {
+            total.saturating_add(band.weight.into())
+        });
+        let current_tick = TickIndex::current_bounded::<T>(netuid);
+
+        let mut layout = sp_std::vec![(TickIndex::MIN, TickIndex::MAX, full_range_share)];
+        for band in bands {
+            let width = i32::try_from(band.width).unwrap_or(i32::MAX);
+            let tick_low = current_tick.saturating_sub(width.saturating_div(2));
+            let tick_high = tick_low.saturating_add(width);
+            let share = Perbill::from_rational(u32::from(band.weight), total_weight)
+                .saturating_mul(concentrated_share);
+            layout.push((tick_low, tick_high, share));
+        }
+
+        layout
+    }
+
+    fn is_full_range(position: &Position<T>) -> bool {
+        position.tick_low == TickIndex::MIN && position.tick_high == TickIndex::MAX
+    }
+
+    /// Sets the protocol liquidity policy of a subnet after checking it against the guardrails.
+    ///
+    /// If the subnet is already on V3 swap, protocol liquidity is reshaped right away.
+    pub fn do_set_protocol_liquidity_policy(
+        netuid: NetUid,
+        policy: ProtocolLiquidityPolicy,
+    ) -> Result<(), Error<T>> {
+        ensure!(
+            T::SubnetInfo::exists(netuid),
+            Error::<T>::SubNetworkDoesNotExist
+        );
+
+        let max_width = TickIndex::MAX
+            .get()
+            .saturating_sub(TickIndex::MIN.get())
+            .unsigned_abs();
+        let is_valid_width =
+            |width: u32| width >= T::MinProtocolBandWidth::get() && width <= max_width;
+        let is_valid = match &policy {
+            ProtocolLiquidityPolicy::FullRange => true,
+            ProtocolLiquidityPolicy::Concentrated { width } => is_valid_width(*width),
+            ProtocolLiquidityPolicy::MultiBand(bands) => {
+                !bands.is_empty()
+                    && bands
+                        .iter()
+                        .all(|band| band.weight > 0 && is_valid_width(band.width))
+            }
+        };
+        ensure!(is_valid, Error::<T>::InvalidProtocolLiquidityPolicy);
+
+        ProtocolLiquidityPolicies::<T>::insert(netuid, policy.clone());
+
+        if SwapV3Initialized::<T>::get(netuid) {
+            Self::rebuild_protocol_liquidity(netuid, 0, 0);
+        }
+
+        Self::deposit_event(Event::ProtocolLiquidityPolicySet { netuid, policy });
+
+        Ok(())
+    }
+
+    /// Returns the protocol liquidity policy of a subnet and the positions it resulted in
+    pub fn protocol_liquidity_info(netuid: NetUid) -> ProtocolLiquidityInfo {
+        let current_sqrt_price = Pallet::<T>::current_price_sqrt(netuid);
+        let positions = Positions::<T>::iter_prefix_values((netuid, Self::protocol_account_id()))
+            .map(|position| {
+                let (tao, alpha) = position
+                    .to_token_amounts(current_sqrt_price)
+                    .unwrap_or_default();
+                ProtocolPositionInfo {
+                    tick_low: position.tick_low.get(),
+                    tick_high: position.tick_high.get(),
+                    liquidity: position.liquidity,
+                    tao,
+                    alpha,
+                }
+            })
+            .collect();
+
+        ProtocolLiquidityInfo {
+            policy: ProtocolLiquidityPolicies::<T>::get(netuid),
+            positions,
+        }
+    }
+
     /// Executes a token swap on the specified subnet.
     ///
     /// # Parameters
@@ -427,9 +697,8 @@ impl<T: Config> Pallet<T> {
             let tao_reserve = T::SubnetInfo::tao_reserve(netuid.into());
             let alpha_reserve = T::SubnetInfo::alpha_reserve(netuid.into());
 
-            let mut result =
-                Self::swap_inner(netuid, order_type, amount, limit_sqrt_price, drop_fees)
-                    .map_err(Into::into);
+            let result = Self::swap_inner(netuid, order_type, amount, limit_sqrt_price, drop_fees)
+                .map_err(Into::into);
 
             if simulate || result.is_err() {
                 // Simulation only
@@ -445,7 +714,11 @@ impl<T: Config> Pallet<T> {
                     };
 
                     if checked_reserve < swap_result.amount_paid_out {
-                        result = Err(Error::<T>::InsufficientLiquidity.into());
+                        // The failed swap must not move the pool, even when the caller doesn't
+                        // roll back on errors. Also drops its `Swapped` event.
+                        return TransactionOutcome::Rollback(Err(
+                            Error::<T>::InsufficientLiquidity.into(),
+                        ));
                     }
                 }
 
@@ -468,6 +741,7 @@ impl<T: Config> Pallet<T> {
         );
 
         Self::maybe_initialize_v3(netuid)?;
+        let sqrt_price_before = AlphaSqrtPrice::<T>::get(netuid);
 
         // Because user specifies the limit price, check that it is in fact beoynd the current one
         match order_type {
@@ -486,6 +760,8 @@ impl<T: Config> Pallet<T> {
         let mut iteration_counter: u16 = 0;
         let mut in_acc: u64 = 0;
         let mut fee_acc: u64 = 0;
+        let mut protocol_fee_acc: u64 = 0;
+        let mut ticks_crossed: u16 = 0;
 
         log::trace!("======== Start Swap ========");
         log::trace!("Amount Remaining: {}", amount_remaining);
@@ -511,11 +787,13 @@ impl<T: Config> Pallet<T> {
 
             in_acc = in_acc.saturating_add(swap_result.delta_in);
             fee_acc = fee_acc.saturating_add(swap_result.fee_paid);
+            protocol_fee_acc = protocol_fee_acc.saturating_add(swap_result.protocol_fee);
             amount_remaining = amount_remaining.saturating_sub(swap_result.amount_to_take);
             amount_paid_out = amount_paid_out.saturating_add(swap_result.delta_out);
 
-            if swap_step.action == SwapStepAction::Stop {
-                amount_remaining = 0;
+            match swap_step.action {
+                SwapStepAction::Stop => amount_remaining = 0,
+                SwapStepAction::Crossing => ticks_crossed = ticks_crossed.saturating_add(1),
             }
 
             // The swap step didn't exchange anything
@@ -539,6 +817,19 @@ impl<T: Config> Pallet<T> {
             OrderType::Sell => ((amount_paid_out as i64).neg(), in_acc as i64),
         };
 
+        // Events of simulated swaps are dropped together with the rest of the transaction
+        Self::deposit_event(Event::Swapped {
+            netuid,
+            order_type,
+            amount_paid_in: in_acc,
+            amount_paid_out,
+            sqrt_price_before,
+            sqrt_price_after: AlphaSqrtPrice::<T>::get(netuid),
+            ticks_crossed,
+            lp_fee: fee_acc.saturating_sub(protocol_fee_acc),
+            protocol_fee: protocol_fee_acc,
+        });
+
         Ok(SwapResult {
             amount_paid_in: in_acc,
             amount_paid_out,
@@ -600,6 +891,57 @@ impl<T: Config> Pallet<T> {
         }
     }
 
+    /// Part of the fee that goes to the protocol owned liquidity active at the current tick
+    fn protocol_fee_share(netuid: NetUid, fee: u64) -> u64 {
+        let liquidity_curr = CurrentLiquidity::<T>::get(netuid);
+        if fee == 0 || liquidity_curr == 0 {
+            return 0;
+        }
+
+        let protocol_liquidity = Self::current_protocol_liquidity(netuid);
+
+        U64F64::saturating_from_num(fee)
+            .saturating_mul(
+                U64F64::saturating_from_num(protocol_liquidity)
+                    .safe_div(U64F64::saturating_from_num(liquidity_curr)),
+            )
+            .saturating_to_num::<u64>()
+            .min(fee)
+    }
+
+    /// Returns the protocol owned liquidity active at the current tick.
+    ///
+    /// The protocol positions are only read again once the current tick leaves the range between
+    /// their closest ticks, so swap steps within that range read a single value.
+    fn current_protocol_liquidity(netuid: NetUid) -> u64 {
+        let current_tick = TickIndex::current_bounded::<T>(netuid);
+        if let Some((range_low, range_high, liquidity)) = CurrentProtocolLiquidity::<T>::get(netuid)
+        {
+            if range_low <= current_tick && current_tick < range_high {
+                return liquidity;
+            }
+        }
+
+        let mut range_low = TickIndex::MIN;
+        let mut range_high = TickIndex::MAX;
+        let mut liquidity = 0_u64;
+        for position in Positions::<T>::iter_prefix_values((netuid, Self::protocol_account_id())) {
+            if position.tick_low <= current_tick && current_tick < position.tick_high {
+                liquidity = liquidity.saturating_add(position.liquidity);
+            }
+            for tick in [position.tick_low, position.tick_high] {
+                if tick <= current_tick {
+                    range_low = range_low.max(tick);
+                } else {
+                    range_high = range_high.min(tick);
+                }
+            }
+        }
+
+        CurrentProtocolLiquidity::<T>::insert(netuid, (range_low, range_high, liquidity));
+        liquidity
+    }
+
     /// Add fees to the global fee counters
     fn add_fees(netuid: NetUid, order_type: OrderType, fee: u64) {
         let liquidity_curr = Self::current_liquidity_safe(netuid);
@@ -847,6 +1189,7 @@ impl<T: Config> Pallet<T> {
             Error::<T>::InvalidLiquidityValue
         );
 
+        PositionOwners::<T>::insert(position_id, (netuid, coldkey_account_id.clone()));
         Positions::<T>::insert(&(netuid, coldkey_account_id, position.id), position);
 
         Ok((position_id, tao, alpha))
@@ -926,6 +1269,8 @@ impl<T: Config> Pallet<T> {
 
         // Remove user position
         Positions::<T>::remove((netuid, coldkey_account_id, position_id));
+        PositionOwners::<T>::remove(position_id);
+        PositionAutomations::<T>::remove(position_id);
 
         Ok(RemoveLiquidityResult {
             tao,
@@ -1046,6 +1391,8 @@ impl<T: Config> Pallet<T> {
         // Update or, in case if full liquidity is removed, remove the position
         if remove {
             Positions::<T>::remove((netuid, coldkey_account_id, position_id));
+            PositionOwners::<T>::remove(position_id);
+            PositionAutomations::<T>::remove(position_id);
         } else {
             Positions::<T>::insert(&(netuid, coldkey_account_id, position.id), position.clone());
         }
@@ -1061,6 +1408,492 @@ impl<T: Config> Pallet<T> {
         })
     }
 
+    /// Transfers a position to a new owner without touching its liquidity or accrued fees.
+    ///
+    /// Account ID and Position ID identify position in the storage map. The recipient must be
+    /// a different, non-protocol account and must have room for one more position.
+    pub fn do_transfer_position(
+        netuid: NetUid,
+        coldkey_account_id: &T::AccountId,
+        new_coldkey_account_id: &T::AccountId,
+        position_id: PositionId,
+    ) -> Result<(), Error<T>> {
+        ensure!(
+            Positions::<T>::contains_key((netuid, coldkey_account_id, position_id)),
+            Error::<T>::LiquidityNotFound
+        );
+
+        ensure!(
+            coldkey_account_id != new_coldkey_account_id
+                && *new_coldkey_account_id != Self::protocol_account_id(),
+            Error::<T>::InvalidPositionRecipient
+        );
+
+        ensure!(
+            Self::count_positions(netuid, new_coldkey_account_id) < T::MaxPositions::get() as usize,
+            Error::<T>::MaxPositionsExceeded
+        );
+
+        Self::move_position(
+            netuid,
+            coldkey_account_id,
+            new_coldkey_account_id,
+            position_id,
+        );
+
+        // The automation refers to a hotkey of the previous owner
+        PositionAutomations::<T>::remove(position_id);
+
+        Ok(())
+    }
+
+    /// Moves all positions of the old coldkey on all subnets to the new coldkey.
+    ///
+    /// Used by the coldkey swap, so unlike [`Self::do_transfer_position`] it never fails and
+    /// doesn't enforce the positions limit on the new coldkey. Their automations are cleared as
+    /// on a transfer.
+    pub fn do_swap_coldkey_positions(
+        old_coldkey_account_id: &T::AccountId,
+        new_coldkey_account_id: &T::AccountId,
+    ) -> Weight {
+        let mut weight = Weight::zero();
+
+        for netuid in SwapV3Initialized::<T>::iter_keys() {
+            let position_ids =
+                Positions::<T>::iter_key_prefix((netuid, old_coldkey_account_id.clone()))
+                    .collect::<sp_std::vec::Vec<_>>();
+            weight.saturating_accrue(T::DbWeight::get().reads(1));
+
+            for position_id in position_ids {
+                Self::move_position(
+                    netuid,
+                    old_coldkey_account_id,
+                    new_coldkey_account_id,
+                    position_id,
+                );
+                // As for a transfer, the automation refers to a hotkey of the old coldkey
+                PositionAutomations::<T>::remove(position_id);
+                weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 4));
+
+                Self::deposit_event(Event::PositionTransferred {
+                    netuid,
+                    position_id,
+                    from: old_coldkey_account_id.clone(),
+                    to: new_coldkey_account_id.clone(),
+                });
+            }
+        }
+
+        weight
+    }
+
+    /// Re-keys a position under a new owner and updates the lookup index.
+    fn move_position(
+        netuid: NetUid,
+        coldkey_account_id: &T::AccountId,
+        new_coldkey_account_id: &T::AccountId,
+        position_id: PositionId,
+    ) {
+        if let Some(position) = Positions::<T>::take((netuid, coldkey_account_id, position_id)) {
+            Positions::<T>::insert((netuid, new_coldkey_account_id, position_id), position);
+            PositionOwners::<T>::insert(position_id, (netuid, new_coldkey_account_id.clone()));
+        }
+    }
+
+    /// Runs automated management of user positions if the block is an automation block.
+    ///
+    /// At most `MaxAutomatedPositionsPerRun` positions are processed per run. Each run continues
+    /// after the position where the previous one stopped, so all automated positions get their
+    /// turn. A failing position is rolled back and skipped without affecting the others.
+    pub(super) fn run_position_automation(block_number: BlockNumberFor<T>) -> Weight {
+        let block_number: u64 = block_number.saturated_into();
+        let interval = T::PositionAutomationInterval::get() as u64;
+        if block_number.checked_rem(interval) != Some(0) {
+            return Weight::zero();
+        }
+
+        let max_positions = T::MaxAutomatedPositionsPerRun::get() as usize;
+        let batch = match PositionAutomationCursor::<T>::get() {
+            Some(cursor) => PositionAutomations::<T>::iter_from(
+                PositionAutomations::<T>::hashed_key_for(cursor),
+            )
+            .take(max_positions)
+            .collect::<sp_std::vec::Vec<_>>(),
+            None => PositionAutomations::<T>::iter()
+                .take(max_positions)
+                .collect::<sp_std::vec::Vec<_>>(),
+        };
+        let mut weight = T::DbWeight::get().reads((batch.len() as u64).saturating_add(1));
+
+        // Start over from the beginning of the map once its end is reached
+        let cursor = if batch.len() < max_positions {
+            None
+        } else {
+            batch.last().map(|(position_id, _)| *position_id)
+        };
+        PositionAutomationCursor::<T>::set(cursor);
+        weight.saturating_accrue(T::DbWeight::get().writes(1));
+
+        for (position_id, automation) in batch {
+            weight.saturating_accrue(Self::automate_position(position_id, &automation));
+        }
+
+        weight
+    }
+
+    /// Applies the automation to a single position in its own storage transaction.
+    fn automate_position(
+        position_id: PositionId,
+        automation: &PositionAutomation<T::AccountId>,
+    ) -> Weight {
+        let Some((netuid, coldkey)) = PositionOwners::<T>::get(position_id) else {
+            // The position is gone, so is its automation
+            PositionAutomations::<T>::remove(position_id);
+            return T::DbWeight::get().reads_writes(1, 1);
+        };
+
+        // Automation stops together with the other user liquidity operations of the subnet
+        if !EnabledUserLiquidity::<T>::get(netuid) {
+            return T::DbWeight::get().reads(2);
+        }
+
+        let Some(position) = Positions::<T>::get((netuid, &coldkey, position_id)) else {
+            return T::DbWeight::get().reads(3);
+        };
+
+        let current_tick = TickIndex::current_bounded::<T>(netuid);
+        let in_range = position.tick_low <= current_tick && current_tick < position.tick_high;
+        let recenter = automation.keep_centered && !in_range;
+
+        let result = transactional::with_transaction(|| {
+            let result = if recenter {
+                Self::recenter_position(netuid, &coldkey, &automation.hotkey, position)
+            } else if automation.compound_fees {
+                Self::compound_position_fees(netuid, &coldkey, &automation.hotkey, position)
+            } else {
+                Ok(())
+            };
+            if result.is_ok() {
+                TransactionOutcome::Commit(result)
+            } else {
+                TransactionOutcome::Rollback(result)
+            }
+        });
+
+        if let Err(error) = result {
+            log::debug!(
+                "Automation of position {:?} on subnet {:?} failed: {:?}",
+                position_id,
+                netuid,
+                error
+            );
+        }
+
+        // Recentering swaps part of the position through the pool
+        if recenter {
+            T::WeightInfo::recenter_position()
+        } else {
+            T::WeightInfo::automate_position()
+        }
+    }
+
+    /// Reinvests accrued fees of the position into its liquidity.
+    ///
+    /// Fees are left to accumulate until they are worth at least `MinimumLiquidity`. The part
+    /// of the fees that doesn't match the position's token ratio is credited to the owner.
+    fn compound_position_fees(
+        netuid: NetUid,
+        coldkey_account_id: &T::AccountId,
+        hotkey_account_id: &T::AccountId,
+        mut position: Position<T>,
+    ) -> Result<(), DispatchError> {
+        let current_price_sqrt = Pallet::<T>::current_price_sqrt(netuid);
+        let (fee_tao, fee_alpha) = position.collect_fees();
+
+        let liquidity = Self::liquidity_for_amounts(
+            position.tick_low,
+            position.tick_high,
+            current_price_sqrt,
+            fee_tao,
+            fee_alpha,
+        )?;
+        if liquidity < T::MinimumLiquidity::get() {
+            return Ok(());
+        }
+
+        let (tao, alpha) = Self::increase_position_liquidity(netuid, &mut position, liquidity)?;
+        Positions::<T>::insert((netuid, coldkey_account_id, position.id), position.clone());
+
+        T::BalanceOps::increase_provided_tao_reserve(netuid, tao);
+        T::BalanceOps::increase_provided_alpha_reserve(netuid, alpha);
+
+        Self::credit_position_owner(
+            netuid,
+            coldkey_account_id,
+            hotkey_account_id,
+            fee_tao.saturating_sub(tao),
+            fee_alpha.saturating_sub(alpha),
+        )?;
+
+        Self::deposit_event(Event::PositionFeesCompounded {
+            coldkey: coldkey_account_id.clone(),
+            netuid,
+            position_id: position.id,
+            liquidity,
+            tao,
+            alpha,
+        });
+
+        Ok(())
+    }
+
+    /// Moves an out of range position to a range of the same width around the current tick.
+    ///
+    /// The withdrawn tokens and accrued fees are rebalanced by swapping half of the value
+    /// difference through the pool, then provided to the new range. Leftovers are credited to
+    /// the owner.
+    fn recenter_position(
+        netuid: NetUid,
+        coldkey_account_id: &T::AccountId,
+        hotkey_account_id: &T::AccountId,
+        mut position: Position<T>,
+    ) -> Result<(), DispatchError> {
+        // Withdraw the position together with its fees
+        let (fee_tao, fee_alpha) = position.collect_fees();
+        let (tao, alpha) = position.to_token_amounts(Pallet::<T>::current_price_sqrt(netuid))?;
+
+        Self::remove_liquidity_at_index(netuid, position.tick_low, position.liquidity, false);
+        Self::remove_liquidity_at_index(netuid, position.tick_high, position.liquidity, true);
+        Self::update_liquidity_if_needed(
+            netuid,
+            position.tick_low,
+            position.tick_high,
+            (position.liquidity as i128).neg(),
+        );
+        T::BalanceOps::decrease_provided_tao_reserve(netuid, tao);
+        T::BalanceOps::decrease_provided_alpha_reserve(netuid, alpha);
+
+        let mut tao_available = tao.saturating_add(fee_tao);
+        let mut alpha_available = alpha.saturating_add(fee_alpha);
+
+        // Swap half of the value difference to get closer to the 50/50 ratio of a centered range
+        let current_price_sqrt = Pallet::<T>::current_price_sqrt(netuid);
+        let price = current_price_sqrt.saturating_mul(current_price_sqrt);
+        let tao_value = U64F64::saturating_from_num(tao_available);
+        let alpha_value = U64F64::saturating_from_num(alpha_available).saturating_mul(price);
+        let two = U64F64::saturating_from_num(2);
+        if tao_value > alpha_value {
+            let amount = tao_value
+                .saturating_sub(alpha_value)
+                .safe_div(two)
+                .saturating_to_num::<u64>();
+            let (paid, received) = Self::swap_for_position(netuid, OrderType::Buy, amount)?;
+            tao_available = tao_available.saturating_sub(paid);
+            alpha_available = alpha_available.saturating_add(received);
+        } else if alpha_value > tao_value {
+            let amount = alpha_value
+                .saturating_sub(tao_value)
+                .safe_div(two)
+                .safe_div(price)
+                .saturating_to_num::<u64>();
+            let (paid, received) = Self::swap_for_position(netuid, OrderType::Sell, amount)?;
+            alpha_available = alpha_available.saturating_sub(paid);
+            tao_available = tao_available.saturating_add(received);
+        }
+
+        // New range of the same width around the current tick
+        let width = position
+            .tick_high
+            .get()
+            .saturating_sub(position.tick_low.get());
+        let current_tick = TickIndex::current_bounded::<T>(netuid);
+        let tick_low = current_tick.saturating_sub(width.saturating_div(2));
+        let tick_high = tick_low.saturating_add(width);
+        ensure!(tick_low < tick_high, Error::<T>::InvalidTickRange);
+
+        let current_price_sqrt = Pallet::<T>::current_price_sqrt(netuid);
+        let liquidity = Self::liquidity_for_amounts(
+            tick_low,
+            tick_high,
+            current_price_sqrt,
+            tao_available,
+            alpha_available,
+        )?;
+        ensure!(
+            liquidity >= T::MinimumLiquidity::get(),
+            Error::<T>::InvalidLiquidityValue
+        );
+
+        // Ticks go first so that the new position takes the right fee snapshot
+        Self::add_liquidity_at_index(netuid, tick_low, liquidity, false);
+        Self::add_liquidity_at_index(netuid, tick_high, liquidity, true);
+        Self::update_liquidity_if_needed(netuid, tick_low, tick_high, liquidity as i128);
+
+        let position = Position::new(position.id, netuid, tick_low, tick_high, liquidity);
+        let (tao, alpha) = position.to_token_amounts(current_price_sqrt)?;
+        Positions::<T>::insert((netuid, coldkey_account_id, position.id), position.clone());
+
+        T::BalanceOps::increase_provided_tao_reserve(netuid, tao);
+        T::BalanceOps::increase_provided_alpha_reserve(netuid, alpha);
+
+        Self::credit_position_owner(
+            netuid,
+            coldkey_account_id,
+            hotkey_account_id,
+            tao_available.saturating_sub(tao),
+            alpha_available.saturating_sub(alpha),
+        )?;
+
+        Self::deposit_event(Event::PositionRecentered {
+            coldkey: coldkey_account_id.clone(),
+            netuid,
+            position_id: position.id,
+            liquidity,
+            tick_low,
+            tick_high,
+        });
+
+        Ok(())
+    }
+
+    /// Adds liquidity to an existing position
+    ///
+    /// Returns the (TAO, Alpha) amounts the added liquidity is worth at the current price.
+    fn increase_position_liquidity(
+        netuid: NetUid,
+        position: &mut Position<T>,
+        liquidity: u64,
+    ) -> Result<(u64, u64), Error<T>> {
+        let mut delta = position.clone();
+        delta.liquidity = liquidity;
+        let (tao, alpha) = delta.to_token_amounts(Pallet::<T>::current_price_sqrt(netuid))?;
+
+        Self::add_liquidity_at_index(netuid, position.tick_low, liquidity, false);
+        Self::add_liquidity_at_index(netuid, position.tick_high, liquidity, true);
+        Self::update_liquidity_if_needed(
+            netuid,
+            position.tick_low,
+            position.tick_high,
+            liquidity as i128,
+        );
+
+        position.liquidity = position.liquidity.saturating_add(liquidity);
+
+        Ok((tao, alpha))
+    }
+
+    /// Swaps tokens of an automated position through the pool, bypassing the user's balances.
+    ///
+    /// Returns the amount spent (including fees) and the amount received.
+    fn swap_for_position(
+        netuid: NetUid,
+        order_type: OrderType,
+        amount: u64,
+    ) -> Result<(u64, u64), Error<T>> {
+        if amount == 0 {
+            return Ok((0, 0));
+        }
+
+        let limit_sqrt_price = match order_type {
+            OrderType::Buy => TickIndex::max_sqrt_price(),
+            OrderType::Sell => TickIndex::min_sqrt_price(),
+        };
+        let result = Self::swap_inner(netuid, order_type, amount, limit_sqrt_price, false)?;
+
+        // Check if reserves are overused
+        let checked_reserve = match order_type {
+            OrderType::Buy => T::SubnetInfo::alpha_reserve(netuid),
+            OrderType::Sell => T::SubnetInfo::tao_reserve(netuid),
+        };
+        ensure!(
+            checked_reserve >= result.amount_paid_out,
+            Error::<T>::InsufficientLiquidity
+        );
+
+        match order_type {
+            OrderType::Buy => {
+                T::BalanceOps::increase_provided_tao_reserve(netuid, result.amount_paid_in);
+                T::BalanceOps::decrease_provided_alpha_reserve(netuid, result.amount_paid_out);
+            }
+            OrderType::Sell => {
+                T::BalanceOps::increase_provided_alpha_reserve(netuid, result.amount_paid_in);
+                T::BalanceOps::decrease_provided_tao_reserve(netuid, result.amount_paid_out);
+            }
+        }
+
+        Ok((
+            result.amount_paid_in.saturating_add(result.fee_paid),
+            result.amount_paid_out,
+        ))
+    }
+
+    /// Returns the maximum liquidity that the given token amounts can provide to a range at the
+    /// current price
+    ///
+    /// Pseudocode:
+    ///     if sqrt_price_curr < sqrt_pa:
+    ///         L = alpha / (1 / sqrt_pa - 1 / sqrt_pb)
+    ///     elif sqrt_price_curr > sqrt_pb:
+    ///         L = tao / (sqrt_pb - sqrt_pa)
+    ///     else:
+    ///         L = min(tao / (sqrt_price_curr - sqrt_pa), alpha / (1 / sqrt_price_curr - 1 / sqrt_pb))
+    ///
+    fn liquidity_for_amounts(
+        tick_low: TickIndex,
+        tick_high: TickIndex,
+        sqrt_price_curr: SqrtPrice,
+        tao: u64,
+        alpha: u64,
+    ) -> Result<u64, Error<T>> {
+        let one = U64F64::saturating_from_num(1);
+        let sqrt_pa = tick_low
+            .try_to_sqrt_price()
+            .map_err(|_| Error::<T>::InvalidTickRange)?;
+        let sqrt_pb = tick_high
+            .try_to_sqrt_price()
+            .map_err(|_| Error::<T>::InvalidTickRange)?;
+        let tao = U64F64::saturating_from_num(tao);
+        let alpha = U64F64::saturating_from_num(alpha);
+
+        // Zero denominator means the amount doesn't constrain liquidity
+        let liquidity_from_tao = |sqrt_price: SqrtPrice| {
+            tao.safe_div_or(sqrt_price.saturating_sub(sqrt_pa), U64F64::max_value())
+        };
+        let liquidity_from_alpha = |sqrt_price: SqrtPrice| {
+            let denom = one
+                .safe_div(sqrt_price)
+                .saturating_sub(one.safe_div(sqrt_pb));
+            alpha.safe_div_or(denom, U64F64::max_value())
+        };
+
+        let liquidity = if sqrt_price_curr < sqrt_pa {
+            liquidity_from_alpha(sqrt_pa)
+        } else if sqrt_price_curr > sqrt_pb {
+            liquidity_from_tao(sqrt_pb)
+        } else {
+            liquidity_from_tao(sqrt_price_curr).min(liquidity_from_alpha(sqrt_price_curr))
+        };
+
+        Ok(liquidity.saturating_to_num::<u64>())
+    }
+
+    /// Credits tokens left over by automated operations to the position owner.
+    fn credit_position_owner(
+        netuid: NetUid,
+        coldkey_account_id: &T::AccountId,
+        hotkey_account_id: &T::AccountId,
+        tao: u64,
+        alpha: u64,
+    ) -> Result<(), DispatchError> {
+        if tao > 0 {
+            T::BalanceOps::increase_balance(coldkey_account_id, tao);
+        }
+        if alpha > 0 {
+            T::BalanceOps::increase_stake(coldkey_account_id, hotkey_account_id, netuid, alpha)?;
+        }
+        Ok(())
+    }
+
     /// Adds or updates liquidity at a specific tick index for a subnet
     ///
     /// # Arguments
@@ -1271,12 +2104,24 @@ impl<T: Config> SwapHandler<T::AccountId> for Pallet<T> {
     fn is_user_liquidity_enabled(netuid: NetUid) -> bool {
         EnabledUserLiquidity::<T>::get(netuid)
     }
+
+    fn swap_coldkey_positions(old_coldkey: &T::AccountId, new_coldkey: &T::AccountId) -> Weight {
+        Self::do_swap_coldkey_positions(old_coldkey, new_coldkey)
+    }
+
+    fn set_protocol_liquidity_policy(
+        netuid: NetUid,
+        policy: ProtocolLiquidityPolicy,
+    ) -> DispatchResult {
+        Self::do_set_protocol_liquidity_policy(netuid, policy).map_err(Into::into)
+    }
 }
 
 #[derive(Debug, PartialEq)]
 struct SwapStepResult {
     amount_to_take: u64,
     fee_paid: u64,
+    protocol_fee: u64,
     delta_in: u64,
     delta_out: u64,
 }
@@ -3,11 +3,14 @@ use core::ops::Neg;
 
 use frame_support::{PalletId, pallet_prelude::*, traits::Get};
 use frame_system::pallet_prelude::*;
+use sp_runtime::Perbill;
 use substrate_fixed::types::U64F64;
 use subtensor_runtime_common::{BalanceOps, NetUid, SubnetInfo};
+use subtensor_swap_interface::{OrderType, ProtocolLiquidityPolicy};
 
 use crate::{
-    position::{Position, PositionId},
+    migrations,
+    position::{Position, PositionAutomation, PositionId},
     tick::{LayerLevel, Tick, TickIndex},
     weights::WeightInfo,
 };
@@ -17,6 +20,8 @@ pub use pallet::*;
 mod impls;
 #[cfg(test)]
 mod tests;
+#[cfg(any(feature = "try-runtime", feature = "fuzzing", test))]
+mod try_state;
 
 #[allow(clippy::module_inception)]
 #[frame_support::pallet]
@@ -61,6 +66,23 @@ mod pallet {
         #[pallet::constant]
         type MinimumReserve: Get<NonZeroU64>;
 
+        /// Number of blocks between runs of automated position management (fee compounding and
+        /// range recentering)
+        #[pallet::constant]
+        type PositionAutomationInterval: Get<u32>;
+
+        /// The maximum number of automated positions processed in a single run
+        #[pallet::constant]
+        type MaxAutomatedPositionsPerRun: Get<u32>;
+
+        /// The minimum width in ticks of a concentrated protocol liquidity band
+        #[pallet::constant]
+        type MinProtocolBandWidth: Get<u32>;
+
+        /// The share of protocol liquidity that stays full range when it is concentrated
+        #[pallet::constant]
+        type ProtocolFullRangeShare: Get<Perbill>;
+
         /// Weight information for extrinsics in this pallet.
         type WeightInfo: WeightInfo;
     }
@@ -103,12 +125,23 @@ mod pallet {
     #[pallet::storage]
     pub type CurrentLiquidity<T> = StorageMap<_, Twox64Concat, NetUid, u64, ValueQuery>;
 
+    /// Protocol owned part of the current liquidity, with the range of ticks it holds in as
+    /// `(tick_low, tick_high, liquidity)`. Recomputed once the current tick leaves the range.
+    #[pallet::storage]
+    pub type CurrentProtocolLiquidity<T> =
+        StorageMap<_, Twox64Concat, NetUid, (TickIndex, TickIndex, u64), OptionQuery>;
+
     /// Indicates whether a subnet has been switched to V3 swap from V2.
     /// If `true`, the subnet is permanently on V3 swap mode allowing add/remove liquidity
     /// operations. Once set to `true` for a subnet, it cannot be changed back to `false`.
     #[pallet::storage]
     pub type EnabledUserLiquidity<T> = StorageMap<_, Twox64Concat, NetUid, bool, ValueQuery>;
 
+    /// Shape of the protocol owned liquidity per subnet
+    #[pallet::storage]
+    pub type ProtocolLiquidityPolicies<T> =
+        StorageMap<_, Twox64Concat, NetUid, ProtocolLiquidityPolicy, ValueQuery>;
+
     /// Storage for user positions, using subnet ID and account ID as keys
     /// The value is a bounded vector of Position structs with details about the liquidity positions
     #[pallet::storage]
@@ -127,6 +160,11 @@ mod pallet {
     #[pallet::storage]
     pub type LastPositionId<T> = StorageValue<_, u128, ValueQuery>;
 
+    /// Lookup index from position ID to the subnet and account that currently own the position
+    #[pallet::storage]
+    pub type PositionOwners<T: Config> =
+        StorageMap<_, Twox64Concat, PositionId, (NetUid, T::AccountId), OptionQuery>;
+
     /// Tick index bitmap words storage
     #[pallet::storage]
     pub type TickIndexBitmapWords<T: Config> = StorageNMap<
@@ -140,6 +178,20 @@ mod pallet {
         ValueQuery,
     >;
 
+    /// Automation settings of user positions, keyed by position ID
+    #[pallet::storage]
+    pub type PositionAutomations<T: Config> =
+        StorageMap<_, Twox64Concat, PositionId, PositionAutomation<T::AccountId>, OptionQuery>;
+
+    /// The last position processed by the automation run, next run continues after it
+    #[pallet::storage]
+    pub type PositionAutomationCursor<T> = StorageValue<_, PositionId, OptionQuery>;
+
+    /// Storage for the migration run status.
+    #[pallet::storage]
+    pub type HasMigrationRun<T: Config> =
+        StorageMap<_, Identity, BoundedVec<u8, ConstU32<128>>, bool, ValueQuery>;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -224,6 +276,92 @@ mod pallet {
             /// the upper tick
             tick_high: TickIndex,
         },
+
+        /// Event emitted when a liquidity position changes owner.
+        PositionTransferred {
+            /// The subnet identifier
+            netuid: NetUid,
+            /// Unique identifier for the liquidity position
+            position_id: PositionId,
+            /// The coldkey account that owned the position
+            from: T::AccountId,
+            /// The coldkey account that owns the position now
+            to: T::AccountId,
+        },
+
+        /// Event emitted when automation settings of a position are updated.
+        PositionAutomationSet {
+            /// The coldkey account that owns the position
+            coldkey: T::AccountId,
+            /// The subnet identifier
+            netuid: NetUid,
+            /// Unique identifier for the liquidity position
+            position_id: PositionId,
+            /// Whether accrued fees are reinvested into the position
+            compound_fees: bool,
+            /// Whether the position is re-ranged around the current tick when out of range
+            keep_centered: bool,
+        },
+
+        /// Event emitted when accrued fees are reinvested into a position.
+        PositionFeesCompounded {
+            /// The coldkey account that owns the position
+            coldkey: T::AccountId,
+            /// The subnet identifier
+            netuid: NetUid,
+            /// Unique identifier for the liquidity position
+            position_id: PositionId,
+            /// The amount of liquidity added to the position
+            liquidity: u64,
+            /// The amount of TAO fees reinvested
+            tao: u64,
+            /// The amount of Alpha fees reinvested
+            alpha: u64,
+        },
+
+        /// Event emitted when a position is re-ranged around the current tick.
+        PositionRecentered {
+            /// The coldkey account that owns the position
+            coldkey: T::AccountId,
+            /// The subnet identifier
+            netuid: NetUid,
+            /// Unique identifier for the liquidity position
+            position_id: PositionId,
+            /// The new liquidity of the position
+            liquidity: u64,
+            /// the new lower tick
+            tick_low: TickIndex,
+            /// the new upper tick
+            tick_high: TickIndex,
+        },
+
+        /// Event emitted when the protocol liquidity policy has been updated for a subnet
+        ProtocolLiquidityPolicySet {
+            netuid: NetUid,
+            policy: ProtocolLiquidityPolicy,
+        },
+
+        /// Event emitted when a swap is executed against a subnet's liquidity pool.
+        Swapped {
+            /// The subnet identifier
+            netuid: NetUid,
+            /// Buy (TAO in, Alpha out) or sell (Alpha in, TAO out)
+            order_type: OrderType,
+            /// The amount of input tokens swapped, excluding fees
+            amount_paid_in: u64,
+            /// The amount of output tokens received
+            amount_paid_out: u64,
+            /// The square root of the Alpha price before the swap
+            sqrt_price_before: U64F64,
+            /// The square root of the Alpha price after the swap
+            sqrt_price_after: U64F64,
+            /// The number of initialized ticks crossed by the swap
+            ticks_crossed: u16,
+            /// The part of the fee accrued to user liquidity positions
+            lp_fee: u64,
+            /// The part of the fee accrued to protocol owned liquidity
+            protocol_fee: u64,
+        },
     }
 
     #[pallet::error]
@@ -267,6 +405,38 @@ mod pallet {
 
         /// User liquidity operations are disabled for this subnet
         UserLiquidityDisabled,
+
+        /// The position cannot be transferred to the given account
+        InvalidPositionRecipient,
+
+        /// The protocol liquidity policy has no bands, a zero weight band or a band width out
+        /// of the allowed range
+        InvalidProtocolLiquidityPolicy,
+
+        /// The hotkey is not owned by the coldkey of the position
+        NonAssociatedHotkey,
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(block_number: BlockNumberFor<T>) -> Weight {
+            Self::run_position_automation(block_number)
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            let mut weight = Weight::from_parts(0, 0);
+
+            weight = weight
+                // Populate the position ID -> owner lookup index
+                .saturating_add(migrations::migrate_populate_position_owners::<T>());
+
+            weight
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn try_state(_n: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
+            Self::do_try_state()
+        }
     }
 
     #[pallet::call]
@@ -567,5 +737,122 @@ mod pallet {
 
             Ok(())
         }
+
+        /// Transfer a liquidity position to another coldkey without withdrawing it.
+        ///
+        /// The position keeps its range, liquidity and uncollected fees. The new owner collects
+        /// the fees on the next modification or removal of the position.
+        ///
+        /// Parameters:
+        /// - origin: The origin of the transaction, must be the current owner of the position
+        /// - netuid: Subnet ID
+        /// - position_id: ID of the position to transfer
+        /// - new_coldkey: The coldkey that receives the position
+        ///
+        /// Emits `Event::PositionTransferred` on success
+        #[pallet::call_index(5)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::transfer_position())]
+        pub fn transfer_position(
+            origin: OriginFor<T>,
+            netuid: NetUid,
+            position_id: PositionId,
+            new_coldkey: T::AccountId,
+        ) -> DispatchResult {
+            let coldkey = ensure_signed(origin)?;
+
+            // Ensure that the subnet exists.
+            ensure!(
+                T::SubnetInfo::exists(netuid.into()),
+                Error::<T>::SubNetworkDoesNotExist
+            );
+
+            Self::do_transfer_position(netuid, &coldkey, &new_coldkey, position_id)?;
+
+            Self::deposit_event(Event::PositionTransferred {
+                netuid,
+                position_id,
+                from: coldkey,
+                to: new_coldkey,
+            });
+
+            Ok(())
+        }
+
+        /// Configure automated management of a liquidity position.
+        ///
+        /// Automated positions are processed every `PositionAutomationInterval` blocks, at most
+        /// `MaxAutomatedPositionsPerRun` at a time:
+        /// - with `compound_fees`, accrued fees are reinvested into the position, and the part
+        ///   of the fees that doesn't fit the position's token ratio is credited to the owner;
+        /// - with `keep_centered`, a position that fell out of range is moved to a range of the
+        ///   same width around the current tick, swapping part of its tokens through the pool.
+        ///
+        /// Disabling both flags removes the automation. Automation requires user liquidity to be
+        /// enabled on the subnet and stops while it is disabled.
+        ///
+        /// Parameters:
+        /// - origin: The origin of the transaction, must be the owner of the position
+        /// - hotkey: The hotkey where Alpha comes from or goes to during automated operations
+        /// - netuid: Subnet ID
+        /// - position_id: ID of the position
+        /// - compound_fees: Reinvest accrued fees into the position
+        /// - keep_centered: Re-range the position around the current tick when out of range
+        ///
+        /// Emits `Event::PositionAutomationSet` on success
+        #[pallet::call_index(6)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::set_position_automation())]
+        pub fn set_position_automation(
+            origin: OriginFor<T>,
+            hotkey: T::AccountId,
+            netuid: NetUid,
+            position_id: PositionId,
+            compound_fees: bool,
+            keep_centered: bool,
+        ) -> DispatchResult {
+            let coldkey = ensure_signed(origin)?;
+
+            // Ensure that the subnet exists.
+            ensure!(
+                T::SubnetInfo::exists(netuid.into()),
+                Error::<T>::SubNetworkDoesNotExist
+            );
+
+            ensure!(
+                Positions::<T>::contains_key((netuid, &coldkey, position_id)),
+                Error::<T>::LiquidityNotFound
+            );
+
+            if compound_fees || keep_centered {
+                ensure!(
+                    EnabledUserLiquidity::<T>::get(netuid),
+                    Error::<T>::UserLiquidityDisabled
+                );
+                ensure!(
+                    T::BalanceOps::coldkey_owns_hotkey(&coldkey, &hotkey),
+                    Error::<T>::NonAssociatedHotkey
+                );
+
+                PositionAutomations::<T>::insert(
+                    position_id,
+                    PositionAutomation {
+                        hotkey,
+                        compound_fees,
+                        keep_centered,
+                    },
+                );
+            } else {
+                PositionAutomations::<T>::remove(position_id);
+            }
+
+            Self::deposit_event(Event::PositionAutomationSet {
+                coldkey,
+                netuid,
+                position_id,
+                compound_fees,
+                keep_centered,
+            });
+
+            Ok(())
+        }
     }
 }
@@ -8,9 +8,10 @@ use sp_arithmetic::helpers_128bit;
 use sp_runtime::DispatchError;
 use substrate_fixed::types::U96F32;
 use subtensor_runtime_common::NetUid;
+use subtensor_swap_interface::{ProtocolLiquidityBand, ProtocolLiquidityPolicy};
 
 use super::*;
-use crate::{OrderType, SqrtPrice, mock::*};
+use crate::{OrderType, SqrtPrice, mock::*, tick::ActiveTickIndexManager};
 
 // this function is used to convert price (NON-SQRT price!) to TickIndex. it's only utility for
 // testing, all the implementation logic is based on sqrt prices
@@ -575,6 +576,499 @@ fn test_remove_liquidity_nonexisting_position() {
     });
 }
 
+#[test]
+fn test_transfer_position() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+        let tick_low = price_to_tick(0.125);
+        let tick_high = price_to_tick(0.5);
+        let liquidity = 2_000_000_000_u64;
+
+        assert_ok!(Pallet::<Test>::maybe_initialize_v3(netuid));
+
+        let (position_id, _, _) = Pallet::<Test>::do_add_liquidity(
+            netuid,
+            &OK_COLDKEY_ACCOUNT_ID,
+            &OK_HOTKEY_ACCOUNT_ID,
+            tick_low,
+            tick_high,
+            liquidity,
+        )
+        .unwrap();
+        assert_eq!(
+            PositionOwners::<Test>::get(position_id),
+            Some((netuid, OK_COLDKEY_ACCOUNT_ID))
+        );
+
+        let position_before =
+            Positions::<Test>::get((netuid, OK_COLDKEY_ACCOUNT_ID, position_id)).unwrap();
+        let liquidity_before = CurrentLiquidity::<Test>::get(netuid);
+
+        // Only the owner can transfer the position
+        assert_noop!(
+            Swap::transfer_position(
+                RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID_2),
+                netuid,
+                position_id,
+                OK_COLDKEY_ACCOUNT_ID_2,
+            ),
+            Error::<Test>::LiquidityNotFound
+        );
+
+        // Can't transfer to self or to the protocol account
+        assert_noop!(
+            Swap::transfer_position(
+                RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+                netuid,
+                position_id,
+                OK_COLDKEY_ACCOUNT_ID,
+            ),
+            Error::<Test>::InvalidPositionRecipient
+        );
+        assert_noop!(
+            Swap::transfer_position(
+                RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+                netuid,
+                position_id,
+                Pallet::<Test>::protocol_account_id(),
+            ),
+            Error::<Test>::InvalidPositionRecipient
+        );
+
+        assert_noop!(
+            Swap::transfer_position(
+                RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+                NON_EXISTENT_NETUID.into(),
+                position_id,
+                OK_COLDKEY_ACCOUNT_ID_2,
+            ),
+            Error::<Test>::SubNetworkDoesNotExist
+        );
+
+        assert_ok!(Swap::transfer_position(
+            RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+            netuid,
+            position_id,
+            OK_COLDKEY_ACCOUNT_ID_2,
+        ));
+
+        // Position is re-keyed unchanged, pool state is untouched
+        assert!(Positions::<Test>::get((netuid, OK_COLDKEY_ACCOUNT_ID, position_id)).is_none());
+        assert_eq!(
+            Positions::<Test>::get((netuid, OK_COLDKEY_ACCOUNT_ID_2, position_id)),
+            Some(position_before)
+        );
+        assert_eq!(
+            PositionOwners::<Test>::get(position_id),
+            Some((netuid, OK_COLDKEY_ACCOUNT_ID_2))
+        );
+        assert_eq!(CurrentLiquidity::<Test>::get(netuid), liquidity_before);
+
+        System::assert_last_event(
+            Event::<Test>::PositionTransferred {
+                netuid,
+                position_id,
+                from: OK_COLDKEY_ACCOUNT_ID,
+                to: OK_COLDKEY_ACCOUNT_ID_2,
+            }
+            .into(),
+        );
+
+        // The new owner can remove the position, which also clears the index
+        assert_ok!(Pallet::<Test>::do_remove_liquidity(
+            netuid,
+            &OK_COLDKEY_ACCOUNT_ID_2,
+            position_id
+        ));
+        assert!(PositionOwners::<Test>::get(position_id).is_none());
+    });
+}
+
+#[test]
+fn test_transfer_position_max_positions() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+        let tick_low = price_to_tick(0.125);
+        let tick_high = price_to_tick(0.5);
+        let liquidity = 2_000_000_000_u64;
+
+        assert_ok!(Pallet::<Test>::maybe_initialize_v3(netuid));
+
+        let (position_id, _, _) = Pallet::<Test>::do_add_liquidity(
+            netuid,
+            &OK_COLDKEY_ACCOUNT_ID,
+            &OK_HOTKEY_ACCOUNT_ID,
+            tick_low,
+            tick_high,
+            liquidity,
+        )
+        .unwrap();
+
+        for _ in 0..MaxPositions::get() {
+            assert_ok!(Pallet::<Test>::do_add_liquidity(
+                netuid,
+                &OK_COLDKEY_ACCOUNT_ID_2,
+                &OK_HOTKEY_ACCOUNT_ID_2,
+                tick_low,
+                tick_high,
+                liquidity,
+            ));
+        }
+
+        assert_noop!(
+            Swap::transfer_position(
+                RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+                netuid,
+                position_id,
+                OK_COLDKEY_ACCOUNT_ID_2,
+            ),
+            Error::<Test>::MaxPositionsExceeded
+        );
+    });
+}
+
+#[test]
+fn test_swap_coldkey_positions() {
+    new_test_ext().execute_with(|| {
+        let tick_low = price_to_tick(0.125);
+        let tick_high = price_to_tick(0.5);
+        let liquidity = 2_000_000_000_u64;
+        let old_coldkey = OK_COLDKEY_ACCOUNT_ID;
+        let new_coldkey = 3;
+
+        let position_ids = [NetUid::from(1), NetUid::from(2)].map(|netuid| {
+            assert_ok!(Pallet::<Test>::maybe_initialize_v3(netuid));
+            let (position_id, _, _) = Pallet::<Test>::do_add_liquidity(
+                netuid,
+                &old_coldkey,
+                &OK_HOTKEY_ACCOUNT_ID,
+                tick_low,
+                tick_high,
+                liquidity,
+            )
+            .unwrap();
+            PositionAutomations::<Test>::insert(
+                position_id,
+                PositionAutomation {
+                    hotkey: OK_HOTKEY_ACCOUNT_ID,
+                    compound_fees: true,
+                    keep_centered: true,
+                },
+            );
+            (netuid, position_id)
+        });
+
+        Pallet::<Test>::do_swap_coldkey_positions(&old_coldkey, &new_coldkey);
+
+        for (netuid, position_id) in position_ids {
+            assert!(Positions::<Test>::get((netuid, old_coldkey, position_id)).is_none());
+            assert!(Positions::<Test>::get((netuid, new_coldkey, position_id)).is_some());
+            assert_eq!(
+                PositionOwners::<Test>::get(position_id),
+                Some((netuid, new_coldkey))
+            );
+            assert_eq!(Pallet::<Test>::count_positions(netuid, &old_coldkey), 0);
+            assert!(!PositionAutomations::<Test>::contains_key(position_id));
+        }
+
+        // Protocol positions are not affected
+        let protocol_account_id = Pallet::<Test>::protocol_account_id();
+        assert_eq!(
+            Pallet::<Test>::count_positions(NetUid::from(1), &protocol_account_id),
+            1
+        );
+    });
+}
+
+#[test]
+fn test_set_position_automation() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+        let tick_low = price_to_tick(0.125);
+        let tick_high = price_to_tick(0.5);
+
+        assert_ok!(Pallet::<Test>::maybe_initialize_v3(netuid));
+        let (position_id, _, _) = Pallet::<Test>::do_add_liquidity(
+            netuid,
+            &OK_COLDKEY_ACCOUNT_ID,
+            &OK_HOTKEY_ACCOUNT_ID,
+            tick_low,
+            tick_high,
+            2_000_000_000,
+        )
+        .unwrap();
+
+        // Only the owner can automate the position
+        assert_noop!(
+            Swap::set_position_automation(
+                RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID_2),
+                OK_HOTKEY_ACCOUNT_ID_2,
+                netuid,
+                position_id,
+                true,
+                false,
+            ),
+            Error::<Test>::LiquidityNotFound
+        );
+
+        // The hotkey must belong to the owner of the position
+        assert_noop!(
+            Swap::set_position_automation(
+                RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+                NOT_OWNED_HOTKEY_ACCOUNT_ID,
+                netuid,
+                position_id,
+                true,
+                false,
+            ),
+            Error::<Test>::NonAssociatedHotkey
+        );
+
+        assert_ok!(Swap::set_position_automation(
+            RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+            OK_HOTKEY_ACCOUNT_ID,
+            netuid,
+            position_id,
+            true,
+            false,
+        ));
+        assert_eq!(
+            PositionAutomations::<Test>::get(position_id),
+            Some(PositionAutomation {
+                hotkey: OK_HOTKEY_ACCOUNT_ID,
+                compound_fees: true,
+                keep_centered: false,
+            })
+        );
+
+        // Disabling everything removes the automation
+        assert_ok!(Swap::set_position_automation(
+            RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+            OK_HOTKEY_ACCOUNT_ID,
+            netuid,
+            position_id,
+            false,
+            false,
+        ));
+        assert!(!PositionAutomations::<Test>::contains_key(position_id));
+
+        // Removing the position removes its automation
+        assert_ok!(Swap::set_position_automation(
+            RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+            OK_HOTKEY_ACCOUNT_ID,
+            netuid,
+            position_id,
+            true,
+            true,
+        ));
+        assert_ok!(Pallet::<Test>::do_remove_liquidity(
+            netuid,
+            &OK_COLDKEY_ACCOUNT_ID,
+            position_id
+        ));
+        assert!(!PositionAutomations::<Test>::contains_key(position_id));
+    });
+}
+
+#[test]
+fn test_position_automation_compounds_fees() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+        let liquidity = 2_000_000_000_000_u64;
+
+        assert_ok!(Pallet::<Test>::maybe_initialize_v3(netuid));
+        let (position_id, _, _) = Pallet::<Test>::do_add_liquidity(
+            netuid,
+            &OK_COLDKEY_ACCOUNT_ID,
+            &OK_HOTKEY_ACCOUNT_ID,
+            TickIndex::MIN,
+            TickIndex::MAX,
+            liquidity,
+        )
+        .unwrap();
+        assert_ok!(Swap::set_position_automation(
+            RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+            OK_HOTKEY_ACCOUNT_ID,
+            netuid,
+            position_id,
+            true,
+            false,
+        ));
+
+        // Generate fees
+        Pallet::<Test>::do_swap(
+            netuid,
+            OrderType::Buy,
+            liquidity / 10,
+            u64::MAX.into(),
+            false,
+            false,
+        )
+        .unwrap();
+        Pallet::<Test>::do_swap(
+            netuid,
+            OrderType::Sell,
+            liquidity / 10,
+            0_u64.into(),
+            false,
+            false,
+        )
+        .unwrap();
+
+        // Nothing happens outside of the automation interval
+        assert_eq!(Pallet::<Test>::run_position_automation(11), Weight::zero());
+        let position =
+            Positions::<Test>::get((netuid, OK_COLDKEY_ACCOUNT_ID, position_id)).unwrap();
+        assert_eq!(position.liquidity, liquidity);
+
+        assert!(Pallet::<Test>::run_position_automation(10) != Weight::zero());
+
+        let mut position =
+            Positions::<Test>::get((netuid, OK_COLDKEY_ACCOUNT_ID, position_id)).unwrap();
+        assert!(position.liquidity > liquidity);
+        assert_eq!(position.tick_low, TickIndex::MIN);
+        assert_eq!(position.tick_high, TickIndex::MAX);
+
+        // Fees were collected
+        assert_eq!(position.collect_fees(), (0, 0));
+    });
+}
+
+#[test]
+fn test_position_automation_recenters_position() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+        let tick_low = price_to_tick(0.5);
+        let tick_high = price_to_tick(1.0);
+        let liquidity = 2_000_000_000_u64;
+
+        assert_ok!(Pallet::<Test>::maybe_initialize_v3(netuid));
+        let (position_id, _, _) = Pallet::<Test>::do_add_liquidity(
+            netuid,
+            &OK_COLDKEY_ACCOUNT_ID,
+            &OK_HOTKEY_ACCOUNT_ID,
+            tick_low,
+            tick_high,
+            liquidity,
+        )
+        .unwrap();
+        assert_ok!(Swap::set_position_automation(
+            RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+            OK_HOTKEY_ACCOUNT_ID,
+            netuid,
+            position_id,
+            false,
+            true,
+        ));
+
+        // The position is above the current price, so it is out of range
+        assert!(TickIndex::current_bounded::<Test>(netuid) < tick_low);
+
+        Pallet::<Test>::run_position_automation(10);
+
+        let position =
+            Positions::<Test>::get((netuid, OK_COLDKEY_ACCOUNT_ID, position_id)).unwrap();
+        let current_tick = TickIndex::current_bounded::<Test>(netuid);
+        assert!(position.tick_low <= current_tick && current_tick < position.tick_high);
+        assert_eq!(
+            position.tick_high.get() - position.tick_low.get(),
+            tick_high.get() - tick_low.get()
+        );
+        assert!(position.liquidity >= MinimumLiquidity::get());
+
+        // The old ticks are gone
+        assert!(Ticks::<Test>::get(netuid, tick_low).is_none());
+        assert!(Ticks::<Test>::get(netuid, tick_high).is_none());
+    });
+}
+
+#[test]
+fn test_position_automation_stops_with_user_liquidity() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+        let tick_low = price_to_tick(0.5);
+        let tick_high = price_to_tick(1.0);
+
+        assert_ok!(Pallet::<Test>::maybe_initialize_v3(netuid));
+        let (position_id, _, _) = Pallet::<Test>::do_add_liquidity(
+            netuid,
+            &OK_COLDKEY_ACCOUNT_ID,
+            &OK_HOTKEY_ACCOUNT_ID,
+            tick_low,
+            tick_high,
+            2_000_000_000,
+        )
+        .unwrap();
+        assert_ok!(Swap::set_position_automation(
+            RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+            OK_HOTKEY_ACCOUNT_ID,
+            netuid,
+            position_id,
+            false,
+            true,
+        ));
+
+        EnabledUserLiquidity::<Test>::insert(netuid, false);
+        Pallet::<Test>::run_position_automation(10);
+
+        // The out of range position is left untouched
+        let position =
+            Positions::<Test>::get((netuid, OK_COLDKEY_ACCOUNT_ID, position_id)).unwrap();
+        assert_eq!(position.tick_low, tick_low);
+        assert_eq!(position.tick_high, tick_high);
+
+        // No new automation can be set up either
+        assert_noop!(
+            Swap::set_position_automation(
+                RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+                OK_HOTKEY_ACCOUNT_ID,
+                netuid,
+                position_id,
+                true,
+                true,
+            ),
+            Error::<Test>::UserLiquidityDisabled
+        );
+    });
+}
+
+#[test]
+fn test_position_automation_batches() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+        let tick_low = price_to_tick(0.125);
+        let tick_high = price_to_tick(0.5);
+
+        assert_ok!(Pallet::<Test>::maybe_initialize_v3(netuid));
+        for _ in 0..3 {
+            let (position_id, _, _) = Pallet::<Test>::do_add_liquidity(
+                netuid,
+                &OK_COLDKEY_ACCOUNT_ID,
+                &OK_HOTKEY_ACCOUNT_ID,
+                tick_low,
+                tick_high,
+                2_000_000_000,
+            )
+            .unwrap();
+            assert_ok!(Swap::set_position_automation(
+                RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+                OK_HOTKEY_ACCOUNT_ID,
+                netuid,
+                position_id,
+                true,
+                false,
+            ));
+        }
+
+        // MaxAutomatedPositionsPerRun positions are processed, the rest are left for the next run
+        Pallet::<Test>::run_position_automation(10);
+        assert!(PositionAutomationCursor::<Test>::get().is_some());
+
+        Pallet::<Test>::run_position_automation(20);
+        assert!(PositionAutomationCursor::<Test>::get().is_none());
+    });
+}
+
 // cargo test --package pallet-subtensor-swap --lib -- pallet::tests::test_modify_position_basic --exact --show-output
 #[test]
 fn test_modify_position_basic() {
@@ -1452,6 +1946,196 @@ fn test_user_liquidity_disabled() {
     });
 }
 
+#[test]
+fn test_set_protocol_liquidity_policy_guardrails() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+        let band = |width: u32, weight: u16| ProtocolLiquidityBand { width, weight };
+
+        // Too narrow
+        assert_noop!(
+            Pallet::<Test>::do_set_protocol_liquidity_policy(
+                netuid,
+                ProtocolLiquidityPolicy::Concentrated { width: 999 },
+            ),
+            Error::<Test>::InvalidProtocolLiquidityPolicy
+        );
+
+        // Wider than the whole price range
+        assert_noop!(
+            Pallet::<Test>::do_set_protocol_liquidity_policy(
+                netuid,
+                ProtocolLiquidityPolicy::Concentrated { width: u32::MAX },
+            ),
+            Error::<Test>::InvalidProtocolLiquidityPolicy
+        );
+
+        // No bands
+        assert_noop!(
+            Pallet::<Test>::do_set_protocol_liquidity_policy(
+                netuid,
+                ProtocolLiquidityPolicy::MultiBand(BoundedVec::new()),
+            ),
+            Error::<Test>::InvalidProtocolLiquidityPolicy
+        );
+
+        // Zero weight band
+        assert_noop!(
+            Pallet::<Test>::do_set_protocol_liquidity_policy(
+                netuid,
+                ProtocolLiquidityPolicy::MultiBand(BoundedVec::truncate_from(vec![
+                    band(1_000, 1),
+                    band(4_000, 0)
+                ])),
+            ),
+            Error::<Test>::InvalidProtocolLiquidityPolicy
+        );
+
+        assert_noop!(
+            Pallet::<Test>::do_set_protocol_liquidity_policy(
+                NON_EXISTENT_NETUID.into(),
+                ProtocolLiquidityPolicy::Concentrated { width: 1_000 },
+            ),
+            Error::<Test>::SubNetworkDoesNotExist
+        );
+
+        let policy = ProtocolLiquidityPolicy::MultiBand(BoundedVec::truncate_from(vec![
+            band(1_000, 3),
+            band(4_000, 1),
+        ]));
+        assert_ok!(Pallet::<Test>::do_set_protocol_liquidity_policy(
+            netuid,
+            policy.clone()
+        ));
+        assert_eq!(ProtocolLiquidityPolicies::<Test>::get(netuid), policy);
+    });
+}
+
+#[test]
+fn test_concentrated_protocol_liquidity() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+        let protocol_account_id = Pallet::<Test>::protocol_account_id();
+
+        assert_ok!(Pallet::<Test>::maybe_initialize_v3(netuid));
+        let full_range_liquidity = CurrentLiquidity::<Test>::get(netuid);
+        let protocol_tokens = || {
+            Pallet::<Test>::protocol_liquidity_info(netuid)
+                .positions
+                .iter()
+                .fold((0_u64, 0_u64), |(tao, alpha), position| {
+                    (tao + position.tao, alpha + position.alpha)
+                })
+        };
+        let (tao_before, alpha_before) = protocol_tokens();
+
+        assert_ok!(Pallet::<Test>::do_set_protocol_liquidity_policy(
+            netuid,
+            ProtocolLiquidityPolicy::Concentrated { width: 1_000 },
+        ));
+
+        // What the band can't use stays in the full range position
+        let (tao_after, alpha_after) = protocol_tokens();
+        assert_abs_diff_eq!(tao_after, tao_before, epsilon = tao_before / 100);
+        assert_abs_diff_eq!(alpha_after, alpha_before, epsilon = alpha_before / 100);
+
+        // A full range position and a band around the current price
+        assert_eq!(
+            Pallet::<Test>::count_positions(netuid, &protocol_account_id),
+            2
+        );
+        let current_tick = TickIndex::current_bounded::<Test>(netuid);
+        let band = Positions::<Test>::iter_prefix_values((netuid, protocol_account_id))
+            .find(|position| position.tick_low != TickIndex::MIN)
+            .unwrap();
+        assert!(band.tick_low <= current_tick && current_tick < band.tick_high);
+        assert_eq!(band.tick_high.get() - band.tick_low.get(), 1_000);
+
+        // Same tokens provide much deeper liquidity at the current price
+        assert!(CurrentLiquidity::<Test>::get(netuid) > full_range_liquidity * 10);
+
+        // New reserves are added to the existing positions
+        let liquidity_before = CurrentLiquidity::<Test>::get(netuid);
+        Pallet::<Test>::adjust_protocol_liquidity(netuid, 1_000_000_000, 4_000_000_000);
+        assert!(CurrentLiquidity::<Test>::get(netuid) > liquidity_before);
+        let adjusted_band = Positions::<Test>::get((netuid, protocol_account_id, band.id)).unwrap();
+        assert!(adjusted_band.liquidity > band.liquidity);
+
+        // Push the price above the band
+        Pallet::<Test>::do_swap(
+            netuid,
+            OrderType::Buy,
+            1_000_000_000_000,
+            u64::MAX.into(),
+            false,
+            false,
+        )
+        .unwrap();
+        let current_tick = TickIndex::current_bounded::<Test>(netuid);
+        assert!(current_tick >= band.tick_high);
+
+        // The band follows the price
+        Pallet::<Test>::adjust_protocol_liquidity(netuid, 0, 0);
+        assert_eq!(
+            Pallet::<Test>::count_positions(netuid, &protocol_account_id),
+            2
+        );
+        let band = Positions::<Test>::iter_prefix_values((netuid, protocol_account_id))
+            .find(|position| position.tick_low != TickIndex::MIN)
+            .unwrap();
+        assert!(band.tick_low <= current_tick && current_tick < band.tick_high);
+
+        // Switching back to full range leaves a single protocol position
+        assert_ok!(Pallet::<Test>::do_set_protocol_liquidity_policy(
+            netuid,
+            ProtocolLiquidityPolicy::FullRange,
+        ));
+        assert_eq!(
+            Pallet::<Test>::count_positions(netuid, &protocol_account_id),
+            1
+        );
+    });
+}
+
+#[test]
+fn test_protocol_liquidity_info() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+        let policy = ProtocolLiquidityPolicy::MultiBand(BoundedVec::truncate_from(vec![
+            ProtocolLiquidityBand {
+                width: 1_000,
+                weight: 1,
+            },
+            ProtocolLiquidityBand {
+                width: 10_000,
+                weight: 1,
+            },
+        ]));
+
+        // Policy set before the switch to V3 is applied on initialization
+        assert_ok!(Pallet::<Test>::do_set_protocol_liquidity_policy(
+            netuid,
+            policy.clone()
+        ));
+        assert_ok!(Pallet::<Test>::maybe_initialize_v3(netuid));
+
+        let info = Pallet::<Test>::protocol_liquidity_info(netuid);
+        assert_eq!(info.policy, policy);
+        assert_eq!(info.positions.len(), 3);
+        assert!(
+            info.positions
+                .iter()
+                .any(|position| position.tick_low == TickIndex::MIN.get()
+                    && position.tick_high == TickIndex::MAX.get())
+        );
+        assert!(
+            info.positions
+                .iter()
+                .all(|position| position.liquidity > 0 && position.tao > 0 && position.alpha > 0)
+        );
+    });
+}
+
 /// Test correctness of swap fees:
 ///   - Fees are distribued to (concentrated) liquidity providers
 ///
@@ -1896,3 +2580,273 @@ fn test_less_price_movement() {
         });
     });
 }
+
+/// cargo test --package pallet-subtensor-swap --lib -- pallet::tests::test_try_state_holds_after_pool_operations --exact --show-output
+#[test]
+fn test_try_state_holds_after_pool_operations() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+        let tick_low = price_to_tick(0.2);
+        let tick_high = price_to_tick(0.3);
+
+        assert_ok!(Pallet::<Test>::maybe_initialize_v3(netuid));
+        assert_ok!(Pallet::<Test>::do_try_state());
+
+        assert_ok!(Swap::add_liquidity(
+            RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+            OK_HOTKEY_ACCOUNT_ID,
+            netuid,
+            tick_low,
+            tick_high,
+            2_000_000_000,
+        ));
+        assert_ok!(Pallet::<Test>::do_try_state());
+        let position_id = PositionId::from(LastPositionId::<Test>::get());
+
+        // Apply the swap to the mocked reserves the way subtensor does
+        let swap_result = Pallet::<Test>::do_swap(
+            netuid,
+            OrderType::Buy,
+            1_000_000,
+            TickIndex::max_sqrt_price(),
+            false,
+            false,
+        )
+        .unwrap();
+        adjust_reserves(
+            netuid,
+            swap_result.tao_reserve_delta,
+            swap_result.alpha_reserve_delta,
+        );
+        assert_ok!(Pallet::<Test>::do_try_state());
+
+        assert_ok!(Swap::remove_liquidity(
+            RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+            OK_HOTKEY_ACCOUNT_ID,
+            netuid,
+            position_id,
+        ));
+        assert_ok!(Pallet::<Test>::do_try_state());
+    });
+}
+
+/// cargo test --package pallet-subtensor-swap --lib -- pallet::tests::test_try_state_detects_broken_invariants --exact --show-output
+#[test]
+fn test_try_state_detects_broken_invariants() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+        let tick_low = price_to_tick(0.2);
+        let tick_high = price_to_tick(0.3);
+
+        assert_ok!(Pallet::<Test>::maybe_initialize_v3(netuid));
+        assert_ok!(Pallet::<Test>::do_add_liquidity(
+            netuid,
+            &OK_COLDKEY_ACCOUNT_ID,
+            &OK_HOTKEY_ACCOUNT_ID,
+            tick_low,
+            tick_high,
+            2_000_000_000,
+        ));
+
+        // The position was added without moving the reserves
+        assert!(Pallet::<Test>::check_reserves_cover_positions(netuid).is_err());
+
+        // Current liquidity out of sync with the ticks
+        assert_ok!(Pallet::<Test>::check_current_liquidity(netuid));
+        CurrentLiquidity::<Test>::mutate(netuid, |liquidity| *liquidity += 1);
+        assert!(Pallet::<Test>::check_current_liquidity(netuid).is_err());
+
+        // Tick removed without updating the bitmap
+        assert_ok!(Pallet::<Test>::check_active_ticks(netuid));
+        let tick = Ticks::<Test>::take(netuid, tick_low).unwrap();
+        assert!(Pallet::<Test>::check_active_ticks(netuid).is_err());
+
+        // Tick present without the bitmap bit
+        Ticks::<Test>::insert(netuid, tick_low, tick);
+        ActiveTickIndexManager::<Test>::remove(netuid, tick_low);
+        assert!(Pallet::<Test>::check_active_ticks(netuid).is_err());
+    });
+}
+
+/// cargo test --package pallet-subtensor-swap --lib -- pallet::tests::test_swap_emits_swapped_event --exact --show-output
+#[test]
+fn test_swap_emits_swapped_event() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+        let amount = 1_000_000_000;
+
+        assert_ok!(Pallet::<Test>::maybe_initialize_v3(netuid));
+        let swapped_events = || {
+            System::events()
+                .into_iter()
+                .filter(|record| {
+                    matches!(
+                        record.event,
+                        RuntimeEvent::Swap(Event::<Test>::Swapped { .. })
+                    )
+                })
+                .count()
+        };
+
+        // Simulated swaps leave no trace
+        assert_ok!(Pallet::<Test>::do_swap(
+            netuid,
+            OrderType::Buy,
+            amount,
+            TickIndex::max_sqrt_price(),
+            false,
+            true,
+        ));
+        assert_eq!(swapped_events(), 0);
+
+        // Only protocol liquidity is in range, so it receives the whole fee
+        let sqrt_price_before = AlphaSqrtPrice::<Test>::get(netuid);
+        let swap_result = Pallet::<Test>::do_swap(
+            netuid,
+            OrderType::Buy,
+            amount,
+            TickIndex::max_sqrt_price(),
+            false,
+            false,
+        )
+        .unwrap();
+        assert!(swap_result.fee_paid > 0);
+        System::assert_last_event(
+            Event::<Test>::Swapped {
+                netuid,
+                order_type: OrderType::Buy,
+                amount_paid_in: swap_result.amount_paid_in,
+                amount_paid_out: swap_result.amount_paid_out,
+                sqrt_price_before,
+                sqrt_price_after: AlphaSqrtPrice::<Test>::get(netuid),
+                ticks_crossed: 0,
+                lp_fee: 0,
+                protocol_fee: swap_result.fee_paid,
+            }
+            .into(),
+        );
+
+        // A user position with the same full range liquidity takes half of the fees
+        let protocol_liquidity = CurrentLiquidity::<Test>::get(netuid);
+        assert_ok!(Pallet::<Test>::do_add_liquidity(
+            netuid,
+            &OK_COLDKEY_ACCOUNT_ID_RICH,
+            &OK_HOTKEY_ACCOUNT_ID_RICH,
+            TickIndex::MIN,
+            TickIndex::MAX,
+            protocol_liquidity,
+        ));
+        let swap_result = Pallet::<Test>::do_swap(
+            netuid,
+            OrderType::Sell,
+            amount,
+            TickIndex::min_sqrt_price(),
+            false,
+            false,
+        )
+        .unwrap();
+        let RuntimeEvent::Swap(Event::<Test>::Swapped {
+            order_type,
+            ticks_crossed,
+            lp_fee,
+            protocol_fee,
+            ..
+        }) = System::events().last().unwrap().event.clone()
+        else {
+            panic!("Swapped event expected");
+        };
+        assert_eq!(order_type, OrderType::Sell);
+        assert_eq!(ticks_crossed, 0);
+        assert_eq!(lp_fee + protocol_fee, swap_result.fee_paid);
+        assert_abs_diff_eq!(lp_fee, protocol_fee, epsilon = 1);
+        assert_eq!(swapped_events(), 2);
+    });
+}
+
+/// cargo test --package pallet-subtensor-swap --lib -- pallet::tests::test_current_protocol_liquidity_follows_price --exact --show-output
+#[test]
+fn test_current_protocol_liquidity_follows_price() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+        let protocol_account_id = Pallet::<Test>::protocol_account_id();
+
+        assert_ok!(Pallet::<Test>::maybe_initialize_v3(netuid));
+        assert_ok!(Pallet::<Test>::do_set_protocol_liquidity_policy(
+            netuid,
+            ProtocolLiquidityPolicy::Concentrated { width: 1_000 },
+        ));
+        assert!(CurrentProtocolLiquidity::<Test>::get(netuid).is_none());
+        let (full_range, band): (Vec<_>, Vec<_>) =
+            Positions::<Test>::iter_prefix_values((netuid, protocol_account_id))
+                .partition(|position| position.tick_low == TickIndex::MIN);
+
+        // Within the band both protocol positions take fees
+        assert_ok!(Pallet::<Test>::do_swap(
+            netuid,
+            OrderType::Buy,
+            1_000_000,
+            u64::MAX.into(),
+            false,
+            false,
+        ));
+        let (range_low, range_high, liquidity) =
+            CurrentProtocolLiquidity::<Test>::get(netuid).unwrap();
+        assert_eq!(
+            (range_low, range_high),
+            (band[0].tick_low, band[0].tick_high)
+        );
+        assert_eq!(liquidity, full_range[0].liquidity + band[0].liquidity);
+
+        // Past the band only the full range position does
+        assert_ok!(Pallet::<Test>::do_swap(
+            netuid,
+            OrderType::Buy,
+            1_000_000_000_000,
+            u64::MAX.into(),
+            false,
+            false,
+        ));
+        let (range_low, range_high, liquidity) =
+            CurrentProtocolLiquidity::<Test>::get(netuid).unwrap();
+        assert_eq!((range_low, range_high), (band[0].tick_high, TickIndex::MAX));
+        assert_eq!(liquidity, full_range[0].liquidity);
+        assert_ok!(Pallet::<Test>::check_current_protocol_liquidity(netuid));
+    });
+}
+
+/// cargo test --package pallet-subtensor-swap --lib -- pallet::tests::test_swap_overusing_reserves_is_rolled_back --exact --show-output
+#[test]
+fn test_swap_overusing_reserves_is_rolled_back() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+
+        assert_ok!(Pallet::<Test>::maybe_initialize_v3(netuid));
+
+        // The pool holds more alpha than the subnet reserve
+        let alpha_reserve = MockLiquidityProvider::alpha_reserve(netuid);
+        adjust_reserves(netuid, 0, -(alpha_reserve as i64 - 1_000));
+
+        let sqrt_price_before = AlphaSqrtPrice::<Test>::get(netuid);
+        let tick_before = CurrentTick::<Test>::get(netuid);
+        let fee_global_tao_before = FeeGlobalTao::<Test>::get(netuid);
+        let events_before = System::events().len();
+
+        assert_noop!(
+            Pallet::<Test>::do_swap(
+                netuid,
+                OrderType::Buy,
+                1_000_000_000,
+                TickIndex::max_sqrt_price(),
+                false,
+                false,
+            ),
+            Error::<Test>::InsufficientLiquidity
+        );
+
+        // Nothing of the failed swap is committed, the price, fees and events included
+        assert_eq!(AlphaSqrtPrice::<Test>::get(netuid), sqrt_price_before);
+        assert_eq!(CurrentTick::<Test>::get(netuid), tick_before);
+        assert_eq!(FeeGlobalTao::<Test>::get(netuid), fee_global_tao_before);
+        assert_eq!(System::events().len(), events_before);
+    });
+}
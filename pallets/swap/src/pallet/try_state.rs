@@ -0,0 +1,133 @@
+use sp_runtime::TryRuntimeError;
+
+use super::*;
+use crate::tick::{ActiveTickIndexManager, BitmapLayer, TickIndexBitmap};
+
+impl<T: Config> Pallet<T> {
+    /// Checks the pool invariants of every subnet that has been switched to V3 swap
+    pub fn do_try_state() -> Result<(), TryRuntimeError> {
+        for (netuid, initialized) in SwapV3Initialized::<T>::iter() {
+            if !initialized {
+                continue;
+            }
+
+            Self::check_current_liquidity(netuid)?;
+            Self::check_active_ticks(netuid)?;
+            Self::check_reserves_cover_positions(netuid)?;
+            Self::check_current_protocol_liquidity(netuid)?;
+        }
+
+        Ok(())
+    }
+
+    /// Checks [`CurrentLiquidity`] equals the sum of `liquidity_net` of all ticks at or below the
+    /// current tick.
+    pub(crate) fn check_current_liquidity(netuid: NetUid) -> Result<(), TryRuntimeError> {
+        let current_tick = TickIndex::current_bounded::<T>(netuid);
+
+        let expected_liquidity = Ticks::<T>::iter_prefix(netuid)
+            .filter(|(index, _)| *index <= current_tick)
+            .fold(0_i128, |acc, (_, tick)| {
+                acc.saturating_add(tick.liquidity_net)
+            });
+
+        ensure!(
+            expected_liquidity == CurrentLiquidity::<T>::get(netuid) as i128,
+            "CurrentLiquidity does not match the net liquidity of the crossed ticks",
+        );
+
+        Ok(())
+    }
+
+    /// Checks [`CurrentProtocolLiquidity`], while the current tick is in its range, equals the
+    /// liquidity of the protocol positions active at the current tick.
+    pub(crate) fn check_current_protocol_liquidity(netuid: NetUid) -> Result<(), TryRuntimeError> {
+        let current_tick = TickIndex::current_bounded::<T>(netuid);
+        let Some((range_low, range_high, liquidity)) = CurrentProtocolLiquidity::<T>::get(netuid)
+        else {
+            return Ok(());
+        };
+        if current_tick < range_low || current_tick >= range_high {
+            return Ok(());
+        }
+
+        let expected_liquidity =
+            Positions::<T>::iter_prefix_values((netuid, Self::protocol_account_id()))
+                .filter(|position| {
+                    position.tick_low <= current_tick && current_tick < position.tick_high
+                })
+                .fold(0_u64, |acc, position| {
+                    acc.saturating_add(position.liquidity)
+                });
+
+        ensure!(
+            expected_liquidity == liquidity,
+            "CurrentProtocolLiquidity does not match the active protocol positions",
+        );
+
+        Ok(())
+    }
+
+    /// Checks the bitmap in [`TickIndexBitmapWords`] marks exactly the ticks present in
+    /// [`Ticks`].
+    pub(crate) fn check_active_ticks(netuid: NetUid) -> Result<(), TryRuntimeError> {
+        for (index, _) in Ticks::<T>::iter_prefix(netuid) {
+            ensure!(
+                ActiveTickIndexManager::<T>::tick_is_active(netuid, index),
+                "Initialized tick is not marked active in the bitmap",
+            );
+        }
+
+        for ((layer, word), value) in TickIndexBitmapWords::<T>::iter_prefix((netuid,)) {
+            if layer != LayerLevel::Bottom {
+                continue;
+            }
+
+            for bit in 0..128_u32 {
+                if value & 1_u128.checked_shl(bit).unwrap_or_default() == 0 {
+                    continue;
+                }
+
+                let offset_index = TickIndexBitmap::layer_to_index(BitmapLayer::new(word, bit));
+                let index = TickIndex::from_offset_index(offset_index)
+                    .map_err(|_| "Active bitmap bit is outside of the tick range")?;
+
+                ensure!(
+                    Ticks::<T>::contains_key(netuid, index),
+                    "Active bitmap bit has no initialized tick",
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks the subnet reserves cover the token amounts of all liquidity positions, allowing
+    /// one unit of rounding per position.
+    pub(crate) fn check_reserves_cover_positions(netuid: NetUid) -> Result<(), TryRuntimeError> {
+        let sqrt_price = Self::current_price_sqrt(netuid);
+
+        let mut total_tao: u64 = 0;
+        let mut total_alpha: u64 = 0;
+        let mut tolerance: u64 = 0;
+        for position in Positions::<T>::iter_prefix_values((netuid,)) {
+            let (tao, alpha) = position
+                .to_token_amounts(sqrt_price)
+                .map_err(Into::<DispatchError>::into)?;
+            total_tao = total_tao.saturating_add(tao);
+            total_alpha = total_alpha.saturating_add(alpha);
+            tolerance = tolerance.saturating_add(1);
+        }
+
+        ensure!(
+            total_tao <= T::SubnetInfo::tao_reserve(netuid).saturating_add(tolerance),
+            "TAO reserve does not cover the liquidity positions",
+        );
+        ensure!(
+            total_alpha <= T::SubnetInfo::alpha_reserve(netuid).saturating_add(tolerance),
+            "Alpha reserve does not cover the liquidity positions",
+        );
+
+        Ok(())
+    }
+}
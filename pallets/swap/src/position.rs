@@ -159,6 +159,18 @@ impl<T: Config> Position<T> {
     }
 }
 
+/// Automated management settings of a user position.
+#[freeze_struct("bdb9539b2ffcd8ad")]
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct PositionAutomation<AccountId> {
+    /// Hotkey where Alpha comes from or goes to during automated operations
+    pub hotkey: AccountId,
+    /// Reinvest accrued fees into the position
+    pub compound_fees: bool,
+    /// Re-range the position around the current tick when it falls out of range
+    pub keep_centered: bool,
+}
+
 #[freeze_struct("1f02550d787d80da")]
 #[derive(
     Clone, Copy, Decode, Default, Encode, Eq, MaxEncodedLen, PartialEq, RuntimeDebug, TypeInfo,
@@ -19,6 +19,10 @@ pub trait WeightInfo {
     fn remove_liquidity() -> Weight;
     fn modify_position() -> Weight;
     fn toggle_user_liquidity() -> Weight;
+    fn transfer_position() -> Weight;
+    fn set_position_automation() -> Weight;
+    fn automate_position() -> Weight;
+    fn recenter_position() -> Weight;
 }
 
 /// Default weights for pallet_subtensor_swap.
@@ -58,6 +62,34 @@ impl<T: frame_system::Config> WeightInfo for DefaultWeight<T> {
             .saturating_add(T::DbWeight::get().reads(1))
             .saturating_add(T::DbWeight::get().writes(1))
     }
+
+    fn transfer_position() -> Weight {
+        // Conservative weight estimate for transfer_position
+        Weight::from_parts(30_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().writes(3))
+    }
+
+    fn set_position_automation() -> Weight {
+        // Conservative weight estimate for set_position_automation
+        Weight::from_parts(20_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    fn automate_position() -> Weight {
+        // Conservative weight estimate for compounding the fees of an automated position
+        Weight::from_parts(80_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(10))
+            .saturating_add(T::DbWeight::get().writes(8))
+    }
+
+    fn recenter_position() -> Weight {
+        // Withdrawal, rebalancing swap through the pool and the new range
+        Weight::from_parts(450_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(40))
+            .saturating_add(T::DbWeight::get().writes(28))
+    }
 }
 
 // For backwards compatibility and tests
@@ -91,4 +123,28 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(1))
             .saturating_add(RocksDbWeight::get().writes(1))
     }
+
+    fn transfer_position() -> Weight {
+        Weight::from_parts(30_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(4))
+            .saturating_add(RocksDbWeight::get().writes(3))
+    }
+
+    fn set_position_automation() -> Weight {
+        Weight::from_parts(20_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(4))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+
+    fn automate_position() -> Weight {
+        Weight::from_parts(80_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(10))
+            .saturating_add(RocksDbWeight::get().writes(8))
+    }
+
+    fn recenter_position() -> Weight {
+        Weight::from_parts(450_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(40))
+            .saturating_add(RocksDbWeight::get().writes(28))
+    }
 }
@@ -0,0 +1,57 @@
+use core::marker::PhantomData;
+
+use fp_evm::{ExitError, PrecompileFailure};
+use pallet_evm::PrecompileHandle;
+use precompile_utils::EvmResult;
+use sp_core::H256;
+
+use crate::PrecompileExt;
+
+/// Verifiable randomness from the drand pulses stored on chain.
+///
+/// A round is available once its pulse is written, which is after the drand beacon publishes it,
+/// and until the pulse is pruned. `getRandomness` reverts for the rounds not available, so
+/// contracts commit to a future round and read its randomness once `isRoundAvailable` holds.
+pub struct DrandPrecompile<R>(PhantomData<R>);
+
+impl<R> PrecompileExt<R::AccountId> for DrandPrecompile<R>
+where
+    R: frame_system::Config + pallet_drand::Config,
+    R::AccountId: From<[u8; 32]>,
+{
+    const INDEX: u64 = 2057;
+}
+
+#[precompile_utils::precompile]
+impl<R> DrandPrecompile<R>
+where
+    R: frame_system::Config + pallet_drand::Config,
+{
+    #[precompile::public("getRandomness(uint64)")]
+    #[precompile::view]
+    fn get_randomness(_handle: &mut impl PrecompileHandle, round: u64) -> EvmResult<H256> {
+        pallet_drand::Pallet::<R>::randomness(round)
+            .map(H256::from)
+            .ok_or(PrecompileFailure::Error {
+                exit_status: ExitError::Other("drand round not available".into()),
+            })
+    }
+
+    #[precompile::public("isRoundAvailable(uint64)")]
+    #[precompile::view]
+    fn is_round_available(_handle: &mut impl PrecompileHandle, round: u64) -> EvmResult<bool> {
+        Ok(pallet_drand::Pulses::<R>::contains_key(round))
+    }
+
+    #[precompile::public("getLastStoredRound()")]
+    #[precompile::view]
+    fn get_last_stored_round(_handle: &mut impl PrecompileHandle) -> EvmResult<u64> {
+        Ok(pallet_drand::LastStoredRound::<R>::get())
+    }
+
+    #[precompile::public("getOldestStoredRound()")]
+    #[precompile::view]
+    fn get_oldest_stored_round(_handle: &mut impl PrecompileHandle) -> EvmResult<u64> {
+        Ok(pallet_drand::OldestStoredRound::<R>::get())
+    }
+}
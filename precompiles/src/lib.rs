@@ -26,6 +26,7 @@ use pallet_admin_utils::PrecompileEnum;
 
 use crate::alpha::*;
 use crate::balance_transfer::*;
+use crate::drand::*;
 use crate::ed25519::*;
 use crate::extensions::*;
 use crate::metagraph::*;
@@ -38,6 +39,7 @@ use crate::uid_lookup::*;
 
 mod alpha;
 mod balance_transfer;
+mod drand;
 mod ed25519;
 mod extensions;
 mod metagraph;
@@ -55,6 +57,7 @@ where
         + pallet_evm::Config
         + pallet_balances::Config
         + pallet_admin_utils::Config
+        + pallet_drand::Config
         + pallet_subtensor::Config
         + pallet_subtensor_swap::Config
         + pallet_proxy::Config<ProxyType = ProxyType>,
@@ -80,6 +83,7 @@ where
         + pallet_evm::Config
         + pallet_balances::Config
         + pallet_admin_utils::Config
+        + pallet_drand::Config
         + pallet_subtensor::Config
         + pallet_subtensor_swap::Config
         + pallet_proxy::Config<ProxyType = ProxyType>,
@@ -98,7 +102,7 @@ where
         Self(Default::default())
     }
 
-    pub fn used_addresses() -> [H160; 19] {
+    pub fn used_addresses() -> [H160; 20] {
         [
             hash(1),
             hash(2),
@@ -119,6 +123,7 @@ where
             hash(StorageQueryPrecompile::<R>::INDEX),
             hash(UidLookupPrecompile::<R>::INDEX),
             hash(AlphaPrecompile::<R>::INDEX),
+            hash(DrandPrecompile::<R>::INDEX),
         ]
     }
 }
@@ -128,6 +133,7 @@ where
         + pallet_evm::Config
         + pallet_balances::Config
         + pallet_admin_utils::Config
+        + pallet_drand::Config
         + pallet_subtensor::Config
         + pallet_subtensor_swap::Config
         + pallet_proxy::Config<ProxyType = ProxyType>,
@@ -194,6 +200,9 @@ where
             a if a == hash(AlphaPrecompile::<R>::INDEX) => {
                 AlphaPrecompile::<R>::try_execute::<R>(handle, PrecompileEnum::Alpha)
             }
+            a if a == hash(DrandPrecompile::<R>::INDEX) => {
+                DrandPrecompile::<R>::try_execute::<R>(handle, PrecompileEnum::Drand)
+            }
             _ => None,
         }
     }
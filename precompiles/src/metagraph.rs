@@ -1,9 +1,13 @@
 use alloc::string::String;
+use alloc::vec::Vec;
 use core::marker::PhantomData;
 
 use fp_evm::{ExitError, PrecompileFailure, PrecompileHandle};
-use pallet_subtensor::AxonInfo as SubtensorModuleAxonInfo;
-use precompile_utils::{EvmResult, solidity::Codec};
+use pallet_subtensor::{
+    AxonEndpoint as SubtensorModuleAxonEndpoint, AxonHost, AxonInfo as SubtensorModuleAxonInfo,
+    AxonTransport,
+};
+use precompile_utils::{EvmResult, prelude::UnboundedBytes, solidity::Codec};
 use sp_core::{ByteArray, H256};
 use subtensor_runtime_common::NetUid;
 
@@ -151,6 +155,27 @@ where
         Ok(pallet_subtensor::Pallet::<R>::get_axon_info(netuid.into(), &hotkey).into())
     }
 
+    #[precompile::public("getAxonEndpoints(uint16,uint16)")]
+    #[precompile::view]
+    fn get_axon_endpoints(
+        _: &mut impl PrecompileHandle,
+        netuid: u16,
+        uid: u16,
+    ) -> EvmResult<Vec<AxonEndpoint>> {
+        let hotkey = pallet_subtensor::Pallet::<R>::get_hotkey_for_net_and_uid(netuid.into(), uid)
+            .map_err(|_| PrecompileFailure::Error {
+                exit_status: ExitError::Other("hotkey not found".into()),
+            })?;
+
+        Ok(
+            pallet_subtensor::Pallet::<R>::get_axon_info_v2(netuid.into(), &hotkey)
+                .endpoints
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        )
+    }
+
     #[precompile::public("getHotkey(uint16,uint16)")]
     #[precompile::view]
     fn get_hotkey(_: &mut impl PrecompileHandle, netuid: u16, uid: u16) -> EvmResult<H256> {
@@ -196,3 +221,34 @@ impl From<SubtensorModuleAxonInfo> for AxonInfo {
         }
     }
 }
+
+#[derive(Codec)]
+struct AxonEndpoint {
+    ip_type: u8,
+    ip: u128,
+    hostname: UnboundedBytes,
+    port: u16,
+    transport: u8,
+}
+
+impl From<SubtensorModuleAxonEndpoint> for AxonEndpoint {
+    fn from(value: SubtensorModuleAxonEndpoint) -> Self {
+        // DNS endpoints carry an ip type of 0.
+        let (ip_type, ip, hostname) = match value.host {
+            AxonHost::Ip { ip_type, ip } => (ip_type, ip, Vec::new()),
+            AxonHost::Dns(hostname) => (0, 0, hostname.into_inner()),
+        };
+        Self {
+            ip_type,
+            ip,
+            hostname: hostname.into(),
+            port: value.port,
+            transport: match value.transport {
+                AxonTransport::Tcp => 0,
+                AxonTransport::Udp => 1,
+                AxonTransport::Quic => 2,
+                AxonTransport::Http2 => 3,
+            },
+        }
+    }
+}
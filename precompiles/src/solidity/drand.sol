@@ -0,0 +1,24 @@
+pragma solidity ^0.8.0;
+
+address constant IDRAND_ADDRESS = 0x0000000000000000000000000000000000000809;
+
+interface IDrand {
+    /// @dev Returns the randomness of a drand round, derived from its verified pulse.
+    /// Reverts when the round is not available, either not reached yet or pruned.
+    /// @param round The drand round number.
+    /// @return The 32 bytes of randomness.
+    function getRandomness(uint64 round) external view returns (bytes32);
+
+    /// @dev Returns whether the pulse of a drand round is stored on chain.
+    /// @param round The drand round number.
+    /// @return Whether `getRandomness` returns the randomness of the round.
+    function isRoundAvailable(uint64 round) external view returns (bool);
+
+    /// @dev Returns the last drand round with a stored pulse.
+    /// @return The round number, 0 when no pulse is stored.
+    function getLastStoredRound() external view returns (uint64);
+
+    /// @dev Returns the oldest drand round with a stored pulse, older ones being pruned.
+    /// @return The round number, 0 when no pulse is stored.
+    function getOldestStoredRound() external view returns (uint64);
+}
@@ -11,6 +11,14 @@ struct AxonInfo {
   uint8 protocol;
 }
 
+struct AxonEndpoint {
+  uint8 ip_type;
+  uint128 ip;
+  bytes hostname;
+  uint16 port;
+  uint8 transport;
+}
+
 interface IMetagraph {
   
   /**
@@ -116,6 +124,15 @@ interface IMetagraph {
    */
   function getAxon(uint16 netuid, uint16 uid) external view returns (AxonInfo memory);
 
+  /**
+   * @dev Retrieves all endpoints advertised by the axon of a node with a given network identifier (netuid) and unique identifier (uid).
+   * @param netuid The network identifier for which to retrieve the axon endpoints.
+   * @param uid The unique identifier for which to retrieve the axon endpoints.
+   * @return The endpoints in order of preference. DNS endpoints have an ip_type of 0 and set hostname.
+   *         Transport is 0 for TCP, 1 for UDP, 2 for QUIC and 3 for HTTP/2.
+   */
+  function getAxonEndpoints(uint16 netuid, uint16 uid) external view returns (AxonEndpoint[] memory);
+
   /**
    * @dev Retrieves the hotkey of a node with a given network identifier (netuid) and unique identifier (uid).
    * @param netuid The network identifier for which to retrieve the hotkey.
@@ -0,0 +1,329 @@
+//! Commit-reveal v3 weight commits.
+//!
+//! A CRv3 commit is a `WeightsTlockPayload`, SCALE encoded, timelock encrypted with `tle` to a
+//! drand round and serialized compressed. The chain reveals the commits of an epoch at the epoch
+//! block `RevealPeriodEpochs - 1` epochs later, decrypting them with the pulse of their round, so
+//! the round has to be published, and stored by the offchain worker, before that block. This
+//! crate builds the payload, finds the round and produces the bytes `commit_crv3_weights` takes,
+//! the same way the chain reads them back in `reveal_crv3_commits`.
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(test, allow(clippy::arithmetic_side_effects))]
+#![cfg_attr(test, allow(clippy::unwrap_used))]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use codec::{Decode, Encode};
+use rand_chacha::rand_core::{CryptoRng, RngCore};
+use sha2::{Digest, Sha256};
+use subtensor_macros::freeze_struct;
+use tle::{
+    curves::drand::TinyBLS381, ibe::fullident::Identity,
+    stream_ciphers::AESGCMStreamCipherProvider, tlock::tle,
+};
+use w3f_bls::EngineBLS;
+
+/// Largest commit `commit_crv3_weights` accepts, in bytes.
+pub const MAX_COMMIT_SIZE_BYTES: u32 = 5000;
+
+/// Target block time of the chain, in seconds.
+pub const BLOCK_TIME_SECONDS: u64 = 12;
+
+/// Blocks between the one after which the offchain worker fetches a pulse and the block that
+/// reveals with it. The worker runs once a block is imported and its pulse is stored by the
+/// extrinsics of the next block, after that block revealed in `on_initialize`.
+pub const PULSE_STORE_DELAY_BLOCKS: u64 = 2;
+
+/// Public key of the drand quicknet beacon, hex encoded and compressed in G2.
+pub const QUICKNET_PUBLIC_KEY: &str = "83cf0f2896adee7eb8b5f01fcad3912212c437e0073e911fb90022d3e760183c8c4b450b6a0a6c3ac6a5776a2d1064510d1fec758c921cc22b0e17e63aaf4bcb5ed66304de9cf809bd274ca73bab4af5a6e9c76a4bc09e76eae8991ef5ece45a";
+
+/// Reasons a commit cannot be built.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The public key is not a compressed point of G2.
+    InvalidPublicKey,
+    /// The payload could not be encrypted.
+    EncryptionFailed,
+    /// The ciphertext could not be serialized.
+    SerializationFailed,
+    /// The commit is larger than `MAX_COMMIT_SIZE_BYTES`.
+    CommitTooLarge,
+    /// The subnet never reveals commits made at the block.
+    NoRevealBlock,
+}
+
+/// Contains all necessary information to set weights.
+///
+/// In the context of commit-reveal v3, this is the payload which should be
+/// encrypted, compressed, serialized, and submitted to the `commit_crv3_weights`
+/// extrinsic.
+#[derive(Encode, Decode)]
+#[freeze_struct("46e75a8326ba3665")]
+pub struct WeightsTlockPayload {
+    pub uids: Vec<u16>,
+    pub values: Vec<u16>,
+    pub version_key: u64,
+}
+
+/// The parameters of a subnet which decide when its commits are revealed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SubnetTiming {
+    /// The subnet the weights are committed to.
+    pub netuid: u16,
+    /// `Tempo` of the subnet.
+    pub tempo: u16,
+    /// `RevealPeriodEpochs` of the subnet.
+    pub reveal_period_epochs: u64,
+}
+
+impl SubnetTiming {
+    /// The epoch a commit made at the block is stored under.
+    ///
+    /// Commits made at the epoch block of the subnet already belong to the next epoch.
+    pub fn commit_epoch(&self, block: u64) -> u64 {
+        let tempo_plus_one = u64::from(self.tempo).saturating_add(1);
+        let block_with_offset = block.saturating_add(u64::from(self.netuid).saturating_add(1));
+        let epoch = block_with_offset.checked_div(tempo_plus_one).unwrap_or(0);
+        let runs_epoch = self.tempo != 0
+            && block_with_offset.checked_rem(tempo_plus_one) == Some(u64::from(self.tempo));
+
+        if runs_epoch {
+            epoch.saturating_add(1)
+        } else {
+            epoch
+        }
+    }
+
+    /// The block at which the chain reveals a commit made at the block.
+    ///
+    /// The commits of epoch `e` are revealed at the epoch block ending epoch
+    /// `e + reveal_period_epochs - 1`. There is none if the subnet never runs its epoch, or if that
+    /// epoch is one of the first two, which reveal nothing.
+    pub fn reveal_block(&self, commit_block: u64) -> Option<u64> {
+        if self.tempo == 0 {
+            return None;
+        }
+        let reveal_epoch = self
+            .commit_epoch(commit_block)
+            .saturating_add(self.reveal_period_epochs.saturating_sub(1));
+        if reveal_epoch < 2 {
+            return None;
+        }
+
+        // The epoch block `b` of epoch `k` is the one with `b + netuid + 1 = k * (tempo + 1) + tempo`
+        let tempo_plus_one = u64::from(self.tempo).saturating_add(1);
+        reveal_epoch
+            .checked_mul(tempo_plus_one)?
+            .checked_add(u64::from(self.tempo))?
+            .checked_sub(u64::from(self.netuid).saturating_add(1))
+    }
+}
+
+/// The schedule of a drand beacon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DrandTiming {
+    /// Unix time of the first round, in seconds.
+    pub genesis_time: u64,
+    /// Seconds between two rounds.
+    pub period: u64,
+}
+
+impl DrandTiming {
+    /// The drand quicknet beacon.
+    pub const QUICKNET: Self = Self {
+        genesis_time: 1_692_803_367,
+        period: 3,
+    };
+
+    /// The latest round published at the unix time, in seconds.
+    pub fn round_at(&self, unix_seconds: u64) -> u64 {
+        unix_seconds
+            .saturating_sub(self.genesis_time)
+            .checked_div(self.period)
+            .unwrap_or_default()
+            .saturating_add(1)
+    }
+}
+
+/// The round to timelock a commit made at `commit_block` to.
+///
+/// This is the latest round published `PULSE_STORE_DELAY_BLOCKS` blocks before the reveal block,
+/// estimated from the current block and unix time, so that the offchain worker has stored its
+/// pulse by the end of the block before the reveal.
+pub fn reveal_round(
+    subnet: &SubnetTiming,
+    drand: &DrandTiming,
+    commit_block: u64,
+    current_block: u64,
+    now_unix_seconds: u64,
+) -> Result<u64, Error> {
+    let reveal_block = subnet
+        .reveal_block(commit_block)
+        .ok_or(Error::NoRevealBlock)?;
+    let blocks_until_stored = reveal_block
+        .saturating_sub(current_block)
+        .saturating_sub(PULSE_STORE_DELAY_BLOCKS);
+
+    Ok(drand.round_at(
+        now_unix_seconds.saturating_add(blocks_until_stored.saturating_mul(BLOCK_TIME_SECONDS)),
+    ))
+}
+
+/// The identity ciphertexts of the round are encrypted for, `sha256(round)` with the round big
+/// endian, as drand signs it.
+pub fn round_identity(round: u64) -> Identity {
+    Identity::new(
+        b"",
+        alloc::vec![Sha256::digest(round.to_be_bytes()).to_vec()],
+    )
+}
+
+/// Encrypts the payload to the round of the beacon with the public key, compressed in G2, and
+/// returns the `commit` argument of `commit_crv3_weights`.
+pub fn encrypt_commit<R: RngCore + CryptoRng>(
+    payload: &WeightsTlockPayload,
+    reveal_round: u64,
+    public_key: &[u8],
+    mut rng: R,
+) -> Result<Vec<u8>, Error> {
+    let public_key = <TinyBLS381 as EngineBLS>::PublicKeyGroup::deserialize_compressed(public_key)
+        .map_err(|_| Error::InvalidPublicKey)?;
+
+    let mut msk = [0u8; 32];
+    rng.fill_bytes(&mut msk);
+
+    let ciphertext = tle::<TinyBLS381, AESGCMStreamCipherProvider, R>(
+        public_key,
+        msk,
+        &payload.encode(),
+        round_identity(reveal_round),
+        rng,
+    )
+    .map_err(|_| Error::EncryptionFailed)?;
+
+    let mut commit = Vec::new();
+    ciphertext
+        .serialize_compressed(&mut commit)
+        .map_err(|_| Error::SerializationFailed)?;
+
+    if commit.len() > MAX_COMMIT_SIZE_BYTES as usize {
+        return Err(Error::CommitTooLarge);
+    }
+    Ok(commit)
+}
+
+/// Builds the commit of the weights for the next block, timelocked to the round computed from
+/// the system clock and encrypted with the OS randomness. Returns the `commit` and
+/// `reveal_round` arguments of `commit_crv3_weights`.
+#[cfg(feature = "std")]
+pub fn build_commit(
+    subnet: &SubnetTiming,
+    drand: &DrandTiming,
+    public_key: &[u8],
+    payload: &WeightsTlockPayload,
+    current_block: u64,
+) -> Result<(Vec<u8>, u64), Error> {
+    let now_unix_seconds = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default();
+    let round = reveal_round(
+        subnet,
+        drand,
+        current_block.saturating_add(1),
+        current_block,
+        now_unix_seconds,
+    )?;
+    let commit = encrypt_commit(payload, round, public_key, rand::rngs::OsRng)?;
+
+    Ok((commit, round))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_chacha::{ChaCha20Rng, rand_core::SeedableRng};
+    use tle::tlock::TLECiphertext;
+
+    const SUBNET: SubnetTiming = SubnetTiming {
+        netuid: 1,
+        tempo: 10,
+        reveal_period_epochs: 3,
+    };
+
+    #[test]
+    fn commit_epoch_moves_on_at_the_epoch_block() {
+        // (block + 2) % 11 == 10 at the epoch blocks 8, 19, 30..
+        assert_eq!(SUBNET.commit_epoch(0), 0);
+        assert_eq!(SUBNET.commit_epoch(7), 0);
+        assert_eq!(SUBNET.commit_epoch(8), 1);
+        assert_eq!(SUBNET.commit_epoch(9), 1);
+        assert_eq!(SUBNET.commit_epoch(19), 2);
+    }
+
+    #[test]
+    fn reveal_block_is_the_epoch_block_ending_the_reveal_epoch() {
+        // committed in epoch 1, revealed when epoch 3 ends
+        assert_eq!(SUBNET.reveal_block(9), Some(41));
+        assert_eq!(SUBNET.reveal_block(8), Some(41));
+        // committed in epoch 2
+        assert_eq!(SUBNET.reveal_block(20), Some(52));
+
+        let no_epoch = SubnetTiming { tempo: 0, ..SUBNET };
+        assert_eq!(no_epoch.reveal_block(9), None);
+        let too_early = SubnetTiming {
+            reveal_period_epochs: 1,
+            ..SUBNET
+        };
+        assert_eq!(too_early.reveal_block(0), None);
+        assert_eq!(too_early.reveal_block(19), Some(30));
+    }
+
+    #[test]
+    fn reveal_round_is_stored_before_the_reveal_block() {
+        let drand = DrandTiming::QUICKNET;
+        let now = drand.genesis_time + 3_000;
+        assert_eq!(drand.round_at(now), 1_001);
+
+        // 41 - 9 - 2 blocks of 12 seconds to go, 4 rounds each
+        assert_eq!(reveal_round(&SUBNET, &drand, 9, 9, now), Ok(1_001 + 30 * 4));
+        assert_eq!(
+            reveal_round(&SUBNET, &drand, 9, 45, now),
+            Ok(drand.round_at(now))
+        );
+
+        let no_epoch = SubnetTiming { tempo: 0, ..SUBNET };
+        assert_eq!(
+            reveal_round(&no_epoch, &drand, 9, 9, now),
+            Err(Error::NoRevealBlock)
+        );
+    }
+
+    #[test]
+    fn commit_is_a_compressed_ciphertext() {
+        let public_key = hex_decode(QUICKNET_PUBLIC_KEY);
+        let payload = WeightsTlockPayload {
+            uids: vec![0, 1, 2],
+            values: vec![10, 20, 30],
+            version_key: 7,
+        };
+
+        let commit =
+            encrypt_commit(&payload, 1_000, &public_key, ChaCha20Rng::seed_from_u64(0)).unwrap();
+        assert!(commit.len() <= MAX_COMMIT_SIZE_BYTES as usize);
+        assert!(TLECiphertext::<TinyBLS381>::deserialize_compressed(&commit[..]).is_ok());
+
+        assert_eq!(
+            encrypt_commit(&payload, 1_000, &[0; 96], ChaCha20Rng::seed_from_u64(0)),
+            Err(Error::InvalidPublicKey)
+        );
+    }
+
+    fn hex_decode(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+}
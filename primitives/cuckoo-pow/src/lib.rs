@@ -0,0 +1,329 @@
+//! Cuckoo Cycle proof of work.
+//!
+//! A solution is a cycle of `PROOF_SIZE` edges in a bipartite graph of `2^edge_bits` edges, each
+//! edge joining the nodes siphash-2-4 derives from its index. Finding a cycle takes memory
+//! proportional to the graph, checking one takes `2 * PROOF_SIZE` hashes whatever its size.
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(test, allow(clippy::arithmetic_side_effects))]
+#![cfg_attr(test, allow(clippy::unwrap_used))]
+
+extern crate alloc;
+
+use alloc::{vec, vec::Vec};
+
+/// Number of edges in a solution cycle.
+pub const PROOF_SIZE: usize = 42;
+
+/// Smallest graph a cycle can be searched in, as a power of two of its edges.
+pub const MIN_EDGE_BITS: u8 = 12;
+
+/// Largest graph a cycle can be searched in, edge indices must fit a `u32`.
+pub const MAX_EDGE_BITS: u8 = 32;
+
+/// Longest path the solver follows before giving up on an edge.
+const MAX_PATH_LEN: usize = 8192;
+
+/// Node marking the end of a path in the solver.
+const NIL: u64 = u64::MAX;
+
+/// Reasons a proof is rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The graph size is outside `MIN_EDGE_BITS..=MAX_EDGE_BITS`.
+    InvalidEdgeBits,
+    /// The proof does not hold `PROOF_SIZE` edges.
+    WrongProofSize,
+    /// An edge index is outside the graph.
+    EdgeTooBig,
+    /// The edge indices are not strictly ascending.
+    EdgesNotAscending,
+    /// A node of the proof is not joined to exactly two of its edges.
+    BranchOrDeadEnd,
+    /// The edges form several shorter cycles.
+    ShortCycle,
+}
+
+/// Splits a 32 byte seed into the four siphash keys.
+pub fn keys_from_seed(seed: &[u8; 32]) -> [u64; 4] {
+    let mut keys = [0u64; 4];
+    for (key, chunk) in keys.iter_mut().zip(seed.chunks_exact(8)) {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(chunk);
+        *key = u64::from_le_bytes(bytes);
+    }
+    keys
+}
+
+/// Returns the node an edge joins on side `uorv`, 0 for U and 1 for V.
+pub fn sipnode(keys: &[u64; 4], edge: u32, uorv: u64, edge_bits: u8) -> u64 {
+    let node_mask = u64::MAX >> 64_u8.saturating_sub(edge_bits);
+    siphash24(keys, u64::from(edge).wrapping_mul(2) | uorv) & node_mask
+}
+
+/// Checks the proof is a single cycle of `PROOF_SIZE` edges of the graph.
+pub fn verify(keys: &[u64; 4], edge_bits: u8, proof: &[u32]) -> Result<(), Error> {
+    if !(MIN_EDGE_BITS..=MAX_EDGE_BITS).contains(&edge_bits) {
+        return Err(Error::InvalidEdgeBits);
+    }
+    if proof.len() != PROOF_SIZE {
+        return Err(Error::WrongProofSize);
+    }
+    if proof
+        .iter()
+        .any(|edge| u64::from(*edge) > u64::MAX >> 64_u8.saturating_sub(edge_bits))
+    {
+        return Err(Error::EdgeTooBig);
+    }
+    if proof.windows(2).any(|pair| pair.first() >= pair.last()) {
+        return Err(Error::EdgesNotAscending);
+    }
+
+    // U nodes are even and V nodes odd, so both sides share one id space.
+    let edges: Vec<(u64, u64)> = proof
+        .iter()
+        .map(|edge| {
+            (
+                sipnode(keys, *edge, 0, edge_bits).wrapping_mul(2),
+                sipnode(keys, *edge, 1, edge_bits).wrapping_mul(2) | 1,
+            )
+        })
+        .collect();
+
+    // Every node joined to exactly two edges makes the edges a union of cycles.
+    let degree = |node: u64| {
+        edges
+            .iter()
+            .map(|(u, v)| usize::from(*u == node).saturating_add(usize::from(*v == node)))
+            .sum::<usize>()
+    };
+    if edges
+        .iter()
+        .any(|(u, v)| degree(*u) != 2 || degree(*v) != 2)
+    {
+        return Err(Error::BranchOrDeadEnd);
+    }
+
+    // Walking the cycle of the first edge has to visit every edge.
+    let (mut edge, mut node) = (0, edges.first().map_or(0, |(_, v)| *v));
+    let mut length: usize = 0;
+    loop {
+        let Some((next, (u, v))) = edges
+            .iter()
+            .enumerate()
+            .find(|(index, (u, v))| *index != edge && (*u == node || *v == node))
+        else {
+            return Err(Error::BranchOrDeadEnd);
+        };
+        edge = next;
+        node = if *u == node { *v } else { *u };
+        length = length.saturating_add(1);
+        if edge == 0 {
+            break;
+        }
+    }
+
+    if length == PROOF_SIZE {
+        Ok(())
+    } else {
+        Err(Error::ShortCycle)
+    }
+}
+
+/// Searches the graph for a cycle of `PROOF_SIZE` edges, returning its ascending edge indices.
+///
+/// The graph is held as a forest of directed paths, one `u64` per node. An edge whose endpoints
+/// already share a root closes a cycle, otherwise the shorter path is reversed to join the trees.
+pub fn solve(keys: &[u64; 4], edge_bits: u8) -> Option<Vec<u32>> {
+    if !(MIN_EDGE_BITS..=MAX_EDGE_BITS).contains(&edge_bits) {
+        return None;
+    }
+    let num_edges: u64 = 1_u64.checked_shl(u32::from(edge_bits))?;
+    let num_nodes = usize::try_from(num_edges.checked_mul(2)?).ok()?;
+    let mut cuckoo: Vec<u64> = vec![NIL; num_nodes];
+
+    for edge in 0..num_edges {
+        let edge = u32::try_from(edge).ok()?;
+        let u0 = sipnode(keys, edge, 0, edge_bits).wrapping_mul(2);
+        let v0 = sipnode(keys, edge, 1, edge_bits).wrapping_mul(2) | 1;
+
+        let Some(us) = path(&cuckoo, u0) else {
+            continue;
+        };
+        let Some(vs) = path(&cuckoo, v0) else {
+            continue;
+        };
+
+        if us.last() == vs.last() {
+            // Drop the shared tail, what remains plus this edge is the cycle.
+            let common = us
+                .iter()
+                .rev()
+                .zip(vs.iter().rev())
+                .take_while(|(u, v)| u == v)
+                .count();
+            let us = us.get(..us.len().saturating_sub(common).saturating_add(1))?;
+            let vs = vs.get(..vs.len().saturating_sub(common).saturating_add(1))?;
+            if us.len().saturating_add(vs.len()).saturating_sub(1) == PROOF_SIZE {
+                return Some(recover(keys, edge_bits, us, vs));
+            }
+            continue;
+        }
+
+        if us.len() < vs.len() {
+            reverse(&mut cuckoo, &us);
+            *cuckoo.get_mut(usize::try_from(u0).ok()?)? = v0;
+        } else {
+            reverse(&mut cuckoo, &vs);
+            *cuckoo.get_mut(usize::try_from(v0).ok()?)? = u0;
+        }
+    }
+
+    None
+}
+
+/// Follows a node to the root of its tree, None if the path is too long.
+fn path(cuckoo: &[u64], mut node: u64) -> Option<Vec<u64>> {
+    let mut nodes = vec![node];
+    while let Some(next) = cuckoo
+        .get(usize::try_from(node).ok()?)
+        .copied()
+        .filter(|next| *next != NIL)
+    {
+        if nodes.len() >= MAX_PATH_LEN {
+            return None;
+        }
+        nodes.push(next);
+        node = next;
+    }
+    Some(nodes)
+}
+
+/// Reverses the links of a path so its first node becomes the root.
+fn reverse(cuckoo: &mut [u64], nodes: &[u64]) {
+    for pair in nodes.windows(2).rev() {
+        let slot = pair
+            .last()
+            .and_then(|to| usize::try_from(*to).ok())
+            .and_then(|index| cuckoo.get_mut(index));
+        if let (Some(slot), Some(from)) = (slot, pair.first()) {
+            *slot = *from;
+        }
+    }
+}
+
+/// Collects the edge indices of the cycle made of both paths and the closing edge.
+fn recover(keys: &[u64; 4], edge_bits: u8, us: &[u64], vs: &[u64]) -> Vec<u32> {
+    let mut cycle: Vec<(u64, u64)> = us
+        .windows(2)
+        .chain(vs.windows(2))
+        .filter_map(|pair| Some(ordered(*pair.first()?, *pair.last()?)))
+        .collect();
+    if let (Some(u0), Some(v0)) = (us.first(), vs.first()) {
+        cycle.push(ordered(*u0, *v0));
+    }
+
+    let mut proof = Vec::with_capacity(PROOF_SIZE);
+    let mut edge: u32 = 0;
+    while proof.len() < PROOF_SIZE {
+        let u = sipnode(keys, edge, 0, edge_bits).wrapping_mul(2);
+        let v = sipnode(keys, edge, 1, edge_bits).wrapping_mul(2) | 1;
+        if let Some(position) = cycle.iter().position(|nodes| *nodes == (u, v)) {
+            cycle.swap_remove(position);
+            proof.push(edge);
+        }
+        let Some(next) = edge.checked_add(1) else {
+            break;
+        };
+        edge = next;
+    }
+    proof
+}
+
+/// Orders a pair of nodes as (U node, V node).
+fn ordered(a: u64, b: u64) -> (u64, u64) {
+    if a & 1 == 0 { (a, b) } else { (b, a) }
+}
+
+fn siphash24(keys: &[u64; 4], nonce: u64) -> u64 {
+    let [mut v0, mut v1, mut v2, mut v3] = *keys;
+    v3 ^= nonce;
+    for _ in 0..2 {
+        sip_round(&mut v0, &mut v1, &mut v2, &mut v3);
+    }
+    v0 ^= nonce;
+    v2 ^= 0xff;
+    for _ in 0..4 {
+        sip_round(&mut v0, &mut v1, &mut v2, &mut v3);
+    }
+    v0 ^ v1 ^ v2 ^ v3
+}
+
+fn sip_round(v0: &mut u64, v1: &mut u64, v2: &mut u64, v3: &mut u64) {
+    *v0 = v0.wrapping_add(*v1);
+    *v2 = v2.wrapping_add(*v3);
+    *v1 = v1.rotate_left(13);
+    *v3 = v3.rotate_left(16);
+    *v1 ^= *v0;
+    *v3 ^= *v2;
+    *v0 = v0.rotate_left(32);
+    *v2 = v2.wrapping_add(*v1);
+    *v0 = v0.wrapping_add(*v3);
+    *v1 = v1.rotate_left(17);
+    *v3 = v3.rotate_left(21);
+    *v1 ^= *v2;
+    *v3 ^= *v0;
+    *v2 = v2.rotate_left(32);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solve_any(edge_bits: u8) -> ([u64; 4], Vec<u32>) {
+        (0_u8..=u8::MAX)
+            .find_map(|nonce| {
+                let keys = keys_from_seed(&[nonce; 32]);
+                solve(&keys, edge_bits).map(|proof| (keys, proof))
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn test_solution_verifies() {
+        let (keys, proof) = solve_any(MIN_EDGE_BITS);
+        assert_eq!(proof.len(), PROOF_SIZE);
+        assert_eq!(verify(&keys, MIN_EDGE_BITS, &proof), Ok(()));
+    }
+
+    #[test]
+    fn test_tampered_solution_is_rejected() {
+        let (keys, proof) = solve_any(MIN_EDGE_BITS);
+
+        assert_eq!(
+            verify(&keys, MIN_EDGE_BITS.saturating_sub(1), &proof),
+            Err(Error::InvalidEdgeBits)
+        );
+        assert_eq!(
+            verify(&keys, MIN_EDGE_BITS, proof.get(1..).unwrap()),
+            Err(Error::WrongProofSize)
+        );
+
+        let mut unsorted = proof.clone();
+        unsorted.swap(0, 1);
+        assert_eq!(
+            verify(&keys, MIN_EDGE_BITS, &unsorted),
+            Err(Error::EdgesNotAscending)
+        );
+
+        let mut too_big = proof.clone();
+        *too_big.last_mut().unwrap() = 1 << MIN_EDGE_BITS;
+        assert_eq!(
+            verify(&keys, MIN_EDGE_BITS, &too_big),
+            Err(Error::EdgeTooBig)
+        );
+
+        // Another graph does not hold the same cycle.
+        let other_keys = keys_from_seed(&[u8::MAX; 32]);
+        assert!(verify(&other_keys, MIN_EDGE_BITS, &proof).is_err());
+    }
+}
@@ -37,13 +37,19 @@ use pallet_grandpa::{
 };
 use pallet_registry::CanRegisterIdentity;
 use pallet_subtensor::rpc_info::{
+    crv3_reveal_status::CRV3RevealStatusInfo,
     delegate_info::DelegateInfo,
     dynamic_info::DynamicInfo,
     metagraph::{Metagraph, SelectiveMetagraph},
+    neuron_certificates::NeuronCertificatesInfo,
     neuron_info::{NeuronInfo, NeuronInfoLite},
+    pruning_preview::PruningPreview,
     show_subnet::SubnetState,
     stake_info::StakeInfo,
-    subnet_info::{SubnetHyperparams, SubnetHyperparamsV2, SubnetInfo, SubnetInfov2},
+    subnet_info::{
+        SubnetHyperparams, SubnetHyperparamsV2, SubnetHyperparamsV3, SubnetInfo, SubnetInfov2,
+    },
+    weights_validation::WeightsValidationInfo,
 };
 use smallvec::smallvec;
 use sp_api::impl_runtime_apis;
@@ -111,6 +117,10 @@ impl pallet_drand::Config for Runtime {
     type Verifier = pallet_drand::verifier::QuicknetVerifier;
     type UnsignedPriority = ConstU64<{ 1 << 20 }>;
     type HttpFetchTimeout = ConstU64<1_000>;
+    // A week of quicknet rounds, one every 3 seconds
+    type PulseRetention = ConstU64<{ 28_800 * 7 }>;
+    type OnPulsesStored = SubtensorModule;
+    type UnixTime = Timestamp;
 }
 
 impl frame_system::offchain::SigningTypes for Runtime {
@@ -398,6 +408,7 @@ impl Contains<RuntimeCall> for SafeModeWhitelistedCalls {
                     pallet_subtensor::Call::set_weights { .. }
                         | pallet_subtensor::Call::set_tao_weights { .. }
                         | pallet_subtensor::Call::serve_axon { .. }
+                        | pallet_subtensor::Call::serve_axon_v2 { .. }
                 )
                 | RuntimeCall::Commitments(pallet_commitments::Call::set_commitment { .. })
         )
@@ -721,6 +732,11 @@ impl InstanceFilter<RuntimeCall> for ProxyType {
                     | RuntimeCall::SubtensorModule(pallet_subtensor::Call::move_stake { .. })
                     | RuntimeCall::SubtensorModule(pallet_subtensor::Call::transfer_stake { .. })
                     | RuntimeCall::SubtensorModule(pallet_subtensor::Call::burned_register { .. })
+                    | RuntimeCall::SubtensorModule(
+                        pallet_subtensor::Call::burned_register_with_voucher { .. }
+                    )
+                    | RuntimeCall::SubtensorModule(pallet_subtensor::Call::bid_registration { .. })
+                    | RuntimeCall::SubtensorModule(pallet_subtensor::Call::deregister { .. })
                     | RuntimeCall::SubtensorModule(pallet_subtensor::Call::root_register { .. })
                     | RuntimeCall::SubtensorModule(
                         pallet_subtensor::Call::schedule_swap_coldkey { .. }
@@ -771,6 +787,11 @@ impl InstanceFilter<RuntimeCall> for ProxyType {
                 RuntimeCall::SubtensorModule(pallet_subtensor::Call::dissolve_network { .. })
                     | RuntimeCall::SubtensorModule(pallet_subtensor::Call::root_register { .. })
                     | RuntimeCall::SubtensorModule(pallet_subtensor::Call::burned_register { .. })
+                    | RuntimeCall::SubtensorModule(
+                        pallet_subtensor::Call::burned_register_with_voucher { .. }
+                    )
+                    | RuntimeCall::SubtensorModule(pallet_subtensor::Call::bid_registration { .. })
+                    | RuntimeCall::SubtensorModule(pallet_subtensor::Call::deregister { .. })
                     | RuntimeCall::Triumvirate(..)
                     | RuntimeCall::SubtensorModule(pallet_subtensor::Call::set_tao_weights { .. })
                     | RuntimeCall::Sudo(..)
@@ -808,6 +829,10 @@ impl InstanceFilter<RuntimeCall> for ProxyType {
             ProxyType::Registration => matches!(
                 c,
                 RuntimeCall::SubtensorModule(pallet_subtensor::Call::burned_register { .. })
+                    | RuntimeCall::SubtensorModule(
+                        pallet_subtensor::Call::burned_register_with_voucher { .. }
+                    )
+                    | RuntimeCall::SubtensorModule(pallet_subtensor::Call::bid_registration { .. })
                     | RuntimeCall::SubtensorModule(pallet_subtensor::Call::register { .. })
             ),
             ProxyType::RootWeights => matches!(
@@ -1296,6 +1321,10 @@ parameter_types! {
     pub const SwapMinimumLiquidity: u64 = 1_000;
     pub const SwapMinimumReserve: NonZeroU64 = NonZeroU64::new(1_000_000)
         .expect("1_000_000 fits NonZeroU64");
+    pub const SwapPositionAutomationInterval: u32 = 100;
+    pub const SwapMaxAutomatedPositionsPerRun: u32 = 20;
+    pub const SwapMinProtocolBandWidth: u32 = 2_000; // about ±10% of the price
+    pub SwapProtocolFullRangeShare: Perbill = Perbill::from_percent(20);
 }
 
 impl pallet_subtensor_swap::Config for Runtime {
@@ -1307,6 +1336,10 @@ impl pallet_subtensor_swap::Config for Runtime {
     type MaxPositions = SwapMaxPositions;
     type MinimumLiquidity = SwapMinimumLiquidity;
     type MinimumReserve = SwapMinimumReserve;
+    type PositionAutomationInterval = SwapPositionAutomationInterval;
+    type MaxAutomatedPositionsPerRun = SwapMaxAutomatedPositionsPerRun;
+    type MinProtocolBandWidth = SwapMinProtocolBandWidth;
+    type ProtocolFullRangeShare = SwapProtocolFullRangeShare;
     // TODO: set measured weights when the pallet been benchmarked and the type is generated
     type WeightInfo = pallet_subtensor_swap::weights::DefaultWeight<Runtime>;
 }
@@ -2335,6 +2368,10 @@ impl_runtime_apis! {
         fn get_neuron(netuid: NetUid, uid: u16) -> Option<NeuronInfo<AccountId32>> {
             SubtensorModule::get_neuron(netuid, uid)
         }
+
+        fn get_neuron_certificates(netuid: NetUid) -> Vec<NeuronCertificatesInfo<AccountId32>> {
+            SubtensorModule::get_neuron_certificates(netuid)
+        }
     }
 
     impl subtensor_custom_rpc_runtime_api::SubnetInfoRuntimeApi<Block> for Runtime {
@@ -2362,6 +2399,10 @@ impl_runtime_apis! {
             SubtensorModule::get_subnet_hyperparams_v2(netuid)
         }
 
+        fn get_subnet_hyperparams_v3(netuid: NetUid) -> Option<SubnetHyperparamsV3> {
+            SubtensorModule::get_subnet_hyperparams_v3(netuid)
+        }
+
         fn get_dynamic_info(netuid: NetUid) -> Option<DynamicInfo<AccountId32>> {
             SubtensorModule::get_dynamic_info(netuid)
         }
@@ -2410,6 +2451,20 @@ impl_runtime_apis! {
         fn get_network_registration_cost() -> u64 {
             SubtensorModule::get_network_lock_cost()
         }
+
+        fn get_pruning_preview(netuid: NetUid, count: u16) -> Option<PruningPreview<AccountId32>> {
+            SubtensorModule::get_pruning_preview(netuid, count)
+        }
+    }
+
+    impl subtensor_custom_rpc_runtime_api::WeightsInfoRuntimeApi<Block> for Runtime {
+        fn get_crv3_reveal_statuses(netuid: NetUid, commit_epoch: u64, hotkey: Option<AccountId32>) -> Vec<CRV3RevealStatusInfo<AccountId32>> {
+            SubtensorModule::get_crv3_reveal_statuses(netuid, commit_epoch, hotkey)
+        }
+
+        fn validate_weights(netuid: NetUid, hotkey: AccountId32, uids: Vec<u16>, values: Vec<u16>, version_key: u64) -> WeightsValidationInfo {
+            SubtensorModule::validate_weights(netuid, hotkey, uids, values, version_key)
+        }
     }
 
 
@@ -2421,6 +2476,26 @@ impl_runtime_apis! {
                 .saturating_mul(U96F32::from_num(1_000_000_000))
                 .saturating_to_num()
         }
+
+        fn protocol_liquidity(
+            netuid: u16,
+        ) -> pallet_subtensor_swap_runtime_api::ProtocolLiquidityInfo {
+            pallet_subtensor_swap::Pallet::<Runtime>::protocol_liquidity_info(netuid.into())
+        }
+    }
+
+    impl pallet_drand_runtime_api::DrandRuntimeApi<Block> for Runtime {
+        fn randomness(round: u64) -> Option<[u8; 32]> {
+            pallet_drand::Pallet::<Runtime>::randomness(round)
+        }
+
+        fn last_stored_round() -> u64 {
+            pallet_drand::LastStoredRound::<Runtime>::get()
+        }
+
+        fn oldest_stored_round() -> u64 {
+            pallet_drand::OldestStoredRound::<Runtime>::get()
+        }
     }
 }
 
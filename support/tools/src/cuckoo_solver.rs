@@ -0,0 +1,83 @@
+//! Reference solver for subnets registering with `PowAlgorithm::CuckooCycle`.
+//!
+//! Prints the nonce and work to pass to `register`, for the block whose hash is given.
+use anyhow::{Context, bail, ensure};
+use clap::Parser;
+use sp_crypto_hashing::blake2_256;
+
+#[derive(Parser)]
+struct CliArgs {
+    /// Hash of the block the work is done at, hex encoded.
+    #[arg(long)]
+    block_hash: String,
+    /// Public key of the hotkey registering, hex encoded.
+    #[arg(long)]
+    hotkey: String,
+    /// Graph size of the subnet's cuckoo cycle, in edge bits.
+    #[arg(long)]
+    edge_bits: u8,
+    /// Current difficulty of the subnet.
+    #[arg(long)]
+    difficulty: u64,
+    /// First nonce to try.
+    #[arg(long, default_value_t = 0)]
+    start_nonce: u64,
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = CliArgs::parse();
+    let block_hash = decode_32(&args.block_hash).context("invalid block hash")?;
+    let hotkey = decode_32(&args.hotkey).context("invalid hotkey")?;
+    ensure!(
+        (cuckoo_pow::MIN_EDGE_BITS..=cuckoo_pow::MAX_EDGE_BITS).contains(&args.edge_bits),
+        "edge bits must be between {} and {}",
+        cuckoo_pow::MIN_EDGE_BITS,
+        cuckoo_pow::MAX_EDGE_BITS
+    );
+
+    let mut nonce = args.start_nonce;
+    loop {
+        let keys = cuckoo_pow::keys_from_seed(&seed(&block_hash, &hotkey, nonce));
+        if let Some(proof) = cuckoo_pow::solve(&keys, args.edge_bits) {
+            let work: Vec<u8> = proof.iter().flat_map(|edge| edge.to_le_bytes()).collect();
+            if meets_difficulty(&blake2_256(&work), args.difficulty) {
+                println!("nonce: {nonce}");
+                println!("work: 0x{}", hex::encode(work));
+                return Ok(());
+            }
+        }
+        let Some(next) = nonce.checked_add(1) else {
+            bail!("no solution found up to nonce {}", u64::MAX);
+        };
+        nonce = next;
+    }
+}
+
+fn decode_32(value: &str) -> anyhow::Result<[u8; 32]> {
+    let bytes = hex::decode(value.trim_start_matches("0x"))?;
+    bytes
+        .try_into()
+        .map_err(|bytes: Vec<u8>| anyhow::anyhow!("expected 32 bytes, got {}", bytes.len()))
+}
+
+/// Mirrors `Pallet::create_cuckoo_seed`.
+fn seed(block_hash: &[u8; 32], hotkey: &[u8; 32], nonce: u64) -> [u8; 32] {
+    let mut full_bytes = Vec::with_capacity(72);
+    full_bytes.extend_from_slice(block_hash);
+    full_bytes.extend_from_slice(hotkey);
+    full_bytes.extend_from_slice(&nonce.to_le_bytes());
+    blake2_256(&full_bytes)
+}
+
+/// Mirrors `Pallet::hash_meets_difficulty`, the little endian hash times the difficulty must
+/// not overflow 256 bits.
+fn meets_difficulty(hash: &[u8; 32], difficulty: u64) -> bool {
+    let mut carry: u128 = 0;
+    for limb in hash.chunks_exact(8) {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(limb);
+        let product = u128::from(u64::from_le_bytes(bytes)) * u128::from(difficulty) + carry;
+        carry = product >> 64;
+    }
+    carry == 0
+}